@@ -10,6 +10,14 @@ pub mod filters {
             None => Ok("".to_string()),
         }
     }
+
+    /// A doc comment on its own line before a member of an object
+    pub fn member_doc(doc: &Option<super::DocComment>) -> askama::Result<String> {
+        match doc {
+            Some(doc) => Ok(format!("{}\n\t", doc.to_string().replace('\n', "\n\t"))),
+            None => Ok("".to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Display)]
@@ -21,6 +29,36 @@ impl StringLiteral {
     pub fn from_raw(input: &str) -> Self {
         StringLiteral::from(input.to_string())
     }
+
+    /// The value of the literal, without its escapes
+    pub fn unescaped(&self) -> String {
+        let mut output = String::with_capacity(self.0.len());
+        let mut chars = self.0.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                output.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => output.push('\n'),
+                Some('r') => output.push('\r'),
+                Some('t') => output.push('\t'),
+                Some('0') => output.push('\0'),
+                Some('u') => {
+                    let code: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                    if let Some(c) = u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                    {
+                        output.push(c);
+                    }
+                }
+                Some(other) => output.push(other),
+                None => (),
+            }
+        }
+        output
+    }
 }
 
 impl From<String> for StringLiteral {
@@ -38,6 +76,12 @@ pub fn sanitize_string_literal(input: &str) -> String {
 /// A numeric literal, must have a numeric value (ie. no Infinity, no NaN)
 pub struct NumericLiteral(f64);
 
+impl NumericLiteral {
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Display)]
 #[display("{0} is not a valid numeric literal")]
 pub struct WrongNumericLiteral(f64);
@@ -49,7 +93,7 @@ impl TryFrom<f64> for NumericLiteral {
         if input.is_finite() {
             return Ok(NumericLiteral(input));
         }
-        return Err(WrongNumericLiteral(input));
+        Err(WrongNumericLiteral(input))
     }
 }
 
@@ -58,12 +102,61 @@ impl TryFrom<f64> for NumericLiteral {
 /// A boolean literal
 pub struct BooleanLiteral(bool);
 
+impl BooleanLiteral {
+    pub fn value(&self) -> bool {
+        self.0
+    }
+}
+
 impl From<bool> for BooleanLiteral {
     fn from(input: bool) -> Self {
         BooleanLiteral(input)
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// A JSDoc comment, `/** ... */`, on a single line when its text is
+pub struct DocComment(Vec<String>);
+
+impl DocComment {
+    /// The comment of a text, `None` when it is blank
+    pub fn new(text: &str) -> Option<Self> {
+        let lines: Vec<String> = text
+            .trim_matches('\n')
+            .lines()
+            .map(|line| line.trim_end().replace("*/", "*\\/"))
+            .collect();
+        if lines.iter().all(|line| line.trim().is_empty()) {
+            None
+        } else {
+            Some(DocComment(lines))
+        }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for DocComment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.as_slice() {
+            [line] => write!(f, "/** {} */", line.trim_start()),
+            lines => {
+                writeln!(f, "/**")?;
+                for line in lines {
+                    if line.is_empty() {
+                        writeln!(f, " *")?;
+                    } else {
+                        writeln!(f, " * {}", line)?;
+                    }
+                }
+                write!(f, " */")
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -76,6 +169,33 @@ pub mod tests {
         );
     }
 
+    #[test]
+    pub fn should_unescape_string_literal() {
+        for raw in &[
+            "my \"string\" literal",
+            "tab\there",
+            "accentué\n",
+            "back\\slash",
+        ] {
+            assert_eq!(StringLiteral::from_raw(raw).unescaped(), *raw);
+        }
+    }
+
+    #[test]
+    pub fn should_display_doc_comments() {
+        assert_eq!(DocComment::new("  \n"), None);
+        assert_eq!(
+            DocComment::new("A user").unwrap().to_string(),
+            "/** A user */"
+        );
+        assert_eq!(
+            DocComment::new("A user\n\nSee */users\n")
+                .unwrap()
+                .to_string(),
+            "/**\n * A user\n *\n * See *\\/users\n */"
+        );
+    }
+
     #[test]
     pub fn should_validate_numeric_literal() {
         assert_eq!(NumericLiteral::try_from(1.2), Ok(NumericLiteral(1.2)),);
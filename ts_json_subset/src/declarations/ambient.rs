@@ -0,0 +1,115 @@
+use crate::{
+    common::{filters, StringLiteral},
+    declarations::function::FunctionParameter,
+    export::ExportStatement,
+    ident::TSIdent,
+    import::ImportStatement,
+    types::{TsType, TypeParameters},
+};
+use askama::Template;
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "function {{ ident }}{{ type_params|display_opt }}({{ params|join(\", \") }}): {{ return_type }};",
+    ext = "txt"
+)]
+/// The signature of a function, without its body, for the declaration files (`.d.ts`)
+pub struct AmbientFunctionDeclaration {
+    pub ident: TSIdent,
+    pub type_params: Option<TypeParameters>,
+    pub params: Vec<FunctionParameter>,
+    pub return_type: TsType,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "const {{ ident }}: {{ type_annotation }};", ext = "txt")]
+/// A constant without its value, for the declaration files (`.d.ts`)
+pub struct AmbientVariableDeclaration {
+    pub ident: TSIdent,
+    pub type_annotation: TsType,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "declare module {{ name }} {\n{% for import in imports %}{{ import }}\n{% endfor %}
+    {%- for export in exports %}{{ export }}\n{% endfor %}}",
+    ext = "txt"
+)]
+/// An ambient module, `declare module "api" { ... }`, whose exports can't have values
+pub struct AmbientModuleDeclaration {
+    pub name: StringLiteral,
+    pub imports: Vec<ImportStatement>,
+    pub exports: Vec<ExportStatement>,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        declarations::type_alias::TypeAliasDeclaration,
+        import::{ImportKind, ImportList},
+        types::{PredefinedType, PrimaryType, TypeReference},
+    };
+
+    use super::*;
+
+    fn reference(name: &str) -> TsType {
+        TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+            name: TSIdent::from_str(name).unwrap(),
+            args: None,
+        }))
+    }
+
+    #[test]
+    fn display_ambient_declarations() {
+        assert_eq!(
+            AmbientFunctionDeclaration {
+                ident: TSIdent::from_str("mockId").unwrap(),
+                type_params: None,
+                params: vec![FunctionParameter {
+                    name: TSIdent::from_str("overrides").unwrap(),
+                    optional: true,
+                    param_type: reference("Id"),
+                }],
+                return_type: reference("Id"),
+            }
+            .to_string(),
+            "function mockId(overrides?: Id): Id;"
+        );
+        assert_eq!(
+            AmbientVariableDeclaration {
+                ident: TSIdent::from_str("IdCodec").unwrap(),
+                type_annotation: reference("Codec"),
+            }
+            .to_string(),
+            "const IdCodec: Codec;"
+        );
+    }
+
+    #[test]
+    fn display_ambient_module_declaration() {
+        assert_eq!(
+            AmbientModuleDeclaration {
+                name: StringLiteral::from_raw("api"),
+                imports: vec![ImportStatement {
+                    import_kind: ImportKind::ImportList(ImportList {
+                        items: vec![TSIdent::from_str("Other").unwrap().into()],
+                    }),
+                    path: "\"other\"".to_string(),
+                    type_only: false,
+                }],
+                exports: vec![ExportStatement::TypeAliasDeclaration(TypeAliasDeclaration {
+                    ident: TSIdent::from_str("Id").unwrap(),
+                    type_params: None,
+                    inner_type: TsType::PrimaryType(PrimaryType::Predefined(
+                        PredefinedType::Number,
+                    )),
+                    doc: None,
+                })],
+            }
+            .to_string(),
+            "declare module \"api\" {\nimport { Other } from \"other\";\nexport type Id = number;\n}"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::const_enum::ConstEnumBody;
+use crate::{common::DocComment, ident::TSIdent};
+use askama::Template;
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "enum {{ ident }} {{ body }}", ext = "txt")]
+/// An enum with string literals, which unlike a [ConstEnumDeclaration](super::const_enum::ConstEnumDeclaration)
+/// also exists at runtime, so that its values can be listed.
+///
+/// Its doc comment is written by the [ExportStatement](crate::export::ExportStatement), before `export`.
+pub struct EnumDeclaration {
+    pub ident: TSIdent,
+    pub body: ConstEnumBody,
+    pub doc: Option<DocComment>,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{common::StringLiteral, declarations::const_enum::ConstEnumVariant};
+
+    #[test]
+    fn display_enum_declaration() {
+        assert_eq!(
+            EnumDeclaration {
+                ident: TSIdent::from_str("Color").unwrap(),
+                body: ConstEnumBody {
+                    variants: vec![
+                        ConstEnumVariant {
+                            ident: TSIdent::from_str("Red").unwrap(),
+                            value: StringLiteral::from_raw("red"),
+                        },
+                        ConstEnumVariant {
+                            ident: TSIdent::from_str("DarkBlue").unwrap(),
+                            value: StringLiteral::from_raw("dark_blue"),
+                        },
+                    ],
+                },
+                doc: None,
+            }
+            .to_string(),
+            r#"enum Color { Red = "red", DarkBlue = "dark_blue" }"#,
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    common::filters,
+    expressions::Expression,
+    ident::TSIdent,
+    types::{TsType, TypeParameters},
+};
+use askama::Template;
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "function {{ ident }}{{ type_params|display_opt }}({{ params|join(\", \") }}): {{ return_type }} {\n\treturn {{ body }};\n}",
+    ext = "txt"
+)]
+/// A function declaration whose body returns an expression,
+/// supports generics parameters
+pub struct FunctionDeclaration {
+    pub ident: TSIdent,
+    pub type_params: Option<TypeParameters>,
+    pub params: Vec<FunctionParameter>,
+    pub return_type: TsType,
+    pub body: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{{ name }}{% if optional %}?{% endif %}: {{ param_type }}",
+    ext = "txt"
+)]
+/// A parameter of a function
+pub struct FunctionParameter {
+    pub name: TSIdent,
+    pub optional: bool,
+    pub param_type: TsType,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        expressions::CoalesceExpression,
+        types::{PrimaryType, TypeReference},
+    };
+
+    use super::*;
+
+    #[test]
+    fn display_function_declaration() {
+        let id = TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+            name: TSIdent::from_str("Id").unwrap(),
+            args: None,
+        }));
+        assert_eq!(
+            FunctionDeclaration {
+                ident: TSIdent::from_str("mockId").unwrap(),
+                type_params: None,
+                params: vec![FunctionParameter {
+                    name: TSIdent::from_str("overrides").unwrap(),
+                    optional: true,
+                    param_type: id.clone(),
+                }],
+                return_type: id,
+                body: CoalesceExpression {
+                    left: Box::new(TSIdent::from_str("overrides").unwrap().into()),
+                    right: Box::new(Expression::Null),
+                }
+                .into(),
+            }
+            .to_string(),
+            "function mockId(overrides?: Id): Id {\n\treturn overrides ?? null;\n}"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use crate::types::{ObjectType, TypeParameters, TypeReference};
-use crate::{common::filters, ident::TSIdent};
+use crate::{
+    common::{filters, DocComment},
+    ident::TSIdent,
+};
 use askama::Template;
 
 #[derive(Debug, Clone, PartialEq, Template)]
@@ -21,12 +24,15 @@ pub struct InterfaceExtendsClause {
     ext = "txt"
 )]
 /// An interface declaration,
-/// supports generics parameters and extends
+/// supports generics parameters and extends.
+///
+/// Its doc comment is written by the [ExportStatement](crate::export::ExportStatement), before `export`.
 pub struct InterfaceDeclaration {
     pub ident: TSIdent,
     pub type_params: Option<TypeParameters>,
     pub extends_clause: Option<InterfaceExtendsClause>,
     pub obj_type: ObjectType,
+    pub doc: Option<DocComment>,
 }
 
 #[cfg(test)]
@@ -91,6 +97,7 @@ pub mod tests {
                 obj_type: ObjectType {
                     body: TypeBody { members: vec![] }
                 },
+                doc: None,
             }
             .to_string(),
             "interface MyInterface {\n\t\n}"
@@ -107,20 +114,25 @@ pub mod tests {
                             TypeMember::PropertySignature(PropertySignature {
                                 name: PropertyName::from("value".to_string()),
                                 optional: false,
+                                readonly: false,
                                 inner_type: TsType::PrimaryType(PrimaryType::Predefined(
                                     crate::types::PredefinedType::Number
                                 )),
+                                doc: None,
                             }),
                             TypeMember::PropertySignature(PropertySignature {
                                 name: PropertyName::from("name".to_string()),
                                 optional: true,
+                                readonly: false,
                                 inner_type: TsType::PrimaryType(PrimaryType::Predefined(
                                     crate::types::PredefinedType::String
                                 )),
+                                doc: None,
                             })
                         ]
                     }
                 },
+                doc: None,
             }
             .to_string(),
             "interface MyInterface {\n\tvalue: number,\n\tname?: string\n}"
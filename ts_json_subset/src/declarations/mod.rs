@@ -1,4 +1,9 @@
+pub mod ambient;
 pub mod const_enum;
+pub mod enumeration;
+pub mod function;
 pub mod interface;
+pub mod namespace;
 pub mod reexport;
 pub mod type_alias;
+pub mod variable;
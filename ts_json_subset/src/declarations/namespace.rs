@@ -0,0 +1,86 @@
+use askama::Template;
+use displaythis::Display;
+use from_variants::FromVariants;
+
+use crate::{export::ExportStatement, ident::TSIdent, import::ImportAliasDeclaration};
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "namespace {% for namespace in qualifier %}{{ namespace }}.{% endfor %}{{ ident }} {\n{{ elements|join(\"\n\") }}\n}",
+    ext = "txt"
+)]
+/// A namespace declaration, that can contain export statements and nested namespaces
+pub struct NamespaceDeclaration {
+    /// The namespaces it is nested in, `namespace api.models { ... }` declaring `models` in `api`
+    pub qualifier: Vec<TSIdent>,
+    pub ident: TSIdent,
+    pub elements: Vec<NamespaceElement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Display, FromVariants)]
+/// An element of the body of a namespace
+pub enum NamespaceElement {
+    #[display("{0}")]
+    ImportAliasDeclaration(ImportAliasDeclaration),
+    #[display("{0}")]
+    ExportStatement(ExportStatement),
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        declarations::type_alias::TypeAliasDeclaration,
+        types::{PredefinedType, PrimaryType, TsType},
+    };
+
+    use super::*;
+
+    #[test]
+    fn display_namespace_declaration() {
+        let inner = NamespaceDeclaration {
+            qualifier: Vec::new(),
+            ident: TSIdent::from_str("models").unwrap(),
+            elements: vec![
+                ImportAliasDeclaration {
+                    ident: TSIdent::from_str("User").unwrap(),
+                    entity: vec![
+                        TSIdent::from_str("my_crate").unwrap(),
+                        TSIdent::from_str("User").unwrap(),
+                    ],
+                }
+                .into(),
+                ExportStatement::TypeAliasDeclaration(TypeAliasDeclaration {
+                    ident: TSIdent::from_str("Id").unwrap(),
+                    type_params: None,
+                    inner_type: TsType::PrimaryType(PrimaryType::Predefined(
+                        PredefinedType::Number,
+                    )),
+                    doc: None,
+                })
+                .into(),
+            ],
+        };
+        assert_eq!(
+            NamespaceDeclaration {
+                qualifier: Vec::new(),
+                ident: TSIdent::from_str("my_crate").unwrap(),
+                elements: vec![ExportStatement::NamespaceDeclaration(inner.clone()).into()],
+            }
+            .to_string(),
+            "namespace my_crate {\nexport namespace models {\nimport User = my_crate.User;\nexport type Id = number;\n}\n}",
+        );
+        assert_eq!(
+            NamespaceDeclaration {
+                qualifier: vec![
+                    TSIdent::from_str("my_crate").unwrap(),
+                    TSIdent::from_str("api").unwrap()
+                ],
+                ..inner
+            }
+            .to_string(),
+            "namespace my_crate.api.models {\nimport User = my_crate.User;\nexport type Id = number;\n}",
+        );
+    }
+}
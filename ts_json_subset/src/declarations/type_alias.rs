@@ -1,5 +1,8 @@
 use crate::types::{TsType, TypeParameters};
-use crate::{common::filters, ident::TSIdent};
+use crate::{
+    common::{filters, DocComment},
+    ident::TSIdent,
+};
 use askama::Template;
 
 #[derive(Debug, Clone, PartialEq, Template)]
@@ -8,11 +11,14 @@ use askama::Template;
     ext = "txt"
 )]
 /// A type alias declaration,
-/// supports generics parameters
+/// supports generics parameters.
+///
+/// Its doc comment is written by the [ExportStatement](crate::export::ExportStatement), before `export`.
 pub struct TypeAliasDeclaration {
     pub ident: TSIdent,
     pub type_params: Option<TypeParameters>,
     pub inner_type: TsType,
+    pub doc: Option<DocComment>,
 }
 
 #[cfg(test)]
@@ -30,6 +36,7 @@ pub mod tests {
                 ident: TSIdent::from_str("MyType").unwrap(),
                 type_params: None,
                 inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Any)),
+                doc: None,
             }
             .to_string(),
             "type MyType = any;",
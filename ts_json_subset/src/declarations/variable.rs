@@ -0,0 +1,44 @@
+use crate::{expressions::Expression, ident::TSIdent, types::TsType};
+use askama::Template;
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "const {{ ident }}
+    {%- match type_annotation %}{% when Some with (ty) %}: {{ ty }}{% when None %}{% endmatch %} = {{ value }};",
+    ext = "txt"
+)]
+/// A constant declaration, whose type is annotated or inferred from its value
+pub struct VariableDeclaration {
+    pub ident: TSIdent,
+    pub type_annotation: Option<TsType>,
+    pub value: Expression,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use crate::types::{PrimaryType, RawType};
+
+    use super::*;
+
+    #[test]
+    fn display_variable_declaration() {
+        let declaration = VariableDeclaration {
+            ident: TSIdent::from_str("UserCodec").unwrap(),
+            type_annotation: None,
+            value: TSIdent::from_str("codec").unwrap().into(),
+        };
+        assert_eq!(declaration.to_string(), "const UserCodec = codec;");
+        assert_eq!(
+            VariableDeclaration {
+                type_annotation: Some(TsType::PrimaryType(PrimaryType::RawType(RawType(
+                    "t.Type<User>".to_string()
+                )))),
+                ..declaration
+            }
+            .to_string(),
+            "const UserCodec: t.Type<User> = codec;"
+        );
+    }
+}
@@ -1,19 +1,98 @@
-use crate::declarations::{
-    const_enum::ConstEnumDeclaration, interface::InterfaceDeclaration,
-    reexport::ReexportDeclaration, type_alias::TypeAliasDeclaration,
+use crate::{
+    common::DocComment,
+    declarations::{
+        ambient::{AmbientFunctionDeclaration, AmbientVariableDeclaration},
+        const_enum::ConstEnumDeclaration,
+        enumeration::EnumDeclaration,
+        function::FunctionDeclaration,
+        interface::InterfaceDeclaration,
+        namespace::NamespaceDeclaration,
+        reexport::ReexportDeclaration,
+        type_alias::TypeAliasDeclaration,
+        variable::VariableDeclaration,
+    },
+    ident::TSIdent,
 };
 use displaythis::Display;
 use from_variants::FromVariants;
 
 #[derive(Debug, Clone, PartialEq, FromVariants, Display)]
-/// An export statement, with support for exporting interfaces, types, const enum, and reexports
+/// An export statement, with support for exporting interfaces, types, enums, const enums, reexports, namespaces, functions and constants.
+///
+/// The doc comment of the declaration, if any, is written before `export`.
 pub enum ExportStatement {
-    #[display("export {0}")]
+    #[display("{}export {0}", doc_prefix(&_0.doc))]
     InterfaceDeclaration(InterfaceDeclaration),
-    #[display("export {0}")]
+    #[display("{}export {0}", doc_prefix(&_0.doc))]
     TypeAliasDeclaration(TypeAliasDeclaration),
+    #[display("{}export {0}", doc_prefix(&_0.doc))]
+    EnumDeclaration(EnumDeclaration),
     #[display("export {0}")]
     ConstEnumDeclaration(ConstEnumDeclaration),
     #[display("export {0}")]
     ReexportDeclaration(ReexportDeclaration),
+    #[display("export {0}")]
+    NamespaceDeclaration(NamespaceDeclaration),
+    #[display("export {0}")]
+    FunctionDeclaration(FunctionDeclaration),
+    #[display("export {0}")]
+    VariableDeclaration(VariableDeclaration),
+    #[display("export {0}")]
+    AmbientFunctionDeclaration(AmbientFunctionDeclaration),
+    #[display("export {0}")]
+    AmbientVariableDeclaration(AmbientVariableDeclaration),
+}
+
+fn doc_prefix(doc: &Option<DocComment>) -> String {
+    match doc {
+        Some(doc) => format!("{}\n", doc),
+        None => String::new(),
+    }
+}
+
+impl ExportStatement {
+    /// The doc comment of the exported declaration
+    pub fn doc_mut(&mut self) -> Option<&mut Option<DocComment>> {
+        match self {
+            ExportStatement::InterfaceDeclaration(declaration) => Some(&mut declaration.doc),
+            ExportStatement::TypeAliasDeclaration(declaration) => Some(&mut declaration.doc),
+            ExportStatement::EnumDeclaration(declaration) => Some(&mut declaration.doc),
+            _ => None,
+        }
+    }
+
+    /// The identifier of the exported declaration, re-exports have none
+    pub fn ident(&self) -> Option<&TSIdent> {
+        match self {
+            ExportStatement::InterfaceDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::TypeAliasDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::EnumDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::ConstEnumDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::NamespaceDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::FunctionDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::VariableDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::AmbientFunctionDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::AmbientVariableDeclaration(declaration) => Some(&declaration.ident),
+            ExportStatement::ReexportDeclaration(_) => None,
+        }
+    }
+
+    pub fn ident_mut(&mut self) -> Option<&mut TSIdent> {
+        match self {
+            ExportStatement::InterfaceDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::TypeAliasDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::EnumDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::ConstEnumDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::NamespaceDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::FunctionDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::VariableDeclaration(declaration) => Some(&mut declaration.ident),
+            ExportStatement::AmbientFunctionDeclaration(declaration) => {
+                Some(&mut declaration.ident)
+            }
+            ExportStatement::AmbientVariableDeclaration(declaration) => {
+                Some(&mut declaration.ident)
+            }
+            ExportStatement::ReexportDeclaration(_) => None,
+        }
+    }
 }
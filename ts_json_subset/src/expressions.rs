@@ -0,0 +1,219 @@
+//! A subset of the TS expressions, enough to build JSON-like values (e.g. the mocks of the exported types)
+use crate::{
+    common::filters,
+    declarations::function::FunctionParameter,
+    ident::TSIdent,
+    types::{LiteralType, PropertyName, TsType, TypeArguments, TypeParameters},
+};
+use askama::Template;
+use displaythis::Display;
+use from_variants::FromVariants;
+
+#[derive(Debug, Clone, PartialEq, Display, FromVariants)]
+/// A TS expression
+pub enum Expression {
+    #[display("{0}")]
+    Literal(LiteralType),
+    #[display("null")]
+    #[from_variants(skip)]
+    Null,
+    #[display("undefined")]
+    #[from_variants(skip)]
+    Undefined,
+    #[display("{0}")]
+    Identifier(TSIdent),
+    #[display("{0}")]
+    ArrayLiteral(ArrayLiteral),
+    #[display("{0}")]
+    ObjectLiteral(ObjectLiteral),
+    #[display("{0}")]
+    CallExpression(CallExpression),
+    #[display("{0}")]
+    MemberExpression(MemberExpression),
+    #[display("{0}")]
+    AsExpression(AsExpression),
+    #[display("{0}")]
+    CoalesceExpression(CoalesceExpression),
+    #[display("{0}")]
+    ArrowFunction(ArrowFunction),
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "[{{ elements|join(\", \") }}]", ext = "txt")]
+/// An array literal, e.g. `[1, 2]`
+pub struct ArrayLiteral {
+    pub elements: Vec<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{% if members.is_empty() %}{}{% else %}{ {{ members|join(\", \") }} }{% endif %}",
+    ext = "txt"
+)]
+/// An object literal, e.g. `{ name: "name", ...overrides }`
+pub struct ObjectLiteral {
+    pub members: Vec<ObjectLiteralMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Display)]
+/// A member of an object literal
+pub enum ObjectLiteralMember {
+    #[display("{0}: {1}")]
+    Property(PropertyName, Expression),
+    #[display("...{0}")]
+    Spread(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{{ callee }}{{ type_args|display_opt }}({{ args|join(\", \") }})",
+    ext = "txt"
+)]
+/// A call to a function, e.g. `mockPage<User>()` or `t.array(UserCodec)`
+pub struct CallExpression {
+    pub callee: Box<Expression>,
+    pub type_args: Option<TypeArguments>,
+    pub args: Vec<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "{{ object }}.{{ member }}", ext = "txt")]
+/// An access to a member, e.g. `Level.Low`
+pub struct MemberExpression {
+    pub object: TSIdent,
+    pub member: TSIdent,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "({{ expression }} as {{ as_type }})", ext = "txt")]
+/// A type assertion, e.g. `(undefined as unknown)`
+pub struct AsExpression {
+    pub expression: Box<Expression>,
+    pub as_type: TsType,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "{{ left }} ?? {{ right }}", ext = "txt")]
+/// A nullish coalescing, e.g. `overrides ?? 0`
+pub struct CoalesceExpression {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{{ type_params|display_opt }}({{ params|join(\", \") }})
+    {%- match return_type %}{% when Some with (ty) %}: {{ ty }}{% when None %}{% endmatch %} => {{ body }}",
+    ext = "txt"
+)]
+/// An arrow function returning an expression, e.g. `<T>(T: t.Type<T>) => t.array(T)` or `() => UserCodec`
+pub struct ArrowFunction {
+    pub type_params: Option<TypeParameters>,
+    pub params: Vec<FunctionParameter>,
+    pub return_type: Option<TsType>,
+    pub body: Box<Expression>,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        common::StringLiteral,
+        types::{PredefinedType, PrimaryType},
+    };
+
+    #[test]
+    fn display_expressions() {
+        let object = ObjectLiteral {
+            members: vec![
+                ObjectLiteralMember::Property(
+                    PropertyName::from("name".to_string()),
+                    LiteralType::from(StringLiteral::from_raw("name")).into(),
+                ),
+                ObjectLiteralMember::Property(
+                    PropertyName::from("tags".to_string()),
+                    ArrayLiteral { elements: vec![] }.into(),
+                ),
+                ObjectLiteralMember::Spread(TSIdent::from_str("overrides").unwrap().into()),
+            ],
+        };
+        assert_eq!(
+            object.to_string(),
+            r#"{ name: "name", tags: [], ...overrides }"#
+        );
+        assert_eq!(ObjectLiteral { members: vec![] }.to_string(), "{}");
+
+        let cast = AsExpression {
+            expression: Box::new(Expression::Undefined),
+            as_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Unknown)),
+        };
+        let coalesce = CoalesceExpression {
+            left: Box::new(TSIdent::from_str("overrides").unwrap().into()),
+            right: Box::new(
+                CallExpression {
+                    callee: Box::new(TSIdent::from_str("mockId").unwrap().into()),
+                    type_args: None,
+                    args: vec![cast.into()],
+                }
+                .into(),
+            ),
+        };
+        assert_eq!(
+            coalesce.to_string(),
+            "overrides ?? mockId((undefined as unknown))"
+        );
+    }
+
+    #[test]
+    fn display_arrow_functions() {
+        let t = |member: &str| -> Expression {
+            MemberExpression {
+                object: TSIdent::from_str("t").unwrap(),
+                member: TSIdent::from_str(member).unwrap(),
+            }
+            .into()
+        };
+        let codec = |ty: &str| {
+            TsType::PrimaryType(PrimaryType::RawType(crate::types::RawType(format!(
+                "t.Type<{}>",
+                ty
+            ))))
+        };
+        let generic = ArrowFunction {
+            type_params: Some(TypeParameters {
+                parameters: vec![crate::types::TypeParameter {
+                    identifier: TSIdent::from_str("T").unwrap(),
+                    constraint: None,
+                    default: None,
+                }],
+            }),
+            params: vec![FunctionParameter {
+                name: TSIdent::from_str("T").unwrap(),
+                optional: false,
+                param_type: codec("T"),
+            }],
+            return_type: Some(codec("T[]")),
+            body: Box::new(
+                CallExpression {
+                    callee: Box::new(t("array")),
+                    type_args: None,
+                    args: vec![TSIdent::from_str("T").unwrap().into()],
+                }
+                .into(),
+            ),
+        };
+        assert_eq!(
+            generic.to_string(),
+            "<T>(T: t.Type<T>): t.Type<T[]> => t.array(T)"
+        );
+        let lazy = ArrowFunction {
+            type_params: None,
+            params: Vec::new(),
+            return_type: None,
+            body: Box::new(t("string")),
+        };
+        assert_eq!(lazy.to_string(), "() => t.string");
+    }
+}
@@ -0,0 +1,586 @@
+//! The layout of the rendered code.
+//!
+//! The declarations are displayed in a canonical layout : tabs, semicolons, no trailing commas and double quotes.
+//! [FormatOptions] lays that code out again, so that it matches the settings of a project's formatter :
+//!
+//! ```
+//! use ts_json_subset::format::{FormatOptions, Indent, Quotes};
+//!
+//! let options = FormatOptions {
+//!     indent: Indent::Spaces(2),
+//!     semicolons: false,
+//!     trailing_commas: true,
+//!     quotes: Quotes::Single,
+//!     print_width: Some(40),
+//! };
+//! assert_eq!(
+//!     options.format("export type Role = \"admin\" | \"user\";\nexport interface User {\n\trole: Role | \"administrator\" | \"owner\"\n}\n"),
+//!     "export type Role = 'admin' | 'user'\nexport interface User {\n  role:\n    | Role\n    | 'administrator'\n    | 'owner',\n}\n"
+//! );
+//! ```
+//!
+//! The nested object types are indented by their depth, which the canonical layout doesn't do,
+//! and the unions of the long lines are wrapped like prettier does.
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub indent: Indent,
+    /// Terminate the statements with a semicolon
+    pub semicolons: bool,
+    /// Add a comma after the last member of the objects and enums written on several lines
+    pub trailing_commas: bool,
+    /// The quotes of the string literals
+    pub quotes: Quotes,
+    /// Wrap the unions of the lines longer than this one variant per line, a tab counting for [TAB_WIDTH] columns
+    pub print_width: Option<usize>,
+}
+
+/// The columns of a tab, see [FormatOptions::print_width]
+pub const TAB_WIDTH: usize = 4;
+
+impl Default for FormatOptions {
+    /// The canonical layout
+    fn default() -> Self {
+        FormatOptions {
+            indent: Indent::Tabs,
+            semicolons: true,
+            trailing_commas: false,
+            quotes: Quotes::Double,
+            print_width: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// One level of indentation
+pub enum Indent {
+    Tabs,
+    Spaces(usize),
+}
+
+impl FromStr for Indent {
+    type Err = FormatOptionError;
+
+    /// `tab`, or a number of spaces
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tab" | "tabs" => Ok(Indent::Tabs),
+            _ => s
+                .parse()
+                .map(Indent::Spaces)
+                .map_err(|_| FormatOptionError::InvalidIndent(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quotes {
+    Double,
+    Single,
+}
+
+impl FromStr for Quotes {
+    type Err = FormatOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "double" => Ok(Quotes::Double),
+            "single" => Ok(Quotes::Single),
+            _ => Err(FormatOptionError::InvalidQuotes(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FormatOptionError {
+    #[error("invalid indent {0}, expected tab or a number of spaces")]
+    InvalidIndent(String),
+    #[error("invalid quotes {0}, expected single or double")]
+    InvalidQuotes(String),
+}
+
+/// A value displayed with [FormatOptions], see [FormatOptions::display]
+pub struct Formatted<'a, T> {
+    options: &'a FormatOptions,
+    value: &'a T,
+}
+
+impl<'a, T: Display> Display for Formatted<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.options.format(&self.value.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Code,
+    String(char),
+    Template,
+    BlockComment,
+}
+
+/// A bracket opened by a previous line
+struct Block {
+    /// The statements of a namespace, module or function, which aren't separated by commas
+    statements: bool,
+}
+
+/// A line of code once scanned
+struct Line {
+    text: String,
+    depth: usize,
+    /// The line starts in a comment or a template literal, its text is kept
+    verbatim: bool,
+    /// The position after the last character of code, and that character
+    last_code: Option<(usize, char)>,
+    /// The innermost bracket opened by the line or a previous one is a list of members
+    in_list: bool,
+    starts_with_closer: bool,
+}
+
+impl FormatOptions {
+    /// Lays out the code of `value`, e.g. `options.display(&export_statement).to_string()`
+    pub fn display<'a, T: Display>(&'a self, value: &'a T) -> Formatted<'a, T> {
+        Formatted {
+            options: self,
+            value,
+        }
+    }
+
+    /// Lays out rendered code
+    pub fn format(&self, code: &str) -> String {
+        let lines = self.scan(code);
+        let mut output = String::with_capacity(code.len());
+        for (index, line) in lines.iter().enumerate() {
+            if line.verbatim {
+                output.push_str(&line.text);
+                output.push('\n');
+                continue;
+            }
+            let mut text = line.text.clone();
+            let trailing_comma = self.trailing_commas
+                && line.in_list
+                && lines
+                    .get(index + 1)
+                    .is_some_and(|next| next.starts_with_closer);
+            if let Some((position, last)) = line.last_code {
+                if last == ';' && !self.semicolons {
+                    text.remove(position - 1);
+                } else if trailing_comma && !matches!(last, '{' | '[' | '(' | ',' | ';') {
+                    text.insert(position, ',');
+                }
+            }
+            if !text.is_empty() {
+                output.push_str(&self.indentation(line.depth));
+                match self.wrap_union(&text, line.depth) {
+                    Some(wrapped) => output.push_str(&wrapped),
+                    None => output.push_str(&text),
+                }
+            }
+            output.push('\n');
+        }
+        if !code.ends_with('\n') {
+            output.pop();
+        }
+        output
+    }
+
+    fn indentation(&self, depth: usize) -> String {
+        match self.indent {
+            Indent::Tabs => "\t".repeat(depth),
+            Indent::Spaces(width) => " ".repeat(depth * width),
+        }
+    }
+
+    /// The columns of the indentation
+    fn indentation_width(&self, depth: usize) -> usize {
+        match self.indent {
+            Indent::Tabs => depth * TAB_WIDTH,
+            Indent::Spaces(width) => depth * width,
+        }
+    }
+
+    /// The line with its union one variant per line, when it is longer than the print width :
+    ///
+    /// ```text
+    /// export type Event =
+    ///     | "created"
+    ///     | "deleted";
+    /// ```
+    fn wrap_union(&self, text: &str, depth: usize) -> Option<String> {
+        let print_width = self.print_width?;
+        if self.indentation_width(depth) + text.chars().count() <= print_width {
+            return None;
+        }
+        let (assignments, bars) = top_level_separators(text)?;
+        let first_bar = *bars.first()?;
+        // The union is the value of a type alias or the type of a property, and ends the line
+        let assignment = *assignments
+            .iter()
+            .rev()
+            .find(|position| **position < first_bar)?;
+        if assignments.iter().any(|position| *position > first_bar) {
+            return None;
+        }
+        let mut variants: Vec<&str> = Vec::with_capacity(bars.len() + 1);
+        let mut start = assignment + 1;
+        for bar in bars {
+            variants.push(text[start..bar].trim());
+            start = bar + 1;
+        }
+        let last = text[start..].trim();
+        let (last, suffix) = match last.strip_suffix([';', ','].as_ref()) {
+            Some(variant) => (variant, &last[variant.len()..]),
+            None => (last, ""),
+        };
+        variants.push(last.trim_end());
+
+        let mut wrapped = text[..=assignment].to_string();
+        for variant in variants {
+            wrapped.push('\n');
+            wrapped.push_str(&self.indentation(depth + 1));
+            wrapped.push_str("| ");
+            wrapped.push_str(variant);
+        }
+        wrapped.push_str(suffix);
+        Some(wrapped)
+    }
+
+    fn scan(&self, code: &str) -> Vec<Line> {
+        let mut state = State::Code;
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut lines = Vec::new();
+        for raw in code.split('\n') {
+            match state {
+                State::Template => {
+                    state = self.scan_line(raw, state, &mut blocks).1;
+                    lines.push(Line {
+                        text: raw.to_string(),
+                        depth: 0,
+                        verbatim: true,
+                        last_code: None,
+                        in_list: false,
+                        starts_with_closer: false,
+                    });
+                    continue;
+                }
+                State::BlockComment => {
+                    let depth = blocks.len();
+                    state = self.scan_line(raw, state, &mut blocks).1;
+                    let trimmed = raw.trim_start();
+                    let text = if trimmed.starts_with('*') {
+                        format!("{} {}", self.indentation(depth), trimmed)
+                    } else {
+                        raw.to_string()
+                    };
+                    lines.push(Line {
+                        text,
+                        depth,
+                        verbatim: true,
+                        last_code: None,
+                        in_list: false,
+                        starts_with_closer: false,
+                    });
+                    continue;
+                }
+                _ => {}
+            }
+            let trimmed = raw.trim();
+            // The blank lines of an empty object
+            if trimmed.is_empty() && !raw.is_empty() {
+                continue;
+            }
+            let starts_with_closer = trimmed.starts_with(['}', ']', ')'].as_ref());
+            let depth = blocks.len() - usize::from(starts_with_closer && !blocks.is_empty());
+            let (scanned, next_state) = self.scan_line(trimmed, state, &mut blocks);
+            state = next_state;
+            lines.push(Line {
+                text: scanned.text,
+                depth,
+                verbatim: false,
+                last_code: scanned.last_code,
+                in_list: blocks.last().is_some_and(|block| !block.statements),
+                starts_with_closer,
+            });
+        }
+        if code.ends_with('\n') {
+            lines.pop();
+        }
+        lines
+    }
+
+    /// Converts the quotes of a line, and keeps track of the brackets it opens and closes
+    fn scan_line(&self, line: &str, mut state: State, blocks: &mut Vec<Block>) -> (Scanned, State) {
+        let mut text = String::with_capacity(line.len());
+        let mut last_code = None;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match state {
+                State::Code => match c {
+                    '"' | '\'' => {
+                        state = State::String(c);
+                        text.push(self.quote());
+                    }
+                    '`' => {
+                        state = State::Template;
+                        text.push(c);
+                    }
+                    '/' if chars.peek() == Some(&'/') => {
+                        text.extend(Some(c).into_iter().chain(chars.by_ref()));
+                        break;
+                    }
+                    '/' if chars.peek() == Some(&'*') => {
+                        state = State::BlockComment;
+                        text.push(c);
+                    }
+                    '{' | '[' | '(' => {
+                        blocks.push(Block {
+                            statements: c == '{' && opens_statements(&text),
+                        });
+                        text.push(c);
+                    }
+                    '}' | ']' | ')' => {
+                        blocks.pop();
+                        text.push(c);
+                    }
+                    _ => text.push(c),
+                },
+                State::String(quote) => match c {
+                    '\\' => {
+                        let escaped = chars.next();
+                        // Only the quote of the literal needs to be escaped
+                        match escaped {
+                            Some('"') | Some('\'') if escaped != Some(self.quote()) => {}
+                            _ => text.push(c),
+                        }
+                        text.extend(escaped);
+                    }
+                    c if c == quote => {
+                        state = State::Code;
+                        text.push(self.quote());
+                    }
+                    c if c == self.quote() => {
+                        text.push('\\');
+                        text.push(c);
+                    }
+                    _ => text.push(c),
+                },
+                State::Template => {
+                    if c == '\\' {
+                        text.push(c);
+                        text.extend(chars.next());
+                        continue;
+                    }
+                    if c == '`' {
+                        state = State::Code;
+                    }
+                    text.push(c);
+                }
+                State::BlockComment => {
+                    if c == '*' && chars.peek() == Some(&'/') {
+                        state = State::Code;
+                        text.push(c);
+                        text.extend(chars.next());
+                        continue;
+                    }
+                    text.push(c);
+                }
+            }
+            if state == State::Code && !c.is_whitespace() {
+                last_code = Some((text.len(), c));
+            }
+        }
+        (Scanned { text, last_code }, state)
+    }
+
+    /// The quote of the string literals in the output
+    fn quote(&self) -> char {
+        match self.quotes {
+            Quotes::Double => '"',
+            Quotes::Single => '\'',
+        }
+    }
+}
+
+struct Scanned {
+    text: String,
+    last_code: Option<(usize, char)>,
+}
+
+/// The positions of the `:` and `=` and of the `|` of a line that are outside of its brackets and literals,
+/// `None` when the line has a comment or unbalanced brackets
+fn top_level_separators(line: &str) -> Option<(Vec<usize>, Vec<usize>)> {
+    let mut assignments = Vec::new();
+    let mut bars = Vec::new();
+    let mut depth = 0usize;
+    let mut literal: Option<char> = None;
+    let bytes = line.as_bytes();
+    let mut chars = line.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        if let Some(quote) = literal {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                c if c == quote => literal = None,
+                _ => {}
+            }
+            continue;
+        }
+        let previous = position.checked_sub(1).map(|previous| bytes[previous]);
+        let next = chars.peek().map(|(_, next)| *next);
+        match c {
+            '"' | '\'' | '`' => literal = Some(c),
+            '/' if matches!(next, Some('/') | Some('*')) => return None,
+            '{' | '[' | '(' | '<' => depth += 1,
+            '>' if previous == Some(b'=') => {}
+            '}' | ']' | ')' | '>' => depth = depth.checked_sub(1)?,
+            _ if depth > 0 || next != Some(' ') => {}
+            ':' => assignments.push(position),
+            '=' if !matches!(previous, Some(b'=') | Some(b'!') | Some(b'<') | Some(b'>')) => {
+                assignments.push(position)
+            }
+            '|' if previous == Some(b' ') => bars.push(position),
+            _ => {}
+        }
+    }
+    if depth != 0 || literal.is_some() {
+        return None;
+    }
+    Some((assignments, bars))
+}
+
+/// Whether the `{` following `line` opens the body of a namespace, a module or a function
+fn opens_statements(line: &str) -> bool {
+    let mut line = line.trim_start();
+    for modifier in ["export ", "declare "].iter() {
+        line = line.strip_prefix(modifier).unwrap_or(line);
+    }
+    ["namespace ", "module ", "function "]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const CODE: &str = "import { Id } from \"../ids\";\n\
+        \n\
+        /**\n * A user\n */\n\
+        export interface User {\n\
+        \t/** The \"login\" of the user */\n\
+        \t\"user-name\": string,\n\
+        \taddress: {\n\
+        \tcity: 'O\\'Brien'\n\
+        }\n\
+        }\n\
+        export interface Empty {\n\
+        \t\n\
+        }\n\
+        export namespace api {\n\
+        export type Path = `/${string}`;\n\
+        export enum Role {\n\
+        \tAdmin = \"admin\"\n\
+        }\n\
+        }\n";
+
+    #[test]
+    fn should_indent_the_nested_objects() {
+        assert_eq!(
+            FormatOptions::default().format(CODE),
+            "import { Id } from \"../ids\";\n\
+             \n\
+             /**\n * A user\n */\n\
+             export interface User {\n\
+             \t/** The \"login\" of the user */\n\
+             \t\"user-name\": string,\n\
+             \taddress: {\n\
+             \t\tcity: \"O'Brien\"\n\
+             \t}\n\
+             }\n\
+             export interface Empty {\n\
+             }\n\
+             export namespace api {\n\
+             \texport type Path = `/${string}`;\n\
+             \texport enum Role {\n\
+             \t\tAdmin = \"admin\"\n\
+             \t}\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_apply_the_options() {
+        let options = FormatOptions {
+            indent: Indent::Spaces(2),
+            semicolons: false,
+            trailing_commas: true,
+            quotes: Quotes::Single,
+            print_width: None,
+        };
+        assert_eq!(
+            options.format(CODE),
+            "import { Id } from '../ids'\n\
+             \n\
+             /**\n * A user\n */\n\
+             export interface User {\n  \
+               /** The \"login\" of the user */\n  \
+               'user-name': string,\n  \
+               address: {\n    \
+                 city: 'O\\'Brien',\n  \
+               },\n\
+             }\n\
+             export interface Empty {\n\
+             }\n\
+             export namespace api {\n  \
+               export type Path = `/${string}`\n  \
+               export enum Role {\n    \
+                 Admin = 'admin',\n  \
+               }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_wrap_the_long_unions() {
+        let options = FormatOptions {
+            print_width: Some(40),
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            options.format(
+                "export type Event = \"created\" | \"updated\" | \"deleted\";\n\
+                 export type Short = \"a\" | \"b\";\n\
+                 export interface Change {\n\
+                 \tevent: Event,\n\
+                 \tprevious?: Array<\"created\" | \"updated\"> | null,\n\
+                 \tmap: { [key: string]: \"a\" | \"b\" | \"c\" | \"d\" | \"e\" }\n\
+                 }\n"
+            ),
+            "export type Event =\n\
+             \t| \"created\"\n\
+             \t| \"updated\"\n\
+             \t| \"deleted\";\n\
+             export type Short = \"a\" | \"b\";\n\
+             export interface Change {\n\
+             \tevent: Event,\n\
+             \tprevious?:\n\
+             \t\t| Array<\"created\" | \"updated\">\n\
+             \t\t| null,\n\
+             \tmap: { [key: string]: \"a\" | \"b\" | \"c\" | \"d\" | \"e\" }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_parse_the_options() {
+        assert_eq!(Indent::from_str("tab").unwrap(), Indent::Tabs);
+        assert_eq!(Indent::from_str("4").unwrap(), Indent::Spaces(4));
+        assert!(Indent::from_str("wide").is_err());
+        assert_eq!(Quotes::from_str("single").unwrap(), Quotes::Single);
+    }
+}
@@ -4,18 +4,55 @@ use displaythis::Display;
 use crate::ident::TSIdent;
 
 #[derive(Debug, Clone, PartialEq, Template)]
-#[template(source = "import {{ import_kind }} from {{ path }};", ext = "txt")]
+#[template(
+    source = "import{% if type_only %} type{% endif %} {{ import_kind }} from {{ path }};",
+    ext = "txt"
+)]
 /// An import statement, supporting multiple imports from a file
 pub struct ImportStatement {
     pub import_kind: ImportKind,
     pub path: String,
+    /// `import type`, which only imports the types and is erased from the compiled code
+    pub type_only: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Template)]
 #[template(source = "{{ items|join(\", \") }}", ext = "txt")]
 /// An list of imported identifiers
 pub struct ImportList {
-    pub items: Vec<TSIdent>,
+    pub items: Vec<ImportSpecifier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{{ ident }}{% match alias %}{% when Some with (alias) %} as {{ alias }}{% when None %}{% endmatch %}",
+    ext = "txt"
+)]
+/// An imported identifier, and the name it is imported under if it is renamed
+pub struct ImportSpecifier {
+    pub ident: TSIdent,
+    pub alias: Option<TSIdent>,
+}
+
+impl ImportSpecifier {
+    /// The name the identifier is referred to by in the importing module
+    pub fn local_name(&self) -> &TSIdent {
+        self.alias.as_ref().unwrap_or(&self.ident)
+    }
+}
+
+impl From<TSIdent> for ImportSpecifier {
+    fn from(ident: TSIdent) -> Self {
+        ImportSpecifier { ident, alias: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(source = "import {{ ident }} = {{ entity|join(\".\") }};", ext = "txt")]
+/// An import alias declaration, that gives a local name to an entity of a namespace
+pub struct ImportAliasDeclaration {
+    pub ident: TSIdent,
+    pub entity: Vec<TSIdent>,
 }
 
 #[derive(Debug, Clone, PartialEq, Display)]
@@ -40,8 +77,8 @@ pub mod tests {
         assert_eq!(
             ImportList {
                 items: vec![
-                    TSIdent::from_str("Test").unwrap(),
-                    TSIdent::from_str("TestOther").unwrap()
+                    TSIdent::from_str("Test").unwrap().into(),
+                    TSIdent::from_str("TestOther").unwrap().into()
                 ],
             }
             .to_string(),
@@ -49,12 +86,30 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn display_import_specifier() {
+        assert_eq!(
+            ImportList {
+                items: vec![
+                    ImportSpecifier {
+                        ident: TSIdent::from_str("User").unwrap(),
+                        alias: Some(TSIdent::from_str("MUser").unwrap()),
+                    },
+                    TSIdent::from_str("Group").unwrap().into()
+                ],
+            }
+            .to_string(),
+            "User as MUser, Group",
+        );
+    }
+
     #[test]
     fn display_import_statement() {
         assert_eq!(
             ImportStatement {
                 import_kind: ImportKind::Identifier(TSIdent::from_str("Test").unwrap()),
                 path: r#""types/users""#.to_string(),
+                type_only: false,
             }
             .to_string(),
             r#"import Test from "types/users";"#
@@ -64,6 +119,7 @@ pub mod tests {
             ImportStatement {
                 import_kind: ImportKind::GlobAsIdentifier(TSIdent::from_str("Test").unwrap()),
                 path: r#""types/users""#.to_string(),
+                type_only: false,
             }
             .to_string(),
             r#"import * as Test from "types/users";"#
@@ -73,14 +129,43 @@ pub mod tests {
             ImportStatement {
                 import_kind: ImportKind::ImportList(ImportList {
                     items: vec![
-                        TSIdent::from_str("Test").unwrap(),
-                        TSIdent::from_str("TestOther").unwrap()
+                        TSIdent::from_str("Test").unwrap().into(),
+                        TSIdent::from_str("TestOther").unwrap().into()
                     ]
                 }),
                 path: r#""types/users""#.to_string(),
+                type_only: false,
             }
             .to_string(),
             r#"import { Test, TestOther } from "types/users";"#
         );
+
+        assert_eq!(
+            ImportStatement {
+                import_kind: ImportKind::ImportList(ImportList {
+                    items: vec![TSIdent::from_str("Test").unwrap().into()]
+                }),
+                path: r#""types/users""#.to_string(),
+                type_only: true,
+            }
+            .to_string(),
+            r#"import type { Test } from "types/users";"#
+        );
+    }
+
+    #[test]
+    fn display_import_alias_declaration() {
+        assert_eq!(
+            ImportAliasDeclaration {
+                ident: TSIdent::from_str("User").unwrap(),
+                entity: vec![
+                    TSIdent::from_str("my_crate").unwrap(),
+                    TSIdent::from_str("models").unwrap(),
+                    TSIdent::from_str("User").unwrap(),
+                ],
+            }
+            .to_string(),
+            "import User = my_crate.models.User;"
+        );
     }
 }
@@ -3,6 +3,8 @@
 //! * interfaces declarations,
 //! * type aliases declarations,
 //! * const enums declarations
+//! * namespaces declarations
+//! * functions returning JSON-like values (e.g. the mocks of the declared types)
 //!
 //! This subset allows to represent types in TypeScript that can get deserialized from JSON.
 //!
@@ -15,6 +17,9 @@
 pub mod common;
 pub mod declarations;
 pub mod export;
+pub mod expressions;
+pub mod format;
 pub mod ident;
 pub mod import;
 pub mod types;
+pub mod visit_mut;
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use crate::{
-    common::{filters, BooleanLiteral, NumericLiteral, StringLiteral},
+    common::{filters, BooleanLiteral, DocComment, NumericLiteral, StringLiteral},
     ident::TSIdent,
 };
 use askama::Template;
@@ -32,12 +32,15 @@ pub struct TypeParameters {
 
 #[derive(Debug, Clone, PartialEq, Template)]
 #[template(
-    source = "{{ identifier -}} {{ constraint|display_opt -}}",
+    source = "{{ identifier -}} {{ constraint|display_opt -}}
+    {%- match default %}{% when Some with (default) %} = {{ default }}{% when None %}{% endmatch %}",
     ext = "txt"
 )]
 pub struct TypeParameter {
     pub identifier: TSIdent,
     pub constraint: Option<ExtendsConstraint>,
+    /// The type used when the parameter is omitted, e.g. `T = number`
+    pub default: Option<TsType>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Template)]
@@ -117,6 +120,51 @@ pub struct IntersectionType {
     pub types: Vec<TsType>,
 }
 
+impl UnionType {
+    /// The union of any types, e.g. `A | B | null` : the nested unions are merged and the duplicated types left out.
+    ///
+    /// A single type is given back as is, and the union of no types is `never`.
+    pub fn of(types: Vec<TsType>) -> TsType {
+        let mut union: Vec<TsType> = Vec::new();
+        for ty in types.into_iter().flat_map(|ty| match ty {
+            TsType::UnionType(nested) => nested.types,
+            other => vec![other],
+        }) {
+            if !union.contains(&ty) {
+                union.push(ty);
+            }
+        }
+        match union.len() {
+            0 => TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Never)),
+            1 => union.remove(0),
+            _ => TsType::UnionType(UnionType { types: union }),
+        }
+    }
+}
+
+impl IntersectionType {
+    /// The intersection of any types, e.g. `A & (B | C)` : the unions are parenthesized and the nested intersections merged.
+    ///
+    /// A single type is given back as is.
+    pub fn of(types: Vec<TsType>) -> TsType {
+        let mut types: Vec<TsType> = types
+            .into_iter()
+            .flat_map(|ty| match ty {
+                TsType::IntersectionType(intersection) => intersection.types,
+                TsType::UnionType(union) => vec![TsType::ParenthesizedType(ParenthesizedType {
+                    inner: Box::new(TsType::UnionType(union)),
+                })],
+                other => vec![other],
+            })
+            .collect();
+        if types.len() == 1 {
+            types.remove(0)
+        } else {
+            TsType::IntersectionType(IntersectionType { types })
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Template)]
 #[template(source = "( {{ inner }} )", ext = "txt")]
 /// A TS type surrounded by parenthesis
@@ -139,22 +187,56 @@ pub struct TypeBody {
 
 #[derive(Debug, Clone, PartialEq, Display, FromVariants)]
 /// A member is a part of a type. It can be a property or a method, though
-/// we currently only support type Properties.
+/// we currently only support type Properties and index signatures.
 pub enum TypeMember {
     #[display("{0}")]
     PropertySignature(PropertySignature),
+    #[display("{0}")]
+    IndexSignature(IndexSignature),
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{% if readonly %}{{ \"readonly \" }}{% endif %}[{{ key }}: {{ key_type }}]: {{ inner_type }}",
+    ext = "txt"
+)]
+/// The type of the properties of an object, by the type of their key : `[key: string]: number`
+pub struct IndexSignature {
+    pub key: TSIdent,
+    /// `string`, `number` or a template literal type
+    pub key_type: TsType,
+    pub readonly: bool,
+    pub inner_type: TsType,
+}
+
+#[derive(Debug, Clone, PartialEq, Template)]
+#[template(
+    source = "{\n\t{% if readonly %}{{ \"readonly \" }}{% endif %}[{{ key }} in {{ keys }}]{% if optional %}?{% endif %}: {{ inner_type }}\n}",
+    ext = "txt"
+)]
+/// An object type with a property for each type of a union, e.g. `{ [K in "a" | "b"]: number }`
+pub struct MappedType {
+    pub key: TSIdent,
+    /// The union of the keys, e.g. `"a" | "b"` or `keyof T`
+    pub keys: Box<TsType>,
+    pub readonly: bool,
+    pub optional: bool,
+    /// The type of the properties, which can refer to `key`
+    pub inner_type: Box<TsType>,
 }
 
 #[derive(Debug, Clone, PartialEq, Template)]
 #[template(
-    source = "{{ name }}{% if optional %}?{% endif %}: {{ inner_type }}",
+    source = "{{ doc|member_doc }}{% if readonly %}{{ \"readonly \" }}{% endif %}{{ name }}{% if optional %}?{% endif %}: {{ inner_type }}",
     ext = "txt"
 )]
 /// An object property definition
 pub struct PropertySignature {
     pub name: PropertyName,
     pub optional: bool,
+    pub readonly: bool,
     pub inner_type: TsType,
+    pub doc: Option<DocComment>,
 }
 
 #[derive(Debug, Clone, PartialEq, Display, FromVariants)]
@@ -185,13 +267,24 @@ pub enum PrimaryType {
     #[display("{0}")]
     ObjectType(ObjectType),
     #[display("{0}")]
+    MappedType(MappedType),
+    #[display("{0}")]
     ArrayType(ArrayType),
     #[display("{0}")]
     TupleType(TupleType),
     #[display("{0}")]
     LiteralType(LiteralType),
+    #[display("{0}")]
+    RawType(RawType),
 }
 
+#[derive(Debug, Clone, PartialEq, Display)]
+#[display("{0}")]
+/// A TS type written by hand, output as is.
+///
+/// It is not parsed, so it should only be used where its precedence does not matter (e.g. as the type of a type alias).
+pub struct RawType(pub String);
+
 #[derive(Debug, Clone, PartialEq, Display)]
 /// A globally defined TS type
 pub enum PredefinedType {
@@ -199,6 +292,8 @@ pub enum PredefinedType {
     Any,
     #[display("number")]
     Number,
+    #[display("bigint")]
+    BigInt,
     #[display("boolean")]
     Boolean,
     #[display("string")]
@@ -234,13 +329,92 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn display_intersection_of_any_type() {
+        let reference = |name: &str| {
+            TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+                name: TSIdent::from_str(name).unwrap(),
+                args: None,
+            }))
+        };
+        assert_eq!(
+            IntersectionType::of(vec![
+                reference("A"),
+                TsType::UnionType(UnionType {
+                    types: vec![reference("B"), reference("C")]
+                }),
+                IntersectionType::of(vec![reference("D"), reference("E")]),
+            ])
+            .to_string(),
+            "A & ( B | C ) & D & E"
+        );
+        assert_eq!(IntersectionType::of(vec![reference("A")]).to_string(), "A");
+    }
+
+    #[test]
+    fn display_union_of_any_type() {
+        let null = || TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Null));
+        let number = || TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number));
+        assert_eq!(
+            UnionType::of(vec![
+                UnionType::of(vec![number(), null()]),
+                null(),
+                TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
+            ])
+            .to_string(),
+            "number | null | string"
+        );
+        assert_eq!(UnionType::of(vec![null(), null()]).to_string(), "null");
+        assert_eq!(UnionType::of(vec![]).to_string(), "never");
+    }
+
+    #[test]
+    fn display_index_signature() {
+        assert_eq!(
+            IndexSignature {
+                key: TSIdent::from_str("key").unwrap(),
+                key_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+            }
+            .to_string(),
+            "[key: string]: number"
+        );
+    }
+
+    #[test]
+    fn display_mapped_type() {
+        let literal = |value: &str| {
+            TsType::PrimaryType(PrimaryType::LiteralType(LiteralType::StringLiteral(
+                StringLiteral::from_raw(value),
+            )))
+        };
+        assert_eq!(
+            MappedType {
+                key: TSIdent::from_str("K").unwrap(),
+                keys: Box::new(TsType::UnionType(UnionType {
+                    types: vec![literal("a"), literal("b")]
+                })),
+                readonly: true,
+                optional: true,
+                inner_type: Box::new(TsType::PrimaryType(PrimaryType::Predefined(
+                    PredefinedType::Number
+                ))),
+            }
+            .to_string(),
+            "{\n\treadonly [K in \"a\" | \"b\"]?: number\n}"
+        );
+    }
+
     #[test]
     fn display_property_signature() {
         assert_eq!(
             PropertySignature {
                 name: PropertyName::Identifier(TSIdent::from_str("test").unwrap()),
                 optional: false,
-                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String))
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
+                doc: None,
             }
             .to_string(),
             "test: string"
@@ -250,7 +424,9 @@ pub mod tests {
             PropertySignature {
                 name: PropertyName::Identifier(TSIdent::from_str("test").unwrap()),
                 optional: true,
-                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number))
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+                doc: None,
             }
             .to_string(),
             "test?: number"
@@ -260,11 +436,25 @@ pub mod tests {
             PropertySignature {
                 name: PropertyName::StringLiteral(StringLiteral::from_raw("test")),
                 optional: true,
-                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number))
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+                doc: None,
             }
             .to_string(),
             r#""test"?: number"#
         );
+
+        assert_eq!(
+            PropertySignature {
+                name: PropertyName::Identifier(TSIdent::from_str("test").unwrap()),
+                optional: true,
+                readonly: true,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+                doc: None,
+            }
+            .to_string(),
+            "readonly test?: number"
+        );
     }
 
     #[test]
@@ -275,16 +465,20 @@ pub mod tests {
                     TypeMember::PropertySignature(PropertySignature {
                         name: PropertyName::Identifier(TSIdent::from_str("test").unwrap()),
                         optional: false,
+                        readonly: false,
                         inner_type: TsType::PrimaryType(PrimaryType::Predefined(
                             PredefinedType::Number
-                        ))
+                        )),
+                        doc: None,
                     }),
                     TypeMember::PropertySignature(PropertySignature {
                         name: PropertyName::StringLiteral(StringLiteral::from_raw("test_other")),
                         optional: false,
+                        readonly: false,
                         inner_type: TsType::PrimaryType(PrimaryType::Predefined(
                             PredefinedType::Any
-                        ))
+                        )),
+                        doc: None,
                     }),
                 ]
             }
@@ -347,6 +541,7 @@ pub mod tests {
                 parameters: vec![TypeParameter {
                     identifier: TSIdent::from_str("MyType").unwrap(),
                     constraint: None,
+                    default: None,
                 },]
             }
             .to_string(),
@@ -364,11 +559,33 @@ pub mod tests {
                         types: vec![TsType::PrimaryType(PrimaryType::Predefined(
                             PredefinedType::String
                         )),],
-                    })
+                    }),
+                    default: None,
                 },]
             }
             .to_string(),
             "<MyType extends string>",
         )
     }
+
+    #[test]
+    fn display_type_parameters_with_default() {
+        assert_eq!(
+            TypeParameters {
+                parameters: vec![TypeParameter {
+                    identifier: TSIdent::from_str("MyType").unwrap(),
+                    constraint: Some(ExtendsConstraint {
+                        types: vec![TsType::PrimaryType(PrimaryType::Predefined(
+                            PredefinedType::String
+                        )),],
+                    }),
+                    default: Some(TsType::PrimaryType(PrimaryType::LiteralType(
+                        LiteralType::StringLiteral(StringLiteral::from_raw("a"))
+                    ))),
+                },]
+            }
+            .to_string(),
+            "<MyType extends string = \"a\">",
+        )
+    }
 }
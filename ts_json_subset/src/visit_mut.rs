@@ -0,0 +1,352 @@
+//! Mutable traversal of the AST.
+//!
+//! Implement [VisitMut] and override the methods of the nodes you are interested in.
+//! The default implementations recurse into the children with the free functions of this module,
+//! which are to be called as well from an overridden method to keep on walking the tree.
+use crate::{
+    declarations::{
+        ambient::{AmbientFunctionDeclaration, AmbientVariableDeclaration},
+        function::FunctionDeclaration,
+        interface::InterfaceDeclaration,
+        namespace::{NamespaceDeclaration, NamespaceElement},
+        type_alias::TypeAliasDeclaration,
+        variable::VariableDeclaration,
+    },
+    export::ExportStatement,
+    expressions::{Expression, ObjectLiteralMember},
+    types::{
+        ObjectType, PrimaryType, TsType, TypeArguments, TypeMember, TypeParameters, TypeReference,
+    },
+};
+
+pub trait VisitMut {
+    fn visit_export_statement_mut(&mut self, node: &mut ExportStatement) {
+        visit_export_statement_mut(self, node)
+    }
+
+    fn visit_interface_declaration_mut(&mut self, node: &mut InterfaceDeclaration) {
+        visit_interface_declaration_mut(self, node)
+    }
+
+    fn visit_type_alias_declaration_mut(&mut self, node: &mut TypeAliasDeclaration) {
+        visit_type_alias_declaration_mut(self, node)
+    }
+
+    fn visit_namespace_declaration_mut(&mut self, node: &mut NamespaceDeclaration) {
+        visit_namespace_declaration_mut(self, node)
+    }
+
+    fn visit_function_declaration_mut(&mut self, node: &mut FunctionDeclaration) {
+        visit_function_declaration_mut(self, node)
+    }
+
+    fn visit_variable_declaration_mut(&mut self, node: &mut VariableDeclaration) {
+        visit_variable_declaration_mut(self, node)
+    }
+
+    fn visit_ambient_function_declaration_mut(&mut self, node: &mut AmbientFunctionDeclaration) {
+        visit_ambient_function_declaration_mut(self, node)
+    }
+
+    fn visit_ambient_variable_declaration_mut(&mut self, node: &mut AmbientVariableDeclaration) {
+        visit_ambient_variable_declaration_mut(self, node)
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        visit_expression_mut(self, node)
+    }
+
+    fn visit_type_parameters_mut(&mut self, node: &mut TypeParameters) {
+        visit_type_parameters_mut(self, node)
+    }
+
+    fn visit_ts_type_mut(&mut self, node: &mut TsType) {
+        visit_ts_type_mut(self, node)
+    }
+
+    fn visit_primary_type_mut(&mut self, node: &mut PrimaryType) {
+        visit_primary_type_mut(self, node)
+    }
+
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        visit_type_reference_mut(self, node)
+    }
+
+    fn visit_type_arguments_mut(&mut self, node: &mut TypeArguments) {
+        visit_type_arguments_mut(self, node)
+    }
+
+    fn visit_object_type_mut(&mut self, node: &mut ObjectType) {
+        visit_object_type_mut(self, node)
+    }
+
+    fn visit_type_member_mut(&mut self, node: &mut TypeMember) {
+        visit_type_member_mut(self, node)
+    }
+}
+
+pub fn visit_export_statement_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExportStatement) {
+    match node {
+        ExportStatement::InterfaceDeclaration(node) => v.visit_interface_declaration_mut(node),
+        ExportStatement::TypeAliasDeclaration(node) => v.visit_type_alias_declaration_mut(node),
+        ExportStatement::NamespaceDeclaration(node) => v.visit_namespace_declaration_mut(node),
+        ExportStatement::FunctionDeclaration(node) => v.visit_function_declaration_mut(node),
+        ExportStatement::VariableDeclaration(node) => v.visit_variable_declaration_mut(node),
+        ExportStatement::AmbientFunctionDeclaration(node) => {
+            v.visit_ambient_function_declaration_mut(node)
+        }
+        ExportStatement::AmbientVariableDeclaration(node) => {
+            v.visit_ambient_variable_declaration_mut(node)
+        }
+        ExportStatement::EnumDeclaration(_)
+        | ExportStatement::ConstEnumDeclaration(_)
+        | ExportStatement::ReexportDeclaration(_) => {}
+    }
+}
+
+pub fn visit_interface_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut InterfaceDeclaration,
+) {
+    if let Some(type_params) = &mut node.type_params {
+        v.visit_type_parameters_mut(type_params);
+    }
+    if let Some(extends_clause) = &mut node.extends_clause {
+        extends_clause
+            .type_list
+            .identifiers
+            .iter_mut()
+            .for_each(|reference| v.visit_type_reference_mut(reference));
+    }
+    v.visit_object_type_mut(&mut node.obj_type);
+}
+
+pub fn visit_type_alias_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut TypeAliasDeclaration,
+) {
+    if let Some(type_params) = &mut node.type_params {
+        v.visit_type_parameters_mut(type_params);
+    }
+    v.visit_ts_type_mut(&mut node.inner_type);
+}
+
+pub fn visit_namespace_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut NamespaceDeclaration,
+) {
+    node.elements.iter_mut().for_each(|element| match element {
+        NamespaceElement::ExportStatement(node) => v.visit_export_statement_mut(node),
+        NamespaceElement::ImportAliasDeclaration(_) => {}
+    });
+}
+
+pub fn visit_function_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut FunctionDeclaration,
+) {
+    if let Some(type_params) = &mut node.type_params {
+        v.visit_type_parameters_mut(type_params);
+    }
+    node.params
+        .iter_mut()
+        .for_each(|param| v.visit_ts_type_mut(&mut param.param_type));
+    v.visit_ts_type_mut(&mut node.return_type);
+    v.visit_expression_mut(&mut node.body);
+}
+
+pub fn visit_variable_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut VariableDeclaration,
+) {
+    if let Some(type_annotation) = &mut node.type_annotation {
+        v.visit_ts_type_mut(type_annotation);
+    }
+    v.visit_expression_mut(&mut node.value);
+}
+
+pub fn visit_ambient_function_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut AmbientFunctionDeclaration,
+) {
+    if let Some(type_params) = &mut node.type_params {
+        v.visit_type_parameters_mut(type_params);
+    }
+    node.params
+        .iter_mut()
+        .for_each(|param| v.visit_ts_type_mut(&mut param.param_type));
+    v.visit_ts_type_mut(&mut node.return_type);
+}
+
+pub fn visit_ambient_variable_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut AmbientVariableDeclaration,
+) {
+    v.visit_ts_type_mut(&mut node.type_annotation);
+}
+
+/// Only the types of the expressions are visited, not the identifiers
+pub fn visit_expression_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Expression) {
+    match node {
+        Expression::ArrayLiteral(node) => node
+            .elements
+            .iter_mut()
+            .for_each(|element| v.visit_expression_mut(element)),
+        Expression::ObjectLiteral(node) => {
+            node.members.iter_mut().for_each(|member| match member {
+                ObjectLiteralMember::Property(_, value) => v.visit_expression_mut(value),
+                ObjectLiteralMember::Spread(value) => v.visit_expression_mut(value),
+            })
+        }
+        Expression::CallExpression(node) => {
+            v.visit_expression_mut(node.callee.as_mut());
+            if let Some(type_args) = &mut node.type_args {
+                v.visit_type_arguments_mut(type_args);
+            }
+            node.args
+                .iter_mut()
+                .for_each(|arg| v.visit_expression_mut(arg));
+        }
+        Expression::AsExpression(node) => {
+            v.visit_expression_mut(node.expression.as_mut());
+            v.visit_ts_type_mut(&mut node.as_type);
+        }
+        Expression::CoalesceExpression(node) => {
+            v.visit_expression_mut(node.left.as_mut());
+            v.visit_expression_mut(node.right.as_mut());
+        }
+        Expression::ArrowFunction(node) => {
+            if let Some(type_params) = &mut node.type_params {
+                v.visit_type_parameters_mut(type_params);
+            }
+            node.params
+                .iter_mut()
+                .for_each(|param| v.visit_ts_type_mut(&mut param.param_type));
+            if let Some(return_type) = &mut node.return_type {
+                v.visit_ts_type_mut(return_type);
+            }
+            v.visit_expression_mut(node.body.as_mut());
+        }
+        Expression::Literal(_)
+        | Expression::Null
+        | Expression::Undefined
+        | Expression::Identifier(_)
+        | Expression::MemberExpression(_) => {}
+    }
+}
+
+pub fn visit_type_parameters_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeParameters) {
+    node.parameters.iter_mut().for_each(|parameter| {
+        parameter
+            .constraint
+            .iter_mut()
+            .flat_map(|constraint| constraint.types.iter_mut())
+            .chain(parameter.default.as_mut())
+            .for_each(|ty| v.visit_ts_type_mut(ty))
+    });
+}
+
+pub fn visit_ts_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TsType) {
+    match node {
+        TsType::PrimaryType(node) => v.visit_primary_type_mut(node),
+        TsType::UnionType(node) => node.types.iter_mut().for_each(|ty| v.visit_ts_type_mut(ty)),
+        TsType::IntersectionType(node) => {
+            node.types.iter_mut().for_each(|ty| v.visit_ts_type_mut(ty))
+        }
+        TsType::ParenthesizedType(node) => v.visit_ts_type_mut(node.inner.as_mut()),
+    }
+}
+
+pub fn visit_primary_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PrimaryType) {
+    match node {
+        PrimaryType::TypeReference(node) => v.visit_type_reference_mut(node),
+        PrimaryType::ObjectType(node) => v.visit_object_type_mut(node),
+        PrimaryType::MappedType(node) => {
+            v.visit_ts_type_mut(node.keys.as_mut());
+            v.visit_ts_type_mut(node.inner_type.as_mut());
+        }
+        PrimaryType::ArrayType(node) => v.visit_primary_type_mut(node.inner_type.as_mut()),
+        PrimaryType::TupleType(node) => node
+            .inner_types
+            .iter_mut()
+            .for_each(|ty| v.visit_ts_type_mut(ty)),
+        PrimaryType::Predefined(_) | PrimaryType::LiteralType(_) | PrimaryType::RawType(_) => {}
+    }
+}
+
+pub fn visit_type_reference_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeReference) {
+    if let Some(args) = &mut node.args {
+        v.visit_type_arguments_mut(args);
+    }
+}
+
+pub fn visit_type_arguments_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeArguments) {
+    node.types.iter_mut().for_each(|ty| v.visit_ts_type_mut(ty));
+}
+
+pub fn visit_object_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ObjectType) {
+    node.body
+        .members
+        .iter_mut()
+        .for_each(|member| v.visit_type_member_mut(member));
+}
+
+pub fn visit_type_member_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeMember) {
+    match node {
+        TypeMember::PropertySignature(node) => v.visit_ts_type_mut(&mut node.inner_type),
+        TypeMember::IndexSignature(node) => {
+            v.visit_ts_type_mut(&mut node.key_type);
+            v.visit_ts_type_mut(&mut node.inner_type);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        ident::TSIdent,
+        types::{ArrayType, PropertyName, PropertySignature, TypeBody},
+    };
+
+    struct Renamer;
+
+    impl VisitMut for Renamer {
+        fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+            node.name = TSIdent::from_str(&format!("Renamed{}", node.name)).unwrap();
+            visit_type_reference_mut(self, node);
+        }
+    }
+
+    fn reference(name: &str, args: Option<Vec<TsType>>) -> PrimaryType {
+        PrimaryType::TypeReference(TypeReference {
+            name: TSIdent::from_str(name).unwrap(),
+            args: args.map(|types| TypeArguments { types }),
+        })
+    }
+
+    #[test]
+    fn should_visit_nested_references() {
+        let mut object = ObjectType {
+            body: TypeBody {
+                members: vec![PropertySignature {
+                    name: PropertyName::from("users".to_string()),
+                    optional: false,
+                    readonly: false,
+                    inner_type: TsType::PrimaryType(PrimaryType::ArrayType(ArrayType::new(
+                        reference("Page", Some(vec![reference("User", None).into()])),
+                    ))),
+                    doc: None,
+                }
+                .into()],
+            },
+        };
+        Renamer.visit_object_type_mut(&mut object);
+        assert_eq!(
+            object.to_string(),
+            "{\n\tusers: RenamedPage<RenamedUser>[]\n}"
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Parsing of the `#[ts(...)]` attributes.
+//!
+//! `serde` attributes describe the wire format, but some choices only concern the TypeScript side.
+//! Those are expressed with a dedicated `ts` attribute namespace, e.g. :
+//!
+//! ```text
+//! #[ts(fields("min", "max"))]
+//! pub struct Range(u32, u32);
+//! ```
+//!
+//! The fields take their own `#[ts(...)]` attributes, which take precedence over the serde ones :
+//!
+//! ```text
+//! pub struct User {
+//!     #[ts(rename = "userId", type = "string")]
+//!     id: Uuid,
+//!     #[ts(optional)]
+//!     nickname: String,
+//! }
+//! ```
+//!
+//! Note that `rustc` rejects unknown attributes, so the `ts` namespace must be made inert in your crate
+//! (by a derive macro declaring it as a helper attribute, or behind a `cfg_attr`).
+use std::collections::HashSet;
+
+use serde_derive_internals::{ast::Container, Ctxt, Derive};
+use syn::{Attribute, Data, DeriveInput, Lit, Meta, MetaNameValue, NestedMeta};
+use ts_json_subset::common::DocComment;
+
+use crate::{contexts::exporter::Direction, error::TsExportError};
+
+const TS_ATTRIBUTE: &str = "ts";
+const SERDE_ATTRIBUTE: &str = "serde";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The `#[ts(...)]` attributes found on a container (a struct or an enum)
+pub struct ContainerAttributes {
+    /// `#[ts(fields("a", "b"))]`: names the members of a tuple struct, which is then exported as an object
+    pub fields: Option<Vec<String>>,
+    /// `#[ts(type = "string | number")]`: the TS type of a container that implements `Serialize` by hand, output as is
+    pub ts_type: Option<String>,
+    /// `#[ts(as = "Vec<String>")]`: a Rust type that is serialized like the container, which is exported as an alias of it
+    pub as_type: Option<String>,
+    /// `#[ts(nominal)]`: brands a newtype struct, `UserId = number & { readonly __brand: unique symbol }`,
+    /// so that it is not assignable from another type with the same content
+    pub nominal: bool,
+    /// `#[ts(rename = "UserDto")]`: the name of the declaration, whatever the [TypeNamePolicy](crate::contexts::exporter::TypeNamePolicy)
+    pub rename: Option<String>,
+    /// `#[ts(skip)]`: the container isn't exported, see [ProcessFilter](crate::pipeline::filter::ProcessFilter)
+    pub skip: bool,
+    /// `#[ts(export)]`: the container is exported whatever the [ProcessFilter](crate::pipeline::filter::ProcessFilter) rules
+    pub export: bool,
+}
+
+impl ContainerAttributes {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, TsExportError> {
+        let mut container_attributes = ContainerAttributes::default();
+        for meta in parse_ts_metas(attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("fields") => {
+                    let fields = list
+                        .nested
+                        .iter()
+                        .map(|nested| match nested {
+                            NestedMeta::Lit(Lit::Str(lit)) => Ok(lit.value()),
+                            _ => Err(invalid_attribute(nested)),
+                        })
+                        .collect::<Result<Vec<String>, _>>()?;
+                    container_attributes.fields = Some(fields);
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("type")
+                        || name_value.path.is_ident("as")
+                        || name_value.path.is_ident("rename") =>
+                {
+                    let value = match &name_value.lit {
+                        Lit::Str(lit) => lit.value(),
+                        _ => return Err(invalid_attribute(&meta)),
+                    };
+                    if name_value.path.is_ident("type") {
+                        container_attributes.ts_type = Some(value);
+                    } else if name_value.path.is_ident("as") {
+                        container_attributes.as_type = Some(value);
+                    } else {
+                        container_attributes.rename = Some(value);
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("nominal") => {
+                    container_attributes.nominal = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    container_attributes.skip = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("export") => {
+                    container_attributes.export = true;
+                }
+                _ => return Err(invalid_attribute(&meta)),
+            }
+        }
+        if container_attributes.ts_type.is_some() && container_attributes.as_type.is_some() {
+            return Err(TsExportError::InvalidAttribute(
+                "ts(type) and ts(as) are mutually exclusive".to_string(),
+            ));
+        }
+        if container_attributes.skip && container_attributes.export {
+            return Err(TsExportError::InvalidAttribute(
+                "ts(skip) and ts(export) are mutually exclusive".to_string(),
+            ));
+        }
+        Ok(container_attributes)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The `#[ts(...)]` attributes found on a field, which override what serde describes
+pub struct FieldAttributes {
+    /// `#[ts(rename = "userId")]`: the name of the property
+    pub rename: Option<String>,
+    /// `#[ts(type = "string")]`: the TS type of the property, output as is instead of being solved
+    pub ts_type: Option<String>,
+    /// `#[ts(skip)]`: the field is left out of the declaration
+    pub skip: bool,
+    /// `#[ts(optional)]`: the property may be missing, `nickname?: string`
+    pub optional: bool,
+}
+
+impl FieldAttributes {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, TsExportError> {
+        let mut field_attributes = FieldAttributes::default();
+        for meta in parse_ts_metas(attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("type") || name_value.path.is_ident("rename") =>
+                {
+                    let value = match &name_value.lit {
+                        Lit::Str(lit) => lit.value(),
+                        _ => return Err(invalid_attribute(&meta)),
+                    };
+                    if name_value.path.is_ident("type") {
+                        field_attributes.ts_type = Some(value);
+                    } else {
+                        field_attributes.rename = Some(value);
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    field_attributes.skip = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("optional") => {
+                    field_attributes.optional = true;
+                }
+                _ => return Err(invalid_attribute(&meta)),
+            }
+        }
+        Ok(field_attributes)
+    }
+}
+
+/// `serde_derive_internals` predates the variant-level `#[serde(untagged)]`, and rejects it.
+///
+/// Removes it from the variants of an enum, so that the input can be parsed, and returns the names of the untagged variants.
+pub fn take_untagged_variants(
+    derive_input: &mut DeriveInput,
+) -> Result<HashSet<String>, TsExportError> {
+    let mut untagged_variants = HashSet::new();
+    let variants = match &mut derive_input.data {
+        Data::Enum(data) => &mut data.variants,
+        _ => return Ok(untagged_variants),
+    };
+    for variant in variants.iter_mut() {
+        let mut attrs = Vec::with_capacity(variant.attrs.len());
+        for mut attr in variant.attrs.drain(..) {
+            if !attr.path.is_ident(SERDE_ATTRIBUTE) {
+                attrs.push(attr);
+                continue;
+            }
+            let nested: Vec<NestedMeta> = match attr.parse_meta()? {
+                Meta::List(list) => list.nested.into_iter().collect(),
+                other => return Err(invalid_attribute(&other)),
+            };
+            let (untagged, others): (Vec<NestedMeta>, Vec<NestedMeta>) =
+                nested.into_iter().partition(|meta| {
+                    matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("untagged"))
+                });
+            if untagged.is_empty() {
+                attrs.push(attr);
+                continue;
+            }
+            untagged_variants.insert(variant.ident.to_string());
+            if !others.is_empty() {
+                attr.tokens = quote::quote!((#(#others),*));
+                attrs.push(attr);
+            }
+        }
+        variant.attrs = attrs;
+    }
+    Ok(untagged_variants)
+}
+
+/// `serde_derive_internals` predates the container-level `#[serde(rename_all_fields = "...")]` of enums, and rejects it.
+///
+/// Removes it from the container, and applies it as a `#[serde(rename_all = "...")]` to the variants that don't have one,
+/// which is what serde does.
+pub fn apply_rename_all_fields(derive_input: &mut DeriveInput) -> Result<(), TsExportError> {
+    let variants = match &mut derive_input.data {
+        Data::Enum(data) => &mut data.variants,
+        _ => return Ok(()),
+    };
+    let mut rule: Option<Lit> = None;
+    let mut attrs = Vec::with_capacity(derive_input.attrs.len());
+    for mut attr in derive_input.attrs.drain(..) {
+        if !attr.path.is_ident(SERDE_ATTRIBUTE) {
+            attrs.push(attr);
+            continue;
+        }
+        let nested: Vec<NestedMeta> = match attr.parse_meta()? {
+            Meta::List(list) => list.nested.into_iter().collect(),
+            other => return Err(invalid_attribute(&other)),
+        };
+        let (rename_all_fields, others): (Vec<NestedMeta>, Vec<NestedMeta>) =
+            nested.into_iter().partition(|meta| {
+                matches!(meta, NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("rename_all_fields"))
+            });
+        for meta in rename_all_fields.iter() {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    rule = Some(name_value.lit.clone())
+                }
+                other => return Err(invalid_attribute(other)),
+            }
+        }
+        if rename_all_fields.is_empty() {
+            attrs.push(attr);
+        } else if !others.is_empty() {
+            attr.tokens = quote::quote!((#(#others),*));
+            attrs.push(attr);
+        }
+    }
+    derive_input.attrs = attrs;
+
+    let rule = match rule {
+        Some(rule) => rule,
+        None => return Ok(()),
+    };
+    for variant in variants.iter_mut() {
+        let has_rename_all = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(SERDE_ATTRIBUTE))
+            .any(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested.iter().any(|meta| {
+                    matches!(meta, NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("rename_all"))
+                }),
+                _ => false,
+            });
+        if !has_rename_all {
+            variant
+                .attrs
+                .push(syn::parse_quote!(#[serde(rename_all = #rule)]));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the serde attributes of a declaration.
+///
+/// The attributes that `serde_derive_internals` rejects are logged and ignored, as the crate compiles with them
+pub fn serde_container(derive_input: &DeriveInput, direction: Direction) -> Option<Container<'_>> {
+    let ctxt = Ctxt::new();
+    let derive = match direction {
+        Direction::Serialize => Derive::Serialize,
+        Direction::Deserialize => Derive::Deserialize,
+    };
+    let container = Container::from_ast(&ctxt, derive_input, derive);
+    if let Err(errors) = ctxt.check() {
+        for error in errors {
+            log::warn!(
+                "Ignoring a serde attribute of {} : {}",
+                derive_input.ident,
+                error
+            );
+        }
+    }
+    container
+}
+
+/// The doc comment of an item, from its `///` comments (`#[doc = "..."]` attributes)
+pub fn doc_comment(attrs: &[Attribute]) -> Option<DocComment> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(text),
+                ..
+            })) => Some(text.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+    DocComment::new(&lines.join("\n"))
+}
+
+/// Collects the content of every `#[ts(...)]` attribute
+fn parse_ts_metas(attrs: &[Attribute]) -> Result<Vec<NestedMeta>, TsExportError> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(TS_ATTRIBUTE)) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            other => return Err(invalid_attribute(&other)),
+        }
+    }
+    Ok(metas)
+}
+
+fn invalid_attribute<T: quote::ToTokens>(tokens: &T) -> TsExportError {
+    TsExportError::InvalidAttribute(tokens.to_token_stream().to_string())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use syn::DeriveInput;
+
+    fn container_attributes(input: &str) -> Result<ContainerAttributes, TsExportError> {
+        let derive_input: DeriveInput = syn::parse_str(input).expect("Failed to parse input");
+        ContainerAttributes::from_attrs(&derive_input.attrs)
+    }
+
+    #[test]
+    fn should_parse_fields() {
+        let attributes = container_attributes(
+            r#"
+            #[derive(Serialize)]
+            #[ts(fields("min", "max"))]
+            pub struct Range(u32, u32);
+            "#,
+        )
+        .expect("Failed to parse attributes");
+        assert_eq!(
+            attributes.fields,
+            Some(vec!["min".to_string(), "max".to_string()])
+        );
+    }
+
+    #[test]
+    fn should_parse_overrides() {
+        let attributes = container_attributes(
+            r#"
+            #[ts(type = "string | number")]
+            pub struct Id(Inner);
+            "#,
+        )
+        .expect("Failed to parse attributes");
+        assert_eq!(attributes.ts_type, Some("string | number".to_string()));
+
+        let attributes = container_attributes(
+            r#"
+            #[ts(as = "Vec<String>")]
+            pub struct Tags(HashSet<Tag>);
+            "#,
+        )
+        .expect("Failed to parse attributes");
+        assert_eq!(attributes.as_type, Some("Vec<String>".to_string()));
+
+        assert!(matches!(
+            container_attributes(r#"#[ts(type = "string", as = "String")] pub struct Id(Inner);"#),
+            Err(TsExportError::InvalidAttribute(_))
+        ));
+    }
+
+    #[test]
+    fn should_parse_nominal() {
+        let attributes = container_attributes(
+            r#"
+            #[ts(nominal)]
+            pub struct UserId(i32);
+            "#,
+        )
+        .expect("Failed to parse attributes");
+        assert!(attributes.nominal);
+    }
+
+    #[test]
+    fn should_ignore_other_attributes() {
+        let attributes = container_attributes(
+            r#"
+            #[serde(rename_all = "camelCase")]
+            pub struct Range(u32, u32);
+            "#,
+        )
+        .expect("Failed to parse attributes");
+        assert_eq!(attributes, ContainerAttributes::default());
+    }
+
+    #[test]
+    fn should_take_untagged_variants() {
+        let mut derive_input: DeriveInput = syn::parse_str(
+            r#"
+            #[serde(tag = "type")]
+            pub enum Message {
+                Text { content: String },
+                #[serde(untagged, rename = "raw")]
+                Raw(String),
+                #[serde(untagged)]
+                Empty,
+            }
+            "#,
+        )
+        .expect("Failed to parse input");
+        let untagged_variants =
+            take_untagged_variants(&mut derive_input).expect("Failed to parse attributes");
+        assert_eq!(
+            untagged_variants,
+            vec!["Raw".to_string(), "Empty".to_string()]
+                .into_iter()
+                .collect()
+        );
+
+        let variants = match derive_input.data {
+            Data::Enum(data) => data.variants,
+            _ => unreachable!(),
+        };
+        let attrs: Vec<String> = variants
+            .iter()
+            .flat_map(|variant| variant.attrs.iter())
+            .map(|attr| attr.tokens.to_string())
+            .collect();
+        assert_eq!(attrs, vec!["(rename = \"raw\")".to_string()]);
+    }
+
+    #[test]
+    fn should_fail_on_unknown_attribute() {
+        assert!(matches!(
+            container_attributes("#[ts(unknown)] pub struct Range(u32, u32);"),
+            Err(TsExportError::InvalidAttribute(_))
+        ));
+        assert!(matches!(
+            container_attributes("#[ts(fields(min, max))] pub struct Range(u32, u32);"),
+            Err(TsExportError::InvalidAttribute(_))
+        ));
+    }
+}
@@ -0,0 +1,803 @@
+//! Loading of a `typebinder.toml` file, which describes a run so that a project doesn't need its own driver program :
+//!
+//! ```text
+//! # The root module, or several roots of the same crate: ["src/api/mod.rs", "src/events.rs"]
+//! input = "src/lib.rs"
+//! # The output directory (or file, with the bundle, inline and namespaces exporters), stdout when missing
+//! output = "../frontend/src/types"
+//! # "files" (one file per module), "bundle", "inline" or "namespaces"
+//! exporter = "files"
+//! # The case of the generated file names : "snake_case" (unchanged), "kebab-case", "camelCase" or "PascalCase"
+//! file_case = "kebab-case"
+//! # Write the modules that contain other modules to `index.ts` in their directory
+//! index_files = true
+//! # A PathMapper definition, completed by the [path_mappings] table
+//! path_mapper = "mappings.json"
+//! # The case of the file names of the mapped paths : "snake_case" (unchanged), "kebab-case", "camelCase" or "PascalCase"
+//! path_case = "kebab-case"
+//! # The modules are imported through the `paths` aliases of this tsconfig.json, completed by the [import_aliases] table
+//! tsconfig = "../frontend/tsconfig.json"
+//! # The types no solver solves : "error" (the default), "unknown", or "skip" to leave out their declarations
+//! unsolved_types = "unknown"
+//! # Written at the top of each generated file instead of the standard comment, `{module}` being the Rust module
+//! banner = """
+//! /* eslint-disable */
+//! // Generated from {module}"""
+//!
+//! [path_mappings]
+//! "other_crate::models" = "@other/models"
+//! # `*` matches a segment, `**` any number of them
+//! "my_crate::api::*" = "@company/api/*"
+//!
+//! # The aliases the modules are imported through, `@api/users` rather than `../api/users`
+//! [import_aliases]
+//! "@api/*" = "../frontend/src/types/api/*"
+//!
+//! # How the types of the dependencies are imported, by crate name : from an npm package,
+//! # or from their own export, written next to the crate's (with the files exporter)
+//! [dependencies]
+//! other_crate = "@company/other-crate-types"
+//! shared_models = { export = true }
+//!
+//! # The layout of the generated code, the canonical one (tabs, semicolons, double quotes) when missing
+//! [format]
+//! indent = 2 # or "tab"
+//! semicolons = false
+//! trailing_commas = true
+//! quotes = "single"
+//! # Wrap the unions of the longer lines one variant per line
+//! print_width = 80
+//!
+//! # How the generated modules import each other
+//! [imports]
+//! # `import type { ... }`, the codecs and the mock factories still being imported as values
+//! type_only = true
+//! # The extension of the specifiers of the generated files, "none", "js" or "ts"
+//! extension = "js"
+//!
+//! # The TS types of Rust paths, as written or as resolved through the imports
+//! [type_overrides]
+//! "bson::oid::ObjectId" = "string"
+//! MyOpaque = "unknown"
+//!
+//! # The TS types of the custom serializers, by the path of their module (`with`) or function (`serialize_with`)
+//! [serialize_with]
+//! "crate::serde_hex" = "string"
+//!
+//! [solvers]
+//! structural_json_values = true
+//! branded_uuids = true
+//! forbid_results = true
+//! time_as_milliseconds = true
+//! decimals_as_numbers = true
+//! os_strings_as_never = true
+//! # The TS type of the chrono dates and times, "string" by default
+//! chrono = "number"
+//! # "number", "bigint", "string" or "error"
+//! large_integers = "bigint"
+//! # The fixed-size arrays up to this length are exported as tuples
+//! fixed_arrays_as_tuples = 16
+//! # The fields whose custom serializer isn't mapped : "passthrough", "unknown" or "error"
+//! unmapped_serialize_with = "unknown"
+//! ```
+//!
+//! The paths are relative to the directory of the file.
+//!
+//! The same schema can be written in JSON (`typebinder.json`) or YAML (`typebinder.yaml`), see [ConfigFormat] :
+//!
+//! ```text
+//! {
+//!     "input": "src/lib.rs",
+//!     "exporter": "files",
+//!     "path_mappings": { "other_crate::models": "@other/models" },
+//!     "format": { "indent": 2 }
+//! }
+//! ```
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize};
+use ts_json_subset::{
+    format::{FormatOptions, Indent},
+    types::{PrimaryType, RawType, TsType},
+};
+
+use crate::{
+    contexts::{
+        exporter::UnsolvedTypePolicy,
+        type_solving::{TypeSolvingContext, TypeSolvingContextBuilder},
+    },
+    error::TsExportError,
+    exporters::import_style::ImportStyle,
+    path_mapper::{PathCase, PathMapper},
+    tsconfig::ImportAliases,
+    type_solving::solvers::{
+        chrono::ChronoSolver,
+        fs::FsSolver,
+        primitives::LargeIntegerPolicy,
+        result::ResultSolver,
+        serde_json_value::SerdeJsonValueSolver,
+        serialize_with::{SerializeWithPolicy, SerializeWithSolver},
+        skip_serialize_if::SkipSerializeIf,
+        time::TimeSolver,
+        type_overrides::TypeOverridesSolver,
+        uuid::UuidSolver,
+    },
+};
+
+/// The name of the file the CLI looks for in the current directory
+pub const CONFIG_FILE: &str = "typebinder.toml";
+
+/// The formats of the configuration files, told apart by their extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// `.toml`
+    Toml,
+    /// `.json`
+    Json,
+    /// `.yaml` or `.yml`
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// The format of a file, an error for the extensions of the other formats
+    pub fn of(path: &Path) -> Result<Self, TsExportError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
+            _ => Err(invalid_config(&format!(
+                "{} is not a .toml, .json or .yaml file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parses the contents of a file of this format
+    pub fn parse<T: DeserializeOwned>(self, input: &str) -> Result<T, TsExportError> {
+        Ok(match self {
+            ConfigFormat::Toml => toml::from_str(input)?,
+            ConfigFormat::Json => serde_json::from_str(input)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(input)?,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub input: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub exporter: ExporterKind,
+    /// See [FileExporter::set_file_case](crate::exporters::file::FileExporter::set_file_case)
+    pub file_case: PathCase,
+    /// See [FileExporter::set_index_files](crate::exporters::file::FileExporter::set_index_files)
+    pub index_files: bool,
+    pub path_mapper_file: Option<PathBuf>,
+    /// The mappings of the `[path_mappings]` table, added to those of the PathMapper file
+    pub path_mappings: Vec<(String, String)>,
+    /// See [PathMapper::set_case]
+    pub path_case: PathCase,
+    /// The `tsconfig.json` whose path aliases the modules are imported through
+    pub tsconfig: Option<PathBuf>,
+    /// The `[import_aliases]` table, added to those of the `tsconfig.json`
+    pub import_aliases: Vec<(String, PathBuf)>,
+    /// See [ExporterOptions::unsolved_types](crate::contexts::exporter::ExporterOptions::unsolved_types)
+    pub unsolved_types: UnsolvedTypePolicy,
+    /// See [HeaderComment::Banner](crate::exporters::file::HeaderComment::Banner)
+    pub banner: Option<String>,
+    /// The `[format]` table
+    pub format: Option<FormatOptions>,
+    /// The `[imports]` table
+    pub imports: ImportStyle,
+    pub solvers: SolversConfig,
+    /// The `[dependencies]` table
+    pub dependencies: Vec<(String, DependencyMode)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How the types of a dependency are imported
+pub enum DependencyMode {
+    /// From an npm package, the crate being mapped to it by the PathMapper
+    Package(String),
+    /// From their own export, the dependency being located with
+    /// [CargoDependencies](crate::step_spawner::dependencies::CargoDependencies)
+    Export,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// How the modules are output
+pub enum ExporterKind {
+    /// Each module in its own file, see [FileExporter](crate::exporters::file::FileExporter)
+    #[default]
+    Files,
+    /// All the modules in a single file, see [BundleExporter](crate::exporters::bundle::BundleExporter)
+    Bundle,
+    /// Self-contained declarations in a single file, see [InlineExporter](crate::exporters::inline::InlineExporter)
+    Inline,
+    /// All the modules in a single file, each in a namespace, see [NamespaceExporter](crate::exporters::namespaces::NamespaceExporter)
+    Namespaces,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The optional solvers, added before the default ones
+pub struct SolversConfig {
+    /// The `[type_overrides]` table, see [TypeOverridesSolver]
+    pub type_overrides: HashMap<String, String>,
+    /// The `[serialize_with]` table, see [SerializeWithSolver]
+    pub serialize_with: HashMap<String, String>,
+    /// See [SerializeWithPolicy]
+    pub unmapped_serialize_with: SerializeWithPolicy,
+    /// See [SerdeJsonValueSolver::structural]
+    pub structural_json_values: bool,
+    /// See [UuidSolver::branded]
+    pub branded_uuids: bool,
+    /// See [ResultSolver::forbidden]
+    pub forbid_results: bool,
+    /// See [TimeSolver::milliseconds]
+    pub time_as_milliseconds: bool,
+    /// See [DecimalSolver::number](crate::type_solving::solvers::decimal::DecimalSolver::number),
+    /// ignored without the `decimal` feature
+    pub decimals_as_numbers: bool,
+    /// See [FsSolver::os_strings_as_never]
+    pub os_strings_as_never: bool,
+    /// The TS type of the `chrono` types, output as is, see [ChronoSolver::new]
+    pub chrono: Option<String>,
+    /// See [TypeSolvingContextBuilder::large_integers]
+    pub large_integers: LargeIntegerPolicy,
+    /// See [TypeSolvingContextBuilder::fixed_arrays_as_tuples]
+    pub fixed_arrays_as_tuples: Option<usize>,
+}
+
+impl SolversConfig {
+    pub fn type_solving_context(&self) -> TypeSolvingContext {
+        let mut builder = TypeSolvingContextBuilder::default().large_integers(self.large_integers);
+        if let Some(max_len) = self.fixed_arrays_as_tuples {
+            builder = builder.fixed_arrays_as_tuples(max_len);
+        }
+        if !self.type_overrides.is_empty() {
+            builder = builder.add_solver(TypeOverridesSolver::new(self.type_overrides.clone()));
+        }
+        if self.structural_json_values {
+            builder = builder.add_solver(SerdeJsonValueSolver::structural());
+        }
+        if self.branded_uuids {
+            builder = builder.add_solver(UuidSolver::branded());
+        }
+        if self.forbid_results {
+            builder = builder.add_solver(ResultSolver::forbidden());
+        }
+        if self.time_as_milliseconds {
+            builder = builder.add_solver(TimeSolver::milliseconds());
+        }
+        if self.os_strings_as_never {
+            builder = builder.add_solver(FsSolver::os_strings_as_never());
+        }
+        #[cfg(feature = "decimal")]
+        if self.decimals_as_numbers {
+            builder =
+                builder.add_solver(crate::type_solving::solvers::decimal::DecimalSolver::number());
+        }
+        if let Some(chrono) = &self.chrono {
+            builder = builder.add_solver(ChronoSolver::new(TsType::PrimaryType(
+                PrimaryType::RawType(RawType(chrono.clone())),
+            )));
+        }
+        let mut builder = builder.add_default_solvers();
+        if !self.serialize_with.is_empty()
+            || self.unmapped_serialize_with != SerializeWithPolicy::Passthrough
+        {
+            // After the TimeRsSolver, which knows the serializers of `time::serde`
+            builder = builder.insert_before::<SkipSerializeIf, _>(SerializeWithSolver::new(
+                self.serialize_with.clone(),
+                self.unmapped_serialize_with,
+            ));
+        }
+        builder.finish()
+    }
+}
+
+impl Config {
+    /// Loads a configuration file, in the format of its extension
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Self, TsExportError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::of(path)?;
+        let content = std::fs::read_to_string(path)?;
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        Self::load_from_str(&content, format, dir)
+    }
+
+    /// Parses a configuration of the given format whose paths are relative to `dir`
+    pub fn load_from_str(
+        input: &str,
+        format: ConfigFormat,
+        dir: &Path,
+    ) -> Result<Self, TsExportError> {
+        let file: ConfigFile = format
+            .parse(input)
+            .map_err(|error| invalid_config(&error.to_string()))?;
+        file.into_config(dir)
+    }
+
+    /// The PathMapper of the file, completed by the `[path_mappings]` table and the packages of the `[dependencies]` table
+    pub fn path_mapper(&self) -> Result<PathMapper, TsExportError> {
+        let mut path_mapper = match &self.path_mapper_file {
+            Some(path) => PathMapper::load_from(path)?,
+            None => PathMapper::default(),
+        };
+        path_mapper.set_case(self.path_case);
+        for (name, mode) in self.dependencies.iter() {
+            if let DependencyMode::Package(package) = mode {
+                path_mapper.add_mapping(name, package.clone());
+            }
+        }
+        for (path, output) in self.path_mappings.iter() {
+            path_mapper.add_mapping(path, output.clone());
+        }
+        Ok(path_mapper)
+    }
+
+    /// The path aliases of the `tsconfig.json`, completed by the `[import_aliases]` table
+    pub fn import_aliases(&self) -> Result<ImportAliases, TsExportError> {
+        let mut import_aliases = match &self.tsconfig {
+            Some(path) => ImportAliases::from_tsconfig(path)?,
+            None => ImportAliases::default(),
+        };
+        for (alias, target) in self.import_aliases.iter() {
+            import_aliases.add_alias(alias, target)?;
+        }
+        Ok(import_aliases)
+    }
+
+    /// The dependencies whose types are exported next to the crate's, see [DependencyMode::Export]
+    pub fn exported_dependencies(&self) -> Vec<&str> {
+        self.dependencies
+            .iter()
+            .filter(|(_, mode)| *mode == DependencyMode::Export)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// The keys of a configuration file, see [Config]
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    input: Option<Inputs>,
+    output: Option<PathBuf>,
+    exporter: Option<ExporterKind>,
+    file_case: Option<String>,
+    #[serde(default)]
+    index_files: bool,
+    path_mapper: Option<PathBuf>,
+    path_case: Option<String>,
+    tsconfig: Option<PathBuf>,
+    unsolved_types: Option<String>,
+    banner: Option<String>,
+    #[serde(default)]
+    path_mappings: IndexMap<String, String>,
+    #[serde(default)]
+    import_aliases: IndexMap<String, PathBuf>,
+    #[serde(default)]
+    dependencies: IndexMap<String, DependencyFile>,
+    format: Option<FormatFile>,
+    imports: Option<ImportsFile>,
+    #[serde(default)]
+    type_overrides: HashMap<String, String>,
+    #[serde(default)]
+    serialize_with: HashMap<String, String>,
+    #[serde(default)]
+    solvers: SolversFile,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Inputs {
+    One(PathBuf),
+    Several(Vec<PathBuf>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DependencyFile {
+    Package(String),
+    Export(ExportDependency),
+}
+
+/// `{ export = true }`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExportDependency {
+    export: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FormatFile {
+    indent: Option<IndentFile>,
+    semicolons: Option<bool>,
+    trailing_commas: Option<bool>,
+    print_width: Option<usize>,
+    quotes: Option<String>,
+}
+
+/// A number of spaces, or `"tab"`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum IndentFile {
+    Spaces(usize),
+    Named(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ImportsFile {
+    #[serde(default)]
+    type_only: bool,
+    extension: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SolversFile {
+    structural_json_values: bool,
+    branded_uuids: bool,
+    forbid_results: bool,
+    time_as_milliseconds: bool,
+    decimals_as_numbers: bool,
+    os_strings_as_never: bool,
+    chrono: Option<String>,
+    large_integers: Option<String>,
+    fixed_arrays_as_tuples: Option<usize>,
+    unmapped_serialize_with: Option<String>,
+}
+
+impl ConfigFile {
+    fn into_config(self, dir: &Path) -> Result<Config, TsExportError> {
+        let input = match self.input {
+            Some(Inputs::One(input)) => vec![input],
+            Some(Inputs::Several(inputs)) => inputs,
+            None => Vec::new(),
+        };
+        let dependencies = self
+            .dependencies
+            .into_iter()
+            .map(|(name, mode)| match mode {
+                DependencyFile::Package(package) => Ok((name, DependencyMode::Package(package))),
+                DependencyFile::Export(ExportDependency { export: true }) => {
+                    Ok((name, DependencyMode::Export))
+                }
+                DependencyFile::Export(_) => Err(invalid_config(&format!(
+                    "the dependency {} should be an npm package or {{ export = true }}",
+                    name
+                ))),
+            })
+            .collect::<Result<_, _>>()?;
+        let format = self.format.map(FormatFile::into_options).transpose()?;
+        let imports = match self.imports {
+            Some(imports) => ImportStyle {
+                type_only: imports.type_only,
+                extension: parse_or_default(imports.extension)?,
+            },
+            None => ImportStyle::default(),
+        };
+        let solvers = self.solvers;
+        Ok(Config {
+            input: input.into_iter().map(|input| dir.join(input)).collect(),
+            output: self.output.map(|output| dir.join(output)),
+            exporter: self.exporter.unwrap_or_default(),
+            file_case: parse_or_default(self.file_case)?,
+            index_files: self.index_files,
+            path_mapper_file: self.path_mapper.map(|path_mapper| dir.join(path_mapper)),
+            path_mappings: self.path_mappings.into_iter().collect(),
+            path_case: parse_or_default(self.path_case)?,
+            tsconfig: self.tsconfig.map(|tsconfig| dir.join(tsconfig)),
+            import_aliases: self
+                .import_aliases
+                .into_iter()
+                .map(|(alias, target)| (alias, dir.join(target)))
+                .collect(),
+            unsolved_types: parse_or_default(self.unsolved_types)?,
+            banner: self.banner,
+            format,
+            imports,
+            solvers: SolversConfig {
+                type_overrides: self.type_overrides,
+                serialize_with: self.serialize_with,
+                unmapped_serialize_with: parse_or_default(solvers.unmapped_serialize_with)?,
+                structural_json_values: solvers.structural_json_values,
+                branded_uuids: solvers.branded_uuids,
+                forbid_results: solvers.forbid_results,
+                time_as_milliseconds: solvers.time_as_milliseconds,
+                decimals_as_numbers: solvers.decimals_as_numbers,
+                os_strings_as_never: solvers.os_strings_as_never,
+                chrono: solvers.chrono,
+                large_integers: parse_or_default(solvers.large_integers)?,
+                fixed_arrays_as_tuples: solvers.fixed_arrays_as_tuples,
+            },
+            dependencies,
+        })
+    }
+}
+
+impl FormatFile {
+    fn into_options(self) -> Result<FormatOptions, TsExportError> {
+        let mut format = FormatOptions::default();
+        match self.indent {
+            Some(IndentFile::Spaces(width)) => format.indent = Indent::Spaces(width),
+            Some(IndentFile::Named(indent)) => {
+                format.indent = indent
+                    .parse()
+                    .map_err(|_| invalid_value("indent", &indent))?
+            }
+            None => {}
+        }
+        if let Some(semicolons) = self.semicolons {
+            format.semicolons = semicolons;
+        }
+        if let Some(trailing_commas) = self.trailing_commas {
+            format.trailing_commas = trailing_commas;
+        }
+        match self.print_width {
+            Some(0) => return Err(invalid_value("print_width", &0)),
+            print_width => format.print_width = print_width,
+        }
+        if let Some(quotes) = self.quotes {
+            format.quotes = quotes
+                .parse()
+                .map_err(|_| invalid_value("quotes", &quotes))?;
+        }
+        Ok(format)
+    }
+}
+
+/// Parses the value of an optional key, which has its own syntax (e.g. a case or a policy)
+fn parse_or_default<T>(value: Option<String>) -> Result<T, TsExportError>
+where
+    T: FromStr<Err = TsExportError> + Default,
+{
+    value
+        .map(|value| value.parse())
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn invalid_config(message: &str) -> TsExportError {
+    TsExportError::InvalidConfig(message.to_string())
+}
+
+fn invalid_value(key: &str, value: &dyn std::fmt::Display) -> TsExportError {
+    invalid_config(&format!("unexpected {} = {}", key, value))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::exporters::import_style::ImportExtension;
+    use ts_json_subset::format::Quotes;
+
+    #[test]
+    fn should_load_from_toml() {
+        let config = Config::load_from_str(
+            r#"
+            input = ["src/api/mod.rs", "src/events.rs"]
+            output = "../frontend/types"
+            exporter = "bundle"
+            file_case = "camelCase"
+            index_files = true
+            banner = "/* eslint-disable */"
+            path_case = "kebab-case"
+            tsconfig = "../frontend/tsconfig.json"
+            unsolved_types = "skip"
+
+            [import_aliases]
+            "@api/*" = "../frontend/types/api/*"
+
+            [imports]
+            type_only = true
+            extension = "js"
+
+            [format]
+            indent = 2
+            quotes = "single"
+            print_width = 100
+
+            [path_mappings]
+            "other_crate::models" = "@other/models"
+
+            [dependencies]
+            other_crate = "@company/other"
+            shared = { export = true }
+
+            [type_overrides]
+            "rust_decimal::Decimal" = "string"
+
+            [serialize_with]
+            "crate::serde_hex" = "string"
+
+            [solvers]
+            branded_uuids = true
+            unmapped_serialize_with = "error"
+            large_integers = "string"
+            fixed_arrays_as_tuples = 8
+            chrono = "number"
+            "#,
+            ConfigFormat::Toml,
+            Path::new("backend"),
+        )
+        .expect("Failed to read the config");
+        assert_eq!(
+            config,
+            Config {
+                input: vec![
+                    PathBuf::from("backend/src/api/mod.rs"),
+                    PathBuf::from("backend/src/events.rs")
+                ],
+                output: Some(PathBuf::from("backend/../frontend/types")),
+                exporter: ExporterKind::Bundle,
+                file_case: PathCase::CamelCase,
+                index_files: true,
+                path_mapper_file: None,
+                path_mappings: vec![(
+                    "other_crate::models".to_string(),
+                    "@other/models".to_string()
+                )],
+                path_case: PathCase::KebabCase,
+                tsconfig: Some(PathBuf::from("backend/../frontend/tsconfig.json")),
+                import_aliases: vec![(
+                    "@api/*".to_string(),
+                    PathBuf::from("backend/../frontend/types/api/*")
+                )],
+                unsolved_types: UnsolvedTypePolicy::Skip,
+                banner: Some("/* eslint-disable */".to_string()),
+                format: Some(FormatOptions {
+                    indent: Indent::Spaces(2),
+                    quotes: Quotes::Single,
+                    print_width: Some(100),
+                    ..FormatOptions::default()
+                }),
+                imports: ImportStyle {
+                    type_only: true,
+                    extension: ImportExtension::Js,
+                },
+                solvers: SolversConfig {
+                    type_overrides: vec![(
+                        "rust_decimal::Decimal".to_string(),
+                        "string".to_string()
+                    )]
+                    .into_iter()
+                    .collect(),
+                    serialize_with: vec![("crate::serde_hex".to_string(), "string".to_string())]
+                        .into_iter()
+                        .collect(),
+                    unmapped_serialize_with: SerializeWithPolicy::Error,
+                    structural_json_values: false,
+                    branded_uuids: true,
+                    forbid_results: false,
+                    time_as_milliseconds: false,
+                    decimals_as_numbers: false,
+                    os_strings_as_never: false,
+                    chrono: Some("number".to_string()),
+                    large_integers: LargeIntegerPolicy::String,
+                    fixed_arrays_as_tuples: Some(8),
+                },
+                dependencies: vec![
+                    (
+                        "other_crate".to_string(),
+                        DependencyMode::Package("@company/other".to_string())
+                    ),
+                    ("shared".to_string(), DependencyMode::Export),
+                ],
+            }
+        );
+        assert_eq!(config.exported_dependencies(), vec!["shared"]);
+        assert_eq!(
+            config.path_mapper().unwrap().get("other_crate::api"),
+            Some("@company/other/api".to_string())
+        );
+        assert_eq!(
+            config
+                .path_mapper()
+                .unwrap()
+                .get("other_crate::models::user_roles"),
+            Some("@other/models/user-roles".to_string())
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_keys() {
+        assert!(matches!(
+            Config::load_from_str("exporter = \"zip\"", ConfigFormat::Toml, Path::new(".")),
+            Err(TsExportError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            Config::load_from_str("outptu = \"types\"", ConfigFormat::Toml, Path::new(".")),
+            Err(TsExportError::InvalidConfig(_))
+        ));
+    }
+
+    /// The same configuration in each format
+    const TOML_CONFIG: &str = r#"
+        input = "src/lib.rs"
+        exporter = "namespaces"
+        path_mapper = "mappings.yaml"
+
+        [path_mappings]
+        "other_crate::models" = "@other/models"
+
+        [dependencies]
+        shared = { export = true }
+
+        [format]
+        indent = "tab"
+        semicolons = false
+
+        [solvers]
+        fixed_arrays_as_tuples = 4
+        "#;
+
+    #[test]
+    fn should_load_from_json() {
+        let config = Config::load_from_str(
+            r#"{
+                "input": "src/lib.rs",
+                "exporter": "namespaces",
+                "path_mapper": "mappings.yaml",
+                "path_mappings": { "other_crate::models": "@other/models" },
+                "dependencies": { "shared": { "export": true } },
+                "format": { "indent": "tab", "semicolons": false },
+                "solvers": { "fixed_arrays_as_tuples": 4 }
+            }"#,
+            ConfigFormat::Json,
+            Path::new("."),
+        )
+        .expect("Failed to read the config");
+        assert_eq!(
+            config,
+            Config::load_from_str(TOML_CONFIG, ConfigFormat::Toml, Path::new(".")).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_load_from_yaml() {
+        let config = Config::load_from_str(
+            "input: src/lib.rs\n\
+             exporter: namespaces\n\
+             path_mapper: mappings.yaml\n\
+             path_mappings:\n  \"other_crate::models\": \"@other/models\"\n\
+             dependencies:\n  shared:\n    export: true\n\
+             format:\n  indent: tab\n  semicolons: false\n\
+             solvers:\n  fixed_arrays_as_tuples: 4\n",
+            ConfigFormat::Yaml,
+            Path::new("."),
+        )
+        .expect("Failed to read the config");
+        assert_eq!(
+            config,
+            Config::load_from_str(TOML_CONFIG, ConfigFormat::Toml, Path::new(".")).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_tell_the_format_from_the_extension() {
+        assert_eq!(
+            ConfigFormat::of(Path::new("typebinder.yml")).unwrap(),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::of(Path::new("mappings.json")).unwrap(),
+            ConfigFormat::Json
+        );
+        assert!(matches!(
+            ConfigFormat::of(Path::new("mappings.txt")),
+            Err(TsExportError::InvalidConfig(_))
+        ));
+    }
+}
@@ -1,29 +1,57 @@
-use std::str::FromStr;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
 use super::{import::ImportContext, type_solving::TypeSolvingContext};
 use crate::{
+    attributes::{doc_comment, serde_container, ContainerAttributes, FieldAttributes},
     error::TsExportError,
     macros::{context::MacroSolvingContext, MacroInfo},
+    pipeline::{
+        cfg::CfgSet,
+        codecs::CodecLibrary,
+        filter::ProcessFilter,
+        profile::{timed, SolverProfiler},
+        report::Recovery,
+        warp_endpoints::{Endpoint, JsonBody},
+    },
     type_solving::{
         generic_constraints::GenericConstraints, member_info::MemberInfo, result::SolverResult,
         type_info::TypeInfo,
     },
-    type_solving::{result::Solved, ImportEntry},
+    type_solving::{
+        result::Solved,
+        solvers::{reference::strip_references, type_overrides::raw_type},
+        ImportEntry,
+    },
 };
 use serde_derive_internals::{
     ast::{Container, Data, Field, Style, Variant},
-    attr::TagType,
+    attr::{self, TagType},
+};
+use syn::{
+    visit::Visit, DeriveInput, GenericParam, Generics, Ident, ItemType, PathArguments, Type,
+    TypePath,
 };
-use syn::{GenericParam, Generics, ItemType};
 use ts_json_subset::{
-    declarations::{interface::InterfaceDeclaration, type_alias::TypeAliasDeclaration},
+    common::DocComment,
+    declarations::{
+        const_enum::{ConstEnumBody, ConstEnumVariant},
+        enumeration::EnumDeclaration,
+        interface::InterfaceDeclaration,
+        type_alias::TypeAliasDeclaration,
+    },
     export::ExportStatement,
     ident::{IdentError, TSIdent},
     types::{
-        IntersectionType, LiteralType, ObjectType, ParenthesizedType, PrimaryType, PropertyName,
-        PropertySignature, TsType, TupleType, TypeBody, TypeMember, TypeParameter, TypeParameters,
-        UnionType,
+        ExtendsConstraint, IndexSignature, IntersectionType, LiteralType, ObjectType,
+        ParenthesizedType, PredefinedType, PrimaryType, PropertyName, PropertySignature, RawType,
+        TsType, TupleType, TypeArguments, TypeBody, TypeMember, TypeParameter, TypeParameters,
+        TypeReference, UnionType,
     },
+    visit_mut::{self, VisitMut},
 };
 
 /// The global exporting context. Wraps the other contexts.
@@ -34,6 +62,348 @@ pub struct ExporterContext<'a> {
     pub macro_context: &'a MacroSolvingContext,
     /// A context that contains all the imports
     pub import_context: ImportContext,
+    /// Options that customize the generated declarations
+    pub options: &'a ExporterOptions,
+    /// The structs and enums declared in the current module, by name
+    pub local_declarations: HashMap<String, &'a DeriveInput>,
+    /// The variants marked `#[serde(untagged)]` in the enums of the current module, by enum name
+    pub untagged_variants: HashMap<String, HashSet<String>>,
+    /// The foreign types described by a `#[serde(remote = "...")]` container of the current module,
+    /// by path (as written, and as resolved through the imports), to the ident of the container
+    pub remote_types: HashMap<String, String>,
+    /// Records the calls to the solvers, when [ExporterOptions::profile] is set
+    pub solver_profiler: Option<SolverProfiler>,
+    /// The side of serde the declarations are exported for, one of the [directions](ProcessMode::directions) of the [ProcessMode]
+    pub direction: Direction,
+    /// The unsolved types exported as `unknown` and the declarations left out, see [ExporterOptions::unsolved_types].
+    ///
+    /// They are located like the errors, in their field and container
+    pub warnings: RefCell<Vec<(Recovery, TsExportError)>>,
+}
+
+#[derive(Debug, Default, Clone)]
+/// Options that customize the generated declarations
+pub struct ExporterOptions {
+    /// When set, externally tagged enums are exported with the variant name stored under this key,
+    /// next to the variant's content: `{ kind: "A", ...content }`.
+    ///
+    /// This does not match serde's representation, it is meant for custom serializers that flatten externally tagged enums.
+    pub external_tag_key: Option<String>,
+    /// When set, the `pub use` declarations that re-export items of the current crate are exported
+    /// as TS re-exports, so that the output modules match the public API of the crate.
+    pub follow_reexports: bool,
+    /// How the declarations of structs and enums are named
+    pub type_names: TypeNamePolicy,
+    /// When set, the time spent by phase, module and solver is recorded in the [RunReport](crate::pipeline::report::RunReport)
+    pub profile: bool,
+    /// When set, the modules that fail to be read, parsed or solved are skipped instead of aborting the run,
+    /// and listed in the [RunReport](crate::pipeline::report::RunReport)
+    pub skip_failing_modules: bool,
+    /// What to do with the types no solver solves, the run failing on them by default
+    pub unsolved_types: UnsolvedTypePolicy,
+    /// When set, the endpoints of the [warp](https://docs.rs/warp) filters of each module are exported as an interface of this name
+    /// (e.g. [ENDPOINTS_INTERFACE](crate::pipeline::warp_endpoints::ENDPOINTS_INTERFACE)),
+    /// mapping `"<METHOD> <path>"` to the types of their path parameters, request and response,
+    /// see [warp_endpoints](crate::pipeline::warp_endpoints)
+    pub warp_endpoints: Option<String>,
+    /// When set, a `mock<Name>(overrides?)` factory returning a plausible value is exported next to each declaration,
+    /// see [mocks](crate::pipeline::mocks)
+    pub mocks: bool,
+    /// When set, a `<Name>Codec` validating the values at runtime is exported next to each declaration,
+    /// built with io-ts or runtypes, see [codecs](crate::pipeline::codecs)
+    pub codecs: Option<CodecLibrary>,
+    /// When set, the functions and constants are declared without their values, `function mockUser(...): User;`,
+    /// so that the modules can be written as declaration files (`.d.ts`), see [ambient](crate::pipeline::ambient)
+    pub declaration_files: bool,
+    /// When set, every property of the generated declarations is `readonly`, for frontends that treat the payloads as immutable
+    pub readonly: bool,
+    /// When set, the externally tagged enums whose variants are all units are exported as TS enums,
+    /// `enum Color { Red = "red" }`, instead of unions of string literals
+    pub ts_enums: bool,
+    /// Whether the declarations describe the data Rust serializes, the data it deserializes, or both
+    pub process_mode: ProcessMode,
+    /// The suffix of the declarations of the deserialized data with [ProcessMode::Both], `Input` when not set
+    pub input_suffix: Option<String>,
+    /// When set, the catch-all `#[serde(other)]` variants are deserialized from `(string & {})` instead of `string`,
+    /// which doesn't absorb the string literals of the other variants and keeps them suggested by the editors
+    pub open_catch_all_variants: bool,
+    /// When set, the items, fields and variants whose `#[cfg(...)]` doesn't hold for these features and options are skipped,
+    /// see [cfg](crate::pipeline::cfg). They are all exported otherwise
+    pub cfg: Option<CfgSet>,
+    /// When set, only the structs, enums and type aliases accepted by the filter are exported,
+    /// see [filter](crate::pipeline::filter). They are all exported otherwise, except the ones marked `#[ts(skip)]`
+    pub filter: Option<ProcessFilter>,
+}
+
+impl ExporterOptions {
+    /// The suffix of the declarations exported in `direction`, `None` when they are named after the type
+    pub fn declaration_suffix(&self, direction: Direction) -> Option<&str> {
+        match (self.process_mode, direction) {
+            (ProcessMode::Both, Direction::Deserialize) => {
+                Some(self.input_suffix.as_deref().unwrap_or("Input"))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// How the declarations of structs and enums are named.
+///
+/// Whichever name is chosen, the references to a type use the same name.
+pub enum TypeNamePolicy {
+    /// The Rust ident of the type
+    #[default]
+    RustIdent,
+    /// The name given by a container-level `#[serde(rename = "...")]`, which defaults to the Rust ident
+    SerdeName,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// What to do with the types no solver solves.
+///
+/// The lenient policies let a large crate be exported while its types are being made exportable,
+/// each recovery being listed in the warnings of the [RunReport](crate::pipeline::report::RunReport).
+pub enum UnsolvedTypePolicy {
+    /// The run fails on the first unsolved type
+    #[default]
+    Error,
+    /// The unsolved types are exported as `unknown`
+    Unknown,
+    /// The declarations with an unsolved type are left out
+    Skip,
+}
+
+impl FromStr for UnsolvedTypePolicy {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(UnsolvedTypePolicy::Error),
+            "unknown" => Ok(UnsolvedTypePolicy::Unknown),
+            "skip" => Ok(UnsolvedTypePolicy::Skip),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown unsolved type policy {}, expected error, unknown or skip",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The direction of the data described by the declarations, serde handling some attributes differently on each side
+pub enum Direction {
+    /// The data Rust serializes, e.g. the responses of an API
+    #[default]
+    Serialize,
+    /// The data Rust deserializes, e.g. the requests a frontend sends.
+    ///
+    /// The fields with a `#[serde(default)]`, or of a struct with a `#[serde(default)]`, may be omitted and are optional,
+    /// and the fields and variants marked `#[serde(skip_deserializing)]` are left out instead of the `skip_serializing` ones.
+    Deserialize,
+}
+
+impl Direction {
+    /// The serialized or the deserialized name
+    pub fn name_of(&self, name: &attr::Name) -> String {
+        match self {
+            Direction::Serialize => name.serialize_name(),
+            Direction::Deserialize => name.deserialize_name(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The sides of serde the declarations are exported for
+pub enum ProcessMode {
+    /// Each type is declared for the data Rust serializes
+    #[default]
+    Serialize,
+    /// Each type is declared for the data Rust deserializes
+    Deserialize,
+    /// Each type is declared twice, `User` for the data Rust serializes and `UserInput` for the data it deserializes,
+    /// see [ExporterOptions::input_suffix].
+    ///
+    /// The references of the deserialized declarations to the types of the crate point to their deserialized declaration,
+    /// the macros, the endpoints and the re-exports are only exported once.
+    Both,
+}
+
+impl ProcessMode {
+    /// The directions the declarations are exported in, in their order in the output
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            ProcessMode::Serialize => &[Direction::Serialize],
+            ProcessMode::Deserialize => &[Direction::Deserialize],
+            ProcessMode::Both => &[Direction::Serialize, Direction::Deserialize],
+        }
+    }
+}
+
+impl FromStr for ProcessMode {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "serialize" => Ok(ProcessMode::Serialize),
+            "deserialize" => Ok(ProcessMode::Deserialize),
+            "both" => Ok(ProcessMode::Both),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown process mode {}, expected serialize, deserialize or both",
+                s
+            ))),
+        }
+    }
+}
+
+/// Marks all the properties it visits `readonly`, see [ExporterOptions::readonly]
+pub(crate) struct ReadonlyProperties;
+
+impl VisitMut for ReadonlyProperties {
+    fn visit_type_member_mut(&mut self, node: &mut TypeMember) {
+        match node {
+            TypeMember::PropertySignature(property) => property.readonly = true,
+            TypeMember::IndexSignature(signature) => signature.readonly = true,
+        }
+        visit_mut::visit_type_member_mut(self, node);
+    }
+
+    fn visit_primary_type_mut(&mut self, node: &mut PrimaryType) {
+        if let PrimaryType::MappedType(mapped) = node {
+            mapped.readonly = true;
+        }
+        visit_mut::visit_primary_type_mut(self, node);
+    }
+}
+
+/// `T & { readonly __brand: unique symbol }`, each `unique symbol` being a distinct type
+fn brand(ty: TsType) -> TsType {
+    let brand = TypeMember::PropertySignature(PropertySignature {
+        name: PropertyName::from("__brand".to_string()),
+        optional: false,
+        readonly: true,
+        inner_type: TsType::PrimaryType(PrimaryType::RawType(RawType("unique symbol".to_string()))),
+        doc: None,
+    });
+    IntersectionType::of(vec![
+        ty,
+        TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+            body: TypeBody {
+                members: vec![brand],
+            },
+        })),
+    ])
+}
+
+/// Sets the doc comment of the first property of an object, or of the first object of an intersection
+fn document_first_member(ty: &mut TsType, doc: Option<DocComment>) {
+    match ty {
+        TsType::PrimaryType(PrimaryType::ObjectType(object)) => {
+            if let Some(TypeMember::PropertySignature(property)) = object.body.members.first_mut() {
+                if property.doc.is_none() {
+                    property.doc = doc;
+                }
+            }
+        }
+        TsType::IntersectionType(intersection) => {
+            if let Some(first) = intersection.types.first_mut() {
+                document_first_member(first, doc);
+            }
+        }
+        TsType::ParenthesizedType(parenthesized) => {
+            document_first_member(&mut parenthesized.inner, doc)
+        }
+        _ => (),
+    }
+}
+
+/// Whether `ty` is a `PhantomData`, which serde serializes as a unit
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Finds the references to a type parameter in a type, including its associated types such as `T::Id`
+struct ParameterUse<'a> {
+    ident: &'a Ident,
+    used: bool,
+}
+
+impl<'ast> Visit<'ast> for ParameterUse<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        if ty.qself.is_none()
+            && ty.path.leading_colon.is_none()
+            && ty
+                .path
+                .segments
+                .first()
+                .is_some_and(|segment| segment.ident == *self.ident)
+        {
+            self.used = true;
+        }
+        syn::visit::visit_type_path(self, ty);
+    }
+}
+
+fn uses_parameter(ty: &Type, ident: &Ident) -> bool {
+    let mut visitor = ParameterUse { ident, used: false };
+    visitor.visit_type(ty);
+    visitor.used
+}
+
+/// The indices of the type parameters of a container that only its `PhantomData` fields use.
+///
+/// They are left out of the declaration, and their type arguments out of the references to it,
+/// see [drop_phantom_arguments](crate::pipeline::phantoms::drop_phantom_arguments).
+pub(crate) fn phantom_type_parameters(
+    container: &Container,
+    attributes: &ContainerAttributes,
+) -> Vec<usize> {
+    if attributes.ts_type.is_some() || attributes.as_type.is_some() {
+        return Vec::new();
+    }
+    let fields: Vec<&Field> = match &container.data {
+        Data::Enum(variants) => variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        Data::Struct(_, fields) => fields.iter().collect(),
+    };
+    let (phantoms, others): (Vec<&Field>, Vec<&Field>) = fields
+        .into_iter()
+        .partition(|field| is_phantom_data(field.ty));
+    container
+        .generics
+        .type_params()
+        .enumerate()
+        .filter(|(_, param)| {
+            let uses = |field: &&Field| uses_parameter(field.ty, &param.ident);
+            phantoms.iter().any(uses) && !others.iter().any(uses)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Removes `null` from a union, e.g. the type of an `Option`, or gives the type back if it is not nullable
+pub(crate) fn strip_null(ty: TsType) -> Result<TsType, TsType> {
+    let null = TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Null));
+    match ty {
+        TsType::UnionType(UnionType { types }) if types.contains(&null) => {
+            let mut types: Vec<TsType> = types.into_iter().filter(|ty| *ty != null).collect();
+            if types.len() == 1 {
+                Ok(types.remove(0))
+            } else {
+                Ok(TsType::UnionType(UnionType { types }))
+            }
+        }
+        ty => Err(ty),
+    }
 }
 
 pub fn apply_generic_constraints(
@@ -46,6 +416,40 @@ pub fn apply_generic_constraints(
         .for_each(|param| param.constraint = constraints.get_constraints(&param.identifier))
 }
 
+/// The type parameters of the declarations
+fn type_parameters(statements: &mut [ExportStatement]) -> impl Iterator<Item = &mut TypeParameter> {
+    statements
+        .iter_mut()
+        .filter_map(|statement| match statement {
+            ExportStatement::InterfaceDeclaration(declaration) => declaration.type_params.as_mut(),
+            ExportStatement::TypeAliasDeclaration(declaration) => declaration.type_params.as_mut(),
+            _ => None,
+        })
+        .flat_map(|type_params| type_params.parameters.iter_mut())
+}
+
+/// Removes the type parameters named `idents` from the declarations, and the empty lists of parameters
+fn remove_type_parameters(statements: &mut [ExportStatement], idents: &[String]) {
+    if idents.is_empty() {
+        return;
+    }
+    for statement in statements.iter_mut() {
+        let type_params = match statement {
+            ExportStatement::InterfaceDeclaration(declaration) => &mut declaration.type_params,
+            ExportStatement::TypeAliasDeclaration(declaration) => &mut declaration.type_params,
+            _ => continue,
+        };
+        if let Some(params) = type_params {
+            params
+                .parameters
+                .retain(|param| !idents.contains(&param.identifier.to_string()));
+            if params.parameters.is_empty() {
+                *type_params = None;
+            }
+        }
+    }
+}
+
 fn extract_type_parameters(generics: &Generics) -> Result<Option<TypeParameters>, IdentError> {
     // TODO: rename to parameters
     let identifiers: Vec<TSIdent> = generics
@@ -66,6 +470,7 @@ fn extract_type_parameters(generics: &Generics) -> Result<Option<TypeParameters>
             .map(|identifier| TypeParameter {
                 identifier,
                 constraint: None,
+                default: None,
             })
             .collect();
         Ok(Some(TypeParameters { parameters }))
@@ -74,28 +479,172 @@ fn extract_type_parameters(generics: &Generics) -> Result<Option<TypeParameters>
 
 impl ExporterContext<'_> {
     pub fn solve_type(&self, solver_info: &TypeInfo) -> Result<Solved<TsType>, TsExportError> {
-        for solver in self.type_solving_context.solvers() {
-            match solver.as_ref().solve_as_type(&self, solver_info) {
+        for (index, solver) in self.type_solving_context.solvers().iter().enumerate() {
+            let result = match &self.solver_profiler {
+                Some(profiler) => {
+                    let (result, time) = timed(|| solver.as_ref().solve_as_type(self, solver_info));
+                    profiler.record(self.type_solving_context.solver_name(index), time);
+                    result
+                }
+                None => solver.as_ref().solve_as_type(self, solver_info),
+            };
+            match result {
                 SolverResult::Continue => (),
                 SolverResult::Solved(solved) => return Ok(solved),
                 SolverResult::Error(inner) => return Err(inner),
             }
         }
-        Err(TsExportError::UnsolvedType(solver_info.ty.clone()))
+        let error = TsExportError::UnsolvedType(Box::new(solver_info.ty.clone()));
+        if self.options.unsolved_types == UnsolvedTypePolicy::Unknown {
+            self.warnings.borrow_mut().push((Recovery::Unknown, error));
+            return Ok(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+                PredefinedType::Unknown,
+            ))));
+        }
+        Err(error)
     }
 
+    /// Solves a field, its errors being located in it
     pub fn solve_member(
         &self,
         solver_info: &MemberInfo,
     ) -> Result<Solved<TypeMember>, TsExportError> {
-        for solver in self.type_solving_context.solvers() {
-            match solver.as_ref().solve_as_member(&self, solver_info) {
+        let warnings = self.warnings.borrow().len();
+        let solved = self
+            .solve_member_unlocated(solver_info)
+            .map_err(|error| error.in_field(solver_info.field, &solver_info.name));
+        // The warnings of the field are located in it, like its errors
+        let mut all_warnings = self.warnings.borrow_mut();
+        let located: Vec<_> = all_warnings
+            .drain(warnings..)
+            .map(|(recovery, warning)| {
+                (
+                    recovery,
+                    warning.in_field(solver_info.field, &solver_info.name),
+                )
+            })
+            .collect();
+        all_warnings.extend(located);
+        solved
+    }
+
+    fn solve_member_unlocated(
+        &self,
+        solver_info: &MemberInfo,
+    ) -> Result<Solved<TypeMember>, TsExportError> {
+        let attributes = FieldAttributes::from_attrs(&solver_info.field.attrs)?;
+        // `#[ts(type = "...")]` is output as is, the solvers aren't called
+        if let Some(ts_type) = &attributes.ts_type {
+            let solved = Solved::new(TypeMember::PropertySignature(PropertySignature {
+                name: PropertyName::from(solver_info.name.clone()),
+                optional: false,
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::RawType(RawType(ts_type.clone()))),
+                doc: None,
+            }));
+            return Ok(self.complete_member(solver_info, &attributes, solved));
+        }
+        for (index, solver) in self.type_solving_context.solvers().iter().enumerate() {
+            let result = match &self.solver_profiler {
+                Some(profiler) => {
+                    let (result, time) =
+                        timed(|| solver.as_ref().solve_as_member(self, solver_info));
+                    profiler.record(self.type_solving_context.solver_name(index), time);
+                    result
+                }
+                None => solver.as_ref().solve_as_member(self, solver_info),
+            };
+            match result {
                 SolverResult::Continue => (),
-                SolverResult::Solved(solved) => return Ok(solved),
+                SolverResult::Solved(solved) => {
+                    return Ok(self.complete_member(solver_info, &attributes, solved))
+                }
                 SolverResult::Error(inner) => return Err(inner),
             }
         }
-        Err(TsExportError::UnsolvedField(solver_info.field.clone()))
+        let error = TsExportError::UnsolvedField(Box::new(solver_info.field.clone()));
+        if self.options.unsolved_types == UnsolvedTypePolicy::Unknown {
+            self.warnings.borrow_mut().push((Recovery::Unknown, error));
+            let solved = Solved::new(TypeMember::PropertySignature(PropertySignature {
+                name: PropertyName::from(solver_info.name.clone()),
+                optional: false,
+                readonly: false,
+                inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Unknown)),
+                doc: None,
+            }));
+            return Ok(self.complete_member(solver_info, &attributes, solved));
+        }
+        Err(error)
+    }
+
+    /// Applies to a solved property what the attributes of its field say, e.g. its doc comment or `#[ts(optional)]`
+    fn complete_member(
+        &self,
+        solver_info: &MemberInfo,
+        attributes: &FieldAttributes,
+        mut solved: Solved<TypeMember>,
+    ) -> Solved<TypeMember> {
+        if let TypeMember::PropertySignature(property) = &mut solved.inner {
+            if property.doc.is_none() {
+                property.doc = doc_comment(&solver_info.field.attrs);
+            }
+            if self.direction == Direction::Deserialize
+                && !matches!(solver_info.serde_field.default(), attr::Default::None)
+            {
+                property.optional = true;
+            }
+            if self.is_output_only(solver_info.serde_field) {
+                property.readonly = true;
+            }
+            if attributes.optional {
+                property.optional = true;
+            }
+            if let Some(rename) = &attributes.rename {
+                property.name = PropertyName::from(rename.clone());
+            }
+        }
+        solved
+    }
+
+    /// Whether serde leaves out the field in the [Direction] of the export, or it is marked `#[ts(skip)]`
+    fn skips_field(&self, field: &Field) -> bool {
+        let skipped = match self.direction {
+            Direction::Serialize => field.attrs.skip_serializing(),
+            Direction::Deserialize => field.attrs.skip_deserializing(),
+        };
+        // An invalid attribute is reported when the field is solved
+        skipped
+            || FieldAttributes::from_attrs(&field.original.attrs)
+                .is_ok_and(|attributes| attributes.skip)
+    }
+
+    /// Whether the field is serialized but never deserialized, when the types are declared in both directions :
+    /// the frontend receives it but can't send it back, it is `readonly`
+    fn is_output_only(&self, field: &attr::Field) -> bool {
+        self.options.process_mode == ProcessMode::Both
+            && self.direction == Direction::Serialize
+            && field.skip_deserializing()
+            && !field.skip_serializing()
+    }
+
+    /// Whether serde fails on the variant in the [Direction] of the export
+    fn skips_variant(&self, variant: &attr::Variant) -> bool {
+        match self.direction {
+            Direction::Serialize => variant.skip_serializing(),
+            Direction::Deserialize => variant.skip_deserializing(),
+        }
+    }
+
+    /// The serialized or deserialized name, in the [Direction] of the export
+    fn serde_name(&self, name: &attr::Name) -> String {
+        self.direction.name_of(name)
+    }
+
+    /// The [MemberInfo] of a field, named in the [Direction] of the export
+    fn member_info<'b>(&self, generics: &'b Generics, field: &'b Field<'b>) -> MemberInfo<'b> {
+        let mut member_info = MemberInfo::from_generics_and_field(generics, field);
+        member_info.name = self.serde_name(field.attrs.name());
+        member_info
     }
 
     pub fn export_statements_from_macro(
@@ -117,131 +666,645 @@ impl ExporterContext<'_> {
         &self,
         container: Container,
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
-        let name = container.ident.to_string();
-        match container.data {
-            Data::Enum(variants) => match container.attrs.tag() {
-                TagType::External => self.export_enum_external(name, container.generics, variants),
-                TagType::Internal { tag } => {
-                    self.export_enum_internal(name, container.generics, variants, tag)
+        let attributes = ContainerAttributes::from_attrs(&container.original.attrs)?;
+        let name = match (&attributes.rename, self.options.type_names) {
+            (Some(rename), _) => rename.clone(),
+            (None, TypeNamePolicy::RustIdent) => container.ident.to_string(),
+            (None, TypeNamePolicy::SerdeName) => self.serde_name(container.attrs.name()),
+        };
+        if attributes.ts_type.is_some() || attributes.as_type.is_some() {
+            let mut solved = self.export_override(name, container.generics, &attributes)?;
+            self.apply_type_parameter_defaults(container.generics, &mut solved)?;
+            return Ok(solved);
+        }
+        let phantoms = phantom_type_parameters(&container, &attributes);
+        let phantom_idents: Vec<String> = container
+            .generics
+            .type_params()
+            .enumerate()
+            .filter(|(index, _)| phantoms.contains(index))
+            .map(|(_, param)| param.ident.to_string())
+            .collect();
+        let mut solved = match container.data {
+            Data::Enum(variants) => {
+                // serde fails to serialize the skipped variants, and leaves out the skipped fields of the others
+                let variants = variants
+                    .into_iter()
+                    .filter(|variant| !self.skips_variant(&variant.attrs))
+                    .map(|mut variant| {
+                        variant.fields.retain(|field| !self.skips_field(field));
+                        variant
+                    })
+                    .collect::<Vec<_>>();
+                let (tagged, untagged): (Vec<Variant>, Vec<Variant>) =
+                    match self.untagged_variants.get(&container.ident.to_string()) {
+                        Some(untagged_variants) => variants.into_iter().partition(|variant| {
+                            !untagged_variants.contains(&variant.ident.to_string())
+                        }),
+                        None => (variants, Vec::new()),
+                    };
+                let tag = container.attrs.tag();
+                if untagged.is_empty() && self.is_ts_enum(tag, &tagged) {
+                    self.export_ts_enum(name, tagged)
+                } else if untagged.is_empty() {
+                    self.export_enum(name, container.generics, tagged, tag)
+                } else if tagged.is_empty() {
+                    self.export_enum_untagged(name, container.generics, untagged)
+                } else {
+                    // serde only tries the untagged variants when none of the tagged ones matched
+                    let tagged = self.export_enum(name.clone(), container.generics, tagged, tag)?;
+                    let untagged = self.export_enum_untagged(name, container.generics, untagged)?;
+                    merge_unions(&container.ident, tagged, untagged, container.generics)
                 }
-                TagType::Adjacent { tag, content } => {
-                    self.export_enum_adjacent(name, container.generics, variants, tag, content)
+            }
+            // serde serializes a transparent struct as its only field that isn't skipped
+            Data::Struct(_, fields) if container.attrs.transparent() => {
+                match fields.iter().find(|field| field.attrs.transparent()) {
+                    Some(field) => self.export_struct_newtype(
+                        name,
+                        container.generics,
+                        field,
+                        attributes.nominal,
+                    ),
+                    None => Err(TsExportError::MalformedInput),
                 }
-                TagType::None => self.export_enum_untagged(name, container.generics, variants),
-            },
+            }
+            Data::Struct(Style::Newtype, fields) => {
+                self.export_struct_newtype(name, container.generics, &fields[0], attributes.nominal)
+            }
+            _ if attributes.nominal => Err(TsExportError::InvalidAttribute(
+                "ts(nominal) only applies to newtype and transparent structs".to_string(),
+            )),
             Data::Struct(style, fields) => match style {
-                Style::Unit => Ok(Solved::new(vec![])), // Unit structs are a no-op because they dont have a TS representation
-                Style::Newtype => self.export_struct_newtype(name, container.generics, fields),
-                Style::Tuple => self.export_struct_tuple(name, container.generics, fields),
-                Style::Struct => self.export_struct_struct(name, container.generics, fields),
+                Style::Unit => self.export_struct_unit(name),
+                Style::Newtype => unreachable!("Newtype structs are exported above"),
+                Style::Tuple => match attributes.fields {
+                    Some(names) => {
+                        self.export_struct_tuple_as_object(name, container.generics, fields, names)
+                    }
+                    None => {
+                        let fields = fields
+                            .into_iter()
+                            .filter(|field| !self.skips_field(field))
+                            .collect();
+                        self.export_struct_tuple(name, container.generics, fields)
+                    }
+                },
+                Style::Struct => {
+                    // serde fills the missing fields of a struct with a `#[serde(default)]`
+                    let all_optional = self.direction == Direction::Deserialize
+                        && !matches!(container.attrs.default(), attr::Default::None);
+                    self.export_struct_struct(name, container.generics, fields, all_optional)
+                }
             },
+        }?;
+        remove_type_parameters(&mut solved.inner, &phantom_idents);
+        self.apply_type_parameter_defaults(container.generics, &mut solved)?;
+        if let Some(doc) = solved.inner.first_mut().and_then(ExportStatement::doc_mut) {
+            *doc = doc_comment(&container.original.attrs);
         }
+        Ok(solved)
     }
 
-    pub fn export_statements_from_type_alias(
+    /// Carries the default types of the generic parameters (`struct Foo<T = u32>`) to the declarations (`Foo<T = number>`)
+    fn apply_type_parameter_defaults(
         &self,
-        type_alias: ItemType,
+        generics: &Generics,
+        solved: &mut Solved<Vec<ExportStatement>>,
+    ) -> Result<(), TsExportError> {
+        // The defaults may constrain the other parameters, e.g. `K` in `V = HashMap<K, u32>`
+        let mut default_constraints = GenericConstraints::default();
+        for param in generics.type_params() {
+            let default = match &param.default {
+                Some(default) => default,
+                None => continue,
+            };
+            let mut default = self.solve_type(&TypeInfo {
+                generics,
+                ty: default,
+            })?;
+            solved.import_entries.append(&mut default.import_entries);
+            default_constraints.merge(default.generic_constraints);
+            let default_type = default.inner;
+            let ident = param.ident.to_string();
+            type_parameters(&mut solved.inner)
+                .filter(|parameter| parameter.identifier.to_string() == ident)
+                .for_each(|parameter| parameter.default = Some(default_type.clone()));
+        }
+        for parameter in type_parameters(&mut solved.inner) {
+            if let Some(constraint) = default_constraints.get_constraints(&parameter.identifier) {
+                parameter
+                    .constraint
+                    .get_or_insert_with(ExtendsConstraint::default)
+                    .merge(&constraint);
+            }
+        }
+        Ok(())
+    }
+
+    /// Exports a container whose wire shape is declared with `#[ts(type)]` or `#[ts(as)]` as a type alias,
+    /// since its `Serialize` implementation can't be derived from its fields
+    fn export_override(
+        &self,
+        ident: String,
+        generics: &Generics,
+        attributes: &ContainerAttributes,
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
-        let ident = TSIdent::from_str(&type_alias.ident.to_string())?;
-        let solver_info = TypeInfo {
-            generics: &type_alias.generics,
-            ty: type_alias.ty.as_ref(),
+        let solved = match (&attributes.ts_type, &attributes.as_type) {
+            (Some(ts_type), _) => Solved::new(TsType::PrimaryType(PrimaryType::RawType(RawType(
+                ts_type.clone(),
+            )))),
+            (None, Some(as_type)) => {
+                let ty: Type = syn::parse_str(as_type)?;
+                self.solve_type(&TypeInfo { generics, ty: &ty })?
+            }
+            (None, None) => return Err(TsExportError::MalformedInput),
         };
-        let solved = self.solve_type(&solver_info)?;
-        // TODO: Or maybe apply_generic_constraints inside extract_type_parameters ?
-        let mut type_params = extract_type_parameters(&type_alias.generics)?;
+        let mut type_params = extract_type_parameters(generics)?;
         if let Some(params) = type_params.as_mut() {
             apply_generic_constraints(params, &solved.generic_constraints);
         }
-        Ok(solved.map(move |inner_type| {
-            vec![ExportStatement::TypeAliasDeclaration(
-                TypeAliasDeclaration {
-                    ident,
-                    inner_type,
-                    type_params,
-                },
-            )]
+        let ident = TSIdent::from_str(&ident)?;
+        Ok(solved.map(|inner_type| {
+            vec![TypeAliasDeclaration {
+                ident,
+                inner_type,
+                type_params,
+                doc: None,
+            }
+            .into()]
         }))
     }
 
-    fn export_struct_struct(
+    /// Exports the endpoints of the warp filters of the module as the interface `name`, see [ExporterOptions::warp_endpoints] :
+    ///
+    /// ```text
+    /// export interface Endpoints {
+    ///     "POST /users/{0}/posts": {
+    ///         params: [ number ],
+    ///         request: NewPost,
+    ///         response: Post
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// `params` and `request` are omitted when the endpoint has none, and so is `response` when the handler doesn't reply with JSON.
+    pub fn export_statements_from_endpoints(
         &self,
-        ident: String,
-        generics: &Generics,
-        fields: Vec<Field>,
+        name: &str,
+        endpoints: &[Endpoint],
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
-        let mut imports = Vec::new();
-        let mut constraints = GenericConstraints::default();
-        let members: Vec<TypeMember> = fields
-            .into_iter()
-            .filter_map(|field| {
-                if field.attrs.skip_serializing() {
-                    return None;
+        if endpoints.is_empty() {
+            return Ok(Solved::new(Vec::new()));
+        }
+        let generics = Generics::default();
+        let mut import_entries: Vec<ImportEntry> = Vec::new();
+        let mut solve = |ty: Option<&Type>| -> Result<TsType, TsExportError> {
+            match ty {
+                Some(ty) => {
+                    let solved = self.solve_type(&TypeInfo {
+                        generics: &generics,
+                        ty,
+                    })?;
+                    import_entries.extend(solved.import_entries);
+                    Ok(solved.inner)
                 }
-                let solver_info = MemberInfo::from_generics_and_field(generics, &field);
-                Some(self.solve_member(&solver_info))
-            })
-            .collect::<Result<Vec<Solved<TypeMember>>, TsExportError>>()?
-            .into_iter()
-            .map(|mut solved| {
-                imports.append(&mut solved.import_entries);
-                constraints.merge(solved.generic_constraints);
-                solved.inner
+                None => Ok(TsType::PrimaryType(PrimaryType::Predefined(
+                    PredefinedType::Unknown,
+                ))),
+            }
+        };
+        let property = |name: &str, inner_type: TsType| -> TypeMember {
+            TypeMember::PropertySignature(PropertySignature {
+                name: PropertyName::from(name.to_string()),
+                optional: false,
+                readonly: false,
+                inner_type,
+                doc: None,
             })
-            .collect();
-        let mut type_params = extract_type_parameters(generics)?;
-        if let Some(params) = type_params.as_mut() {
-            apply_generic_constraints(params, &constraints);
+        };
+        let body_type = |body: &JsonBody| match body {
+            JsonBody::Typed(ty) => Some(ty.as_ref().clone()),
+            JsonBody::Untyped => None,
+        };
+
+        let mut members: Vec<TypeMember> = Vec::new();
+        for endpoint in endpoints {
+            let mut endpoint_members: Vec<TypeMember> = Vec::new();
+            if !endpoint.params.is_empty() {
+                let inner_types = endpoint
+                    .params
+                    .iter()
+                    .map(|param| solve(param.as_ref()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                endpoint_members.push(property(
+                    "params",
+                    TsType::PrimaryType(PrimaryType::TupleType(TupleType { inner_types })),
+                ));
+            }
+            if let Some(request) = &endpoint.request {
+                endpoint_members.push(property("request", solve(body_type(request).as_ref())?));
+            }
+            if let Some(response) = &endpoint.response {
+                endpoint_members.push(property("response", solve(body_type(response).as_ref())?));
+            }
+            members.push(property(
+                &endpoint.key(),
+                TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                    body: TypeBody {
+                        members: endpoint_members,
+                    },
+                })),
+            ));
         }
-        let ident = TSIdent::from_str(&ident)?;
+
         Ok(Solved {
             inner: vec![ExportStatement::InterfaceDeclaration(
                 InterfaceDeclaration {
-                    ident,
+                    ident: TSIdent::from_str(name)?,
                     extends_clause: None,
-                    type_params,
+                    type_params: None,
                     obj_type: ObjectType {
                         body: TypeBody { members },
                     },
+                    doc: None,
                 },
             )],
-            import_entries: imports,
-            generic_constraints: constraints,
+            import_entries,
+            generic_constraints: GenericConstraints::default(),
         })
     }
 
-    fn export_struct_newtype(
+    /// The name of a variant, or the value of its tag : its string literal, or any string for the catch-all
+    /// `#[serde(other)]` variant when deserializing, see [ExporterOptions::open_catch_all_variants]
+    fn variant_tag(&self, variant: &Variant) -> TsType {
+        if self.is_catch_all(variant) {
+            if self.options.open_catch_all_variants {
+                raw_type("string & {}")
+            } else {
+                TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String))
+            }
+        } else {
+            TsType::PrimaryType(PrimaryType::LiteralType(LiteralType::StringLiteral(
+                self.serde_name(variant.attrs.name()).into(),
+            )))
+        }
+    }
+
+    /// Whether serde deserializes the variant from any unknown tag
+    fn is_catch_all(&self, variant: &Variant) -> bool {
+        self.direction == Direction::Deserialize && variant.attrs.other()
+    }
+
+    /// Whether the enum is exported as a TS enum, see [ExporterOptions::ts_enums]
+    fn is_ts_enum(&self, tag: &TagType, variants: &[Variant]) -> bool {
+        self.options.ts_enums
+            && self.options.external_tag_key.is_none()
+            && matches!(tag, TagType::External)
+            && !variants.is_empty()
+            && variants
+                .iter()
+                .all(|variant| matches!(variant.style, Style::Unit) && !self.is_catch_all(variant))
+    }
+
+    /// Exports a fieldless enum as a TS enum, whose members are named after the variants and valued with their serialized names
+    fn export_ts_enum(
         &self,
         ident: String,
-        generics: &Generics,
-        fields: Vec<Field>,
+        variants: Vec<Variant>,
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
-        let field = &fields[0];
-        let solver_info = TypeInfo {
-            generics,
-            ty: field.ty,
-        };
-        let solved = self.solve_type(&solver_info)?;
-        let mut type_params = extract_type_parameters(generics)?;
-        if let Some(params) = type_params.as_mut() {
-            apply_generic_constraints(params, &solved.generic_constraints);
+        let variants = variants
+            .iter()
+            .map(|variant| {
+                Ok(ConstEnumVariant {
+                    ident: TSIdent::from_str(&variant.ident.to_string())?,
+                    value: self.serde_name(variant.attrs.name()).into(),
+                })
+            })
+            .collect::<Result<_, TsExportError>>()?;
+        Ok(Solved::new(vec![EnumDeclaration {
+            ident: TSIdent::from_str(&ident)?,
+            body: ConstEnumBody { variants },
+            doc: None,
         }
-        let ident = TSIdent::from_str(&ident)?;
-        Ok(solved.map(|inner_type| {
-            vec![TypeAliasDeclaration {
-                ident,
-                inner_type,
-                type_params,
-            }
-            .into()]
-        }))
+        .into()]))
     }
 
-    fn export_struct_tuple(
+    fn export_enum(
         &self,
         ident: String,
         generics: &Generics,
-        fields: Vec<Field>,
+        variants: Vec<Variant>,
+        tag: &TagType,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        let docs: Vec<Option<DocComment>> = variants
+            .iter()
+            .map(|variant| doc_comment(&variant.original.attrs))
+            .collect();
+        let mut solved = match tag {
+            TagType::External => self.export_enum_external(ident, generics, variants),
+            TagType::Internal { tag } => self.export_enum_internal(ident, generics, variants, tag),
+            TagType::Adjacent { tag, content } => {
+                self.export_enum_adjacent(ident, generics, variants, tag, content)
+            }
+            TagType::None => return self.export_enum_untagged(ident, generics, variants),
+        }?;
+        // The doc comment of a variant goes on its first property, the tag or the variant name
+        if let Some(ExportStatement::TypeAliasDeclaration(declaration)) = solved.inner.first_mut() {
+            match &mut declaration.inner_type {
+                TsType::UnionType(union) if union.types.len() == docs.len() => {
+                    for (ty, doc) in union.types.iter_mut().zip(docs) {
+                        document_first_member(ty, doc);
+                    }
+                }
+                ty => {
+                    if let [doc] = docs.as_slice() {
+                        document_first_member(ty, doc.clone());
+                    }
+                }
+            }
+        }
+        Ok(solved)
+    }
+
+    pub fn export_statements_from_type_alias(
+        &self,
+        type_alias: ItemType,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        // Type aliases are not filtered by a derive, so skip the ones that cannot be serialized
+        if let Type::BareFn(_)
+        | Type::ImplTrait(_)
+        | Type::TraitObject(_)
+        | Type::Ptr(_)
+        | Type::Never(_)
+        | Type::Infer(_) = strip_references(type_alias.ty.as_ref())
+        {
+            log::warn!(
+                "Type alias {} cannot be serialized, skipping",
+                type_alias.ident
+            );
+            return Ok(Solved::new(vec![]));
+        }
+        let ident = TSIdent::from_str(&type_alias.ident.to_string())?;
+        let solver_info = TypeInfo {
+            generics: &type_alias.generics,
+            ty: type_alias.ty.as_ref(),
+        };
+        let solved = self.solve_type(&solver_info)?;
+        // TODO: Or maybe apply_generic_constraints inside extract_type_parameters ?
+        let mut type_params = extract_type_parameters(&type_alias.generics)?;
+        if let Some(params) = type_params.as_mut() {
+            apply_generic_constraints(params, &solved.generic_constraints);
+        }
+        let doc = doc_comment(&type_alias.attrs);
+        let mut solved = solved.map(move |inner_type| {
+            vec![ExportStatement::TypeAliasDeclaration(
+                TypeAliasDeclaration {
+                    ident,
+                    inner_type,
+                    type_params,
+                    doc,
+                },
+            )]
+        });
+        self.apply_type_parameter_defaults(&type_alias.generics, &mut solved)?;
+        Ok(solved)
+    }
+
+    fn export_struct_struct(
+        &self,
+        ident: String,
+        generics: &Generics,
+        fields: Vec<Field>,
+        all_optional: bool,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        let Solved {
+            inner: mut members,
+            mut import_entries,
+            mut generic_constraints,
+        } = self.solve_struct_members(generics, &fields)?;
+        if all_optional {
+            for member in members.iter_mut() {
+                if let TypeMember::PropertySignature(property) = member {
+                    property.optional = true;
+                }
+            }
+        }
+        let mut flattened = self.solve_flattened_fields(generics, &fields)?;
+        import_entries.append(&mut flattened.import_entries);
+        generic_constraints.merge(flattened.generic_constraints);
+        let mut type_params = extract_type_parameters(generics)?;
+        if let Some(params) = type_params.as_mut() {
+            apply_generic_constraints(params, &generic_constraints);
+        }
+        let ident = TSIdent::from_str(&ident)?;
+        if !flattened.inner.is_empty() {
+            // serde merges the members of the flattened fields with the other ones
+            let object = TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                body: TypeBody { members },
+            }));
+            let types: Vec<TsType> = Some(object)
+                .filter(|_| !fields.iter().all(|field| field.attrs.flatten()))
+                .into_iter()
+                .chain(flattened.inner)
+                .collect();
+            let inner_type = IntersectionType::of(types);
+            return Ok(Solved {
+                inner: vec![TypeAliasDeclaration {
+                    ident,
+                    inner_type,
+                    type_params,
+                    doc: None,
+                }
+                .into()],
+                import_entries,
+                generic_constraints,
+            });
+        }
+        Ok(Solved {
+            inner: vec![ExportStatement::InterfaceDeclaration(
+                InterfaceDeclaration {
+                    ident,
+                    extends_clause: None,
+                    type_params,
+                    obj_type: ObjectType {
+                        body: TypeBody { members },
+                    },
+                    doc: None,
+                },
+            )],
+            import_entries,
+            generic_constraints,
+        })
+    }
+
+    /// Solves the types of the `#[serde(flatten)]` fields of a struct, to be intersected with its other members.
+    ///
+    /// The members of a flattened `Option` may all be missing, it is solved as `Partial<T>`.
+    fn solve_flattened_fields(
+        &self,
+        generics: &Generics,
+        fields: &[Field],
+    ) -> Result<Solved<Vec<TsType>>, TsExportError> {
+        let mut imports = Vec::new();
+        let mut constraints = GenericConstraints::default();
+        let mut types = Vec::new();
+        for field in fields
+            .iter()
+            .filter(|field| field.attrs.flatten() && !self.skips_field(field))
+        {
+            let mut solved = self.solve_type(&TypeInfo {
+                generics,
+                ty: field.ty,
+            })?;
+            imports.append(&mut solved.import_entries);
+            constraints.merge(solved.generic_constraints);
+            let ty = match strip_null(solved.inner) {
+                Ok(inner) => TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+                    name: TSIdent::from_str("Partial")?,
+                    args: Some(TypeArguments { types: vec![inner] }),
+                })),
+                Err(ty @ TsType::UnionType(_)) => TsType::ParenthesizedType(ParenthesizedType {
+                    inner: Box::new(ty),
+                }),
+                Err(ty) => ty,
+            };
+            types.push(ty);
+        }
+        Ok(Solved {
+            inner: types,
+            import_entries: imports,
+            generic_constraints: constraints,
+        })
+    }
+
+    /// Solves the members of a struct, skipping the fields that are not serialized and the flattened ones
+    fn solve_struct_members(
+        &self,
+        generics: &Generics,
+        fields: &[Field],
+    ) -> Result<Solved<Vec<TypeMember>>, TsExportError> {
+        let mut imports = Vec::new();
+        let mut constraints = GenericConstraints::default();
+        let members: Vec<TypeMember> = fields
+            .iter()
+            .filter_map(|field| {
+                if self.skips_field(field) || field.attrs.flatten() {
+                    return None;
+                }
+                let solver_info = self.member_info(generics, field);
+                Some(self.solve_member(&solver_info))
+            })
+            .collect::<Result<Vec<Solved<TypeMember>>, TsExportError>>()?
+            .into_iter()
+            .map(|mut solved| {
+                imports.append(&mut solved.import_entries);
+                constraints.merge(solved.generic_constraints);
+                solved.inner
+            })
+            .collect();
+        Ok(Solved {
+            inner: members,
+            import_entries: imports,
+            generic_constraints: constraints,
+        })
+    }
+
+    /// Solves the members of a struct with named fields declared in the current module, referenced by `ty`.
+    ///
+    /// Returns `None` when `ty` is not such a struct, when it is generic or declared in another module :
+    /// the callers then intersect the referenced type instead of inlining its members.
+    fn solve_local_struct_members(
+        &self,
+        ty: &Type,
+    ) -> Result<Option<Solved<Vec<TypeMember>>>, TsExportError> {
+        let ident = match strip_references(ty) {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                match type_path
+                    .path
+                    .segments
+                    .iter()
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                {
+                    [segment] if segment.arguments == PathArguments::None => {
+                        segment.ident.to_string()
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+        let derive_input = match self.local_declarations.get(&ident) {
+            Some(derive_input) if derive_input.generics.params.is_empty() => *derive_input,
+            _ => return Ok(None),
+        };
+        match serde_container(derive_input, self.direction) {
+            Some(Container {
+                data: Data::Struct(Style::Struct, fields),
+                generics,
+                ..
+            }) if !fields.iter().any(|field| field.attrs.flatten()) => {
+                self.solve_struct_members(generics, &fields).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// serde serializes a unit struct as `null`
+    fn export_struct_unit(
+        &self,
+        ident: String,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        Ok(Solved::new(vec![TypeAliasDeclaration {
+            ident: TSIdent::from_str(&ident)?,
+            inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Null)),
+            type_params: None,
+            doc: None,
+        }
+        .into()]))
+    }
+
+    /// Exports a newtype struct, or a transparent struct, as an alias of the type of its field,
+    /// branded when `nominal` is set (see [ContainerAttributes::nominal])
+    fn export_struct_newtype(
+        &self,
+        ident: String,
+        generics: &Generics,
+        field: &Field,
+        nominal: bool,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        let solver_info = TypeInfo {
+            generics,
+            ty: field.ty,
+        };
+        let solved = self.solve_type(&solver_info)?;
+        let mut type_params = extract_type_parameters(generics)?;
+        if let Some(params) = type_params.as_mut() {
+            apply_generic_constraints(params, &solved.generic_constraints);
+        }
+        let ident = TSIdent::from_str(&ident)?;
+        Ok(solved.map(|inner_type| {
+            let inner_type = if nominal {
+                brand(inner_type)
+            } else {
+                inner_type
+            };
+            vec![TypeAliasDeclaration {
+                ident,
+                inner_type,
+                type_params,
+                doc: None,
+            }
+            .into()]
+        }))
+    }
+
+    fn export_struct_tuple(
+        &self,
+        ident: String,
+        generics: &Generics,
+        fields: Vec<Field>,
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
         let mut imports: Vec<ImportEntry> = Vec::new();
         let mut constraints = GenericConstraints::default();
@@ -273,6 +1336,7 @@ impl ExporterContext<'_> {
                 ident,
                 inner_type,
                 type_params,
+                doc: None,
             }
             .into()],
             import_entries: imports,
@@ -280,6 +1344,77 @@ impl ExporterContext<'_> {
         })
     }
 
+    /// Exports a tuple struct as an interface, naming its members with the `#[ts(fields(...))]` attribute
+    fn export_struct_tuple_as_object(
+        &self,
+        ident: String,
+        generics: &Generics,
+        fields: Vec<Field>,
+        names: Vec<String>,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        if names.len() != fields.len() {
+            return Err(TsExportError::WrongFieldsCount(
+                ident,
+                fields.len(),
+                names.len(),
+            ));
+        }
+        log::warn!(
+            "{} is exported as an object, but serde still serializes it as an array unless it uses a custom serializer",
+            ident
+        );
+        let mut imports: Vec<ImportEntry> = Vec::new();
+        let mut constraints = GenericConstraints::default();
+        let members: Vec<TypeMember> = fields
+            .into_iter()
+            .zip(names)
+            .map(|(field, name)| {
+                let solver_info = TypeInfo {
+                    generics,
+                    ty: field.ty,
+                };
+                self.solve_type(&solver_info).map(|solved| {
+                    solved.map(|inner_type| {
+                        TypeMember::PropertySignature(PropertySignature {
+                            name: PropertyName::from(name),
+                            inner_type,
+                            optional: false,
+                            readonly: false,
+                            doc: None,
+                        })
+                    })
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|mut solved| {
+                imports.append(&mut solved.import_entries);
+                constraints.merge(solved.generic_constraints);
+                solved.inner
+            })
+            .collect();
+        let mut type_params = extract_type_parameters(generics)?;
+        if let Some(params) = type_params.as_mut() {
+            apply_generic_constraints(params, &constraints);
+        }
+        let ident = TSIdent::from_str(&ident)?;
+        Ok(Solved {
+            inner: vec![ExportStatement::InterfaceDeclaration(
+                InterfaceDeclaration {
+                    ident,
+                    extends_clause: None,
+                    type_params,
+                    obj_type: ObjectType {
+                        body: TypeBody { members },
+                    },
+                    doc: None,
+                },
+            )],
+            import_entries: imports,
+            generic_constraints: constraints,
+        })
+    }
+
     fn export_enum_internal(
         &self,
         ident: String,
@@ -292,55 +1427,78 @@ impl ExporterContext<'_> {
         let types: Vec<TsType> = variants
             .into_iter()
             .map(|variant| {
+                let tag_member = TypeMember::PropertySignature(PropertySignature {
+                    name: PropertyName::from(tag.to_string()),
+                    inner_type: self.variant_tag(&variant),
+                    optional: false,
+                    readonly: false,
+                    doc: None,
+                });
                 let variant_type = match (variant.style, variant.fields.as_slice()) {
                     (Style::Unit, []) | (Style::Tuple, _) => None,
                     (Style::Newtype, [field]) => {
+                        // serde flattens the fields of the wrapped struct next to the tag
+                        if let Some(mut solved) = self.solve_local_struct_members(field.ty)? {
+                            imports.append(&mut solved.import_entries);
+                            constraints.merge(solved.generic_constraints);
+                            let members = Some(tag_member).into_iter().chain(solved.inner);
+                            return Ok(TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                                body: TypeBody {
+                                    members: members.collect(),
+                                },
+                            })));
+                        }
                         let mut solved = self.solve_type(&TypeInfo {
                             generics,
-                            ty: &field.ty,
+                            ty: field.ty,
                         })?;
+                        if !is_flattenable(&solved.inner) {
+                            return Err(TsExportError::InvalidAttribute(format!(
+                                "The variant `{}` of `{}` holds a `{}`, which serde cannot serialize next to the tag `{}`",
+                                variant.ident, ident, solved.inner, tag
+                            )));
+                        }
                         imports.append(&mut solved.import_entries);
                         constraints.merge(solved.generic_constraints);
                         Some(solved.inner)
                     }
                     (Style::Struct, fields) => {
-                        let members: Vec<TypeMember> = fields
-                            .iter()
-                            .map(|field| {
-                                let solver_info =
-                                    MemberInfo::from_generics_and_field(generics, field);
-                                self.solve_member(&solver_info)
-                            })
-                            .collect::<Result<Vec<_>, _>>()?
-                            .into_iter()
-                            .map(|mut solved| {
-                                imports.append(&mut solved.import_entries);
-                                constraints.merge(solved.generic_constraints);
-                                solved.inner
-                            })
-                            .collect();
-                        Some(TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
-                            body: TypeBody { members },
-                        })))
+                        // serde writes the fields next to the tag
+                        let mut solved = self.solve_struct_members(generics, fields)?;
+                        let mut flattened = self.solve_flattened_fields(generics, fields)?;
+                        imports.append(&mut solved.import_entries);
+                        imports.append(&mut flattened.import_entries);
+                        constraints.merge(solved.generic_constraints);
+                        constraints.merge(flattened.generic_constraints);
+                        let members = Some(tag_member).into_iter().chain(solved.inner);
+                        let object = TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                            body: TypeBody {
+                                members: members.collect(),
+                            },
+                        }));
+                        if flattened.inner.is_empty() {
+                            return Ok(object);
+                        }
+                        let types = Some(object).into_iter().chain(flattened.inner).collect();
+                        return Ok(TsType::ParenthesizedType(ParenthesizedType {
+                            inner: Box::new(IntersectionType::of(types)),
+                        }));
                     }
                     _ => return Err(TsExportError::MalformedInput),
                 };
 
                 let tag_type = TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
                     body: TypeBody {
-                        members: vec![TypeMember::PropertySignature(PropertySignature {
-                            name: PropertyName::from(tag.to_string()),
-                            inner_type: TsType::PrimaryType(PrimaryType::LiteralType(
-                                LiteralType::StringLiteral(
-                                    variant.attrs.name().serialize_name().into(),
-                                ),
-                            )),
-                            optional: false,
-                        })],
+                        members: vec![tag_member],
                     },
                 }));
+                // A unit variant only holds its tag
+                let variant_type = match variant_type {
+                    Some(variant_type) => variant_type,
+                    None => return Ok(tag_type),
+                };
                 let inter = TsType::IntersectionType(IntersectionType {
-                    types: Some(tag_type).into_iter().chain(variant_type).collect(),
+                    types: vec![tag_type, variant_type],
                 });
                 Ok(TsType::ParenthesizedType(ParenthesizedType {
                     inner: Box::new(inter),
@@ -358,6 +1516,7 @@ impl ExporterContext<'_> {
                     ident,
                     inner_type: TsType::UnionType(UnionType { types }),
                     type_params,
+                    doc: None,
                 },
             )],
             import_entries: imports,
@@ -420,11 +1579,7 @@ impl ExporterContext<'_> {
                     let members: Vec<TypeMember> = variant
                         .fields
                         .into_iter()
-                        .map(|field| {
-                            self.solve_member(&MemberInfo::from_generics_and_field(
-                                generics, &field,
-                            ))
-                        })
+                        .map(|field| self.solve_member(&self.member_info(generics, &field)))
                         .collect::<Result<Vec<_>, _>>()?
                         .into_iter()
                         .map(|mut solved| {
@@ -450,7 +1605,8 @@ impl ExporterContext<'_> {
                 solved.inner
             })
             .collect();
-        let inner_type = TsType::UnionType(UnionType { types });
+        // serde tries the variants in order, a type matched by an earlier variant is left out
+        let inner_type = UnionType::of(types);
         let mut type_params = extract_type_parameters(generics)?;
         if let Some(params) = type_params.as_mut() {
             apply_generic_constraints(params, &constraints);
@@ -461,6 +1617,7 @@ impl ExporterContext<'_> {
                 ident,
                 inner_type,
                 type_params,
+                doc: None,
             }
             .into()],
             import_entries: imports,
@@ -481,11 +1638,12 @@ impl ExporterContext<'_> {
         let types: Vec<TsType> = variants
             .into_iter()
             .map(|variant| {
+                let tag_type = self.variant_tag(&variant);
                 let members: Vec<TypeMember> = variant
                     .fields
                     .into_iter()
                     .map(|field| {
-                        let solver_info = MemberInfo::from_generics_and_field(generics, &field);
+                        let solver_info = self.member_info(generics, &field);
                         self.solve_member(&solver_info)
                     })
                     .collect::<Result<Vec<_>, _>>()?
@@ -506,12 +1664,11 @@ impl ExporterContext<'_> {
                             inner_types,
                         })))
                     }
-                    Style::Struct => Some({
-                        let members = members;
-                        TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                    Style::Struct => {
+                        Some(TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
                             body: TypeBody { members },
-                        }))
-                    }),
+                        })))
+                    }
                 };
 
                 let content_member = inner_type.map(|inner_type| {
@@ -519,15 +1676,17 @@ impl ExporterContext<'_> {
                         name: PropertyName::from(content.to_string()),
                         inner_type,
                         optional: false,
+                        readonly: false,
+                        doc: None,
                     })
                 });
 
                 let tag_member = TypeMember::PropertySignature(PropertySignature {
                     name: PropertyName::from(tag.to_string()),
-                    inner_type: TsType::PrimaryType(PrimaryType::LiteralType(
-                        LiteralType::StringLiteral(variant.attrs.name().serialize_name().into()),
-                    )),
+                    inner_type: tag_type,
                     optional: false,
+                    readonly: false,
+                    doc: None,
                 });
 
                 let members = Some(tag_member).into_iter().chain(content_member).collect();
@@ -548,6 +1707,7 @@ impl ExporterContext<'_> {
                 ident,
                 inner_type,
                 type_params,
+                doc: None,
             }
             .into()],
             import_entries: imports,
@@ -561,20 +1721,21 @@ impl ExporterContext<'_> {
         generics: &Generics,
         variants: Vec<Variant>,
     ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        if let Some(tag) = &self.options.external_tag_key {
+            return self.export_enum_external_flattened(ident, generics, variants, tag);
+        }
         let mut imports = Vec::new();
         let mut constraints = GenericConstraints::default();
         let types: Vec<TsType> = variants
             .into_iter()
             .map(|variant| {
-                let variant_name = variant.attrs.name().serialize_name();
+                let variant_name = self.serde_name(variant.attrs.name());
                 let container = match (variant.style, variant.fields.as_slice()) {
-                    (Style::Unit, []) => TsType::PrimaryType(PrimaryType::LiteralType(
-                        LiteralType::StringLiteral(variant_name.into()),
-                    )),
+                    (Style::Unit, []) => self.variant_tag(&variant),
                     (Style::Newtype, [field]) => {
                         let mut solved = self.solve_type(&TypeInfo {
                             generics,
-                            ty: &field.ty,
+                            ty: field.ty,
                         })?;
                         imports.append(&mut solved.import_entries);
                         constraints.merge(solved.generic_constraints);
@@ -584,7 +1745,9 @@ impl ExporterContext<'_> {
                                 members: vec![TypeMember::PropertySignature(PropertySignature {
                                     inner_type: solved.inner,
                                     optional: false,
+                                    readonly: false,
                                     name: PropertyName::StringLiteral(variant_name.into()),
+                                    doc: None,
                                 })],
                             },
                         }))
@@ -592,11 +1755,7 @@ impl ExporterContext<'_> {
                     (Style::Struct, fields) => {
                         let members: Vec<TypeMember> = fields
                             .iter()
-                            .map(|field| {
-                                self.solve_member(&MemberInfo::from_generics_and_field(
-                                    generics, field,
-                                ))
-                            })
+                            .map(|field| self.solve_member(&self.member_info(generics, field)))
                             .collect::<Result<Vec<_>, _>>()?
                             .into_iter()
                             .map(|mut solved| {
@@ -613,7 +1772,9 @@ impl ExporterContext<'_> {
                                 members: vec![TypeMember::PropertySignature(PropertySignature {
                                     inner_type,
                                     optional: false,
+                                    readonly: false,
                                     name: PropertyName::StringLiteral(variant_name.into()),
+                                    doc: None,
                                 })],
                             },
                         }))
@@ -624,7 +1785,7 @@ impl ExporterContext<'_> {
                             .map(|field| {
                                 self.solve_type(&TypeInfo {
                                     generics,
-                                    ty: &field.ty,
+                                    ty: field.ty,
                                 })
                             })
                             .collect::<Result<Vec<_>, _>>()?
@@ -642,7 +1803,9 @@ impl ExporterContext<'_> {
                                 members: vec![TypeMember::PropertySignature(PropertySignature {
                                     inner_type,
                                     optional: false,
+                                    readonly: false,
                                     name: PropertyName::StringLiteral(variant_name.into()),
+                                    doc: None,
                                 })],
                             },
                         }))
@@ -663,23 +1826,1532 @@ impl ExporterContext<'_> {
                 ident,
                 inner_type,
                 type_params,
+                doc: None,
             }
             .into()],
             import_entries: imports,
             generic_constraints: constraints,
         })
     }
-}
 
-fn extract_inner_types(members: Vec<TypeMember>) -> Vec<TsType> {
-    members
-        .into_iter()
-        .map(|member| match member {
-            TypeMember::PropertySignature(PropertySignature {
-                name: _name,
-                inner_type,
-                optional: _optional,
-            }) => inner_type,
-        })
-        .collect()
+    /// Exports an externally tagged enum as `{ tag: "Variant", ...content }`, see [ExporterOptions::external_tag_key]
+    fn export_enum_external_flattened(
+        &self,
+        ident: String,
+        generics: &Generics,
+        variants: Vec<Variant>,
+        tag: &str,
+    ) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+        let mut imports = Vec::new();
+        let mut constraints = GenericConstraints::default();
+        let types: Vec<TsType> = variants
+            .into_iter()
+            .map(|variant| {
+                let tag_member = TypeMember::PropertySignature(PropertySignature {
+                    name: PropertyName::from(tag.to_string()),
+                    inner_type: self.variant_tag(&variant),
+                    optional: false,
+                    readonly: false,
+                    doc: None,
+                });
+                let ts_type = match (variant.style, variant.fields.as_slice()) {
+                    (Style::Unit, []) => TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                        body: TypeBody {
+                            members: vec![tag_member],
+                        },
+                    })),
+                    (Style::Newtype, [field]) => {
+                        let mut solved = self.solve_type(&TypeInfo {
+                            generics,
+                            ty: field.ty,
+                        })?;
+                        if !is_flattenable(&solved.inner) {
+                            return Err(TsExportError::InvalidAttribute(format!(
+                                "The variant `{}` of `{}` holds a `{}`, which cannot be flattened next to the tag `{}`",
+                                variant.ident, ident, solved.inner, tag
+                            )));
+                        }
+                        imports.append(&mut solved.import_entries);
+                        constraints.merge(solved.generic_constraints);
+                        let tag_type = TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                            body: TypeBody {
+                                members: vec![tag_member],
+                            },
+                        }));
+                        TsType::ParenthesizedType(ParenthesizedType {
+                            inner: Box::new(TsType::IntersectionType(IntersectionType {
+                                types: vec![tag_type, solved.inner],
+                            })),
+                        })
+                    }
+                    (Style::Struct, fields) => {
+                        let members: Vec<TypeMember> = fields
+                            .iter()
+                            .map(|field| self.solve_member(&self.member_info(generics, field)))
+                            .collect::<Result<Vec<_>, _>>()?
+                            .into_iter()
+                            .map(|mut solved| {
+                                imports.append(&mut solved.import_entries);
+                                constraints.merge(solved.generic_constraints);
+                                solved.inner
+                            })
+                            .collect();
+                        TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                            body: TypeBody {
+                                members: Some(tag_member).into_iter().chain(members).collect(),
+                            },
+                        }))
+                    }
+                    // The content of a tuple variant is an array, it cannot be flattened next to the tag
+                    _ => {
+                        return Err(TsExportError::InvalidAttribute(format!(
+                            "The tuple variant `{}` of `{}` cannot be flattened next to the tag `{}`",
+                            variant.ident, ident, tag
+                        )))
+                    }
+                };
+                Ok(ts_type)
+            })
+            .collect::<Result<_, TsExportError>>()?;
+        let inner_type = TsType::UnionType(UnionType { types });
+        let mut type_params = extract_type_parameters(generics)?;
+        if let Some(params) = type_params.as_mut() {
+            apply_generic_constraints(params, &constraints);
+        }
+        let ident = TSIdent::from_str(&ident)?;
+        Ok(Solved {
+            inner: vec![TypeAliasDeclaration {
+                ident,
+                inner_type,
+                type_params,
+                doc: None,
+            }
+            .into()],
+            import_entries: imports,
+            generic_constraints: constraints,
+        })
+    }
+}
+
+/// Whether the content of a variant can be flattened next to its tag, i.e. whether it may serialize as a map
+fn is_flattenable(ts_type: &TsType) -> bool {
+    !matches!(
+        ts_type,
+        TsType::PrimaryType(
+            PrimaryType::Predefined(
+                PredefinedType::Number
+                    | PredefinedType::BigInt
+                    | PredefinedType::Boolean
+                    | PredefinedType::String
+            ) | PrimaryType::LiteralType(_)
+                | PrimaryType::ArrayType(_)
+                | PrimaryType::TupleType(_)
+        )
+    )
+}
+
+/// Merges the unions exported for the tagged and the untagged variants of an enum into a single declaration.
+///
+/// A declaration that isn't a union is merged as a union of a single member.
+fn merge_unions(
+    enum_ident: &Ident,
+    tagged: Solved<Vec<ExportStatement>>,
+    untagged: Solved<Vec<ExportStatement>>,
+    generics: &Generics,
+) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+    let mut types = Vec::new();
+    let mut ident = None;
+    for statement in tagged.inner.into_iter().chain(untagged.inner) {
+        match statement {
+            ExportStatement::TypeAliasDeclaration(TypeAliasDeclaration {
+                ident: declaration_ident,
+                inner_type,
+                ..
+            }) => {
+                ident = Some(declaration_ident);
+                types.push(inner_type);
+            }
+            ExportStatement::InterfaceDeclaration(InterfaceDeclaration {
+                ident: declaration_ident,
+                extends_clause: None,
+                obj_type,
+                ..
+            }) => {
+                ident = Some(declaration_ident);
+                types.push(TsType::PrimaryType(PrimaryType::ObjectType(obj_type)));
+            }
+            statement => {
+                return Err(TsExportError::InvalidAttribute(format!(
+                    "The tagged and the untagged variants of `{}` cannot be merged into a union, found `{}`",
+                    enum_ident, statement
+                )))
+            }
+        }
+    }
+    let ident = ident.ok_or_else(|| {
+        TsExportError::InvalidAttribute(format!("`{}` has no variant left to export", enum_ident))
+    })?;
+
+    let mut import_entries = tagged.import_entries;
+    import_entries.extend(untagged.import_entries);
+    let mut generic_constraints = tagged.generic_constraints;
+    generic_constraints.merge(untagged.generic_constraints);
+    let mut type_params = extract_type_parameters(generics)?;
+    if let Some(params) = type_params.as_mut() {
+        apply_generic_constraints(params, &generic_constraints);
+    }
+    Ok(Solved {
+        inner: vec![TypeAliasDeclaration {
+            ident,
+            inner_type: UnionType::of(types),
+            type_params,
+            doc: None,
+        }
+        .into()],
+        import_entries,
+        generic_constraints,
+    })
+}
+
+fn extract_inner_types(members: Vec<TypeMember>) -> Vec<TsType> {
+    members
+        .into_iter()
+        .map(|member| match member {
+            TypeMember::PropertySignature(PropertySignature { inner_type, .. }) => inner_type,
+            TypeMember::IndexSignature(IndexSignature { inner_type, .. }) => inner_type,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[cfg(feature = "decimal")]
+    use crate::type_solving::solvers::decimal::DecimalSolver;
+    use crate::{
+        contexts::type_solving::{TypeSolvingContext, TypeSolvingContextBuilder},
+        pipeline::module_step::{tests::launch_test_step, ModuleStep},
+        type_solving::solvers::{
+            chrono::ChronoSolver,
+            fs::FsSolver,
+            primitives::LargeIntegerPolicy,
+            result::ResultSolver,
+            serialize_with::{SerializeWithPolicy, SerializeWithSolver},
+            skip_serialize_if::SkipSerializeIf,
+            time::TimeSolver,
+            type_overrides::TypeOverridesSolver,
+        },
+    };
+
+    fn export(source: &str) -> String {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        try_export(&solving_context, &ExporterOptions::default(), source).expect("Failed to launch")
+    }
+
+    fn try_export(
+        solving_context: &TypeSolvingContext,
+        options: &ExporterOptions,
+        source: &str,
+    ) -> Result<String, TsExportError> {
+        let items = syn::parse_file(source).expect("Failed to parse").items;
+        let path = syn::Path {
+            leading_colon: None,
+            segments: Default::default(),
+        };
+        Ok(launch_test_step(
+            ModuleStep::new(path, items, "my_crate"),
+            solving_context,
+            options,
+        )
+        // The errors are matched without their location
+        .map_err(TsExportError::into_inner)?
+        .data
+        .exports
+        .iter()
+        .map(|export| format!("{}\n", export))
+        .collect())
+    }
+
+    #[test]
+    fn should_solve_serde_json_values() {
+        let output = export(
+            r#"
+            use serde_json::{Map, Value};
+
+            #[derive(Serialize)]
+            pub struct Payload {
+                raw: serde_json::value::Value,
+                fields: Map<String, Value>,
+                counts: serde_json::Map<String, u32>,
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Payload {\n\traw: unknown,\n\tfields: Record<string, unknown>,\n\tcounts: Record<string, number>\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_uuids() {
+        let output = export(
+            r#"
+            use uuid::Uuid;
+
+            #[derive(Serialize)]
+            pub struct Team { id: Uuid, members: Vec<uuid::Uuid> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Team {\n\tid: string,\n\tmembers: string[]\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_the_fixed_size_arrays_as_tuples() {
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Packet<const N: usize> { ip: [u8; 4], hash: [u8; 32], data: [u8; N], ids: &'static [u32] }
+            "#;
+        let solving_context = TypeSolvingContextBuilder::default()
+            .fixed_arrays_as_tuples(16)
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Packet {\n\tip: [ number, number, number, number ],\n\thash: number[],\n\tdata: number[],\n\tids: number[]\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_results() {
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Outcome { result: Result<u32, String> }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Outcome {\n\tresult: {\n\tOk: number\n} | {\n\tErr: string\n}\n}\n"
+        );
+
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(ResultSolver::forbidden())
+            .add_default_solvers()
+            .finish();
+        assert!(matches!(
+            try_export(&solving_context, &ExporterOptions::default(), source),
+            Err(TsExportError::ForbiddenType(ty, _)) if ty == "std::result::Result"
+        ));
+    }
+
+    #[test]
+    fn should_solve_smart_pointers_as_their_content() {
+        let output = export(
+            r#"
+            use std::{cell::RefCell, rc::Rc, sync::{Arc, Mutex}};
+
+            #[derive(Serialize)]
+            pub struct Session { user: Arc<User>, tags: Box<[String]>, hits: Rc<RefCell<u32>>, lock: std::sync::RwLock<Mutex<bool>> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Session {\n\tuser: User,\n\ttags: string[],\n\thits: number,\n\tlock: boolean\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_mark_the_properties_readonly() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            readonly: true,
+            ..ExporterOptions::default()
+        };
+        let output = try_export(
+            &solving_context,
+            &options,
+            r#"
+            #[derive(Serialize)]
+            pub struct User { id: u32, address: Address }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Address { Street { name: String } }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "export interface User {\n\treadonly id: number,\n\treadonly address: Address\n}\n\
+             export type Address = {\n\treadonly type: \"Street\",\n\treadonly name: string\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_fieldless_enums_as_ts_enums() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            ts_enums: true,
+            ..ExporterOptions::default()
+        };
+        let output = try_export(
+            &solving_context,
+            &options,
+            r#"
+            /// A color
+            #[derive(Serialize)]
+            #[serde(rename_all = "snake_case")]
+            pub enum Color { Red, DarkBlue, #[serde(rename = "GREEN")] Green }
+
+            #[derive(Serialize)]
+            pub enum Shape { Point, Circle(f64) }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Event { Started, Stopped }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "/** A color */\nexport enum Color { Red = \"red\", DarkBlue = \"dark_blue\", Green = \"GREEN\" }\n\
+             export type Shape = \"Point\" | {\n\t\"Circle\": number\n};\n\
+             export type Event = {\n\ttype: \"Started\"\n} | {\n\ttype: \"Stopped\"\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_large_integers_by_policy() {
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Counters { small: u32, big: u64, huge: i128 }
+            "#;
+        let export_with = |policy| {
+            let solving_context = TypeSolvingContextBuilder::default()
+                .large_integers(policy)
+                .add_default_solvers()
+                .finish();
+            try_export(&solving_context, &ExporterOptions::default(), source)
+        };
+        assert_eq!(
+            export_with(LargeIntegerPolicy::Number).unwrap(),
+            "export interface Counters {\n\tsmall: number,\n\tbig: number,\n\thuge: number\n}\n"
+        );
+        assert_eq!(
+            export_with(LargeIntegerPolicy::BigInt).unwrap(),
+            "export interface Counters {\n\tsmall: number,\n\tbig: bigint,\n\thuge: bigint\n}\n"
+        );
+        assert_eq!(
+            export_with(LargeIntegerPolicy::String).unwrap(),
+            "export interface Counters {\n\tsmall: number,\n\tbig: string,\n\thuge: string\n}\n"
+        );
+        assert!(matches!(
+            export_with(LargeIntegerPolicy::Error),
+            Err(TsExportError::ForbiddenType(ty, _)) if ty == "u64"
+        ));
+    }
+
+    #[test]
+    fn should_solve_the_numeric_wrappers_as_their_integer() {
+        let source = r#"
+            use std::num::{NonZeroU32, NonZeroI64, Wrapping};
+
+            #[derive(Serialize)]
+            pub struct Counters { id: NonZeroU32, delta: NonZeroI64, hash: Wrapping<u8>, total: std::num::Saturating<u64> }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Counters {\n\tid: number,\n\tdelta: number,\n\thash: number,\n\ttotal: number\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .large_integers(LargeIntegerPolicy::BigInt)
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Counters {\n\tid: number,\n\tdelta: bigint,\n\thash: number,\n\ttotal: bigint\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_borrowed_data_as_its_content() {
+        let output = export(
+            r#"
+            use std::borrow::Cow;
+
+            #[derive(Serialize)]
+            pub struct Page<'a> { title: &'a str, body: Cow<'a, str>, ids: Cow<'a, [u32]>, raw: &'a [u8], tags: std::borrow::Cow<'static, Vec<String>> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Page {\n\ttitle: string,\n\tbody: string,\n\tids: number[],\n\traw: number[],\n\ttags: string[]\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_leave_out_skipped_fields_and_variants() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            pub struct Account {
+                #[serde(skip)]
+                password: String,
+                #[serde(skip_serializing)]
+                token: String,
+                #[serde(skip_serializing_if = "std::option::Option::is_none")]
+                email: std::option::Option<String>,
+                #[serde(skip_serializing_if = "is_zero")]
+                credits: u32,
+            }
+
+            #[derive(Serialize)]
+            pub struct Pair(u32, #[serde(skip)] u32);
+
+            #[derive(Serialize)]
+            pub enum Event {
+                #[serde(skip_serializing)]
+                Internal,
+                Login { #[serde(skip)] session: u32, user: String },
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Account {\n\temail?: string,\n\tcredits?: number\n}\n\
+             export type Pair = [ number ];\n\
+             export type Event = {\n\t\"Login\": {\n\tuser: string\n}\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_apply_the_ts_attributes_of_the_fields() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[ts(rename = "UserDto")]
+            pub struct User {
+                #[ts(rename = "userId", type = "string")]
+                id: Uuid,
+                #[ts(optional)]
+                nickname: String,
+                #[ts(skip)]
+                password_hash: String,
+                #[serde(rename = "displayName")]
+                #[ts(rename = "label")]
+                display_name: String,
+            }
+
+            #[derive(Serialize)]
+            pub enum Event {
+                Login { #[ts(skip)] session: u32, #[ts(type = "`u-${number}`")] user: u32 },
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface UserDto {\n\tuserId: string,\n\tnickname?: string,\n\tlabel: string\n}\n\
+             export type Event = {\n\t\"Login\": {\n\tuser: `u-${number}`\n}\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_name_the_properties_and_types_after_the_serde_renames() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            type_names: TypeNamePolicy::SerdeName,
+            ..ExporterOptions::default()
+        };
+        let output = try_export(
+            &solving_context,
+            &options,
+            r#"
+            #[derive(Serialize)]
+            #[serde(rename = "UserDto")]
+            pub struct User {
+                #[serde(rename = "user-name")]
+                name: String,
+                #[serde(rename = "@type")]
+                kind: String,
+                #[serde(rename(serialize = "2fa"))]
+                two_factor: bool,
+                #[serde(rename = "displayName")]
+                display_name: String,
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "export interface UserDto {\n\t\"user-name\": string,\n\t\"@type\": string,\n\t\"2fa\": boolean,\n\
+             \tdisplayName: string\n}\n"
+        );
+        // A TS type must be named by an identifier
+        assert!(matches!(
+            try_export(
+                &solving_context,
+                &options,
+                r#"
+                #[derive(Serialize)]
+                #[serde(rename = "user-dto")]
+                pub struct User { name: String }
+                "#,
+            ),
+            Err(TsExportError::InvalidAttribute(message)) if message.contains("user-dto")
+        ));
+    }
+
+    #[test]
+    fn should_require_the_conditionally_skipped_fields_when_deserializing() {
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Post {
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                tags: Vec<String>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                cover: Option<String>,
+                #[serde(skip_serializing_if = "is_zero", default)]
+                views: u32,
+            }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Post {\n\ttags?: string[],\n\tcover?: string,\n\tviews?: number\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Deserialize,
+            ..ExporterOptions::default()
+        };
+        assert_eq!(
+            try_export(&solving_context, &options, source).unwrap(),
+            "export interface Post {\n\ttags: string[],\n\tcover: string | null,\n\tviews?: number\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_make_the_default_fields_optional_when_deserializing() {
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Query {
+                search: String,
+                #[serde(default)]
+                page: u32,
+                #[serde(default = "default_size")]
+                size: u32,
+                #[serde(skip_deserializing)]
+                total: u32,
+                #[serde(skip_serializing)]
+                token: String,
+            }
+
+            #[derive(Serialize)]
+            #[serde(default)]
+            pub struct Filters { archived: bool, tags: Vec<String> }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Query {\n\tsearch: string,\n\tpage: number,\n\tsize: number,\n\ttotal: number\n}\n\
+             export interface Filters {\n\tarchived: boolean,\n\ttags: string[]\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Deserialize,
+            ..ExporterOptions::default()
+        };
+        assert_eq!(
+            try_export(&solving_context, &options, source).unwrap(),
+            "export interface Query {\n\tsearch: string,\n\tpage?: number,\n\tsize?: number,\n\ttoken: string\n}\n\
+             export interface Filters {\n\tarchived?: boolean,\n\ttags?: string[]\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_deserialize_the_catch_all_variants_from_any_string() {
+        let source = r#"
+            #[derive(Serialize, Deserialize)]
+            pub enum Status { Active, #[serde(other)] Unknown }
+
+            #[derive(Serialize, Deserialize)]
+            #[serde(tag = "kind")]
+            pub enum Event { Click { x: i32 }, #[serde(other)] Other }
+            "#;
+        assert_eq!(
+            export(source),
+            "export type Status = \"Active\" | \"Unknown\";\n\
+             export type Event = {\n\tkind: \"Click\",\n\tx: number\n} | {\n\tkind: \"Other\"\n};\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Deserialize,
+            ..ExporterOptions::default()
+        };
+        assert_eq!(
+            try_export(&solving_context, &options, source).unwrap(),
+            "export type Status = \"Active\" | string;\n\
+             export type Event = {\n\tkind: \"Click\",\n\tx: number\n} | {\n\tkind: string\n};\n"
+        );
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Deserialize,
+            open_catch_all_variants: true,
+            ts_enums: true,
+            ..ExporterOptions::default()
+        };
+        assert_eq!(
+            try_export(&solving_context, &options, source).unwrap(),
+            "export type Status = \"Active\" | ( string & {} );\n\
+             export type Event = {\n\tkind: \"Click\",\n\tx: number\n} | {\n\tkind: ( string & {} )\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_tuple_and_unit_structs() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            pub struct UserPair(i32, i32);
+
+            #[derive(Serialize)]
+            pub struct Marker;
+
+            #[derive(Serialize)]
+            pub struct Flagged { marker: Marker }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type UserPair = [ number, number ];\n\
+             export type Marker = null;\n\
+             export interface Flagged {\n\tmarker: Marker\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_export_the_phantom_data_as_null_without_its_type_parameter() {
+        let output = export(
+            r#"
+            use std::marker::PhantomData;
+
+            #[derive(Serialize)]
+            pub struct Id<T> { id: u32, _marker: PhantomData<T> }
+
+            #[derive(Serialize)]
+            pub struct Tagged<T>(u32, std::marker::PhantomData<T>);
+
+            #[derive(Serialize)]
+            pub enum Event<T> { Created { id: u32, _marker: PhantomData<T> } }
+
+            #[derive(Serialize)]
+            pub struct Typed<T, U> { value: T, _marker: PhantomData<(T, U)> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Id {\n\tid: number,\n\t_marker: null\n}\n\
+             export type Tagged = [ number, null ];\n\
+             export type Event = {\n\t\"Created\": {\n\tid: number,\n\t_marker: null\n}\n};\n\
+             export interface Typed<T> {\n\tvalue: T,\n\t_marker: null\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_carry_doc_comments() {
+        let output = export(
+            r#"
+            /// A user
+            ///
+            /// Created at sign-up
+            #[derive(Serialize)]
+            pub struct User {
+                /// The display name
+                name: String,
+                age: u32,
+            }
+
+            /// The role of a user
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Role {
+                /// An administrator
+                Admin { level: u8 },
+                Member,
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "/**\n * A user\n *\n * Created at sign-up\n */\n\
+             export interface User {\n\t/** The display name */\n\tname: string,\n\tage: number\n}\n\
+             /** The role of a user */\n\
+             export type Role = {\n\t/** An administrator */\n\ttype: \"Admin\",\n\tlevel: number\n} | {\n\ttype: \"Member\"\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_flatten_structs_of_internally_tagged_newtype_variants() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
+            pub struct Person { first_name: String, #[serde(skip)] secret: String }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Event { Joined(Person), Left(models::Person) }
+            "#,
+        );
+        assert!(output.contains(
+            "export type Event = {\n\ttype: \"Joined\",\n\tfirstName: string\n} | ( {\n\ttype: \"Left\"\n} & Person );"
+        ), "{}", output);
+
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        assert!(matches!(
+            try_export(
+                &solving_context,
+                &ExporterOptions::default(),
+                "#[derive(Serialize)]\n#[serde(tag = \"type\")]\npub enum Event { Count(u32) }",
+            ),
+            Err(TsExportError::InvalidAttribute(message)) if message.contains("`Count` of `Event`")
+        ));
+    }
+
+    #[test]
+    fn should_intersect_flattened_fields() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            pub struct Pagination { page: u32 }
+
+            #[derive(Serialize)]
+            pub struct Query {
+                search: String,
+                #[serde(flatten)]
+                pagination: Pagination,
+                #[serde(flatten)]
+                filters: Option<Pagination>,
+            }
+
+            #[derive(Serialize)]
+            pub struct Wrapper { #[serde(flatten)] inner: Pagination }
+            "#,
+        );
+        assert!(
+            output.contains(
+                "export type Query = {\n\tsearch: string\n} & Pagination & Partial<Pagination>;"
+            ),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains("export type Wrapper = Pagination;"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn should_merge_fields_of_internally_tagged_struct_variants() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(tag = "type", rename_all_fields = "camelCase")]
+            pub enum WorkflowStatus {
+                PendingReview { external_id: u32 },
+                #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+                Rejected { reason: String },
+                Done,
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type WorkflowStatus = {\n\ttype: \"PendingReview\",\n\texternalId: number\n} | {\n\ttype: \"Rejected\",\n\tREASON: string\n} | {\n\ttype: \"Done\"\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_newtypes_and_transparent_structs_as_aliases() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            pub struct UserId(i32);
+
+            #[derive(Serialize)]
+            #[serde(transparent)]
+            pub struct Email { address: String }
+
+            #[derive(Serialize)]
+            #[serde(transparent)]
+            pub struct Tagged<T> { #[serde(skip)] tag: u32, value: Vec<T> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type UserId = number;\nexport type Email = string;\nexport type Tagged<T> = T[];\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_chrono_types() {
+        let source = r#"
+            use chrono::{DateTime, Utc, NaiveDate, NaiveTime};
+
+            #[derive(Serialize)]
+            pub struct Shift {
+                created_at: DateTime<Utc>,
+                updated_at: chrono::DateTime<chrono::FixedOffset>,
+                day: NaiveDate,
+                start: NaiveTime,
+            }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Shift {\n\tcreated_at: string,\n\tupdated_at: string,\n\tday: string,\n\tstart: string\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(ChronoSolver::new(TsType::PrimaryType(
+                PrimaryType::Predefined(PredefinedType::Number),
+            )))
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Shift {\n\tcreated_at: number,\n\tupdated_at: number,\n\tday: number,\n\tstart: number\n}\n"
+        );
+        // chrono doesn't serialize its durations
+        assert!(matches!(
+            try_export(
+                &solving_context,
+                &ExporterOptions::default(),
+                "#[derive(Serialize)]\npub struct Shift { length: chrono::Duration }"
+            ),
+            Err(TsExportError::ForbiddenType(ty, _)) if ty == "chrono::Duration"
+        ));
+    }
+
+    #[test]
+    fn should_solve_time_types_by_serializer() {
+        let source = r#"
+            use time::{OffsetDateTime, Date};
+            use time::serde::{rfc3339, timestamp};
+
+            #[derive(Serialize)]
+            pub struct Meeting {
+                day: Date,
+                #[serde(with = "time::serde::rfc3339")]
+                starts_at: OffsetDateTime,
+                #[serde(with = "time::serde::timestamp")]
+                created_at: OffsetDateTime,
+                #[serde(with = "time::serde::timestamp::milliseconds::option")]
+                ended_at: Option<OffsetDateTime>,
+                #[serde(skip_serializing_if = "Option::is_none", with = "time::serde::rfc3339::option")]
+                cancelled_at: Option<OffsetDateTime>,
+                #[serde(with = "rfc3339")]
+                updated_at: OffsetDateTime,
+                #[serde(serialize_with = "timestamp::serialize")]
+                seen_at: OffsetDateTime,
+            }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Meeting {\n\tday: string,\n\tstarts_at: string,\n\tcreated_at: number,\n\
+             \tended_at: number | null,\n\tcancelled_at?: string,\n\tupdated_at: string,\n\tseen_at: number\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Deserialize,
+            ..ExporterOptions::default()
+        };
+        // `seen_at` is deserialized from the default format of `time`
+        assert_eq!(
+            try_export(&solving_context, &options, source).unwrap(),
+            "export interface Meeting {\n\tday: string,\n\tstarts_at: string,\n\tcreated_at: number,\n\
+             \tended_at: number | null,\n\tcancelled_at: string | null,\n\tupdated_at: string,\n\tseen_at: string\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_durations_and_system_times() {
+        let source = r#"
+            use std::time::{Duration, SystemTime};
+
+            #[derive(Serialize)]
+            pub struct Job { timeout: Duration, started_at: SystemTime }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Job {\n\ttimeout: {\n\tsecs: number,\n\tnanos: number\n},\n\
+             \tstarted_at: {\n\tsecs_since_epoch: number,\n\tnanos_since_epoch: number\n}\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(TimeSolver::milliseconds())
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Job {\n\ttimeout: number,\n\tstarted_at: number\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_the_network_addresses() {
+        let output = export(
+            r#"
+            use std::net::{IpAddr, SocketAddr};
+
+            #[derive(Serialize)]
+            pub struct Peer { ip: IpAddr, addr: SocketAddr, v6: std::net::Ipv6Addr, v4: Option<core::net::SocketAddrV4> }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Peer {\n\tip: string,\n\taddr: string,\n\tv6: string,\n\tv4: string | null\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_the_paths_and_os_strings() {
+        let source = r#"
+            use std::{ffi::{CString, OsString}, path::{Path, PathBuf}};
+
+            #[derive(Serialize)]
+            pub struct Config<'a> { root: PathBuf, file: &'a Path, name: OsString, raw: CString }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Config {\n\troot: string,\n\tfile: string,\n\tname: {\n\tUnix: number[]\n} | {\n\tWindows: number[]\n},\n\traw: number[]\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(FsSolver::os_strings_as_never())
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Config {\n\troot: string,\n\tfile: string,\n\tname: never,\n\traw: never\n}\n"
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn should_solve_decimals() {
+        let source = r#"
+            use rust_decimal::Decimal;
+
+            #[derive(Serialize)]
+            pub struct Invoice { total: Decimal, rate: bigdecimal::BigDecimal }
+            "#;
+        assert_eq!(
+            export(source),
+            "export interface Invoice {\n\ttotal: string,\n\trate: string\n}\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(DecimalSolver::number())
+            .add_default_solvers()
+            .finish();
+        assert_eq!(
+            try_export(&solving_context, &ExporterOptions::default(), source).unwrap(),
+            "export interface Invoice {\n\ttotal: number,\n\trate: number\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_overridden_types() {
+        let overrides = vec![
+            ("rust_decimal::Decimal", "string"),
+            ("MyOpaque", "unknown"),
+            ("Amount", "number | string"),
+        ]
+        .into_iter()
+        .map(|(path, ts_type)| (path.to_string(), ts_type.to_string()))
+        .collect();
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_solver(TypeOverridesSolver::new(overrides))
+            .add_default_solvers()
+            .finish();
+        let output = try_export(
+            &solving_context,
+            &ExporterOptions::default(),
+            r#"
+            use rust_decimal::Decimal;
+
+            #[derive(Serialize)]
+            pub struct Invoice { total: Decimal, opaque: MyOpaque, amount: Amount }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "export interface Invoice {\n\ttotal: string,\n\topaque: unknown,\n\tamount: ( number | string )\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_brand_nominal_newtypes() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[ts(nominal)]
+            pub struct UserId(i32);
+
+            #[derive(Serialize)]
+            #[serde(transparent)]
+            #[ts(nominal)]
+            pub struct Email { address: String }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type UserId = number & {\n\treadonly __brand: unique symbol\n};\n\
+             export type Email = string & {\n\treadonly __brand: unique symbol\n};\n"
+        );
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        assert!(matches!(
+            try_export(
+                &solving_context,
+                &ExporterOptions::default(),
+                "#[derive(Serialize)] #[ts(nominal)] pub struct User { id: u32 }"
+            ),
+            Err(TsExportError::InvalidAttribute(_))
+        ));
+    }
+
+    #[test]
+    fn should_rename_the_variants_and_their_fields() {
+        // As with serde, the rename_all of an enum renames its variants, and the rename_all of a variant renames its fields
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
+            pub enum External {
+                UserJoined { user_id: u32 },
+                #[serde(rename_all = "camelCase")]
+                UserLeft { user_id: u32 },
+                GroupClosed(u32),
+            }
+
+            #[derive(Serialize)]
+            #[serde(tag = "t", content = "c", rename_all = "kebab-case")]
+            pub enum Adjacent {
+                #[serde(rename_all = "PascalCase")]
+                UserJoined { user_id: u32 },
+                GroupClosed,
+            }
+
+            #[derive(Serialize)]
+            #[serde(untagged)]
+            pub enum Untagged {
+                #[serde(rename_all = "camelCase")]
+                UserJoined { user_id: u32 },
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type External = {\n\t\"userJoined\": {\n\tuser_id: number\n}\n} | {\n\t\"userLeft\": {\n\tuserId: number\n}\n} | {\n\t\"groupClosed\": number\n};\n\
+             export type Adjacent = {\n\tt: \"user-joined\",\n\tc: {\n\tUserId: number\n}\n} | {\n\tt: \"group-closed\"\n};\n\
+             export type Untagged = {\n\tuserId: number\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_untagged_enums_with_mixed_variants() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(untagged)]
+            pub enum Protected<T> {
+                Visible(T),
+                Confidential,
+            }
+
+            #[derive(Serialize)]
+            #[serde(untagged)]
+            pub enum Setting {
+                Disabled,
+                Value(Option<u32>),
+                Range(u32, u32),
+                Named { name: String, #[serde(skip_serializing)] secret: String },
+                Missing,
+            }
+
+            #[derive(Serialize)]
+            #[serde(untagged)]
+            pub enum Nothing {}
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type Protected<T> = T | null;\n\
+             export type Setting = null | number | [ number, number ] | {\n\tname: string\n};\n\
+             export type Nothing = never;\n"
+        );
+    }
+
+    #[test]
+    fn should_export_untagged_variants_of_tagged_enums() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(tag = "kind", content = "data")]
+            pub enum Value {
+                Text(String),
+                #[serde(untagged)]
+                Number(u32),
+                #[serde(untagged)]
+                Pair(u32, u32),
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type Value = {\n\tkind: \"Text\",\n\tdata: string\n} | number | [ number, number ];\n"
+        );
+    }
+
+    #[test]
+    fn should_merge_the_declarations_of_the_tagged_and_untagged_variants() {
+        let enum_ident = Ident::new("Value", proc_macro2::Span::call_site());
+        let generics = Generics::default();
+        let ident = TSIdent::from_str("Value").unwrap();
+        let interface = || {
+            Solved::new(vec![InterfaceDeclaration {
+                ident: ident.clone(),
+                type_params: None,
+                extends_clause: None,
+                obj_type: ObjectType {
+                    body: TypeBody {
+                        members: vec![TypeMember::PropertySignature(PropertySignature {
+                            name: PropertyName::from("text".to_string()),
+                            inner_type: TsType::PrimaryType(PrimaryType::Predefined(
+                                PredefinedType::String,
+                            )),
+                            optional: false,
+                            readonly: false,
+                            doc: None,
+                        })],
+                    },
+                },
+                doc: None,
+            }
+            .into()])
+        };
+        let number = Solved::new(vec![TypeAliasDeclaration {
+            ident: ident.clone(),
+            inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+            type_params: None,
+            doc: None,
+        }
+        .into()]);
+        let merged = merge_unions(&enum_ident, interface(), number, &generics).unwrap();
+        assert_eq!(
+            merged.inner[0].to_string(),
+            "export type Value = {\n\ttext: string\n} | number;"
+        );
+
+        let reexport = Solved::new(vec![
+            ts_json_subset::declarations::reexport::ReexportDeclaration {
+                reexports: Vec::new(),
+            }
+            .into(),
+        ]);
+        assert!(matches!(
+            merge_unions(&enum_ident, interface(), reexport, &generics),
+            Err(TsExportError::InvalidAttribute(message)) if message.contains("`Value`")
+        ));
+    }
+
+    #[test]
+    fn should_export_unit_variants_of_internally_tagged_enums_as_tags() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Status { Active, Inactive }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type Status = {\n\ttype: \"Active\"\n} | {\n\ttype: \"Inactive\"\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_default_type_parameters() {
+        let output = export(
+            r#"
+            #[derive(Serialize)]
+            pub struct Page<T = String, M = Vec<u32>> { items: Vec<T>, meta: M }
+
+            type Pair<T = bool> = (T, T);
+
+            // The bounds aren't exported, and the defaults constrain the other parameters
+            use std::collections::HashMap;
+
+            #[derive(Serialize)]
+            #[serde(bound(serialize = "V: Serialize"))]
+            pub struct Index<K: Eq + Hash = String, V = HashMap<K, u32>>
+            where
+                K: Serialize + Clone,
+                for<'de> V: Deserialize<'de>,
+            { key: K, values: V }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface Page<T = string, M = number[]> {\n\titems: T[],\n\tmeta: M\n}\n\
+             export type Pair<T = boolean> = [ T, T ];\n\
+             export interface Index<K extends string = string, V = Record<K, number>> {\n\tkey: K,\n\tvalues: V\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_constrain_the_generic_map_keys() {
+        let output = export(
+            r#"
+            use std::collections::{BTreeMap, HashMap};
+
+            #[derive(Serialize)]
+            pub struct Index<K, V>(HashMap<K, u32>, V);
+
+            #[derive(Serialize)]
+            #[serde(tag = "kind")]
+            pub enum Lookup<K, T> {
+                Single { values: BTreeMap<K, Vec<K>> },
+                // Only a bare type parameter is constrained
+                Optional { values: HashMap<Option<T>, u32> },
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type Index<K extends string, V> = [ Record<K, number>, V ];\n\
+             export type Lookup<K extends string, T> = {\n\tkind: \"Single\",\n\tvalues: Record<K, K[]>\n} | {\n\tkind: \"Optional\",\n\tvalues: Record<T | null, number>\n};\n"
+        );
+    }
+
+    #[test]
+    fn should_export_overridden_containers() {
+        let output = export(
+            r#"
+            #[ts(type = "`${number}-${number}`")]
+            pub struct Version { major: u32, minor: u32 }
+
+            #[ts(as = "Vec<String>")]
+            pub struct Tags(HashSet<Tag>);
+
+            #[derive(Serialize)]
+            pub struct Release { version: Version, tags: Tags }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export type Version = `${number}-${number}`;\n\
+             export type Tags = string[];\n\
+             export interface Release {\n\tversion: Version,\n\ttags: Tags\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_remote_types_as_their_definition() {
+        let output = export(
+            r#"
+            use std::time::Duration;
+
+            #[derive(Serialize)]
+            #[serde(remote = "Duration")]
+            pub struct DurationDef { secs: u64, nanos: u32 }
+
+            #[derive(Serialize)]
+            pub struct Timeout {
+                #[serde(with = "DurationDef")]
+                read: Duration,
+                write: Option<std::time::Duration>,
+            }
+            "#,
+        );
+        assert_eq!(
+            output,
+            "export interface DurationDef {\n\tsecs: number,\n\tnanos: number\n}\n\
+             export interface Timeout {\n\tread: DurationDef,\n\twrite: DurationDef | null\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_solve_the_fields_by_their_custom_serializer() {
+        let source = r#"
+            use chrono::serde::ts_seconds;
+
+            #[derive(Serialize)]
+            pub struct Block {
+                #[serde(with = "crate::serde_hex")]
+                hash: Vec<u8>,
+                #[serde(serialize_with = "to_seconds", skip_serializing_if = "Option::is_none")]
+                duration: Option<Duration>,
+                #[serde(serialize_with = "to_unix")]
+                created_at: Timestamp,
+                #[serde(with = "ts_seconds")]
+                updated_at: DateTime<Utc>,
+            }
+            "#;
+        let solving_context = |policy| {
+            let serializers = vec![
+                ("crate::serde_hex", "string"),
+                ("to_seconds", "number"),
+                ("chrono::serde::ts_seconds", "number"),
+            ]
+            .into_iter()
+            .map(|(serializer, ts_type)| (serializer.to_string(), ts_type.to_string()))
+            .collect();
+            TypeSolvingContextBuilder::default()
+                .add_default_solvers()
+                .insert_before::<SkipSerializeIf, _>(SerializeWithSolver::new(serializers, policy))
+                .finish()
+        };
+        let output = |policy| {
+            try_export(
+                &solving_context(policy),
+                &ExporterOptions::default(),
+                source,
+            )
+        };
+
+        assert_eq!(
+            output(SerializeWithPolicy::Passthrough).unwrap(),
+            "export interface Block {\n\thash: string,\n\tduration?: number,\n\tcreated_at: Timestamp,\n\tupdated_at: number\n}\n"
+        );
+        assert_eq!(
+            output(SerializeWithPolicy::Unknown).unwrap(),
+            "export interface Block {\n\thash: string,\n\tduration?: number,\n\tcreated_at: unknown,\n\tupdated_at: number\n}\n"
+        );
+        assert!(matches!(
+            output(SerializeWithPolicy::Error),
+            Err(TsExportError::UnmappedSerializer(serializer, field))
+                if serializer == "to_unix" && field == "created_at"
+        ));
+    }
+
+    #[test]
+    fn should_require_the_custom_serialized_fields_when_deserializing() {
+        let serializers = vec![("crate::serde_hex".to_string(), "string".to_string())]
+            .into_iter()
+            .collect();
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .insert_before::<SkipSerializeIf, _>(SerializeWithSolver::new(
+                serializers,
+                SerializeWithPolicy::Passthrough,
+            ))
+            .finish();
+        let source = r#"
+            #[derive(Serialize)]
+            pub struct Block {
+                #[serde(with = "crate::serde_hex", skip_serializing_if = "Vec::is_empty")]
+                hash: Vec<u8>,
+            }
+            "#;
+        let output = |process_mode| {
+            let options = ExporterOptions {
+                process_mode,
+                ..ExporterOptions::default()
+            };
+            try_export(&solving_context, &options, source).unwrap()
+        };
+
+        assert_eq!(
+            output(ProcessMode::Serialize),
+            "export interface Block {\n\thash?: string\n}\n"
+        );
+        assert_eq!(
+            output(ProcessMode::Deserialize),
+            "export interface Block {\n\thash: string\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_flatten_the_external_tag_next_to_the_content_of_the_variants() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = ExporterOptions {
+            external_tag_key: Some("kind".to_string()),
+            ..ExporterOptions::default()
+        };
+        let output = |source| try_export(&solving_context, &options, source);
+
+        assert_eq!(
+            output(
+                r#"
+                #[derive(Serialize)]
+                pub struct Circle { radius: f64 }
+
+                #[derive(Serialize)]
+                pub enum Shape { Point, Circle(Circle), Square { side: f64 } }
+                "#
+            )
+            .unwrap(),
+            "export interface Circle {\n\tradius: number\n}\n\
+             export type Shape = {\n\tkind: \"Point\"\n} | ( {\n\tkind: \"Circle\"\n} & Circle ) | {\n\tkind: \"Square\",\n\tside: number\n};\n"
+        );
+        assert!(matches!(
+            output("#[derive(Serialize)]\npub enum Shape { Point, Line(f64, f64) }"),
+            Err(TsExportError::InvalidAttribute(message)) if message.contains("`Line` of `Shape`")
+        ));
+        assert!(matches!(
+            output("#[derive(Serialize)]\npub enum Metric { Count(u32) }"),
+            Err(TsExportError::InvalidAttribute(message)) if message.contains("`Count` of `Metric`")
+        ));
+    }
+
+    #[test]
+    fn should_export_the_endpoints_as_the_interface_of_the_options() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let options = |name: &str| ExporterOptions {
+            warp_endpoints: Some(name.to_string()),
+            ..ExporterOptions::default()
+        };
+        let source = r#"
+            #[derive(Serialize, Deserialize)]
+            pub struct Endpoints { count: u32 }
+
+            pub fn routes() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+                warp::path!("users" / u32)
+                    .and(warp::post())
+                    .and(warp::body::json())
+                    .map(|id: u32, endpoints: Endpoints| warp::reply::json(&endpoints))
+            }
+            "#;
+
+        assert_eq!(
+            try_export(&solving_context, &options("Api"), source).unwrap(),
+            "export interface Endpoints {\n\tcount: number\n}\n\
+             export interface Api {\n\t\"POST /users/{0}\": {\n\tparams: [ number ],\n\trequest: Endpoints,\n\tresponse: Endpoints\n}\n}\n"
+        );
+        assert!(matches!(
+            try_export(&solving_context, &options("Endpoints"), source),
+            Err(TsExportError::InvalidConfig(message)) if message.contains("`Endpoints`")
+        ));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::utils::crate_paths::{is_relative_path, module_segments, normalize_path};
 use proc_macro2::Span;
 use std::collections::HashMap;
 use syn::{
@@ -13,17 +14,30 @@ const PRELUDE: &str = r#"
     pub use std::string::String;
     pub use std::vec::Vec;"#;
 
+#[derive(Clone)]
 pub struct ImportContext {
     imported: ImportList,
     scoped: ImportList,
     // TODO: Maybe remove, this should probably be static ?
     prelude: ImportList,
+    /// The path of the current module, against which the relative paths are normalized
+    module_path: Vec<String>,
+    crate_name: String,
+    /// The modules declared by the current module, `inner::Thing` being relative to the current module
+    child_modules: Vec<Ident>,
 }
 
 impl ImportContext {
+    /// Sets the module the imports are parsed from, see [normalize_path]
+    pub fn set_current_module(&mut self, current_path: &Path, crate_name: &str) {
+        self.module_path = module_segments(current_path);
+        self.crate_name = crate_name.to_string();
+    }
+
     pub fn parse_imported(&mut self, items: &[Item], crate_name: &str) {
         let import_list = parse_uses(items, crate_name);
         self.imported = import_list;
+        self.child_modules = child_modules(items);
     }
 
     pub fn parse_scoped(&mut self, items: &[Item]) {
@@ -31,6 +45,16 @@ impl ImportContext {
         let import_list = parse_declarations(items);
         self.scoped = import_list;
     }
+
+    /// The ident a name imported with `as` refers to, `None` for the other names
+    pub fn aliased_ident(&self, name: &Ident) -> Option<&Ident> {
+        self.imported.aliases.get(name)
+    }
+
+    /// The paths of the modules imported with a glob by the module, see [ImportList::glob_paths]
+    pub fn glob_paths(&self) -> Vec<String> {
+        self.imported.glob_paths()
+    }
 }
 
 impl Default for ImportContext {
@@ -42,30 +66,43 @@ impl Default for ImportContext {
             imported: Default::default(),
             scoped: Default::default(),
             prelude,
+            module_path: Vec::new(),
+            crate_name: "crate".to_string(),
+            child_modules: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 /// An ImportList matches an Identifier to a known list of segments
 /// This allows to find the full path of a type, which will then be matched by a TypeSolver
-pub struct ImportList(HashMap<Ident, Vec<PathSegment>>);
+pub struct ImportList {
+    paths: HashMap<Ident, Vec<PathSegment>>,
+    /// The idents imported under another name (`use module::Foo as Bar`), by name
+    aliases: HashMap<Ident, Ident>,
+    /// The paths of the modules imported with a glob (`use module::*`)
+    globs: Vec<Vec<PathSegment>>,
+}
 
 impl std::ops::Deref for ImportList {
     type Target = HashMap<Ident, Vec<PathSegment>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.paths
     }
 }
 
 impl ImportList {
+    /// Adds the imports of a `use` tree.
+    ///
+    /// The paths that start with one of the `child_modules` are relative to the current module, they are prefixed with `self`
     // TODO: maybe fix the space-complexity of this function that clones PathSegments all the way
     pub fn add_use_tree(
         &mut self,
         mut segments: Vec<PathSegment>,
         use_tree: &UseTree,
         crate_name: &str,
+        child_modules: &[Ident],
     ) {
         match use_tree {
             UseTree::Path(path) => {
@@ -80,37 +117,73 @@ impl ImportList {
                         arguments: PathArguments::None,
                     },
                 };
+                if segments.is_empty() && child_modules.contains(&path.ident) {
+                    segments.push(PathSegment {
+                        ident: Ident::new("self", Span::call_site()),
+                        arguments: PathArguments::None,
+                    });
+                }
                 segments.push(new_segment);
-                self.add_use_tree(segments, path.tree.as_ref(), crate_name)
+                self.add_use_tree(segments, path.tree.as_ref(), crate_name, child_modules)
             }
             UseTree::Name(name) => {
-                self.0.insert(name.ident.clone(), segments);
+                self.paths.insert(name.ident.clone(), segments);
             }
             UseTree::Rename(rename) => {
-                self.0.insert(rename.rename.clone(), segments);
-            }
-            UseTree::Group(group) => {
-                group
-                    .items
-                    .iter()
-                    .for_each(|use_tree| self.add_use_tree(segments.clone(), use_tree, crate_name));
+                self.paths.insert(rename.rename.clone(), segments);
+                self.aliases
+                    .insert(rename.rename.clone(), rename.ident.clone());
             }
-            UseTree::Glob(_) => log::warn!("Glob imports are not supported by typebinder"),
+            UseTree::Group(group) => group.items.iter().for_each(|use_tree| {
+                self.add_use_tree(segments.clone(), use_tree, crate_name, child_modules)
+            }),
+            UseTree::Glob(_) => self.globs.push(segments),
         }
     }
 
     pub fn add_declaration(&mut self, ident: Ident) {
-        self.0.insert(ident, Vec::new());
+        self.paths.insert(ident, Vec::new());
     }
+
+    /// The paths of the modules imported with a glob, e.g. `super` for `use super::*`
+    pub fn glob_paths(&self) -> Vec<String> {
+        self.globs
+            .iter()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::")
+            })
+            .collect()
+    }
+
+    /// The ident an imported name refers to in its module, which differs from the name when imported with `as`
+    pub fn original_ident<'a>(&'a self, name: &'a Ident) -> &'a Ident {
+        self.aliases.get(name).unwrap_or(name)
+    }
+}
+
+/// The idents of the modules declared by `items`
+fn child_modules(items: &[Item]) -> Vec<Ident> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(item_mod) => Some(item_mod.ident.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
 pub fn parse_uses(items: &[Item], crate_name: &str) -> ImportList {
+    let child_modules = child_modules(items);
     let mut import_list = ImportList::default();
     for item_use in items.iter().filter_map(|item| match item {
         Item::Use(item) => Some(item),
         _ => None,
     }) {
-        import_list.add_use_tree(Vec::new(), &item_use.tree, crate_name);
+        import_list.add_use_tree(Vec::new(), &item_use.tree, crate_name, &child_modules);
     }
     import_list
 }
@@ -128,25 +201,51 @@ pub fn parse_declarations(items: &[Item]) -> ImportList {
 }
 
 impl ImportContext {
+    /// Finds the full path of a type from the imports of the module.
+    ///
+    /// A name imported with `as` is replaced by the ident it refers to, so that the type is solved and imported
+    /// from the module that defines it, the ImportSolver then naming it by its alias again, see [ImportContext::aliased_ident]. The paths relative to the current module are made absolute, see [normalize_path].
     pub fn solve_import(&self, ty_path: &TypePath) -> Option<syn::Type> {
         let segment = ty_path.path.segments.first().expect("Empty path");
         let ident = &segment.ident;
-        let found_segments = self
-            .imported
-            .get(ident)
-            .or_else(|| self.scoped.get(ident))
-            .or_else(|| self.prelude.get(ident))?;
-
-        let segments = found_segments
+        let found = [&self.imported, &self.scoped, &self.prelude]
             .iter()
-            .cloned()
-            .chain(ty_path.path.segments.iter().cloned())
-            .collect::<Punctuated<PathSegment, Colon2>>();
+            .find_map(|import_list| {
+                import_list
+                    .get(ident)
+                    .map(|segments| (*import_list, segments))
+            });
 
-        let path = Path {
-            leading_colon: None,
-            segments,
+        let path = match found {
+            Some((import_list, found_segments)) => {
+                let mut path_segments = ty_path.path.segments.clone();
+                path_segments[0].ident = import_list.original_ident(ident).clone();
+                let segments = found_segments
+                    .iter()
+                    .cloned()
+                    .chain(path_segments)
+                    .collect::<Punctuated<PathSegment, Colon2>>();
+                Path {
+                    leading_colon: None,
+                    segments,
+                }
+            }
+            None if is_relative_path(&ty_path.path) => ty_path.path.clone(),
+            // A path through a child module, e.g. `inner::Thing`, is relative to the current module
+            None if ty_path.path.segments.len() > 1 && self.child_modules.contains(ident) => {
+                let mut path = ty_path.path.clone();
+                path.segments.insert(
+                    0,
+                    PathSegment {
+                        ident: Ident::new("self", Span::call_site()),
+                        arguments: PathArguments::None,
+                    },
+                );
+                path
+            }
+            None => return None,
         };
+        let path = normalize_path(&path, &self.module_path, &self.crate_name);
 
         Some(TypePath { qself: None, path }.into())
     }
@@ -174,7 +273,7 @@ pub mod tests {
         assert_eq!(DisplayPath(&path).to_string(), "std::string");
     }
 
-    const EXAMPLE: &'static str = r#"
+    const EXAMPLE: &str = r#"
         struct A {}
         struct B;
         struct C<T> { _t: T }
@@ -212,4 +311,60 @@ pub mod tests {
         };
         assert_eq!(DisplayPath(&path).to_string(), "");
     }
+
+    #[test]
+    fn test_import_aliases() {
+        let src = syn::parse_file(
+            r#"
+            mod models;
+            use models::{User as Member, Group};
+            use crate::events::Event as Happening;
+            "#,
+        )
+        .expect("Failed to parse");
+        let mut import_context = ImportContext::default();
+        let current_path: Path = syn::parse_str("api").expect("Failed to parse path");
+        import_context.set_current_module(&current_path, "my_crate");
+        import_context.parse_imported(&src.items, "my_crate");
+
+        let solve = |ty: &str| {
+            let ty_path: TypePath = syn::parse_str(ty).expect("Failed to parse type");
+            match import_context.solve_import(&ty_path) {
+                Some(syn::Type::Path(ty_path)) => DisplayPath(&ty_path.path).to_string(),
+                _ => panic!("Failed to solve {}", ty),
+            }
+        };
+        assert_eq!(solve("Member"), "my_crate::api::models::User");
+        assert_eq!(solve("Group"), "my_crate::api::models::Group");
+        assert_eq!(solve("Happening"), "my_crate::events::Event");
+        assert_eq!(solve("super::Session"), "my_crate::Session");
+    }
+
+    #[test]
+    fn test_import_child_module_paths() {
+        let src = syn::parse_file(
+            r#"
+            pub mod inner_things;
+            use chrono::DateTime;
+            "#,
+        )
+        .expect("Failed to parse");
+        let mut import_context = ImportContext::default();
+        let current_path: Path = syn::parse_str("api").expect("Failed to parse path");
+        import_context.set_current_module(&current_path, "my_crate");
+        import_context.parse_imported(&src.items, "my_crate");
+
+        let solve = |ty: &str| {
+            let ty_path: TypePath = syn::parse_str(ty).expect("Failed to parse type");
+            match import_context.solve_import(&ty_path) {
+                Some(syn::Type::Path(ty_path)) => Some(DisplayPath(&ty_path.path).to_string()),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            solve("inner_things::Thing").as_deref(),
+            Some("my_crate::api::inner_things::Thing")
+        );
+        assert_eq!(solve("other_crate::Thing"), None);
+    }
 }
@@ -1,3 +1,5 @@
+use std::any::TypeId;
+
 use crate::type_solving::{solvers::skip_serialize_if::SkipSerializeIf, TypeSolver, TypeSolverExt};
 
 /// The context that contains all TypeSolver implementors for this pipeline.
@@ -9,49 +11,235 @@ use crate::type_solving::{solvers::skip_serialize_if::SkipSerializeIf, TypeSolve
 /// To statically ensure that this is the case, we force the usage of the Builder.
 pub struct TypeSolvingContext {
     solvers: Vec<Box<dyn TypeSolver>>,
+    names: Vec<&'static str>,
 }
 
 impl TypeSolvingContext {
     pub fn solvers(&self) -> &Vec<Box<dyn TypeSolver>> {
         &self.solvers
     }
+
+    /// The name of the type of the solver at `index`, e.g. `TupleSolver`
+    pub fn solver_name(&self, index: usize) -> &'static str {
+        self.names[index]
+    }
 }
 
+#[cfg(feature = "decimal")]
+use crate::type_solving::solvers::decimal::DecimalSolver;
 use crate::type_solving::solvers::{
-    array::ArraySolver, chrono::ChronoSolver, collections::CollectionsSolver,
-    generics::GenericsSolver, import::ImportSolver, option::OptionSolver,
-    primitives::PrimitivesSolver, reference::ReferenceSolver,
-    serde_json_value::SerdeJsonValueSolver, tuple::TupleSolver,
+    array::ArraySolver,
+    chrono::ChronoSolver,
+    collections::CollectionsSolver,
+    fs::FsSolver,
+    generics::GenericsSolver,
+    import::ImportSolver,
+    net::NetSolver,
+    option::OptionSolver,
+    phantom_data::PhantomDataSolver,
+    primitives::{LargeIntegerPolicy, PrimitivesSolver},
+    reference::ReferenceSolver,
+    remote::RemoteSolver,
+    result::ResultSolver,
+    serde_json_value::{SerdeJsonRawValueSolver, SerdeJsonValueSolver},
+    time::TimeSolver,
+    time_rs::TimeRsSolver,
+    transparent::TransparentSolver,
+    tuple::TupleSolver,
+    uuid::UuidSolver,
 };
 
+/// A solver added to a [TypeSolvingContextBuilder], with what identifies it
+struct SolverEntry {
+    solver: Box<dyn TypeSolver>,
+    name: &'static str,
+    type_id: TypeId,
+    priority: i32,
+}
+
+/// Builds a [TypeSolvingContext].
+///
+/// The solvers are tried by decreasing priority, then in the order they were added.
+/// [add_solver](TypeSolvingContextBuilder::add_solver) and the default solvers have the priority 0,
+/// so a custom solver can override the built-in ones (e.g. the `chrono` types) with a higher priority
+/// or by being inserted before them with [insert_before](TypeSolvingContextBuilder::insert_before).
 #[derive(Default)]
 pub struct TypeSolvingContextBuilder {
-    solvers: Vec<Box<dyn TypeSolver>>,
+    solvers: Vec<SolverEntry>,
+    large_integers: LargeIntegerPolicy,
+    max_tuple_len: Option<usize>,
 }
 
 impl TypeSolvingContextBuilder {
-    pub fn add_solver<S: TypeSolver + 'static>(mut self, solver: S) -> Self {
-        self.solvers.push(solver.boxed());
+    /// How [add_default_solvers](TypeSolvingContextBuilder::add_default_solvers) solves `u64`, `i64`, `u128` and `i128`,
+    /// [LargeIntegerPolicy::Number] by default
+    pub fn large_integers(mut self, policy: LargeIntegerPolicy) -> Self {
+        self.large_integers = policy;
+        self
+    }
+
+    /// Makes [add_default_solvers](TypeSolvingContextBuilder::add_default_solvers) solve the arrays of a literal length
+    /// up to `max_len` as tuples, see [ArraySolver::tuples_up_to]
+    pub fn fixed_arrays_as_tuples(mut self, max_len: usize) -> Self {
+        self.max_tuple_len = Some(max_len);
         self
     }
 
+    pub fn add_solver<S: TypeSolver + 'static>(self, solver: S) -> Self {
+        self.add_solver_with_priority(solver, 0)
+    }
+
+    /// Adds a solver that is tried before the solvers of a lower priority, whenever they were added
+    pub fn add_solver_with_priority<S: TypeSolver + 'static>(
+        mut self,
+        solver: S,
+        priority: i32,
+    ) -> Self {
+        self.solvers.push(entry(solver, priority));
+        self
+    }
+
+    /// Inserts a solver right before the first solver of type `T`, with the same priority.
+    ///
+    /// When no solver of type `T` was added, the solver is added last, as with [add_solver](TypeSolvingContextBuilder::add_solver).
+    pub fn insert_before<T: TypeSolver + 'static, S: TypeSolver + 'static>(
+        mut self,
+        solver: S,
+    ) -> Self {
+        match self
+            .solvers
+            .iter()
+            .position(|entry| entry.type_id == TypeId::of::<T>())
+        {
+            Some(index) => {
+                let priority = self.solvers[index].priority;
+                self.solvers.insert(index, entry(solver, priority));
+                self
+            }
+            None => {
+                log::warn!(
+                    "No {} to insert {} before, adding it last",
+                    short_type_name::<T>(),
+                    short_type_name::<S>()
+                );
+                self.add_solver(solver)
+            }
+        }
+    }
+
     pub fn add_default_solvers(self) -> Self {
-        self.add_solver(TupleSolver)
+        let large_integers = self.large_integers;
+        let array_solver = match self.max_tuple_len {
+            Some(max_len) => ArraySolver::tuples_up_to(max_len),
+            None => ArraySolver::default(),
+        };
+        let builder = self
+            .add_solver(TimeRsSolver::default())
+            .add_solver(SkipSerializeIf)
+            .add_solver(TupleSolver)
             .add_solver(ReferenceSolver)
-            .add_solver(ArraySolver)
+            .add_solver(array_solver)
             .add_solver(CollectionsSolver::default())
-            .add_solver(PrimitivesSolver::default())
+            .add_solver(PrimitivesSolver::new(large_integers))
             .add_solver(OptionSolver::default())
+            .add_solver(ResultSolver::default())
+            .add_solver(TransparentSolver::default())
             .add_solver(GenericsSolver)
+            .add_solver(RemoteSolver)
             .add_solver(ChronoSolver::default())
+            .add_solver(TimeSolver::default())
+            .add_solver(UuidSolver::default())
+            .add_solver(NetSolver::default())
+            .add_solver(FsSolver::default())
+            .add_solver(PhantomDataSolver::default())
             .add_solver(SerdeJsonValueSolver::default())
-            .add_solver(SkipSerializeIf)
+            .add_solver(SerdeJsonRawValueSolver::default());
+        #[cfg(feature = "decimal")]
+        let builder = builder.add_solver(DecimalSolver::default());
+        builder
     }
 
-    pub fn finish(self) -> TypeSolvingContext {
-        let builder = self.add_solver(ImportSolver);
-        TypeSolvingContext {
-            solvers: builder.solvers,
-        }
+    pub fn finish(mut self) -> TypeSolvingContext {
+        // The sort is stable, the solvers of a same priority keep their order
+        self.solvers
+            .sort_by_key(|entry| std::cmp::Reverse(entry.priority));
+        // Whatever the priorities, the ImportSolver comes last
+        self.solvers.push(entry(ImportSolver, 0));
+        let (solvers, names) = self
+            .solvers
+            .into_iter()
+            .map(|entry| (entry.solver, entry.name))
+            .unzip();
+        TypeSolvingContext { solvers, names }
+    }
+}
+
+fn entry<S: TypeSolver + 'static>(solver: S, priority: i32) -> SolverEntry {
+    SolverEntry {
+        solver: solver.boxed(),
+        name: short_type_name::<S>(),
+        type_id: TypeId::of::<S>(),
+        priority,
+    }
+}
+
+/// The name of a type without its module path, e.g. `TupleSolver`
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let end = name.find('<').unwrap_or(name.len());
+    match name[..end].rfind("::") {
+        Some(start) => &name[start + 2..],
+        None => name,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn solver_names(context: &TypeSolvingContext) -> Vec<&'static str> {
+        (0..context.solvers().len())
+            .map(|index| context.solver_name(index))
+            .collect()
+    }
+
+    #[test]
+    fn should_order_the_solvers_by_priority() {
+        let context = TypeSolvingContextBuilder::default()
+            .add_solver(TupleSolver)
+            .add_solver_with_priority(ArraySolver::default(), -1)
+            .add_solver_with_priority(ChronoSolver::default(), 1)
+            .add_solver(OptionSolver::default())
+            .finish();
+        assert_eq!(
+            solver_names(&context),
+            vec![
+                "ChronoSolver",
+                "TupleSolver",
+                "OptionSolver",
+                "ArraySolver",
+                "ImportSolver"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_insert_a_solver_before_another() {
+        let context = TypeSolvingContextBuilder::default()
+            .add_solver(TupleSolver)
+            .add_solver(ChronoSolver::default())
+            .insert_before::<ChronoSolver, _>(UuidSolver::branded())
+            .insert_before::<RemoteSolver, _>(ArraySolver::default())
+            .finish();
+        assert_eq!(
+            solver_names(&context),
+            vec![
+                "TupleSolver",
+                "UuidSolver",
+                "ChronoSolver",
+                "ArraySolver",
+                "ImportSolver"
+            ]
+        );
     }
 }
@@ -0,0 +1,233 @@
+//! The location of the errors in the Rust sources, rendered as code frames :
+//!
+//! ```text
+//! Could not resolve type `*const u8`
+//!   --> src/models.rs:12:12
+//!    |
+//! 12 |     price: *const u8,
+//!    |            ^^^^^^^^^
+//!    = in the field `price` of `Order`
+//! ```
+use std::{fmt, path::PathBuf};
+
+use proc_macro2::{LineColumn, Span};
+
+use crate::error::TsExportError;
+
+/// A Rust file read by the pipeline, to locate the errors of its declarations in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+/// Where the tokens of a span are in their file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub start: LineColumn,
+    pub end: LineColumn,
+    /// The source text of the span, to check that a file is the one it was parsed from
+    text: Option<String>,
+}
+
+impl Location {
+    /// The location of a span, `None` for the spans that weren't parsed from a file, e.g. `Span::call_site()`
+    pub fn of(span: Span) -> Option<Self> {
+        let (start, end) = (span.start(), span.end());
+        if start == end {
+            return None;
+        }
+        Some(Location {
+            start,
+            end,
+            text: span.source_text(),
+        })
+    }
+
+    /// The byte offset of the line of the location in the text, if the text there is the one of the span.
+    ///
+    /// The items of an `include!` keep the spans of the included file, which are not located in the including one.
+    fn line_offset(&self, text: &str) -> Option<usize> {
+        let line_offset: usize = text
+            .split_inclusive('\n')
+            .take(self.start.line.checked_sub(1)?)
+            .map(str::len)
+            .sum();
+        let line = text.get(line_offset..)?;
+        let column_offset = line
+            .char_indices()
+            .nth(self.start.column)
+            .map(|(offset, _)| offset)
+            .unwrap_or(line.len());
+        match &self.text {
+            Some(span_text) if !line[column_offset..].starts_with(span_text.as_str()) => None,
+            _ => Some(line_offset),
+        }
+    }
+
+    /// The line of the location, and a line of carets under its tokens (up to the end of the line)
+    fn code_frame(&self, text: &str) -> Option<String> {
+        let line_offset = self.line_offset(text)?;
+        let line = text[line_offset..].lines().next().unwrap_or_default();
+        let end_column = if self.end.line == self.start.line {
+            self.end.column
+        } else {
+            line.chars().count()
+        };
+        // The tabs are kept so that the carets are aligned whatever their width
+        let padding: String = line
+            .chars()
+            .take(self.start.column)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(end_column.saturating_sub(self.start.column).max(1));
+
+        let number = self.start.line.to_string();
+        let gutter = " ".repeat(number.len());
+        Some(format!(
+            "{gutter} |\n{number} | {line}\n{gutter} | {padding}{carets}",
+            gutter = gutter,
+            number = number,
+            line = line,
+            padding = padding,
+            carets = carets
+        ))
+    }
+}
+
+/// An error with where it occurred : the file of the module, the declaration and the field being exported,
+/// and the tokens at fault.
+///
+/// The context is added as the error goes up, see [TsExportError::in_field], [TsExportError::in_container]
+/// and [TsExportError::in_file].
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub error: TsExportError,
+    pub file: Option<PathBuf>,
+    /// The struct, enum or type alias being exported
+    pub container: Option<String>,
+    /// The field of the container being solved, its index for a tuple struct
+    pub field: Option<String>,
+    /// The locations the error may be at, the innermost first, until one is found in the file
+    locations: Vec<Location>,
+    /// The location found in the file, and its code frame
+    pub location: Option<(LineColumn, String)>,
+}
+
+impl Diagnostic {
+    pub fn new(error: TsExportError) -> Self {
+        let locations = error.span().and_then(Location::of).into_iter().collect();
+        Diagnostic {
+            error,
+            file: None,
+            container: None,
+            field: None,
+            locations,
+            location: None,
+        }
+    }
+
+    /// Adds a location the error may be at, after the more precise ones
+    pub fn push_span(&mut self, span: Span) {
+        self.locations.extend(Location::of(span));
+    }
+
+    /// Sets the file of the error, locating it with the first location found in the file
+    pub fn set_file(&mut self, source: &SourceFile) {
+        self.file = Some(source.path.clone());
+        self.location = self.locations.iter().find_map(|location| {
+            location
+                .code_frame(&source.text)
+                .map(|code_frame| (location.start, code_frame))
+        });
+    }
+
+    /// Where the error occurred in the declarations, e.g. "in the field `price` of `Order`"
+    fn context(&self) -> Option<String> {
+        match (&self.field, &self.container) {
+            (Some(field), Some(container)) => {
+                Some(format!("in the field `{}` of `{}`", field, container))
+            }
+            (Some(field), None) => Some(format!("in the field `{}`", field)),
+            (None, Some(container)) => Some(format!("in `{}`", container)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        let gutter = match &self.location {
+            Some((start, _)) => " ".repeat(start.line.to_string().len()),
+            None => String::new(),
+        };
+        match (&self.file, &self.location) {
+            (Some(file), Some((start, code_frame))) => write!(
+                f,
+                "\n{}--> {}:{}:{}\n{}",
+                gutter,
+                file.display(),
+                start.line,
+                start.column + 1,
+                code_frame
+            )?,
+            (Some(file), None) => write!(f, "\n --> {}", file.display())?,
+            _ => {}
+        }
+        if let Some(context) = self.context() {
+            write!(f, "\n{} = {}", gutter, context)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        pipeline::module_step::{tests::launch_test_step, ModuleStep},
+    };
+    use std::sync::Arc;
+
+    fn launch(text: &str) -> TsExportError {
+        let items = syn::parse_file(text).expect("Failed to parse").items;
+        let path = syn::Path {
+            leading_colon: None,
+            segments: Default::default(),
+        };
+        let mut step = ModuleStep::new(path, items, "my_crate");
+        step.set_source(Arc::new(SourceFile {
+            path: PathBuf::from("src/models.rs"),
+            text: text.to_string(),
+        }));
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        launch_test_step(step, &solving_context, &ExporterOptions::default())
+            .err()
+            .expect("The export should fail")
+    }
+
+    #[test]
+    fn should_locate_the_unsolved_types() {
+        let error = launch(
+            "use serde::Serialize;\n\n#[derive(Serialize)]\npub struct Order {\n    id: u32,\n    price: Option<*const u8>,\n}\n",
+        );
+        assert!(matches!(error.inner(), TsExportError::UnsolvedType(_)));
+        assert_eq!(
+            error.to_string(),
+            "Could not resolve type `*const u8`\n --> src/models.rs:6:19\n  |\n6 |     price: Option<*const u8>,\n  |                   ^^^^^^^^^\n  = in the field `price` of `Order`"
+        );
+    }
+
+    #[test]
+    fn should_locate_the_invalid_attributes_at_their_container() {
+        let error = launch("#[derive(Serialize)]\n#[ts(nominal)]\npub struct\tUser { id: u32 }\n");
+        assert!(matches!(error.inner(), TsExportError::InvalidAttribute(_)));
+        assert!(error
+            .to_string()
+            .ends_with("3 | pub struct\tUser { id: u32 }\n  |           \t^^^^\n  = in `User`"));
+    }
+}
@@ -1,9 +1,13 @@
 use std::path::PathBuf;
 
-use syn::GenericArgument;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{spanned::Spanned, GenericArgument, Ident};
 use thiserror::Error;
 use ts_json_subset::types::TsType;
 
+use crate::diagnostic::{Diagnostic, SourceFile};
+
 #[derive(Debug, Error)]
 pub enum TsExportError {
     #[error("Failed to launch the main process")]
@@ -12,28 +16,145 @@ pub enum TsExportError {
     WrongPath(PathBuf),
     #[error("Expected a Rust module, found a directory at {:?}", _0)]
     DirectoryGiven(PathBuf),
+    #[error("The roots {:?} and {:?} belong to different crates", _0, _1)]
+    RootsOfDifferentCrates(PathBuf, PathBuf),
     #[error("IO Error {0}")]
     IoError(#[from] std::io::Error),
+    #[error("Watch error {0}")]
+    WatchError(#[from] notify_debouncer_mini::notify::Error),
     #[error("Syn Parse Error {0}")]
     SynError(#[from] syn::parse::Error),
     #[error("JSON error {0}")]
     SerdeJsonError(#[from] serde_json::Error),
-    #[error("Could not resolve type {:?}", _0)]
-    UnsolvedType(syn::Type),
-    #[error("Could not resolve field {:?}", _0)]
-    UnsolvedField(syn::Field),
+    #[error("TOML error {0}")]
+    TomlError(#[from] toml::de::Error),
+    #[error("YAML error {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    #[error("Could not resolve type `{}`", source_text(_0))]
+    UnsolvedType(Box<syn::Type>),
+    #[error("Could not resolve field `{}`", field_tokens(_0))]
+    UnsolvedField(Box<syn::Field>),
     #[error("Unexpected TS type {:?}", _0)]
-    UnexpectedType(TsType),
+    UnexpectedType(Box<TsType>),
     #[error("Expected generics")]
     ExpectedGenerics,
     #[error("Empty generics")]
     EmptyGenerics,
-    #[error("Wrong generic type {:?}", _0)]
-    WrongGenericType(GenericArgument),
+    #[error("Wrong generic type `{}`", source_text(_0))]
+    WrongGenericType(Box<GenericArgument>),
     #[error("Cargo.toml error {0}")]
     CargoTomlError(#[from] crate::utils::cargo::Error),
+    #[error("cargo metadata failed on {0:?}: {1}")]
+    CargoMetadataFailed(PathBuf, String),
     #[error("Identifier error {}", _0)]
     TSIdentError(#[from] ts_json_subset::ident::IdentError),
     #[error("Malformed input")]
     MalformedInput,
+    #[error("Invalid attribute {0}")]
+    InvalidAttribute(String),
+    #[error("Invalid formatter command {:?}", _0)]
+    InvalidFormatterCommand(String),
+    #[error("Formatter `{0}` failed on {1:?}: {2}")]
+    FormatterFailed(String, PathBuf, String),
+    #[error("Wrong number of fields for {0}: expected {1}, found {2}")]
+    WrongFieldsCount(String, usize, usize),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("The bindings are out of date: {}", _0.join(", "))]
+    OutdatedBindings(Vec<String>),
+    #[error("Forbidden type {0}: {1}")]
+    ForbiddenType(String, String),
+    #[error("No TS type for the custom serializer {0} of the field {1}")]
+    UnmappedSerializer(String, String),
+    #[error("`{0}` refers to `{1}`, which the filter leaves out")]
+    FilteredOutReference(String, String),
+    #[error("{0}")]
+    Diagnostic(Box<Diagnostic>),
+}
+
+/// The code of a node as written in its file, or its tokens for a node that wasn't parsed from a file
+fn source_text<T: ToTokens>(node: &T) -> String {
+    node.span()
+        .source_text()
+        .unwrap_or_else(|| node.to_token_stream().to_string())
+}
+
+/// A field without its attributes, `name: Type`
+fn field_tokens(field: &syn::Field) -> String {
+    match &field.ident {
+        Some(ident) => format!("{}: {}", ident, source_text(&field.ty)),
+        None => source_text(&field.ty),
+    }
+}
+
+impl TsExportError {
+    /// The error, without the [Diagnostic] locating it
+    pub fn inner(&self) -> &TsExportError {
+        match self {
+            TsExportError::Diagnostic(diagnostic) => &diagnostic.error,
+            error => error,
+        }
+    }
+
+    /// The error, without the [Diagnostic] locating it
+    pub fn into_inner(self) -> TsExportError {
+        match self {
+            TsExportError::Diagnostic(diagnostic) => diagnostic.error,
+            error => error,
+        }
+    }
+
+    /// The span of the tokens the error is about, if it holds them
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            TsExportError::UnsolvedType(ty) => Some(ty.span()),
+            TsExportError::UnsolvedField(field) => Some(field.ty.span()),
+            TsExportError::WrongGenericType(argument) => Some(argument.span()),
+            TsExportError::SynError(error) => Some(error.span()),
+            _ => None,
+        }
+    }
+
+    fn diagnostic(self) -> Box<Diagnostic> {
+        match self {
+            TsExportError::Diagnostic(diagnostic) => diagnostic,
+            error => Box::new(Diagnostic::new(error)),
+        }
+    }
+
+    /// Locates the error in a field of the container being exported, `name` being its serialized name.
+    ///
+    /// The fields of the other declarations solved along the way (e.g. when flattened) are overridden,
+    /// so that the field is the one of the container.
+    pub fn in_field(self, field: &syn::Field, name: &str) -> Self {
+        let mut diagnostic = self.diagnostic();
+        diagnostic.field = Some(
+            field
+                .ident
+                .as_ref()
+                .map(|ident| ident.to_string())
+                .unwrap_or_else(|| name.to_string()),
+        );
+        diagnostic.push_span(field.ty.span());
+        TsExportError::Diagnostic(diagnostic)
+    }
+
+    /// Locates the error in a container, that is a struct, an enum or a type alias, unless it already is
+    pub fn in_container(self, ident: &Ident) -> Self {
+        let mut diagnostic = self.diagnostic();
+        if diagnostic.container.is_none() {
+            diagnostic.container = Some(ident.to_string());
+            diagnostic.push_span(ident.span());
+        }
+        TsExportError::Diagnostic(diagnostic)
+    }
+
+    /// Locates the error in the file of a module, unless it already is, e.g. in the file of a child module
+    pub fn in_file(self, source: &SourceFile) -> Self {
+        let mut diagnostic = self.diagnostic();
+        if diagnostic.file.is_none() {
+            diagnostic.set_file(source);
+        }
+        TsExportError::Diagnostic(diagnostic)
+    }
 }
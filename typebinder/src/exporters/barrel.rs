@@ -0,0 +1,156 @@
+use std::{collections::HashMap, io::Write, path::Path};
+
+use super::import_style::ImportStyle;
+use crate::{error::TsExportError, pipeline::module_step::ModuleStepResultData};
+
+/// The name of the barrel file, in the output directory
+pub const BARREL_FILE: &str = "index.ts";
+
+/// An `index.ts` barrel that re-exports every generated module :
+///
+/// ```text
+/// export * from "./api/models";
+/// export * from "./events";
+/// ```
+///
+/// A name declared by several modules would be ambiguous, it is explicitly re-exported from the first module that declares it.
+///
+/// The root module is written to `index.ts` by default, so it has to be given another name, see [FileExporter::set_default_module_name](super::file::FileExporter::set_default_module_name).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Barrel {
+    /// The files of the modules, without their extension, with the names they declare
+    modules: Vec<(String, Vec<String>)>,
+    import_style: ImportStyle,
+}
+
+impl Barrel {
+    /// Registers the declarations exported by a module in `file`, relatively to the output directory
+    pub fn add_module(&mut self, module: &ModuleStepResultData, file: &str) {
+        if module.exports.is_empty() {
+            return;
+        }
+        let file = file
+            .strip_suffix(".d.ts")
+            .or_else(|| file.strip_suffix(".ts"))
+            .unwrap_or(file);
+        let names = module
+            .exports
+            .iter()
+            .filter_map(|export| export.ident())
+            .map(|ident| ident.to_string())
+            .collect();
+        self.modules.push((file.to_string(), names));
+    }
+
+    /// Appends the extension of the style to the specifiers of the modules
+    pub fn set_import_style(&mut self, import_style: ImportStyle) {
+        self.import_style = import_style;
+    }
+
+    pub fn render(&self) -> String {
+        let mut declaring: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (file, names) in self.modules.iter() {
+            for name in names {
+                declaring.entry(name).or_default().push(file);
+            }
+        }
+        let mut explicit: Vec<(&str, &str)> = Vec::new();
+        for (file, names) in self.modules.iter() {
+            for name in names {
+                let files = &declaring[name.as_str()];
+                if files.len() > 1 && files[0] == file {
+                    log::warn!(
+                        "{} is declared by several modules, the barrel re-exports the one of {}",
+                        name,
+                        file
+                    );
+                    explicit.push((name, file));
+                }
+            }
+        }
+
+        let specifier = |file: &str| self.import_style.file_specifier(&format!("./{}", file));
+        self.modules
+            .iter()
+            .map(|(file, _)| format!("export * from \"{}\";\n", specifier(file)))
+            .chain(explicit.into_iter().map(|(name, file)| {
+                format!("export {{ {} }} from \"{}\";\n", name, specifier(file))
+            }))
+            .collect()
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting barrel at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(self.render().as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{tests::launch_test_module, ModuleStepResult},
+    };
+
+    fn add_all(barrel: &mut Barrel, result: ModuleStepResult) {
+        for child in result.children {
+            add_all(barrel, child);
+        }
+        let file = if result.data.path.segments.is_empty() {
+            "lib.ts".to_string()
+        } else {
+            format!(
+                "{}.ts",
+                result
+                    .data
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            )
+        };
+        barrel.add_module(&result.data, &file);
+    }
+
+    #[test]
+    fn should_re_export_every_module() {
+        let result = launch_test_module(
+            r#"
+            pub mod api {
+                pub mod models {
+                    #[derive(Serialize)]
+                    pub struct User { name: String }
+                }
+                pub mod admin {
+                    #[derive(Serialize)]
+                    pub struct User { admin: bool }
+                }
+                pub mod empty {}
+            }
+
+            #[derive(Serialize)]
+            pub struct Session { id: u32 }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut barrel = Barrel::default();
+        add_all(&mut barrel, result);
+
+        assert_eq!(
+            barrel.render(),
+            "export * from \"./api/models\";\n\
+             export * from \"./api/admin\";\n\
+             export * from \"./lib\";\n\
+             export { User } from \"./api/models\";\n"
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+use indexmap::IndexMap;
+use ts_json_subset::{
+    common::StringLiteral,
+    declarations::ambient::AmbientModuleDeclaration,
+    export::ExportStatement,
+    ident::TSIdent,
+    import::{ImportKind, ImportStatement},
+    types::TypeReference,
+    visit_mut::{self, VisitMut},
+};
+
+use super::{
+    formatter::{format_file, lay_out},
+    output_options::OutputOptions,
+    Exporter,
+};
+use crate::{
+    error::TsExportError,
+    pipeline::module_step::ModuleStepResultData,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+        rename::ReferenceRenamer,
+    },
+};
+
+/// The file of the bundle when the output is a directory
+pub const BUNDLE_FILE: &str = "types.ts";
+
+/// A strategy that bundles all the modules of a crate into a single TS file.
+///
+/// When several modules declare a type with the same name, those types are renamed after their module
+/// (e.g. `User` in `models` and in `admin` become `models_User` and `admin_User`, the root module keeping its names),
+/// and all the references to them are rewritten accordingly, the ones through an alias (`use models::User as MUser`) included.
+///
+/// The imports between the modules of the crate are dropped, and so are the re-exports.
+/// The declarations are ordered so that the types they reference are declared before them,
+/// the declarations of a cycle keeping their order.
+///
+/// With [BundleExporter::set_ambient_module], the bundle is an ambient module, `declare module "api" { ... }`.
+pub struct BundleExporter {
+    crate_name: String,
+    modules: Vec<ModuleStepResultData>,
+    output: OutputOptions,
+    ambient_module: Option<String>,
+    declaration_files: bool,
+}
+
+impl BundleExporter {
+    /// `crate_name` is the name used in the Rust paths of the crate, see [RustModuleReader::crate_name](crate::step_spawner::mod_reader::RustModuleReader::crate_name)
+    pub fn new<S: Into<String>>(crate_name: S) -> Self {
+        BundleExporter {
+            crate_name: crate_name.into(),
+            modules: Vec::new(),
+            output: OutputOptions::without_header(),
+            ambient_module: None,
+            declaration_files: false,
+        }
+    }
+
+    /// How the file is formatted and commented, without a header comment by default (`{module}` being the name of the crate).
+    /// The import style applies to the imports of the other crates.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.output = output;
+    }
+
+    pub fn output_options(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// Declares the bundle as an ambient module named `name`, for a declaration file (`.d.ts`),
+    /// which requires [ExporterOptions::declaration_files](crate::contexts::exporter::ExporterOptions::declaration_files)
+    pub fn set_ambient_module(&mut self, name: Option<String>) {
+        self.ambient_module = name;
+    }
+
+    /// Writes the bundle to a declaration file, `types.d.ts` when the output is a directory,
+    /// which requires [ExporterOptions::declaration_files](crate::contexts::exporter::ExporterOptions::declaration_files)
+    pub fn set_declaration_files(&mut self, declaration_files: bool) {
+        self.declaration_files = declaration_files;
+    }
+
+    /// The name of the bundle when the output is a directory, `types.d.ts` with [BundleExporter::set_declaration_files]
+    pub fn bundle_file(&self) -> &'static str {
+        if self.declaration_files {
+            "types.d.ts"
+        } else {
+            BUNDLE_FILE
+        }
+    }
+
+    /// Renders all the modules exported so far
+    pub fn render(&self) -> Result<String, TsExportError> {
+        let modules = &self.modules;
+
+        let mut declarations: IndexMap<String, Vec<Vec<String>>> = IndexMap::new();
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            for ident in module.exports.iter().filter_map(|export| export.ident()) {
+                declarations
+                    .entry(ident.to_string())
+                    .or_default()
+                    .push(module_path.clone());
+            }
+        }
+        let renamed: HashMap<(Vec<String>, String), TSIdent> = declarations
+            .into_iter()
+            .filter(|(_, module_paths)| module_paths.len() > 1)
+            .flat_map(|(name, module_paths)| {
+                module_paths
+                    .into_iter()
+                    .filter(|module_path| !module_path.is_empty())
+                    .map(move |module_path| {
+                        let qualified = format!("{}_{}", module_path.join("_"), name);
+                        Ok(((module_path, name.clone()), TSIdent::from_str(&qualified)?))
+                    })
+            })
+            .collect::<Result<_, TsExportError>>()?;
+
+        let mut imports: Vec<ImportStatement> = Vec::new();
+        let mut value_imports: BTreeSet<String> = BTreeSet::new();
+        let mut exports: Vec<ExportStatement> = Vec::new();
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            let mut renames: HashMap<String, TSIdent> = HashMap::new();
+            let mut local_imports: HashSet<String> = HashSet::new();
+            value_imports.extend(module.value_imports.iter().cloned());
+            for entry in module.import_entries.iter() {
+                if let Some(declaring_module) =
+                    resolve_local_path(entry, &module_path, &self.crate_name)
+                {
+                    local_imports.insert(entry.local_name().to_string());
+                    // The references are resolved through the imports, an alias naming the declaration of another module
+                    match renamed.get(&(declaring_module, entry.ident.clone())) {
+                        Some(qualified) => {
+                            renames.insert(entry.local_name().to_string(), qualified.clone());
+                        }
+                        None if entry.alias.is_some() => {
+                            renames.insert(
+                                entry.local_name().to_string(),
+                                TSIdent::from_str(&entry.ident)?,
+                            );
+                        }
+                        None => (),
+                    }
+                }
+            }
+            for ident in module.exports.iter().filter_map(|export| export.ident()) {
+                let ident = ident.to_string();
+                if let Some(qualified) = renamed.get(&(module_path.clone(), ident.clone())) {
+                    renames.insert(ident, qualified.clone());
+                }
+            }
+
+            for import in module.imports.iter() {
+                if is_local_import(import, &local_imports) {
+                    continue;
+                }
+                if !imports.contains(import) {
+                    imports.push(import.clone());
+                }
+            }
+
+            let mut renamer = ReferenceRenamer { renames: &renames };
+            for export in module.exports.iter() {
+                if let ExportStatement::ReexportDeclaration(_) = export {
+                    log::debug!(
+                        "Dropping a re-export of module {} from the bundle",
+                        DisplayPath(&module.path)
+                    );
+                    continue;
+                }
+                let mut export = export.clone();
+                if let Some(ident) = export.ident_mut() {
+                    if let Some(qualified) = renames.get(&ident.to_string()) {
+                        *ident = qualified.clone();
+                    }
+                }
+                renamer.visit_export_statement_mut(&mut export);
+                exports.push(export);
+            }
+        }
+        let exports = order_declarations(exports);
+        let imports = self
+            .output
+            .import_style
+            .style_imports(imports, &value_imports);
+
+        if let Some(name) = &self.ambient_module {
+            let module = AmbientModuleDeclaration {
+                name: StringLiteral::from_raw(name),
+                imports,
+                exports,
+            };
+            return Ok(self.output.header_comment.prepend_to(
+                &self.crate_name,
+                lay_out(&self.output.format_options, format!("{}\n", module)),
+            ));
+        }
+        let contents = imports
+            .iter()
+            .map(|statement| format!("{}\n", statement))
+            .chain(exports.iter().map(|statement| format!("{}\n", statement)))
+            .collect();
+        let contents = lay_out(&self.output.format_options, contents);
+        Ok(self
+            .output
+            .header_comment
+            .prepend_to(&self.crate_name, contents))
+    }
+
+    /// Writes the bundle to `path`, or to [BundleExporter::bundle_file] in `path` when it is a directory
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        let path = if path.is_dir() {
+            path.join(self.bundle_file())
+        } else {
+            path.to_path_buf()
+        };
+        let path = path.as_path();
+        log::info!("Outputting bundle at {:?}", path);
+        let contents = self.render()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        drop(file);
+        format_file(&self.output.formatter, path)
+    }
+}
+
+impl Exporter for BundleExporter {
+    type Error = TsExportError;
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+        log::info!("Bundling module {}", DisplayPath(&process_result.path));
+        self.modules.push(process_result);
+        Ok(())
+    }
+}
+
+/// Orders the declarations so that the declarations they reference come first, the others keeping their order
+fn order_declarations(exports: Vec<ExportStatement>) -> Vec<ExportStatement> {
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    for (index, ident) in exports
+        .iter()
+        .enumerate()
+        .filter_map(|(index, export)| export.ident().map(|ident| (index, ident)))
+    {
+        indices.entry(ident.to_string()).or_insert(index);
+    }
+    let references: Vec<Vec<usize>> = exports
+        .iter()
+        .map(|export| {
+            let mut collector = ReferenceCollector::default();
+            collector.visit_export_statement_mut(&mut export.clone());
+            collector
+                .references
+                .iter()
+                .filter_map(|name| indices.get(name).copied())
+                .collect()
+        })
+        .collect();
+
+    let mut visited = vec![false; exports.len()];
+    let mut order = Vec::with_capacity(exports.len());
+    for index in 0..exports.len() {
+        visit_declaration(index, &references, &mut visited, &mut order);
+    }
+    let mut exports: Vec<Option<ExportStatement>> = exports.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|index| exports[index].take())
+        .collect()
+}
+
+/// Depth-first traversal of the references, a declaration being visited once, when it is first met in a cycle
+fn visit_declaration(
+    index: usize,
+    references: &[Vec<usize>],
+    visited: &mut [bool],
+    order: &mut Vec<usize>,
+) {
+    if visited[index] {
+        return;
+    }
+    visited[index] = true;
+    for reference in references[index].iter() {
+        visit_declaration(*reference, references, visited, order);
+    }
+    order.push(index);
+}
+
+/// Collects the names of the types referenced by a declaration, in order
+#[derive(Default)]
+pub(crate) struct ReferenceCollector {
+    pub(crate) references: Vec<String>,
+}
+
+impl VisitMut for ReferenceCollector {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        let name = node.name.to_string();
+        if !self.references.contains(&name) {
+            self.references.push(name);
+        }
+        visit_mut::visit_type_reference_mut(self, node);
+    }
+}
+
+/// Whether an import statement only imports items of the current crate, which are all part of the bundle
+pub(crate) fn is_local_import(import: &ImportStatement, local_imports: &HashSet<String>) -> bool {
+    match &import.import_kind {
+        ImportKind::ImportList(list) => list
+            .items
+            .iter()
+            .all(|item| local_imports.contains(&item.local_name().to_string())),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{tests::launch_test_module, ModuleStepResult},
+    };
+
+    fn export_all(exporter: &mut BundleExporter, result: ModuleStepResult) {
+        for child in result.children {
+            export_all(exporter, child);
+        }
+        exporter.export_module(result.data).unwrap();
+    }
+
+    #[test]
+    fn should_rename_colliding_declarations() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::User;
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub struct User { name: String }
+                #[derive(Serialize)]
+                pub struct Group { users: Vec<User> }
+            }
+            pub mod admin {
+                use super::models::Group;
+                #[derive(Serialize)]
+                pub struct User { group: Group }
+            }
+
+            #[derive(Serialize)]
+            pub struct Session { user: User }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = BundleExporter::new("my_crate");
+        export_all(&mut exporter, result);
+
+        assert_eq!(
+            exporter.render().unwrap(),
+            "export interface models_User {\n\tname: string\n}\n\
+             export interface Group {\n\tusers: models_User[]\n}\n\
+             export interface admin_User {\n\tgroup: Group\n}\n\
+             export interface Session {\n\tuser: models_User\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_resolve_the_aliased_imports() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::Group as Team;
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub struct User { name: String }
+                #[derive(Serialize)]
+                pub struct Group { users: Vec<User> }
+            }
+            pub mod api {
+                use super::models::User as MUser;
+                #[derive(Serialize)]
+                pub struct User { id: u32 }
+                #[derive(Serialize)]
+                pub struct Thing { u: MUser, own: User }
+            }
+
+            #[derive(Serialize)]
+            pub struct Session { team: Team }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = BundleExporter::new("my_crate");
+        export_all(&mut exporter, result);
+
+        assert_eq!(
+            exporter.render().unwrap(),
+            "export interface models_User {\n\tname: string\n}\n\
+             export interface Group {\n\tusers: models_User[]\n}\n\
+             export interface api_User {\n\tid: number\n}\n\
+             export interface Thing {\n\tu: models_User,\n\town: api_User\n}\n\
+             export interface Session {\n\tteam: Group\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_declare_an_ambient_module() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::User;
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub struct User { name: String }
+            }
+
+            #[derive(Serialize)]
+            pub struct Session { user: User }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = BundleExporter::new("my_crate");
+        exporter.set_ambient_module(Some("api".to_string()));
+        export_all(&mut exporter, result);
+
+        assert_eq!(
+            exporter.render().unwrap(),
+            "declare module \"api\" {\n\
+             export interface User {\n\tname: string\n}\n\
+             export interface Session {\n\tuser: User\n}\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_declare_the_referenced_types_first() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::Role;
+
+            #[derive(Serialize)]
+            pub struct Session { user: User, previous: Option<Box<Session>> }
+
+            #[derive(Serialize)]
+            pub struct User { role: Role, manager: Option<Box<User>> }
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub enum Role { Admin, User }
+            }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = BundleExporter::new("my_crate");
+        export_all(&mut exporter, result);
+
+        // The recursive references don't change the order
+        assert_eq!(
+            exporter.render().unwrap(),
+            "export type Role = \"Admin\" | \"User\";\n\
+             export interface User {\n\trole: Role,\n\tmanager: User | null\n}\n\
+             export interface Session {\n\tuser: User,\n\tprevious: Session | null\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_write_a_declaration_file_in_the_output_directory() {
+        let result = launch_test_module(
+            r#"
+            #[derive(Serialize)]
+            pub struct User { name: String }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = BundleExporter::new("my_crate");
+        export_all(&mut exporter, result);
+        let dir = crate::utils::test_dir("bundle_declaration_file");
+
+        exporter.write_to(&dir).unwrap();
+        exporter.set_declaration_files(true);
+        exporter.write_to(&dir).unwrap();
+
+        let declarations = std::fs::read_to_string(dir.join("types.d.ts")).unwrap();
+        assert_eq!(declarations, "export interface User {\n\tname: string\n}\n");
+        assert!(dir.join(BUNDLE_FILE).is_file());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+use std::{collections::BTreeSet, path::Path};
+
+use crate::error::TsExportError;
+
+/// The differences between the generated modules and the files on disk, built by a [FileExporter](super::file::FileExporter)
+/// set to check the files instead of writing them, see [FileExporter::set_check](super::file::FileExporter::set_check).
+///
+/// In CI, this verifies that the committed bindings are up to date with the Rust sources.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CheckReport {
+    pub files: Vec<FileDiff>,
+    /// The files compared so far, the others being stale
+    compared: BTreeSet<String>,
+}
+
+/// An output file that differs from the generated module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// The path of the file, relatively to the output directory
+    pub file: String,
+    pub kind: DiffKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The file doesn't exist
+    Missing,
+    /// The file was generated for a module that isn't exported anymore
+    Stale,
+    /// The lines to remove from the file and to add to it to get the generated module
+    Changed(Vec<DiffLine>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A line of the file on disk, numbered from 1
+    Removed(usize, String),
+    /// A line of the generated module, numbered from 1
+    Added(usize, String),
+}
+
+impl CheckReport {
+    /// Compares the generated contents of `file` to the file at `path`
+    pub fn compare(
+        &mut self,
+        file: String,
+        path: &Path,
+        generated: &str,
+    ) -> Result<(), TsExportError> {
+        self.compared.insert(file.clone());
+        let existing = match std::fs::read_to_string(path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.files.push(FileDiff {
+                    file,
+                    kind: DiffKind::Missing,
+                });
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if existing != generated {
+            self.files.push(FileDiff {
+                file,
+                kind: DiffKind::Changed(diff_lines(&existing, generated)),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reports the TS files of `dir` that were not compared as stale, except the files and directories of `ignored`,
+    /// relative to `dir` (e.g. the barrel or the directories of the other crates)
+    pub fn find_stale(&mut self, dir: &Path, ignored: &[String]) -> Result<(), TsExportError> {
+        let mut files = Vec::new();
+        ts_files(dir, "", &mut files)?;
+        files.sort();
+        for file in files {
+            let is_ignored = ignored
+                .iter()
+                .any(|ignored| file == *ignored || file.starts_with(&format!("{}/", ignored)));
+            if !is_ignored && !self.compared.contains(&file) {
+                self.files.push(FileDiff {
+                    file,
+                    kind: DiffKind::Stale,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_up_to_date(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Fails with [TsExportError::OutdatedBindings] when a file differs
+    pub fn into_result(self) -> Result<(), TsExportError> {
+        if self.is_up_to_date() {
+            Ok(())
+        } else {
+            Err(TsExportError::OutdatedBindings(
+                self.files.into_iter().map(|diff| diff.file).collect(),
+            ))
+        }
+    }
+}
+
+/// Collects the `.ts` files of `dir`, recursively, prefixed with `prefix`
+fn ts_files(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), TsExportError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let file = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            ts_files(&entry.path(), &format!("{}/", file), files)?;
+        } else if file.ends_with(".ts") {
+            files.push(file);
+        }
+    }
+    Ok(())
+}
+
+/// The lines removed from `old` and added to it to get `new`, along their longest common subsequence
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(DiffLine::Removed(i + 1, old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(j + 1, new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines
+}
+
+impl std::fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for diff in self.files.iter() {
+            match &diff.kind {
+                DiffKind::Missing => writeln!(f, "{} is missing", diff.file)?,
+                DiffKind::Stale => writeln!(f, "{} is stale", diff.file)?,
+                DiffKind::Changed(lines) => {
+                    writeln!(f, "{} is out of date", diff.file)?;
+                    for line in lines {
+                        match line {
+                            DiffLine::Removed(number, text) => {
+                                writeln!(f, "{:>5} - {}", number, text)?
+                            }
+                            DiffLine::Added(number, text) => {
+                                writeln!(f, "{:>5} + {}", number, text)?
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    #[test]
+    fn should_diff_lines() {
+        assert_eq!(
+            diff_lines(
+                "export interface User {\n\tid: number\n}\n",
+                "export interface User {\n\tid: string,\n\tname: string\n}\n"
+            ),
+            vec![
+                DiffLine::Removed(2, "\tid: number".to_string()),
+                DiffLine::Added(2, "\tid: string,".to_string()),
+                DiffLine::Added(3, "\tname: string".to_string()),
+            ]
+        );
+        assert_eq!(diff_lines("a\nb\n", "a\nb\n"), Vec::new());
+    }
+
+    #[test]
+    fn should_report_the_files_that_differ() {
+        let dir = test_dir("check");
+        std::fs::write(dir.join("same.ts"), "export type A = string;\n").unwrap();
+        std::fs::write(dir.join("changed.ts"), "export type B = string;\n").unwrap();
+
+        std::fs::create_dir_all(dir.join("models")).unwrap();
+        std::fs::write(dir.join("models/removed.ts"), "export type D = string;\n").unwrap();
+        std::fs::create_dir_all(dir.join("other_crate")).unwrap();
+        std::fs::write(
+            dir.join("other_crate/index.ts"),
+            "export type E = string;\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("hashes.json"), "{}\n").unwrap();
+
+        let mut report = CheckReport::default();
+        for file in ["same.ts", "changed.ts", "missing.ts"].iter() {
+            let generated = format!(
+                "export type {} = string;\n",
+                if *file == "changed.ts" { "C" } else { "A" }
+            );
+            report
+                .compare(file.to_string(), &dir.join(file), &generated)
+                .unwrap();
+        }
+        report
+            .find_stale(&dir, &["other_crate".to_string()])
+            .unwrap();
+        assert_eq!(
+            report.to_string(),
+            "changed.ts is out of date\n    1 - export type B = string;\n    1 + export type C = string;\nmissing.ts is missing\nmodels/removed.ts is stale\n"
+        );
+        assert!(matches!(
+            report.into_result(),
+            Err(TsExportError::OutdatedBindings(files)) if files == vec!["changed.ts", "missing.ts", "models/removed.ts"]
+        ));
+    }
+}
@@ -0,0 +1,367 @@
+use std::{collections::HashMap, io::Write, path::Path};
+
+use serde_json::{Map, Value};
+use ts_json_subset::{
+    declarations::{const_enum::ConstEnumDeclaration, enumeration::EnumDeclaration},
+    export::ExportStatement,
+    types::{LiteralType, PredefinedType, PrimaryType, PropertyName, TsType, TypeMember},
+};
+
+use super::symbol_map::rust_path;
+use crate::{
+    error::TsExportError,
+    pipeline::module_step::ModuleStepResultData,
+    utils::crate_paths::{module_segments, resolve_local_path},
+};
+
+/// A sample JSON value of each exported type, keyed by its Rust path like the [SymbolMap](super::symbol_map::SymbolMap) :
+///
+/// ```text
+/// {
+///   "crate::models::User": { "name": "name", "age": 0, "role": { "type": "Admin" }, "tags": ["tags"] }
+/// }
+/// ```
+///
+/// The values are derived from the generated declarations, so that they follow the serde attributes :
+/// * numbers are `0`, booleans `false`, and strings are named after their property,
+/// * the optional properties are present, arrays and maps hold a single element,
+/// * unions take their first member that is not `null`, so that enums get the tag of their first variant,
+/// * the types of the other modules of the crate are followed, those of other crates and the generic parameters are `null`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Examples {
+    modules: Vec<ExampleModule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ExampleModule {
+    path: Vec<String>,
+    /// The declarations, with their Rust path
+    declarations: Vec<(String, ExportStatement)>,
+    /// The modules of the crate the imported items come from, by name
+    imports: HashMap<String, Vec<String>>,
+}
+
+impl Examples {
+    /// Registers the declarations exported by a module
+    pub fn add_module(&mut self, module: &ModuleStepResultData) {
+        let path = module_segments(&module.path);
+        let declarations = module
+            .exports
+            .iter()
+            .filter(|export| {
+                matches!(
+                    export,
+                    ExportStatement::InterfaceDeclaration(_)
+                        | ExportStatement::TypeAliasDeclaration(_)
+                        | ExportStatement::EnumDeclaration(_)
+                        | ExportStatement::ConstEnumDeclaration(_)
+                )
+            })
+            .filter_map(|export| {
+                let name = export.ident()?.to_string();
+                Some((rust_path(module, &name), export.clone()))
+            })
+            .collect();
+        let imports = module
+            .import_entries
+            .iter()
+            .filter_map(|entry| {
+                resolve_local_path(entry, &path, &module.crate_name)
+                    .map(|resolved| (entry.ident.clone(), resolved))
+            })
+            .collect();
+        self.modules.push(ExampleModule {
+            path,
+            declarations,
+            imports,
+        });
+    }
+
+    pub fn to_json(&self) -> Value {
+        let values = self
+            .modules
+            .iter()
+            .flat_map(|module| {
+                module.declarations.iter().map(move |(rust_path, export)| {
+                    let mut generator = Generator {
+                        examples: self,
+                        visiting: Vec::new(),
+                    };
+                    (rust_path.clone(), generator.declaration(module, export))
+                })
+            })
+            .collect();
+        Value::Object(values)
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting examples at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(&mut file, &self.to_json())?;
+        file.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn find(
+        &self,
+        module_path: &[String],
+        name: &str,
+    ) -> Option<(&ExampleModule, &ExportStatement)> {
+        let module = self
+            .modules
+            .iter()
+            .find(|module| module.path == module_path)?;
+        let export = module
+            .declarations
+            .iter()
+            .map(|(_, export)| export)
+            .find(|export| {
+                export.ident().map(|ident| ident.to_string()).as_deref() == Some(name)
+            })?;
+        Some((module, export))
+    }
+}
+
+struct Generator<'a> {
+    examples: &'a Examples,
+    /// The declarations being generated, a recursive reference being `null`
+    visiting: Vec<(Vec<String>, String)>,
+}
+
+impl<'a> Generator<'a> {
+    fn declaration(&mut self, module: &'a ExampleModule, export: &ExportStatement) -> Value {
+        let name = export
+            .ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        let key = (module.path.clone(), name.clone());
+        if self.visiting.contains(&key) {
+            return Value::Null;
+        }
+        self.visiting.push(key);
+        let value = match export {
+            ExportStatement::InterfaceDeclaration(declaration) => {
+                self.members(module, &declaration.obj_type.body.members)
+            }
+            ExportStatement::TypeAliasDeclaration(declaration) => {
+                self.ts_type(module, &declaration.inner_type, &name)
+            }
+            ExportStatement::EnumDeclaration(EnumDeclaration { body, .. })
+            | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { body, .. }) => body
+                .variants
+                .first()
+                .map(|variant| Value::from(variant.value.unescaped()))
+                .unwrap_or(Value::Null),
+            _ => Value::Null,
+        };
+        self.visiting.pop();
+        value
+    }
+
+    fn members(&mut self, module: &'a ExampleModule, members: &[TypeMember]) -> Value {
+        let properties = members
+            .iter()
+            .filter_map(|member| match member {
+                TypeMember::PropertySignature(property) => Some(property),
+                // An empty object fits any index signature
+                TypeMember::IndexSignature(_) => None,
+            })
+            .map(|property| {
+                let name = property_name(&property.name);
+                let value = self.ts_type(module, &property.inner_type, &name);
+                (name, value)
+            })
+            .collect();
+        Value::Object(properties)
+    }
+
+    /// A value of the type, `hint` naming the strings
+    fn ts_type(&mut self, module: &'a ExampleModule, ty: &TsType, hint: &str) -> Value {
+        match ty {
+            TsType::PrimaryType(primary) => self.primary_type(module, primary, hint),
+            TsType::UnionType(union) => union
+                .types
+                .iter()
+                .find(|ty| !is_null(ty))
+                .map(|ty| self.ts_type(module, ty, hint))
+                .unwrap_or(Value::Null),
+            TsType::IntersectionType(intersection) => {
+                let mut merged = Map::new();
+                for ty in intersection.types.iter() {
+                    // A branded primitive, `number & { __brand: ... }`, is a value of the primitive
+                    if let TsType::PrimaryType(PrimaryType::Predefined(_)) = ty {
+                        return self.ts_type(module, ty, hint);
+                    }
+                    if let Value::Object(properties) = self.ts_type(module, ty, hint) {
+                        merged.extend(properties);
+                    }
+                }
+                Value::Object(merged)
+            }
+            TsType::ParenthesizedType(parenthesized) => {
+                self.ts_type(module, &parenthesized.inner, hint)
+            }
+        }
+    }
+
+    fn primary_type(&mut self, module: &'a ExampleModule, ty: &PrimaryType, hint: &str) -> Value {
+        match ty {
+            PrimaryType::Predefined(predefined) => match predefined {
+                PredefinedType::Number | PredefinedType::BigInt => Value::from(0),
+                PredefinedType::Boolean => Value::from(false),
+                PredefinedType::String => Value::from(hint),
+                PredefinedType::Any
+                | PredefinedType::Unknown
+                | PredefinedType::Null
+                | PredefinedType::Never => Value::Null,
+            },
+            PrimaryType::LiteralType(literal) => match literal {
+                LiteralType::StringLiteral(literal) => Value::from(literal.unescaped()),
+                LiteralType::NumericLiteral(literal) => Value::from(literal.value()),
+                LiteralType::BooleanLiteral(literal) => Value::from(literal.value()),
+            },
+            PrimaryType::ObjectType(object) => self.members(module, &object.body.members),
+            PrimaryType::MappedType(mapped) => {
+                let mut keys = Vec::new();
+                string_literals(&mapped.keys, &mut keys);
+                Value::Object(
+                    keys.into_iter()
+                        .map(|key| {
+                            let value = self.ts_type(module, &mapped.inner_type, &key);
+                            (key, value)
+                        })
+                        .collect(),
+                )
+            }
+            PrimaryType::ArrayType(array) => {
+                Value::Array(vec![self.primary_type(module, &array.inner_type, hint)])
+            }
+            PrimaryType::TupleType(tuple) => Value::Array(
+                tuple
+                    .inner_types
+                    .iter()
+                    .map(|ty| self.ts_type(module, ty, hint))
+                    .collect(),
+            ),
+            PrimaryType::TypeReference(reference) => {
+                let name = reference.name.to_string();
+                let args: Vec<&TsType> = reference
+                    .args
+                    .iter()
+                    .flat_map(|args| args.types.iter())
+                    .collect();
+                if name == "Record" && args.len() == 2 {
+                    let mut map = Map::new();
+                    map.insert("key".to_string(), self.ts_type(module, args[1], hint));
+                    return Value::Object(map);
+                }
+                let target = if module.declarations.iter().any(|(_, export)| {
+                    export.ident().map(|ident| ident.to_string()).as_deref() == Some(&name)
+                }) {
+                    Some(module.path.as_slice())
+                } else {
+                    module.imports.get(&name).map(|path| path.as_slice())
+                };
+                match target.and_then(|path| self.examples.find(path, &name)) {
+                    Some((target_module, export)) => self.declaration(target_module, export),
+                    None => Value::Null,
+                }
+            }
+            PrimaryType::RawType(_) => Value::Null,
+        }
+    }
+}
+
+fn is_null(ty: &TsType) -> bool {
+    matches!(
+        ty,
+        TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Null))
+    )
+}
+
+/// The string literals of a union of keys
+fn string_literals(ty: &TsType, keys: &mut Vec<String>) {
+    match ty {
+        TsType::PrimaryType(PrimaryType::LiteralType(LiteralType::StringLiteral(literal))) => {
+            keys.push(literal.unescaped())
+        }
+        TsType::UnionType(union) => union.types.iter().for_each(|ty| string_literals(ty, keys)),
+        TsType::ParenthesizedType(parenthesized) => string_literals(&parenthesized.inner, keys),
+        _ => (),
+    }
+}
+
+fn property_name(name: &PropertyName) -> String {
+    match name {
+        PropertyName::Identifier(ident) => ident.to_string(),
+        PropertyName::StringLiteral(literal) => literal.unescaped(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{tests::launch_test_module, ModuleStepResult},
+    };
+
+    fn add(examples: &mut Examples, result: &ModuleStepResult) {
+        examples.add_module(&result.data);
+        for child in result.children.iter() {
+            add(examples, child);
+        }
+    }
+
+    #[test]
+    fn should_generate_examples() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::Role;
+
+            pub mod models {
+                #[derive(Serialize)]
+                #[serde(tag = "type")]
+                pub enum Role { Admin { level: u8 }, Member }
+            }
+
+            #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
+            pub struct User {
+                user_name: String,
+                role: Role,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                manager: Option<Box<User>>,
+                scores: Vec<(u32, bool)>,
+                id: UserId,
+            }
+
+            #[derive(Serialize)]
+            #[ts(nominal)]
+            pub struct UserId(u32);
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut examples = Examples::default();
+        add(&mut examples, &result);
+
+        assert_eq!(
+            examples.to_json(),
+            serde_json::json!({
+                "crate::User": {
+                    "userName": "userName",
+                    "role": { "type": "Admin", "level": 0 },
+                    "manager": null,
+                    "scores": [[0, false]],
+                    "id": 0
+                },
+                "crate::UserId": 0,
+                "crate::models::Role": { "type": "Admin", "level": 0 }
+            })
+        );
+    }
+}
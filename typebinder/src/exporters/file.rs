@@ -1,7 +1,26 @@
-use super::Exporter;
+use super::{
+    barrel::{Barrel, BARREL_FILE},
+    check::CheckReport,
+    examples::Examples,
+    formatter::{format_contents, format_file, lay_out},
+    hash_manifest::HashManifest,
+    output_options::OutputOptions,
+    symbol_map::SymbolMap,
+    type_tests::{TypeTests, TYPE_TESTS_FILE},
+    Exporter,
+};
 use crate::error::TsExportError;
-use crate::{pipeline::module_step::ModuleStepResultData, utils::display_path::DisplayPath};
+use crate::{
+    path_mapper::PathCase,
+    pipeline::module_step::ModuleStepResultData,
+    tsconfig::{absolute_path, ImportAliases},
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+    },
+};
 use std::{
+    collections::{HashMap, HashSet},
     io::Write,
     path::{Path, PathBuf},
 };
@@ -10,7 +29,25 @@ use std::{
 pub struct FileExporter {
     root_path: PathBuf,
     default_module_name: Option<String>,
-    header_comment: HeaderComment,
+    output: OutputOptions,
+    symbol_map: SymbolMap,
+    type_tests: TypeTests,
+    examples: Examples,
+    hash_manifest: HashManifest,
+    barrel: Barrel,
+    /// The contents written to each file, so that a run launched again only writes the modules that changed
+    written: HashMap<PathBuf, String>,
+    check: bool,
+    check_report: CheckReport,
+    declaration_files: bool,
+    write_barrel: bool,
+    /// The directories the other crates are written to, relatively to the root path, by crate name
+    crate_dirs: HashMap<String, Vec<String>>,
+    import_aliases: ImportAliases,
+    file_case: PathCase,
+    index_files: bool,
+    /// The modules that contain other exported modules, see [Exporter::plan_modules]
+    parent_modules: HashSet<Vec<String>>,
 }
 
 impl Default for FileExporter {
@@ -19,7 +56,22 @@ impl Default for FileExporter {
         FileExporter {
             root_path,
             default_module_name: None,
-            header_comment: HeaderComment::Standard,
+            output: OutputOptions::default(),
+            symbol_map: Default::default(),
+            type_tests: Default::default(),
+            examples: Default::default(),
+            hash_manifest: Default::default(),
+            barrel: Default::default(),
+            written: Default::default(),
+            check: false,
+            check_report: Default::default(),
+            declaration_files: false,
+            write_barrel: false,
+            crate_dirs: HashMap::new(),
+            import_aliases: ImportAliases::default(),
+            file_case: PathCase::Unchanged,
+            index_files: false,
+            parent_modules: HashSet::new(),
         }
     }
 }
@@ -29,10 +81,29 @@ impl FileExporter {
         FileExporter {
             root_path: path,
             default_module_name: None,
-            header_comment: HeaderComment::Standard,
+            output: OutputOptions::default(),
+            symbol_map: Default::default(),
+            type_tests: Default::default(),
+            examples: Default::default(),
+            hash_manifest: Default::default(),
+            barrel: Default::default(),
+            written: Default::default(),
+            check: false,
+            check_report: Default::default(),
+            declaration_files: false,
+            write_barrel: false,
+            crate_dirs: HashMap::new(),
+            import_aliases: ImportAliases::default(),
+            file_case: PathCase::Unchanged,
+            index_files: false,
+            parent_modules: HashSet::new(),
         }
     }
 
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
     pub fn set_root_path(&mut self, path: PathBuf) {
         self.root_path = path;
     }
@@ -46,88 +117,569 @@ impl FileExporter {
         });
     }
 
-    pub fn set_header_comment(&mut self, header_comment: HeaderComment) {
-        self.header_comment = header_comment;
+    /// The case of the file names, the Rust modules being snake_case : `user_roles.ts` by default, `user-roles.ts` with [PathCase::KebabCase]
+    pub fn set_file_case(&mut self, file_case: PathCase) {
+        self.file_case = file_case;
     }
 
-    fn render_header_comment(&self, rust_module_path: &syn::Path) -> Option<String> {
-        match &self.header_comment {
-            HeaderComment::None => None,
-            HeaderComment::Custom(comment) => Some(format!("/* {} */", comment)),
-            HeaderComment::Standard => {
-                let header = format!(
-                    "// This file was auto-generated with typebinder from Rust source code. Do not change this file manually.\n\
-                     // Change the Rust source code instead and regenerate with typebinder.\n\
-                     // Rust source module: {}",
-                     DisplayPath(&rust_module_path)
-                );
-                Some(header)
-            }
+    /// Writes the modules that contain other modules to `index.ts` in their directory, `api/index.ts` next to `api/users.ts`
+    /// instead of `api.ts`
+    pub fn set_index_files(&mut self, index_files: bool) {
+        self.index_files = index_files;
+    }
+
+    /// Imports the modules of another crate from the files written to `dir` by its own run, relatively to the root path
+    /// (e.g. `other_crate` or `../other_crate`), instead of going through the PathMapper.
+    /// Its files are expected to be named with the same [PathCase], without index files.
+    pub fn add_crate_dir(&mut self, crate_name: &str, dir: &Path) {
+        let segments = dir
+            .iter()
+            .map(|segment| segment.to_string_lossy().to_string())
+            .collect();
+        self.crate_dirs.insert(crate_name.to_string(), segments);
+    }
+
+    /// Imports the modules through the TS path aliases covering their file, instead of relatively
+    pub fn set_import_aliases(&mut self, import_aliases: ImportAliases) {
+        self.import_aliases = import_aliases;
+    }
+
+    /// How each file is formatted and commented, [HeaderComment::Standard] by default.
+    /// The import style applies to the modules, the barrel and the type tests.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.barrel.set_import_style(output.import_style);
+        self.type_tests.set_import_style(output.import_style);
+        self.output = output;
+    }
+
+    /// Compares the modules to the files of the output directory instead of writing them,
+    /// the differences being collected in the [check report](FileExporter::check_report)
+    pub fn set_check(&mut self, check: bool) {
+        self.check = check;
+    }
+
+    /// The differences between the modules exported so far and the output files, see [FileExporter::set_check]
+    pub fn check_report(&self) -> CheckReport {
+        self.check_report.clone()
+    }
+
+    /// Writes the modules to declaration files, `.d.ts` instead of `.ts`,
+    /// which requires [ExporterOptions::declaration_files](crate::contexts::exporter::ExporterOptions::declaration_files)
+    pub fn set_declaration_files(&mut self, declaration_files: bool) {
+        self.declaration_files = declaration_files;
+    }
+
+    pub fn output_options(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// The name of the file of the root module, without its extension
+    pub fn root_module_name(&self) -> String {
+        self.default_module_name
+            .as_deref()
+            .and_then(|name| Path::new(name).file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "index".to_string())
+    }
+
+    /// The location of the types exported so far, see [SymbolMap]
+    pub fn symbol_map(&self) -> SymbolMap {
+        self.symbol_map.clone()
+    }
+
+    /// The type tests of the types exported so far, see [TypeTests]
+    pub fn type_tests(&self) -> TypeTests {
+        self.type_tests.clone()
+    }
+
+    /// The example values of the types exported so far, see [Examples]
+    pub fn examples(&self) -> Examples {
+        self.examples.clone()
+    }
+
+    /// The hashes of the declarations exported so far, see [HashManifest]
+    pub fn hash_manifest(&self) -> HashManifest {
+        self.hash_manifest.clone()
+    }
+
+    /// The barrel re-exporting the modules exported so far, see [Barrel]
+    pub fn barrel(&self) -> Barrel {
+        self.barrel.clone()
+    }
+
+    /// Writes the [Barrel] to [FileExporter::barrel_file] in the output directory once the run is finished
+    pub fn set_barrel(&mut self, write_barrel: bool) {
+        self.write_barrel = write_barrel;
+    }
+
+    /// The name of the file of the [Barrel], `index.d.ts` with [FileExporter::set_declaration_files]
+    pub fn barrel_file(&self) -> &'static str {
+        if self.declaration_files {
+            "index.d.ts"
+        } else {
+            BARREL_FILE
         }
     }
+
+    /// The segments of the file of a module, without its extension
+    fn module_file(&self, module_path: &[String]) -> Vec<String> {
+        if module_path.is_empty() {
+            return vec![self.root_module_name()];
+        }
+        let mut file: Vec<String> = module_path
+            .iter()
+            .map(|segment| self.file_case.apply(segment))
+            .collect();
+        if self.index_files && self.parent_modules.contains(module_path) {
+            file.push("index".to_string());
+        }
+        file
+    }
+
+    /// The segments of the file of a module of another crate in the output, see [FileExporter::add_crate_dir]
+    fn crate_dir_file(&self, path: &str) -> Option<Vec<String>> {
+        let mut segments = path.split("::");
+        let dir = self.crate_dirs.get(segments.next()?)?;
+        Some(
+            dir.iter()
+                .cloned()
+                .chain(segments.map(|segment| self.file_case.apply(segment)))
+                .collect(),
+        )
+    }
+
+    /// The path of the file `to`, relatively to the file `from`, e.g. `../models`
+    fn relative_import(&self, from: &[String], to: &[String]) -> String {
+        let from_dir = &from[..from.len() - 1];
+        let common = from_dir
+            .iter()
+            .zip(to[..to.len() - 1].iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let prefix = if common == from_dir.len() {
+            // The directory of another crate may already go up, e.g. `../other_crate`
+            if to.get(common).map(String::as_str) == Some("..") {
+                String::new()
+            } else {
+                "./".to_string()
+            }
+        } else {
+            "../".repeat(from_dir.len() - common)
+        };
+        format!("{}{}", prefix, to[common..].join("/"))
+    }
 }
 
 impl Exporter for FileExporter {
     type Error = TsExportError;
 
-    fn export_module(&self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+    fn start_run(&mut self) {
+        self.parent_modules.clear();
+        self.symbol_map = SymbolMap::default();
+        self.type_tests = TypeTests::default();
+        self.examples = Examples::default();
+        self.hash_manifest = HashManifest::default();
+        self.barrel = Barrel::default();
+        self.barrel.set_import_style(self.output.import_style);
+        self.type_tests.set_import_style(self.output.import_style);
+        self.check_report = CheckReport::default();
+    }
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
         log::info!("Exporting module {}", DisplayPath(&process_result.path));
 
-        let header = self.render_header_comment(&process_result.path);
-        let mut file_path: PathBuf = if process_result.path.segments.is_empty() {
-            self.default_module_name
-                .clone()
-                .unwrap_or_else(|| "index".to_string())
-                .into()
-        } else {
-            process_result
-                .path
-                .segments
-                .into_iter()
-                .map(|segm| segm.ident.to_string())
-                .collect()
-        };
-        file_path.set_extension("ts");
+        let source = DisplayPath(&process_result.path).to_string();
+        let module_path = module_segments(&process_result.path);
+        let module_file = self.module_file(&module_path);
+        let mut file_path: PathBuf = module_file.iter().collect();
+        file_path.set_extension(if self.declaration_files { "d.ts" } else { "ts" });
+        let file = file_path
+            .iter()
+            .map(|segment| segment.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.type_tests.add_module(&process_result, &file)?;
+        self.examples.add_module(&process_result);
+        self.hash_manifest.add_module(&process_result, file.clone());
+        self.symbol_map.add_module(&process_result, file.clone());
+        self.barrel.add_module(&process_result, &file);
         let mut path = self.root_path.clone();
         path.push(file_path);
 
-        let main_content: String = process_result
-            .imports
+        // The modules of the crate that are not mapped by the PathMapper are imported from their file,
+        // as are the modules of the crates written to a directory of their own
+        let root_path = if self.import_aliases.is_empty() {
+            None
+        } else {
+            Some(absolute_path(&self.root_path)?)
+        };
+        let crate_name = &process_result.crate_name;
+        let mut imports = process_result.imports;
+        for import in imports.iter_mut() {
+            let target = process_result
+                .import_entries
+                .iter()
+                .find(|entry| format!("\"{}\"", entry.path) == import.path)
+                .and_then(|entry| {
+                    resolve_local_path(entry, &module_path, crate_name)
+                        .map(|target| self.module_file(&target))
+                        .or_else(|| self.crate_dir_file(&entry.path))
+                });
+            if let Some(target) = target {
+                let alias = root_path.as_ref().and_then(|root_path| {
+                    let file: PathBuf = target.iter().collect();
+                    self.import_aliases.get(&root_path.join(file))
+                });
+                let specifier =
+                    alias.unwrap_or_else(|| self.relative_import(&module_file, &target));
+                import.path = format!(
+                    "\"{}\"",
+                    self.output.import_style.file_specifier(&specifier)
+                );
+            }
+        }
+        let imports = self
+            .output
+            .import_style
+            .style_imports(imports, &process_result.value_imports);
+
+        let main_content: String = imports
             .into_iter()
             .map(|statement| format!("{}\n", statement))
             .chain(
                 process_result
                     .exports
                     .into_iter()
-                    .map(|stm| format!("{}\n", stm.to_string())),
+                    .map(|stm| format!("{}\n", stm)),
             )
             .collect();
 
-        let file_contents = match header {
-            None => main_content,
-            Some(comment) => format!("{}\n\n{}", comment, main_content),
-        };
+        let main_content = lay_out(&self.output.format_options, main_content);
+        let file_contents = self.output.header_comment.prepend_to(&source, main_content);
 
+        if self.check {
+            let generated = format_contents(&self.output.formatter, &path, file_contents)?;
+            return self.check_report.compare(file, &path, &generated);
+        }
+        if path.exists() && self.written.get(&path) == Some(&file_contents) {
+            log::info!("Module at {:?} is unchanged", path);
+            return Ok(());
+        }
         log::info!("Outputting module at {:?}", path);
-        if let Err(e) =
-            std::fs::create_dir_all(&path.parent().expect("Failed to get dir of output module"))
-        {
-            match e.kind() {
-                std::io::ErrorKind::AlreadyExists => (),
-                _ => panic!("{}", e),
-            }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
 
-        let mut file = std::fs::File::create(&path).expect("Failed to open file");
-        file.write_all(file_contents.as_bytes())
-            .expect("Failed to write");
+        let mut file = std::fs::File::create(&path)?;
+        file.write_all(file_contents.as_bytes())?;
+        drop(file);
+        self.written.insert(path.clone(), file_contents);
+
+        format_file(&self.output.formatter, &path)
+    }
 
-        Ok(())
+    fn plan_modules(&mut self, module_paths: &[Vec<String>]) {
+        self.parent_modules = module_paths
+            .iter()
+            .flat_map(|module_path| {
+                (1..module_path.len()).map(move |depth| module_path[..depth].to_vec())
+            })
+            .collect();
+    }
+
+    fn finish(&mut self) -> Result<(), TsExportError> {
+        if self.check {
+            // The barrel, the type tests and the other crates are not compared
+            let mut ignored = vec![TYPE_TESTS_FILE.to_string()];
+            if self.write_barrel {
+                ignored.push(self.barrel_file().to_string());
+            }
+            ignored.extend(self.crate_dirs.values().map(|segments| segments.join("/")));
+            return self.check_report.find_stale(&self.root_path, &ignored);
+        }
+        if !self.write_barrel {
+            return Ok(());
+        }
+        let path = self.root_path.join(self.barrel_file());
+        self.barrel.write_to(&path)?;
+        format_file(&self.output.formatter, &path)
     }
 }
 
+/// The comment written at the top of each generated file
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum HeaderComment {
+    /// A comment warning that the file is generated, with the Rust module it is generated from
+    #[default]
     Standard,
+    /// A `/* ... */` comment
     Custom(String),
+    /// A banner written as is, `{module}` being replaced by the Rust module it is generated from,
+    /// e.g. `/* eslint-disable */` or a license header
+    Banner(String),
     None,
 }
+
+impl HeaderComment {
+    /// Renders the header of the file generated from `source`, the path of a module or the name of a crate
+    pub fn render(&self, source: &str) -> Option<String> {
+        match self {
+            HeaderComment::None => None,
+            HeaderComment::Custom(comment) => Some(format!("/* {} */", comment)),
+            HeaderComment::Banner(banner) => Some(banner.replace("{module}", source)),
+            HeaderComment::Standard => {
+                let header = format!(
+                    "// This file was auto-generated with typebinder from Rust source code. Do not change this file manually.\n\
+                     // Change the Rust source code instead and regenerate with typebinder.\n\
+                     // Rust source module: {}",
+                    source
+                );
+                Some(header)
+            }
+        }
+    }
+
+    /// Prepends the header of the file generated from `source` to `contents`
+    pub fn prepend_to(&self, source: &str, contents: String) -> String {
+        match self.render(source) {
+            None => contents,
+            Some(header) => format!("{}\n\n{}", header, contents),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::type_solving::ImportEntry;
+    use crate::utils::test_dir;
+    use std::str::FromStr;
+    use ts_json_subset::{
+        ident::TSIdent,
+        import::{ImportKind, ImportList, ImportStatement},
+    };
+
+    fn import(path: &str, ident: &str) -> ImportStatement {
+        ImportStatement {
+            import_kind: ImportKind::ImportList(ImportList {
+                items: vec![TSIdent::from_str(ident).unwrap().into()],
+            }),
+            path: format!("\"{}\"", path),
+            type_only: false,
+        }
+    }
+
+    #[test]
+    fn should_import_local_modules_from_their_file() {
+        let root = test_dir("file_exporter");
+        let mut exporter = FileExporter::new(root.clone());
+        exporter.set_output_options(OutputOptions::without_header());
+        exporter.add_crate_dir("shared", Path::new("deps/shared"));
+
+        exporter
+            .export_module(ModuleStepResultData {
+                exports: Vec::new(),
+                imports: vec![
+                    import("my_crate::models::user", "User"),
+                    import("my_crate", "Id"),
+                    import("@types/other", "Other"),
+                    import("shared::models", "Model"),
+                ],
+                import_entries: vec![
+                    ImportEntry {
+                        path: "my_crate::models::user".to_string(),
+                        ident: "User".to_string(),
+                        alias: None,
+                    },
+                    ImportEntry {
+                        path: "my_crate".to_string(),
+                        ident: "Id".to_string(),
+                        alias: None,
+                    },
+                    ImportEntry {
+                        path: "other_crate".to_string(),
+                        ident: "Other".to_string(),
+                        alias: None,
+                    },
+                    ImportEntry {
+                        path: "shared::models".to_string(),
+                        ident: "Model".to_string(),
+                        alias: None,
+                    },
+                ],
+                path: syn::parse_str("api::session").unwrap(),
+                crate_name: "my_crate".to_string(),
+                type_names: Default::default(),
+                phantom_parameters: Default::default(),
+                filtered_out: Vec::new(),
+                globs: Default::default(),
+                reexports: Vec::new(),
+                value_imports: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("api").join("session.ts")).unwrap(),
+            "import { User } from \"../models/user\";\n\
+             import { Id } from \"../index\";\n\
+             import { Other } from \"@types/other\";\n\
+             import { Model } from \"../deps/shared/models\";\n"
+        );
+    }
+
+    #[test]
+    fn should_import_through_the_aliases() {
+        let root = test_dir("file_exporter_aliases");
+        let mut exporter = FileExporter::new(root.join("types"));
+        exporter.set_output_options(OutputOptions::without_header());
+        let mut aliases = ImportAliases::default();
+        aliases
+            .add_alias("@models/*", &root.join("types/models/*"))
+            .unwrap();
+        exporter.set_import_aliases(aliases);
+
+        exporter
+            .export_module(ModuleStepResultData {
+                exports: Vec::new(),
+                imports: vec![
+                    import("my_crate::models::user", "User"),
+                    import("my_crate::api", "Page"),
+                ],
+                import_entries: vec![
+                    ImportEntry {
+                        path: "my_crate::models::user".to_string(),
+                        ident: "User".to_string(),
+                        alias: None,
+                    },
+                    ImportEntry {
+                        path: "my_crate::api".to_string(),
+                        ident: "Page".to_string(),
+                        alias: None,
+                    },
+                ],
+                path: syn::parse_str("api::session").unwrap(),
+                crate_name: "my_crate".to_string(),
+                type_names: Default::default(),
+                phantom_parameters: Default::default(),
+                filtered_out: Vec::new(),
+                globs: Default::default(),
+                reexports: Vec::new(),
+                value_imports: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("types/api/session.ts")).unwrap(),
+            "import { User } from \"@models/user\";\n\
+             import { Page } from \"../api\";\n"
+        );
+    }
+
+    #[test]
+    fn should_name_the_files_after_the_modules() {
+        let root = test_dir("file_exporter_naming");
+        let mut exporter = FileExporter::new(root.clone());
+        exporter.set_output_options(OutputOptions::without_header());
+        exporter.set_file_case(PathCase::KebabCase);
+        exporter.set_index_files(true);
+        exporter.plan_modules(&[
+            vec!["user_roles".to_string()],
+            vec!["user_roles".to_string(), "admin_users".to_string()],
+            vec!["api_keys".to_string()],
+        ]);
+
+        exporter
+            .export_module(ModuleStepResultData {
+                exports: Vec::new(),
+                imports: vec![
+                    import("my_crate::user_roles", "Role"),
+                    import("my_crate::api_keys", "ApiKey"),
+                ],
+                import_entries: vec![
+                    ImportEntry {
+                        path: "my_crate::user_roles".to_string(),
+                        ident: "Role".to_string(),
+                        alias: None,
+                    },
+                    ImportEntry {
+                        path: "my_crate::api_keys".to_string(),
+                        ident: "ApiKey".to_string(),
+                        alias: None,
+                    },
+                ],
+                path: syn::parse_str("user_roles::admin_users").unwrap(),
+                crate_name: "my_crate".to_string(),
+                type_names: Default::default(),
+                phantom_parameters: Default::default(),
+                filtered_out: Vec::new(),
+                globs: Default::default(),
+                reexports: Vec::new(),
+                value_imports: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("user-roles/admin-users.ts")).unwrap(),
+            "import { Role } from \"./index\";\n\
+             import { ApiKey } from \"../api-keys\";\n"
+        );
+    }
+
+    #[test]
+    fn should_fail_when_the_output_cannot_be_written() {
+        let root = test_dir("file_exporter_blocked");
+        // A file stands where the directory of the module should be created
+        std::fs::write(root.join("api"), "").unwrap();
+        let mut exporter = FileExporter::new(root);
+
+        let result = exporter.export_module(ModuleStepResultData {
+            exports: Vec::new(),
+            imports: Vec::new(),
+            import_entries: Vec::new(),
+            path: syn::parse_str("api::session").unwrap(),
+            crate_name: "my_crate".to_string(),
+            type_names: Default::default(),
+            phantom_parameters: Default::default(),
+            filtered_out: Vec::new(),
+            globs: Default::default(),
+            reexports: Vec::new(),
+            value_imports: Default::default(),
+        });
+        assert!(matches!(result, Err(TsExportError::IoError(_))));
+    }
+
+    #[test]
+    fn should_write_the_banner_at_the_top_of_each_module() {
+        let root = test_dir("file_exporter_banner");
+        let mut exporter = FileExporter::new(root.clone());
+        exporter.set_output_options(OutputOptions {
+            header_comment: HeaderComment::Banner(
+                "/* eslint-disable */\n// Generated from {module}".to_string(),
+            ),
+            ..OutputOptions::default()
+        });
+
+        exporter
+            .export_module(ModuleStepResultData {
+                exports: Vec::new(),
+                imports: vec![import("@types/other", "Other")],
+                import_entries: Vec::new(),
+                path: syn::parse_str("api::session").unwrap(),
+                crate_name: "my_crate".to_string(),
+                type_names: Default::default(),
+                phantom_parameters: Default::default(),
+                filtered_out: Vec::new(),
+                globs: Default::default(),
+                reexports: Vec::new(),
+                value_imports: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("api").join("session.ts")).unwrap(),
+            "/* eslint-disable */\n\
+             // Generated from api::session\n\n\
+             import { Other } from \"@types/other\";\n"
+        );
+    }
+}
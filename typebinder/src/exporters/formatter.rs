@@ -0,0 +1,153 @@
+use std::{path::Path, process::Command, str::FromStr};
+
+use ts_json_subset::format::FormatOptions;
+
+use crate::error::TsExportError;
+
+/// A command run on each file once it is written, so that the bindings match the formatting of the repository
+/// (e.g. `prettier --write`, `biome format --write`).
+///
+/// The path of the file is appended to the arguments of the command.
+/// The command is not run through a shell, its arguments are separated by whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatterCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl FormatterCommand {
+    /// Runs the command on the file, failing if the command can't be spawned or exits with an error
+    pub fn run(&self, path: &Path) -> Result<(), TsExportError> {
+        log::info!("Formatting {:?} with {}", path, self);
+        let output = Command::new(&self.program)
+            .args(self.args.iter())
+            .arg(path)
+            .output()
+            .map_err(|error| {
+                TsExportError::FormatterFailed(self.to_string(), path.into(), error.to_string())
+            })?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(TsExportError::FormatterFailed(
+                self.to_string(),
+                path.into(),
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ))
+        }
+    }
+}
+
+/// Runs the formatter, if any, on a written file
+pub(crate) fn format_file(
+    formatter: &Option<FormatterCommand>,
+    path: &Path,
+) -> Result<(), TsExportError> {
+    match formatter {
+        Some(formatter) => formatter.run(path),
+        None => Ok(()),
+    }
+}
+
+/// Lays out the rendered code with the options, if any, before it is written
+pub(crate) fn lay_out(format_options: &Option<FormatOptions>, code: String) -> String {
+    match format_options {
+        Some(format_options) => format_options.format(&code),
+        None => code,
+    }
+}
+
+/// The contents of the file at `path` once formatted, without writing it : the contents are formatted in a temporary file
+/// next to it (in its closest existing directory), so that the formatter applies the configuration of the output directory
+pub(crate) fn format_contents(
+    formatter: &Option<FormatterCommand>,
+    path: &Path,
+    contents: String,
+) -> Result<String, TsExportError> {
+    let formatter = match formatter {
+        Some(formatter) => formatter,
+        None => return Ok(contents),
+    };
+    let dir = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // The file name is kept as a suffix, so that the formatter infers the language from the extension
+    let temporary = dir.join(format!(
+        "typebinder_check_{}_{}",
+        std::process::id(),
+        file_name
+    ));
+    std::fs::write(&temporary, contents)?;
+    let formatted = formatter
+        .run(&temporary)
+        .and_then(|_| Ok(std::fs::read_to_string(&temporary)?));
+    let _ = std::fs::remove_file(&temporary);
+    formatted
+}
+
+impl FromStr for FormatterCommand {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace().map(String::from);
+        let program = words
+            .next()
+            .ok_or_else(|| TsExportError::InvalidFormatterCommand(s.to_string()))?;
+        Ok(FormatterCommand {
+            program,
+            args: words.collect(),
+        })
+    }
+}
+
+impl std::fmt::Display for FormatterCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program)?;
+        self.args.iter().try_for_each(|arg| write!(f, " {}", arg))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    #[test]
+    fn should_run_formatter() {
+        let path = test_dir("formatter").join("file.ts");
+        std::fs::write(&path, "export type A = string;\n").unwrap();
+
+        let formatter: FormatterCommand = "  true  --write ".parse().unwrap();
+        assert_eq!(formatter.to_string(), "true --write");
+        assert!(formatter.run(&path).is_ok());
+
+        let formatter: FormatterCommand = "false".parse().unwrap();
+        assert!(matches!(
+            formatter.run(&path),
+            Err(TsExportError::FormatterFailed(..))
+        ));
+        assert!("  ".parse::<FormatterCommand>().is_err());
+    }
+
+    #[test]
+    fn should_format_the_contents_next_to_the_file() {
+        let dir = test_dir("format_contents");
+        let formatter: Option<FormatterCommand> = Some("sed -i s/string/number/".parse().unwrap());
+
+        let formatted = format_contents(
+            &formatter,
+            &dir.join("models").join("user.ts"),
+            "export type A = string;\n".to_string(),
+        )
+        .unwrap();
+        assert_eq!(formatted, "export type A = number;\n");
+        // Neither the file nor its temporary copy are left behind
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use serde_json::Value;
+
+use crate::{error::TsExportError, pipeline::module_step::ModuleStepResultData};
+
+/// A map of the exported TS declarations to a hash of their generated code, by output file :
+///
+/// ```text
+/// {
+///   "models/user.ts": { "Role": "9b3c2e1f0a4d5c6b", "User": "1f2e3d4c5b6a7980" }
+/// }
+/// ```
+///
+/// Comparing the manifests of two runs tells which declarations changed, so that a frontend build can invalidate only what depends on them.
+/// The hash is the 64-bit FNV-1a of the declaration, which is stable across runs and Rust versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HashManifest {
+    files: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl HashManifest {
+    /// Registers all the declarations exported by a module in `file`
+    pub fn add_module<S: Into<String>>(&mut self, module: &ModuleStepResultData, file: S) {
+        let hashes = self.files.entry(file.into()).or_default();
+        for export in module.exports.iter() {
+            if let Some(ident) = export.ident() {
+                hashes.insert(ident.to_string(), hash(&export.to_string()));
+            }
+        }
+    }
+
+    pub fn get(&self, file: &str, name: &str) -> Option<&str> {
+        self.files.get(file)?.get(name).map(String::as_str)
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(&self.files).expect("A hash manifest is always serializable")
+    }
+
+    /// Writes the manifest to a temporary file that then replaces `path`,
+    /// so that a reader never sees a partially written manifest
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting hash manifest at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        let mut file = std::fs::File::create(&temporary)?;
+        serde_json::to_writer_pretty(&mut file, &self.files)?;
+        file.write_all(b"\n")?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&temporary, path)?;
+        Ok(())
+    }
+}
+
+/// The 64-bit FNV-1a of the declaration, written out rather than taken from the `Hasher`s of std,
+/// whose algorithms may change between Rust versions
+fn hash(declaration: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let hash = declaration.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use ts_json_subset::{
+        declarations::type_alias::TypeAliasDeclaration,
+        export::ExportStatement,
+        ident::TSIdent,
+        types::{PredefinedType, PrimaryType, TsType},
+    };
+
+    fn type_alias(ident: &str, predefined: PredefinedType) -> ExportStatement {
+        TypeAliasDeclaration {
+            ident: TSIdent::from_str(ident).unwrap(),
+            type_params: None,
+            inner_type: TsType::PrimaryType(PrimaryType::Predefined(predefined)),
+            doc: None,
+        }
+        .into()
+    }
+
+    fn module(exports: Vec<ExportStatement>) -> ModuleStepResultData {
+        ModuleStepResultData {
+            exports,
+            imports: Vec::new(),
+            import_entries: Vec::new(),
+            path: syn::parse_str("models").unwrap(),
+            crate_name: "my_crate".to_string(),
+            type_names: Default::default(),
+            phantom_parameters: Default::default(),
+            filtered_out: Vec::new(),
+            globs: Default::default(),
+            reexports: Vec::new(),
+            value_imports: Default::default(),
+        }
+    }
+
+    #[test]
+    fn should_change_the_hash_of_changed_declarations() {
+        let mut before = HashManifest::default();
+        before.add_module(
+            &module(vec![
+                type_alias("Id", PredefinedType::Number),
+                type_alias("Name", PredefinedType::String),
+            ]),
+            "models.ts",
+        );
+        let mut after = HashManifest::default();
+        after.add_module(
+            &module(vec![
+                type_alias("Id", PredefinedType::String),
+                type_alias("Name", PredefinedType::String),
+            ]),
+            "models.ts",
+        );
+
+        assert_eq!(
+            before.get("models.ts", "Name"),
+            after.get("models.ts", "Name")
+        );
+        assert_ne!(before.get("models.ts", "Id"), after.get("models.ts", "Id"));
+        assert_eq!(
+            before.get("models.ts", "Name"),
+            Some(hash("export type Name = string;").as_str())
+        );
+    }
+
+    #[test]
+    fn should_hash_with_fnv_1a() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(hash("foobar"), "85944171f73967e8");
+    }
+}
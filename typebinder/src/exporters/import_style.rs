@@ -0,0 +1,141 @@
+use std::{collections::BTreeSet, str::FromStr};
+
+use ts_json_subset::import::{ImportKind, ImportList, ImportStatement};
+
+use crate::error::TsExportError;
+
+/// How the exporters write their imports :
+///
+/// * `import type { User } from "./models"` rather than `import { User } from "./models"`, for `verbatimModuleSyntax`
+///   or `isolatedModules`. The values (the codecs and the mock factories) are still imported with a plain `import`.
+/// * `"./models.js"` rather than `"./models"`, the ESM resolution of `"moduleResolution": "NodeNext"` requiring the extension.
+///   Only the specifiers of the generated files get an extension, not the packages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportStyle {
+    pub type_only: bool,
+    pub extension: ImportExtension,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The extension appended to the specifiers of the generated files
+pub enum ImportExtension {
+    #[default]
+    None,
+    /// `.js`, the extension of the compiled file
+    Js,
+    /// `.ts`, with `allowImportingTsExtensions`
+    Ts,
+}
+
+impl ImportStyle {
+    /// Appends the extension to the specifier of a generated file, without its quotes
+    pub fn file_specifier(&self, specifier: &str) -> String {
+        match self.extension {
+            ImportExtension::None => specifier.to_string(),
+            ImportExtension::Js => format!("{}.js", specifier),
+            ImportExtension::Ts => format!("{}.ts", specifier),
+        }
+    }
+
+    /// Turns the imports of types into `import type`, splitting the statements that also import values
+    pub fn style_imports(
+        &self,
+        imports: Vec<ImportStatement>,
+        value_imports: &BTreeSet<String>,
+    ) -> Vec<ImportStatement> {
+        if !self.type_only {
+            return imports;
+        }
+        imports
+            .into_iter()
+            .flat_map(|import| {
+                let list = match import.import_kind {
+                    ImportKind::ImportList(list) => list,
+                    import_kind => {
+                        return vec![ImportStatement {
+                            import_kind,
+                            ..import
+                        }]
+                    }
+                };
+                let path = import.path;
+                let (values, types): (Vec<_>, Vec<_>) = list
+                    .items
+                    .into_iter()
+                    .partition(|item| value_imports.contains(&item.local_name().to_string()));
+                vec![(types, true), (values, false)]
+                    .into_iter()
+                    .filter(|(items, _)| !items.is_empty())
+                    .map(|(items, type_only)| ImportStatement {
+                        import_kind: ImportKind::ImportList(ImportList { items }),
+                        path: path.clone(),
+                        type_only,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl FromStr for ImportExtension {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(ImportExtension::None),
+            "js" => Ok(ImportExtension::Js),
+            "ts" => Ok(ImportExtension::Ts),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown import extension {}, expected none, js or ts",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ts_json_subset::ident::TSIdent;
+
+    fn import(path: &str, idents: &[&str]) -> ImportStatement {
+        ImportStatement {
+            import_kind: ImportKind::ImportList(ImportList {
+                items: idents
+                    .iter()
+                    .map(|ident| TSIdent::from_str(ident).unwrap().into())
+                    .collect(),
+            }),
+            path: format!("\"{}\"", path),
+            type_only: false,
+        }
+    }
+
+    #[test]
+    fn should_import_the_types_only() {
+        let style = ImportStyle {
+            type_only: true,
+            extension: ImportExtension::Js,
+        };
+        let value_imports: BTreeSet<String> = vec!["UserCodec".to_string()].into_iter().collect();
+        let imports: Vec<String> = style
+            .style_imports(
+                vec![
+                    import(&style.file_specifier("./models"), &["User", "UserCodec"]),
+                    import("@company/types", &["Id"]),
+                ],
+                &value_imports,
+            )
+            .iter()
+            .map(|import| import.to_string())
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                "import type { User } from \"./models.js\";",
+                "import { UserCodec } from \"./models.js\";",
+                "import type { Id } from \"@company/types\";",
+            ]
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use std::{collections::HashMap, io::Write, path::Path, str::FromStr};
+
+use ts_json_subset::{
+    declarations::{const_enum::ConstEnumDeclaration, enumeration::EnumDeclaration},
+    export::ExportStatement,
+    ident::TSIdent,
+    types::{
+        LiteralType, ParenthesizedType, PrimaryType, TsType, TypeArguments, TypeParameters,
+        TypeReference, UnionType,
+    },
+    visit_mut::{self, VisitMut},
+};
+
+use super::{
+    formatter::{format_file, lay_out},
+    output_options::OutputOptions,
+    Exporter,
+};
+use crate::{
+    error::TsExportError,
+    pipeline::module_step::ModuleStepResultData,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+    },
+};
+
+/// A strategy that outputs self-contained declarations, useful for standalone snippets (documentation, templates...).
+///
+/// Instead of being imported, the types of the crate that are referenced by a declaration are recursively
+/// inlined in it, the generic parameters being replaced by their arguments :
+///
+/// ```text
+/// export interface Session {
+///     user: {
+///         name: string
+///     }
+/// }
+/// ```
+///
+/// Recursive types can't be inlined, the recursive references are kept as is.
+/// Types imported from other crates are kept as references too, and their imports are dropped.
+pub struct InlineExporter {
+    crate_name: String,
+    modules: Vec<ModuleStepResultData>,
+    output: OutputOptions,
+}
+
+/// A declaration is identified by its module path and its name
+type DeclarationKey = (Vec<String>, String);
+
+struct Declaration {
+    type_params: Vec<String>,
+    /// The default types of the generic parameters, used when a reference omits them
+    defaults: Vec<Option<TsType>>,
+    body: TsType,
+}
+
+impl InlineExporter {
+    /// `crate_name` is the name used in the Rust paths of the crate, see [RustModuleReader::crate_name](crate::step_spawner::mod_reader::RustModuleReader::crate_name)
+    pub fn new<S: Into<String>>(crate_name: S) -> Self {
+        InlineExporter {
+            crate_name: crate_name.into(),
+            modules: Vec::new(),
+            output: OutputOptions::without_header(),
+        }
+    }
+
+    /// How the file is formatted and commented, without a header comment by default (`{module}` being the name of the crate).
+    /// The import style is ignored, the types of the other crates not being imported.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.output = output;
+    }
+
+    pub fn output_options(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// Renders all the declarations exported so far, with their references inlined
+    pub fn render(&self) -> Result<String, TsExportError> {
+        let modules = &self.modules;
+
+        let mut declarations: HashMap<DeclarationKey, Declaration> = HashMap::new();
+        let mut scopes: HashMap<Vec<String>, HashMap<String, DeclarationKey>> = HashMap::new();
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            let scope = scopes.entry(module_path.clone()).or_default();
+            for export in module.exports.iter() {
+                if let Some((name, declaration)) = declaration_of(export) {
+                    let key = (module_path.clone(), name.clone());
+                    scope.insert(name, key.clone());
+                    declarations.insert(key, declaration);
+                }
+            }
+        }
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            for entry in module.import_entries.iter() {
+                match resolve_local_path(entry, &module_path, &self.crate_name) {
+                    Some(declaring_module) => {
+                        scopes
+                            .entry(module_path.clone())
+                            .or_default()
+                            .insert(entry.ident.clone(), (declaring_module, entry.ident.clone()));
+                    }
+                    None => log::warn!(
+                        "{}::{} does not belong to the crate, it cannot be inlined",
+                        entry.path,
+                        entry.ident
+                    ),
+                }
+            }
+        }
+
+        let mut inliner = Inliner {
+            declarations: &declarations,
+            scopes: &scopes,
+            stack: Vec::new(),
+        };
+        let mut exports: Vec<String> = Vec::new();
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            for export in module.exports.iter() {
+                let mut export = export.clone();
+                if let Some(ident) = export.ident() {
+                    inliner.stack = vec![(module_path.clone(), ident.to_string())];
+                }
+                match &mut export {
+                    ExportStatement::InterfaceDeclaration(interface) => {
+                        let shadowed = type_params_of(&interface.type_params);
+                        let mut ty = TsType::PrimaryType(PrimaryType::ObjectType(
+                            interface.obj_type.clone(),
+                        ));
+                        inliner.inline_type(&mut ty, &module_path, &shadowed);
+                        if let TsType::PrimaryType(PrimaryType::ObjectType(obj_type)) = ty {
+                            interface.obj_type = obj_type;
+                        }
+                    }
+                    ExportStatement::TypeAliasDeclaration(type_alias) => {
+                        let shadowed = type_params_of(&type_alias.type_params);
+                        inliner.inline_type(&mut type_alias.inner_type, &module_path, &shadowed);
+                    }
+                    ExportStatement::ReexportDeclaration(_) => continue,
+                    _ => {}
+                }
+                exports.push(export.to_string());
+            }
+        }
+
+        let contents = exports
+            .into_iter()
+            .map(|statement| format!("{}\n", statement))
+            .collect();
+        let contents = lay_out(&self.output.format_options, contents);
+        Ok(self
+            .output
+            .header_comment
+            .prepend_to(&self.crate_name, contents))
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting inlined declarations at {:?}", path);
+        let contents = self.render()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        drop(file);
+        format_file(&self.output.formatter, path)
+    }
+}
+
+impl Exporter for InlineExporter {
+    type Error = TsExportError;
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+        log::info!("Collecting module {}", DisplayPath(&process_result.path));
+        self.modules.push(process_result);
+        Ok(())
+    }
+}
+
+/// The name and the structural type of a declaration
+fn declaration_of(export: &ExportStatement) -> Option<(String, Declaration)> {
+    match export {
+        ExportStatement::InterfaceDeclaration(interface) => Some((
+            interface.ident.to_string(),
+            Declaration {
+                type_params: type_params_of(&interface.type_params),
+                defaults: defaults_of(&interface.type_params),
+                body: TsType::PrimaryType(PrimaryType::ObjectType(interface.obj_type.clone())),
+            },
+        )),
+        ExportStatement::TypeAliasDeclaration(type_alias) => Some((
+            type_alias.ident.to_string(),
+            Declaration {
+                type_params: type_params_of(&type_alias.type_params),
+                defaults: defaults_of(&type_alias.type_params),
+                body: type_alias.inner_type.clone(),
+            },
+        )),
+        ExportStatement::EnumDeclaration(EnumDeclaration { ident, body, .. })
+        | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, body }) => Some((
+            ident.to_string(),
+            Declaration {
+                type_params: Vec::new(),
+                defaults: Vec::new(),
+                body: TsType::UnionType(UnionType {
+                    types: body
+                        .variants
+                        .iter()
+                        .map(|variant| {
+                            TsType::PrimaryType(PrimaryType::LiteralType(
+                                LiteralType::StringLiteral(variant.value.clone()),
+                            ))
+                        })
+                        .collect(),
+                }),
+            },
+        )),
+        _ => None,
+    }
+}
+
+fn type_params_of(type_params: &Option<TypeParameters>) -> Vec<String> {
+    type_params
+        .iter()
+        .flat_map(|type_params| type_params.parameters.iter())
+        .map(|parameter| parameter.identifier.to_string())
+        .collect()
+}
+
+fn defaults_of(type_params: &Option<TypeParameters>) -> Vec<Option<TsType>> {
+    type_params
+        .iter()
+        .flat_map(|type_params| type_params.parameters.iter())
+        .map(|parameter| parameter.default.clone())
+        .collect()
+}
+
+struct Inliner<'a> {
+    declarations: &'a HashMap<DeclarationKey, Declaration>,
+    scopes: &'a HashMap<Vec<String>, HashMap<String, DeclarationKey>>,
+    /// The declarations being inlined, to detect recursive types
+    stack: Vec<DeclarationKey>,
+}
+
+impl Inliner<'_> {
+    /// Inlines the references of a type found in `module`, `shadowed` being the generic parameters in scope
+    fn inline_type(&mut self, ty: &mut TsType, module: &[String], shadowed: &[String]) {
+        ReferenceReplacer(|reference: &TypeReference| self.resolve(reference, module, shadowed))
+            .visit_ts_type_mut(ty);
+    }
+
+    fn resolve(
+        &mut self,
+        reference: &TypeReference,
+        module: &[String],
+        shadowed: &[String],
+    ) -> Option<TsType> {
+        let name = reference.name.to_string();
+        if shadowed.contains(&name) {
+            return None;
+        }
+        let key = self.scopes.get(module)?.get(&name)?.clone();
+        if self.stack.contains(&key) {
+            log::warn!("{} is recursive, it cannot be inlined", name);
+            return None;
+        }
+        let declaration = self.declarations.get(&key)?;
+
+        let mut body = declaration.body.clone();
+        self.stack.push(key.clone());
+        self.inline_type(&mut body, &key.0, &declaration.type_params);
+        self.stack.pop();
+
+        let mut args = reference
+            .args
+            .as_ref()
+            .map(|args| args.types.clone())
+            .unwrap_or_default();
+        args.iter_mut()
+            .for_each(|arg| self.inline_type(arg, module, shadowed));
+        for default in declaration.defaults.iter().skip(args.len()) {
+            let mut default = match default {
+                Some(default) => default.clone(),
+                None => break,
+            };
+            self.inline_type(&mut default, &key.0, &[]);
+            args.push(default);
+        }
+        let substitutions: HashMap<&String, TsType> =
+            declaration.type_params.iter().zip(args).collect();
+        ReferenceReplacer(|reference: &TypeReference| match reference.args {
+            None => substitutions.get(&reference.name.to_string()).cloned(),
+            Some(_) => None,
+        })
+        .visit_ts_type_mut(&mut body);
+        Some(body)
+    }
+}
+
+/// Replaces the type references for which the closure returns a type
+struct ReferenceReplacer<F>(F);
+
+impl<F: FnMut(&TypeReference) -> Option<TsType>> VisitMut for ReferenceReplacer<F> {
+    fn visit_ts_type_mut(&mut self, node: &mut TsType) {
+        if let TsType::PrimaryType(PrimaryType::TypeReference(reference)) = node {
+            if let Some(ty) = (self.0)(reference) {
+                *node = match ty {
+                    TsType::UnionType(_) | TsType::IntersectionType(_) => {
+                        TsType::ParenthesizedType(ParenthesizedType {
+                            inner: Box::new(ty),
+                        })
+                    }
+                    ty => ty,
+                };
+                return;
+            }
+        }
+        visit_mut::visit_ts_type_mut(self, node)
+    }
+
+    fn visit_primary_type_mut(&mut self, node: &mut PrimaryType) {
+        if let PrimaryType::ArrayType(array) = node {
+            let mut inner = TsType::PrimaryType(array.inner_type.as_ref().clone());
+            self.visit_ts_type_mut(&mut inner);
+            // `(A | B)[]` is written `Array<A | B>`, as the array type only holds a primary type
+            let inner = match inner {
+                TsType::PrimaryType(primary) => {
+                    *array.inner_type = primary;
+                    return;
+                }
+                TsType::ParenthesizedType(parenthesized) => *parenthesized.inner,
+                ty => ty,
+            };
+            *node = PrimaryType::TypeReference(TypeReference {
+                name: TSIdent::from_str("Array").expect("Array is a valid identifier"),
+                args: Some(TypeArguments { types: vec![inner] }),
+            });
+            return;
+        }
+        visit_mut::visit_primary_type_mut(self, node)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{tests::launch_test_module, ModuleStepResult},
+    };
+
+    fn export_all(exporter: &mut InlineExporter, result: ModuleStepResult) {
+        for child in result.children {
+            export_all(exporter, child);
+        }
+        exporter.export_module(result.data).unwrap();
+    }
+
+    #[test]
+    fn should_inline_referenced_types() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::{Page, Role};
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub enum Role { Admin, User }
+                #[derive(Serialize)]
+                pub struct Page<T> { items: Vec<T>, total: u32 }
+            }
+
+            #[derive(Serialize)]
+            pub struct Tree { children: Vec<Tree> }
+
+            #[derive(Serialize)]
+            pub struct Session { roles: Page<Role>, tree: Tree }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = InlineExporter::new("my_crate");
+        export_all(&mut exporter, result);
+
+        let rendered = exporter.render().unwrap();
+        assert_eq!(
+            rendered,
+            "export type Role = \"Admin\" | \"User\";\n\
+             export interface Page<T> {\n\titems: T[],\n\ttotal: number\n}\n\
+             export interface Tree {\n\tchildren: Tree[]\n}\n\
+             export interface Session {\n\troles: {\n\titems: Array<\"Admin\" | \"User\">,\n\ttotal: number\n},\n\
+             \ttree: {\n\tchildren: Tree[]\n}\n}\n"
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::{io::Write, path::Path, str::FromStr};
+
+use indexmap::IndexMap;
+use ts_json_subset::{
+    declarations::namespace::{NamespaceDeclaration, NamespaceElement},
+    export::ExportStatement,
+    ident::TSIdent,
+    import::ImportAliasDeclaration,
+};
+
+use super::{
+    formatter::{format_file, lay_out},
+    output_options::OutputOptions,
+    Exporter,
+};
+use crate::{
+    error::TsExportError, pipeline::module_step::ModuleStepResultData,
+    utils::display_path::DisplayPath,
+};
+
+/// A strategy that merges the modules of several crates (e.g. the members of a workspace) into a single TS file.
+///
+/// Each crate is exported in its own namespace, and each module in a nested namespace :
+///
+/// ```text
+/// export namespace my_crate {
+/// export namespace models {
+/// import Id = other_crate.Id;
+/// export interface User { ... }
+/// }
+/// }
+/// ```
+///
+/// References to other modules and crates become import aliases to the namespace that declares them.
+///
+/// Run one [Pipeline](crate::pipeline::Pipeline) per crate with the exporter given by [MergedExporter::crate_exporter],
+/// then call [MergedExporter::render] or [MergedExporter::write_to].
+pub struct MergedExporter {
+    crates: IndexMap<String, Vec<ModuleStepResultData>>,
+    output: OutputOptions,
+}
+
+impl Default for MergedExporter {
+    fn default() -> Self {
+        MergedExporter {
+            crates: IndexMap::new(),
+            output: OutputOptions::without_header(),
+        }
+    }
+}
+
+/// The exporter of a single crate, see [MergedExporter]
+pub struct CrateExporter<'a> {
+    crate_name: String,
+    merged: &'a mut MergedExporter,
+}
+
+impl MergedExporter {
+    /// How the file is formatted and commented, without a header comment by default (`{module}` being the names of the crates).
+    /// The import style is ignored, the crates referencing each other through import aliases.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.output = output;
+    }
+
+    pub fn output_options(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// Creates the exporter of a crate, `crate_name` being the name used in Rust paths
+    pub fn crate_exporter<S: Into<String>>(&mut self, crate_name: S) -> CrateExporter<'_> {
+        let crate_name = crate_name.into();
+        self.crates.entry(crate_name.clone()).or_default();
+        CrateExporter {
+            crate_name,
+            merged: self,
+        }
+    }
+
+    /// Renders the merged output of all the crates exported so far
+    pub fn render(&self) -> Result<String, TsExportError> {
+        let crates = &self.crates;
+        let statements = crates
+            .iter()
+            .map(|(crate_name, modules)| {
+                let mut root = NamespaceNode::default();
+                for module in modules {
+                    let node = module.path.segments.iter().fold(&mut root, |node, segm| {
+                        node.children.entry(segm.ident.to_string()).or_default()
+                    });
+                    node.elements.extend(import_aliases(module, crates)?);
+                    node.elements
+                        .extend(module.exports.iter().cloned().map(NamespaceElement::from));
+                }
+                root.into_namespace(crate_name)
+            })
+            .collect::<Result<Vec<ExportStatement>, TsExportError>>()?;
+
+        let contents: String = statements
+            .into_iter()
+            .map(|statement| format!("{}\n", statement))
+            .collect();
+        let contents = lay_out(&self.output.format_options, contents);
+        let crate_names: Vec<&str> = crates.keys().map(String::as_str).collect();
+        Ok(self
+            .output
+            .header_comment
+            .prepend_to(&crate_names.join(", "), contents))
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting merged crates at {:?}", path);
+        let contents = self.render()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        drop(file);
+        format_file(&self.output.formatter, path)
+    }
+}
+
+impl Exporter for CrateExporter<'_> {
+    type Error = TsExportError;
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+        log::info!(
+            "Merging module {} of crate {}",
+            DisplayPath(&process_result.path),
+            self.crate_name
+        );
+        self.merged
+            .crates
+            .entry(self.crate_name.clone())
+            .or_default()
+            .push(process_result);
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct NamespaceNode {
+    elements: Vec<NamespaceElement>,
+    children: IndexMap<String, NamespaceNode>,
+}
+
+impl NamespaceNode {
+    fn into_namespace(self, name: &str) -> Result<ExportStatement, TsExportError> {
+        let NamespaceNode { elements, children } = self;
+        let children = children
+            .into_iter()
+            .map(|(name, child)| child.into_namespace(&name).map(NamespaceElement::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ExportStatement::NamespaceDeclaration(
+            NamespaceDeclaration {
+                qualifier: Vec::new(),
+                ident: TSIdent::from_str(name)?,
+                elements: elements.into_iter().chain(children).collect(),
+            },
+        ))
+    }
+}
+
+/// Turns the imports of a module into aliases to the namespaces of the merged crates
+fn import_aliases(
+    module: &ModuleStepResultData,
+    crates: &IndexMap<String, Vec<ModuleStepResultData>>,
+) -> Result<Vec<NamespaceElement>, TsExportError> {
+    module
+        .import_entries
+        .iter()
+        .filter(|entry| {
+            let crate_name = entry.path.split("::").next().unwrap_or_default();
+            let found = crates.contains_key(crate_name);
+            if !found {
+                log::warn!(
+                    "{}::{} does not belong to a merged crate, it cannot be imported",
+                    entry.path,
+                    entry.ident
+                );
+            }
+            found
+        })
+        .map(|entry| {
+            let entity = entry
+                .path
+                .split("::")
+                .chain(Some(entry.ident.as_str()))
+                .map(TSIdent::from_str)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(NamespaceElement::ImportAliasDeclaration(
+                ImportAliasDeclaration {
+                    ident: TSIdent::from_str(&entry.ident)?,
+                    entity,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        pipeline::module_step::{tests::launch_test_step, ModuleStep},
+    };
+
+    fn export_crate(merged: &mut MergedExporter, crate_name: &str, source: &str) {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let items = syn::parse_file(source).expect("Failed to parse").items;
+        let path = syn::Path {
+            leading_colon: None,
+            segments: Default::default(),
+        };
+        let result = launch_test_step(
+            ModuleStep::new(path, items, crate_name),
+            &solving_context,
+            &ExporterOptions::default(),
+        )
+        .expect("Failed to launch");
+        let mut exporter = merged.crate_exporter(crate_name);
+        for child in result.children {
+            exporter.export_module(child.data).unwrap();
+        }
+        exporter.export_module(result.data).unwrap();
+    }
+
+    #[test]
+    fn should_merge_crates_in_namespaces() {
+        let mut merged = MergedExporter::default();
+        export_crate(
+            &mut merged,
+            "crate_a",
+            r#"
+            #[derive(Serialize)]
+            pub struct Id(u32);
+            "#,
+        );
+        export_crate(
+            &mut merged,
+            "crate_b",
+            r#"
+            pub mod models {
+                use crate_a::Id;
+                #[derive(Serialize)]
+                pub struct User { id: Id }
+            }
+            "#,
+        );
+        assert_eq!(
+            merged.render().unwrap(),
+            "export namespace crate_a {\nexport type Id = number;\n}\n\
+             export namespace crate_b {\nexport namespace models {\nimport Id = crate_a.Id;\n\
+             export interface User {\n\tid: Id\n}\n}\n}\n"
+        );
+    }
+}
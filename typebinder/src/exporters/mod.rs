@@ -1,12 +1,40 @@
 //! How to output your bindings
 use crate::{error::TsExportError, pipeline::module_step::ModuleStepResultData};
 
+pub mod barrel;
+pub mod bundle;
+pub mod check;
+pub mod examples;
 pub mod file;
+pub mod formatter;
+pub mod hash_manifest;
+pub mod import_style;
+pub mod inline;
+pub mod merged;
+pub mod namespaces;
+pub mod npm_package;
+pub mod output_options;
 pub mod stdout;
+pub mod symbol_map;
+pub mod type_tests;
 
 /// An abstraction that specifies the behaviour of how to handle a resulting process' data
 pub trait Exporter {
     type Error: Into<TsExportError>;
 
-    fn export_module(&self, process_result: ModuleStepResultData) -> Result<(), Self::Error>;
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), Self::Error>;
+
+    /// Called by [Pipeline::launch](crate::pipeline::Pipeline::launch) before exporting the modules of a run,
+    /// so that an exporter launched again can forget what the previous run exported
+    fn start_run(&mut self) {}
+
+    /// Called by [Pipeline::launch](crate::pipeline::Pipeline::launch) after [Exporter::start_run], with the paths of all
+    /// the modules about to be exported, so that an exporter can name the files of the modules after the modules they contain
+    fn plan_modules(&mut self, _module_paths: &[Vec<String>]) {}
+
+    /// Called by [Pipeline::launch](crate::pipeline::Pipeline::launch) once all the modules of a run are exported,
+    /// so that an exporter that buffers the modules can write its output
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
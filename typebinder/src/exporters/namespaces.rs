@@ -0,0 +1,243 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+use ts_json_subset::{
+    declarations::namespace::{NamespaceDeclaration, NamespaceElement},
+    export::ExportStatement,
+    ident::TSIdent,
+    import::{ImportAliasDeclaration, ImportStatement},
+};
+
+use super::{
+    bundle::is_local_import,
+    formatter::{format_file, lay_out},
+    output_options::OutputOptions,
+    Exporter,
+};
+use crate::{
+    error::TsExportError,
+    pipeline::module_step::ModuleStepResultData,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+    },
+};
+
+/// A strategy that writes all the modules of a crate into a single TS file, each module in a namespace named after its path :
+///
+/// ```text
+/// export namespace api.models {
+/// import Id = api.Id;
+/// export interface User { ... }
+/// }
+/// ```
+///
+/// The declarations of the root module are written at the top level, after the namespaces.
+/// The references to the other modules of the crate become import aliases, and the imports of other crates are kept.
+pub struct NamespaceExporter {
+    crate_name: String,
+    modules: Vec<ModuleStepResultData>,
+    output: OutputOptions,
+}
+
+impl NamespaceExporter {
+    /// `crate_name` is the name used in the Rust paths of the crate, see [RustModuleReader::crate_name](crate::step_spawner::mod_reader::RustModuleReader::crate_name)
+    pub fn new<S: Into<String>>(crate_name: S) -> Self {
+        NamespaceExporter {
+            crate_name: crate_name.into(),
+            modules: Vec::new(),
+            output: OutputOptions::without_header(),
+        }
+    }
+
+    /// How the file is formatted and commented, without a header comment by default (`{module}` being the name of the crate).
+    /// The import style applies to the imports of the other crates.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.output = output;
+    }
+
+    pub fn output_options(&self) -> &OutputOptions {
+        &self.output
+    }
+
+    /// Renders all the modules exported so far
+    pub fn render(&self) -> Result<String, TsExportError> {
+        let modules = &self.modules;
+
+        let mut imports: Vec<ImportStatement> = Vec::new();
+        let mut value_imports: BTreeSet<String> = BTreeSet::new();
+        let mut namespaces: Vec<ExportStatement> = Vec::new();
+        let mut root: Vec<String> = Vec::new();
+        for module in modules.iter() {
+            let module_path = module_segments(&module.path);
+            value_imports.extend(module.value_imports.iter().cloned());
+            let mut local_imports: HashSet<String> = HashSet::new();
+            let mut aliases: Vec<ImportAliasDeclaration> = Vec::new();
+            for entry in module.import_entries.iter() {
+                let declaring_module =
+                    match resolve_local_path(entry, &module_path, &self.crate_name) {
+                        Some(declaring_module) => declaring_module,
+                        None => continue,
+                    };
+                local_imports.insert(entry.ident.clone());
+                // The declarations of the root module are in the scope of every namespace
+                if declaring_module.is_empty() {
+                    continue;
+                }
+                aliases.push(ImportAliasDeclaration {
+                    ident: TSIdent::from_str(&entry.ident)?,
+                    entity: declaring_module
+                        .iter()
+                        .chain(Some(&entry.ident))
+                        .map(|segment| TSIdent::from_str(segment))
+                        .collect::<Result<_, _>>()?,
+                });
+            }
+            for import in module.imports.iter() {
+                if !is_local_import(import, &local_imports) && !imports.contains(import) {
+                    imports.push(import.clone());
+                }
+            }
+
+            let exports = module.exports.iter().filter(|export| {
+                let reexport = matches!(export, ExportStatement::ReexportDeclaration(_));
+                if reexport {
+                    log::debug!(
+                        "Dropping a re-export of module {} from the namespaces",
+                        DisplayPath(&module.path)
+                    );
+                }
+                !reexport
+            });
+            match module_path.split_last() {
+                None => root.extend(
+                    aliases
+                        .iter()
+                        .map(ToString::to_string)
+                        .chain(exports.map(ToString::to_string)),
+                ),
+                Some((ident, qualifier)) => {
+                    let elements: Vec<NamespaceElement> = aliases
+                        .into_iter()
+                        .map(NamespaceElement::from)
+                        .chain(exports.cloned().map(NamespaceElement::from))
+                        .collect();
+                    if elements.is_empty() {
+                        continue;
+                    }
+                    namespaces.push(ExportStatement::NamespaceDeclaration(
+                        NamespaceDeclaration {
+                            qualifier: qualifier
+                                .iter()
+                                .map(|segment| TSIdent::from_str(segment))
+                                .collect::<Result<_, _>>()?,
+                            ident: TSIdent::from_str(ident)?,
+                            elements,
+                        },
+                    ));
+                }
+            }
+        }
+
+        let imports = self
+            .output
+            .import_style
+            .style_imports(imports, &value_imports);
+        let contents = imports
+            .iter()
+            .map(ToString::to_string)
+            .chain(namespaces.iter().map(ToString::to_string))
+            .chain(root)
+            .map(|statement| format!("{}\n", statement))
+            .collect();
+        let contents = lay_out(&self.output.format_options, contents);
+        Ok(self
+            .output
+            .header_comment
+            .prepend_to(&self.crate_name, contents))
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting namespaces at {:?}", path);
+        let contents = self.render()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        drop(file);
+        format_file(&self.output.formatter, path)
+    }
+}
+
+impl Exporter for NamespaceExporter {
+    type Error = TsExportError;
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+        log::info!(
+            "Adding module {} to the namespaces",
+            DisplayPath(&process_result.path)
+        );
+        self.modules.push(process_result);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{tests::launch_test_module, ModuleStepResult},
+    };
+
+    fn export_all(exporter: &mut NamespaceExporter, result: ModuleStepResult) {
+        for child in result.children {
+            export_all(exporter, child);
+        }
+        exporter.export_module(result.data).unwrap();
+    }
+
+    #[test]
+    fn should_export_the_modules_in_namespaces() {
+        let result = launch_test_module(
+            r#"
+            use crate::api::models::User;
+
+            pub mod api {
+                pub mod models {
+                    use crate::Id;
+                    use other_crate::Avatar;
+
+                    #[derive(Serialize)]
+                    pub struct User { id: Id, avatar: Avatar }
+                }
+            }
+
+            #[derive(Serialize)]
+            pub struct Id(u32);
+
+            #[derive(Serialize)]
+            pub struct Session { user: User }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut exporter = NamespaceExporter::new("my_crate");
+        export_all(&mut exporter, result);
+
+        assert_eq!(
+            exporter.render().unwrap(),
+            "import { Avatar } from \"other_crate\";\n\
+             export namespace api.models {\n\
+             export interface User {\n\tid: Id,\n\tavatar: Avatar\n}\n}\n\
+             import User = api.models.User;\n\
+             export type Id = number;\n\
+             export interface Session {\n\tuser: User\n}\n"
+        );
+    }
+}
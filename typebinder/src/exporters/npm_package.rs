@@ -0,0 +1,244 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use super::{
+    file::FileExporter, formatter::format_file, import_style::ImportStyle,
+    output_options::OutputOptions, Exporter,
+};
+use crate::{error::TsExportError, pipeline::module_step::ModuleStepResultData};
+
+/// The file name of the root module, `index.ts` being the barrel of the package
+const ROOT_MODULE: &str = "lib";
+
+/// The identity of the generated npm package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpmPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/// A strategy that writes the modules like the [FileExporter], and scaffolds a publishable npm package around them :
+/// * a `package.json`, whose `build` script compiles the package with its declarations to `dist`,
+/// * a `tsconfig.json` for that build,
+/// * an `index.ts` barrel that re-exports the root module, and the other modules as namespaces
+///   (e.g. `export * as models_user from "./models/user";`).
+///
+/// The root module is written to `lib.ts`, and the package files are written once the run is finished.
+pub struct NpmPackageExporter {
+    files: FileExporter,
+    package: NpmPackage,
+    modules: Vec<syn::Path>,
+}
+
+impl NpmPackageExporter {
+    pub fn new(path: PathBuf, package: NpmPackage) -> Self {
+        let mut files = FileExporter::new(path);
+        files.set_default_module_name(Path::new(ROOT_MODULE));
+        NpmPackageExporter {
+            files,
+            package,
+            modules: Vec::new(),
+        }
+    }
+
+    /// The exporter that writes the modules
+    pub fn file_exporter(&self) -> &FileExporter {
+        &self.files
+    }
+
+    /// How each module is written, see [FileExporter::set_output_options].
+    /// The formatter and the import style also apply to the `index.ts` barrel.
+    pub fn set_output_options(&mut self, output: OutputOptions) {
+        self.files.set_output_options(output);
+    }
+
+    /// Writes the `package.json`, `tsconfig.json` and `index.ts` files of the package, called by [Exporter::finish]
+    pub fn write_package(&self) -> Result<(), TsExportError> {
+        let root_path = self.files.root_path();
+        let output = self.files.output_options();
+        log::info!(
+            "Outputting npm package {} at {:?}",
+            self.package.name,
+            root_path
+        );
+        std::fs::create_dir_all(root_path)?;
+        write_json(&root_path.join("package.json"), &self.package_json())?;
+        write_json(&root_path.join("tsconfig.json"), &tsconfig_json())?;
+        let barrel_path = root_path.join("index.ts");
+        let mut barrel = std::fs::File::create(&barrel_path)?;
+        barrel.write_all(render_barrel(&self.modules, &output.import_style).as_bytes())?;
+        drop(barrel);
+        format_file(&output.formatter, &barrel_path)
+    }
+
+    fn package_json(&self) -> PackageJson {
+        PackageJson {
+            name: self.package.name.clone(),
+            version: self.package.version.clone(),
+            main: "dist/index.js",
+            types: "dist/index.d.ts",
+            files: vec!["dist"],
+            scripts: vec![("build", "tsc"), ("prepublishOnly", "npm run build")]
+                .into_iter()
+                .collect(),
+            dev_dependencies: vec![("typescript", "^4.0.0")].into_iter().collect(),
+        }
+    }
+}
+
+impl Exporter for NpmPackageExporter {
+    type Error = TsExportError;
+
+    fn start_run(&mut self) {
+        self.modules.clear();
+        self.files.start_run();
+    }
+
+    fn plan_modules(&mut self, module_paths: &[Vec<String>]) {
+        self.files.plan_modules(module_paths);
+    }
+
+    fn export_module(&mut self, process_result: ModuleStepResultData) -> Result<(), TsExportError> {
+        self.modules.push(process_result.path.clone());
+        self.files.export_module(process_result)
+    }
+
+    fn finish(&mut self) -> Result<(), TsExportError> {
+        self.files.finish()?;
+        self.write_package()
+    }
+}
+
+/// The `package.json` of the package, its keys written in the usual order
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    name: String,
+    version: String,
+    main: &'static str,
+    types: &'static str,
+    files: Vec<&'static str>,
+    scripts: IndexMap<&'static str, &'static str>,
+    dev_dependencies: IndexMap<&'static str, &'static str>,
+}
+
+/// The `tsconfig.json` that builds the package with its declarations
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TsConfigJson {
+    compiler_options: CompilerOptions,
+    exclude: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompilerOptions {
+    declaration: bool,
+    out_dir: &'static str,
+    module: &'static str,
+    target: &'static str,
+    strict: bool,
+}
+
+fn tsconfig_json() -> TsConfigJson {
+    TsConfigJson {
+        compiler_options: CompilerOptions {
+            declaration: true,
+            out_dir: "dist",
+            module: "commonjs",
+            target: "es2017",
+            strict: true,
+        },
+        exclude: vec!["dist"],
+    }
+}
+
+fn render_barrel(modules: &[syn::Path], import_style: &ImportStyle) -> String {
+    modules
+        .iter()
+        .map(|path| {
+            if path.segments.is_empty() {
+                format!(
+                    "export * from \"{}\";\n",
+                    import_style.file_specifier(&format!("./{}", ROOT_MODULE))
+                )
+            } else {
+                let segments: Vec<String> = path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect();
+                format!(
+                    "export * as {} from \"{}\";\n",
+                    segments.join("_"),
+                    import_style.file_specifier(&format!("./{}", segments.join("/")))
+                )
+            }
+        })
+        .collect()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), TsExportError> {
+    log::debug!("Writing {:?}", path);
+    let mut file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(&mut file, value)?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::exporters::import_style::ImportExtension;
+
+    #[test]
+    fn should_render_barrel() {
+        let modules: Vec<syn::Path> = vec![
+            syn::parse_str("models::user").unwrap(),
+            syn::parse_str("models").unwrap(),
+            syn::Path {
+                leading_colon: None,
+                segments: Default::default(),
+            },
+        ];
+        assert_eq!(
+            render_barrel(&modules, &ImportStyle::default()),
+            "export * as models_user from \"./models/user\";\n\
+             export * as models from \"./models\";\n\
+             export * from \"./lib\";\n"
+        );
+        let import_style = ImportStyle {
+            extension: ImportExtension::Js,
+            ..ImportStyle::default()
+        };
+        assert_eq!(
+            render_barrel(&modules[..1], &import_style),
+            "export * as models_user from \"./models/user.js\";\n"
+        );
+    }
+
+    #[test]
+    fn should_describe_package() {
+        let exporter = NpmPackageExporter::new(
+            PathBuf::from("types"),
+            NpmPackage {
+                name: "@my-org/api-types".to_string(),
+                version: "1.2.0".to_string(),
+            },
+        );
+        let package_json = exporter.package_json();
+        assert_eq!(
+            serde_json::to_string(&package_json).unwrap(),
+            "{\"name\":\"@my-org/api-types\",\"version\":\"1.2.0\",\"main\":\"dist/index.js\",\
+             \"types\":\"dist/index.d.ts\",\"files\":[\"dist\"],\
+             \"scripts\":{\"build\":\"tsc\",\"prepublishOnly\":\"npm run build\"},\
+             \"devDependencies\":{\"typescript\":\"^4.0.0\"}}"
+        );
+        assert_eq!(exporter.file_exporter().root_module_name(), "lib");
+    }
+}
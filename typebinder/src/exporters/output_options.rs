@@ -0,0 +1,30 @@
+use ts_json_subset::format::FormatOptions;
+
+use super::{file::HeaderComment, formatter::FormatterCommand, import_style::ImportStyle};
+
+/// How the exporters write their TS code, shared by all of them :
+///
+/// * the formatter run on each written file, see [FormatterCommand]
+/// * the layout of the code, in the canonical layout of the declarations by default, see [FormatOptions]
+/// * the comment at the top of each file, see [HeaderComment]
+/// * `import type` and the extensions of the specifiers, see [ImportStyle]
+///
+/// An exporter ignores the options that don't apply to it, e.g. the import style of the [InlineExporter](super::inline::InlineExporter),
+/// which doesn't import anything.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OutputOptions {
+    pub formatter: Option<FormatterCommand>,
+    pub format_options: Option<FormatOptions>,
+    pub header_comment: HeaderComment,
+    pub import_style: ImportStyle,
+}
+
+impl OutputOptions {
+    /// The default options, without a header comment : the default of the exporters that write a single file
+    pub fn without_header() -> Self {
+        OutputOptions {
+            header_comment: HeaderComment::None,
+            ..OutputOptions::default()
+        }
+    }
+}
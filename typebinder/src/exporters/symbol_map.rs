@@ -0,0 +1,141 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    error::TsExportError, pipeline::module_step::ModuleStepResultData,
+    utils::display_path::DisplayPath,
+};
+
+/// A map of the Rust paths of the exported types (e.g. `crate::models::User`) to their location in the generated TS files.
+///
+/// Meant to be consumed by other tools, it is serialized as a JSON object :
+///
+/// ```text
+/// {
+///   "crate::models::User": { "file": "models.ts", "name": "User" }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SymbolMap {
+    symbols: BTreeMap<String, SymbolLocation>,
+}
+
+/// Where a Rust type has been exported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolLocation {
+    /// The output file, relative to the output directory
+    pub file: String,
+    /// The name of the exported TS declaration
+    pub name: String,
+}
+
+impl SymbolMap {
+    /// Registers all the declarations exported by a module in `file`
+    pub fn add_module<S: Into<String>>(&mut self, module: &ModuleStepResultData, file: S) {
+        let file = file.into();
+        for ident in module.exports.iter().filter_map(|export| export.ident()) {
+            let name = ident.to_string();
+            self.symbols.insert(
+                rust_path(module, &name),
+                SymbolLocation {
+                    file: file.clone(),
+                    name,
+                },
+            );
+        }
+    }
+
+    pub fn get(&self, rust_path: &str) -> Option<&SymbolLocation> {
+        self.symbols.get(rust_path)
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(&self.symbols).expect("A symbol map is always serializable")
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting symbol map at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(&mut file, &self.symbols)?;
+        file.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// The Rust path of a declaration exported by a module, e.g. `crate::models::User`
+pub(crate) fn rust_path(module: &ModuleStepResultData, name: &str) -> String {
+    let module_path = DisplayPath(&module.path).to_string();
+    let rust_ident = module
+        .type_names
+        .iter()
+        .find(|(_, type_name)| **type_name == name)
+        .map(|(rust_ident, _)| rust_ident.as_str())
+        .unwrap_or(name);
+    if module_path.is_empty() {
+        format!("crate::{}", rust_ident)
+    } else {
+        format!("crate::{}::{}", module_path, rust_ident)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use ts_json_subset::{
+        declarations::{
+            reexport::{ReexportClause, ReexportDeclaration},
+            type_alias::TypeAliasDeclaration,
+        },
+        ident::TSIdent,
+        types::{PredefinedType, PrimaryType, TsType},
+    };
+
+    fn type_alias(ident: &str) -> ts_json_subset::export::ExportStatement {
+        TypeAliasDeclaration {
+            ident: TSIdent::from_str(ident).unwrap(),
+            type_params: None,
+            inner_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Number)),
+            doc: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn should_map_rust_paths_to_declarations() {
+        let reexport = ReexportDeclaration {
+            reexports: vec![ReexportClause {
+                scope: TSIdent::from_str("User").unwrap(),
+                export_as: TSIdent::from_str("User").unwrap(),
+            }],
+        };
+        let module = ModuleStepResultData {
+            exports: vec![type_alias("UserId"), reexport.into()],
+            imports: Vec::new(),
+            import_entries: Vec::new(),
+            path: syn::parse_str("models::user").unwrap(),
+            crate_name: "my_crate".to_string(),
+            type_names: Default::default(),
+            phantom_parameters: Default::default(),
+            filtered_out: Vec::new(),
+            globs: Default::default(),
+            reexports: Vec::new(),
+            value_imports: Default::default(),
+        };
+        let mut symbol_map = SymbolMap::default();
+        symbol_map.add_module(&module, "models/user.ts");
+
+        assert_eq!(
+            symbol_map.to_json(),
+            serde_json::json!({
+                "crate::models::user::UserId": { "file": "models/user.ts", "name": "UserId" }
+            })
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use std::{io::Write, path::Path, str::FromStr};
+
+use ts_json_subset::{
+    common::StringLiteral,
+    declarations::{const_enum::ConstEnumDeclaration, enumeration::EnumDeclaration},
+    export::ExportStatement,
+    ident::TSIdent,
+    import::{ImportKind, ImportStatement},
+    types::{PrimaryType, PropertyName, TsType, TypeMember, TypeParameters},
+};
+
+use super::import_style::ImportStyle;
+use crate::{error::TsExportError, pipeline::module_step::ModuleStepResultData};
+
+/// The name of the file of the type tests, in the output directory
+pub const TYPE_TESTS_FILE: &str = "__typetests.ts";
+
+const HEADER: &str = "\
+// This file was auto-generated with typebinder, it asserts the structure of the generated types.
+// Keep it when regenerating the types : the type checker fails if their structure changes.
+";
+
+const HELPERS: &str = "\
+type Equal<A, B> = (<T>() => T extends A ? 1 : 2) extends (<T>() => T extends B ? 1 : 2) ? true : false;
+type OptionalKeys<T> = { [K in keyof T]-?: {} extends Pick<T, K> ? K : never }[keyof T];
+function expectType<T extends true>(): void {}
+";
+
+/// The names declared by the helpers, which the namespaces can't take
+const HELPER_NAMES: [&str; 3] = ["Equal", "OptionalKeys", "expectType"];
+
+/// Type-level regression tests of the exported declarations, in the style of `tsd`'s `expectType`.
+///
+/// Each module is imported as a namespace, and the structural facts of its declarations are asserted :
+///
+/// ```text
+/// import * as models_user from "./models/user";
+///
+/// expectType<Equal<keyof models_user.User, "name" | "role">>();
+/// expectType<Equal<OptionalKeys<models_user.User>, never>>();
+/// expectType<Equal<models_user.User["name"], string>>();
+/// expectType<Equal<models_user.Role["type"], "Admin" | "Member">>();
+/// ```
+///
+/// * the keys and the optional keys of the interfaces,
+/// * the types of the properties that don't reference other types,
+/// * the tags of the unions of objects, the members of the unions of literals,
+/// * the members of the const enums.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypeTests {
+    imports: Vec<ImportStatement>,
+    assertions: Vec<String>,
+    import_style: ImportStyle,
+}
+
+impl TypeTests {
+    /// Appends the extension of the style to the specifiers of the modules
+    pub fn set_import_style(&mut self, import_style: ImportStyle) {
+        self.import_style = import_style;
+    }
+
+    /// Registers the declarations exported by a module in `file`, relatively to the output directory
+    pub fn add_module(
+        &mut self,
+        module: &ModuleStepResultData,
+        file: &str,
+    ) -> Result<(), TsExportError> {
+        let file = file
+            .strip_suffix(".d.ts")
+            .or_else(|| file.strip_suffix(".ts"))
+            .unwrap_or(file);
+        let namespace = self.namespace(file)?;
+        let assertions: Vec<String> = module
+            .exports
+            .iter()
+            .flat_map(|export| assert_declaration(&namespace, export))
+            .collect();
+        if assertions.is_empty() {
+            return Ok(());
+        }
+        self.imports.push(ImportStatement {
+            import_kind: ImportKind::GlobAsIdentifier(namespace),
+            path: StringLiteral::from(self.import_style.file_specifier(&format!("./{}", file)))
+                .to_string(),
+            type_only: false,
+        });
+        self.assertions.extend(assertions);
+        Ok(())
+    }
+
+    /// The namespace the module in `file` is imported as, `models/user` being imported as `models_user`.
+    ///
+    /// The names that are not identifiers are prefixed with `_` (`_2fa`, `_new`), and the names already taken
+    /// by another module are suffixed with a number (`models_user_2` for `models_user.ts` next to `models/user.ts`).
+    fn namespace(&self, file: &str) -> Result<TSIdent, TsExportError> {
+        let mut name = file.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        if name.starts_with(|c: char| c.is_ascii_digit()) || TSIdent::from_str(&name).is_err() {
+            name.insert(0, '_');
+        }
+        let taken = |candidate: &str| {
+            HELPER_NAMES.contains(&candidate)
+                || self.imports.iter().any(|import| {
+                    matches!(&import.import_kind, ImportKind::GlobAsIdentifier(namespace) if namespace.to_string() == candidate)
+                })
+        };
+        let mut candidate = name.clone();
+        let mut suffix = 2;
+        while taken(&candidate) {
+            candidate = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+        Ok(TSIdent::from_str(&candidate)?)
+    }
+
+    pub fn render(&self) -> String {
+        let imports: String = self
+            .imports
+            .iter()
+            .map(|import| format!("{}\n", import))
+            .collect();
+        let assertions: String = self
+            .assertions
+            .iter()
+            .map(|assertion| format!("expectType<{}>();\n", assertion))
+            .collect();
+        format!("{}\n{}\n{}\n{}", HEADER, imports, HELPERS, assertions)
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting type tests at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(self.render().as_bytes())?;
+        Ok(())
+    }
+}
+
+fn assert_declaration(namespace: &TSIdent, export: &ExportStatement) -> Vec<String> {
+    match export {
+        ExportStatement::InterfaceDeclaration(declaration) => {
+            let ty = qualified(namespace, &declaration.ident, &declaration.type_params);
+            let properties: Vec<_> = declaration
+                .obj_type
+                .body
+                .members
+                .iter()
+                .filter_map(|member| match member {
+                    TypeMember::PropertySignature(property) => Some(property),
+                    TypeMember::IndexSignature(_) => None,
+                })
+                .collect();
+            let keys = literal_union(properties.iter().map(|property| key(&property.name)));
+            let optional_keys = literal_union(
+                properties
+                    .iter()
+                    .filter(|property| property.optional)
+                    .map(|property| key(&property.name)),
+            );
+            let mut assertions = vec![
+                format!("Equal<keyof {}, {}>", ty, keys),
+                format!("Equal<OptionalKeys<{}>, {}>", ty, optional_keys),
+            ];
+            assertions.extend(
+                properties
+                    .iter()
+                    .filter(|property| {
+                        !property.optional && is_self_contained(&property.inner_type)
+                    })
+                    .map(|property| {
+                        format!(
+                            "Equal<{}[{}], {}>",
+                            ty,
+                            key(&property.name),
+                            property.inner_type
+                        )
+                    }),
+            );
+            assertions
+        }
+        ExportStatement::TypeAliasDeclaration(declaration) => {
+            let ty = qualified(namespace, &declaration.ident, &declaration.type_params);
+            match (&declaration.inner_type, tag(&declaration.inner_type)) {
+                (_, Some((tag, values))) => vec![format!(
+                    "Equal<{}[{}], {}>",
+                    ty,
+                    tag,
+                    literal_union(values.into_iter())
+                )],
+                (inner, None) if is_self_contained(inner) => {
+                    vec![format!("Equal<{}, {}>", ty, inner)]
+                }
+                _ => Vec::new(),
+            }
+        }
+        ExportStatement::EnumDeclaration(EnumDeclaration { ident, body, .. })
+        | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, body }) => {
+            let members = literal_union(
+                body.variants
+                    .iter()
+                    .map(|variant| StringLiteral::from(variant.ident.to_string()).to_string()),
+            );
+            vec![format!(
+                "Equal<keyof typeof {}.{}, {}>",
+                namespace, ident, members
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The reference to a declaration of a module, its type parameters being `any`
+fn qualified(namespace: &TSIdent, ident: &TSIdent, type_params: &Option<TypeParameters>) -> String {
+    let args = match type_params {
+        Some(params) if !params.parameters.is_empty() => {
+            format!("<{}>", vec!["any"; params.parameters.len()].join(", "))
+        }
+        _ => String::new(),
+    };
+    format!("{}.{}{}", namespace, ident, args)
+}
+
+/// The name of a property as a string literal type
+fn key(name: &PropertyName) -> String {
+    match name {
+        PropertyName::Identifier(ident) => StringLiteral::from(ident.to_string()).to_string(),
+        PropertyName::StringLiteral(literal) => literal.to_string(),
+    }
+}
+
+fn literal_union<I: Iterator<Item = String>>(literals: I) -> String {
+    let literals: Vec<String> = literals.collect();
+    if literals.is_empty() {
+        "never".to_string()
+    } else {
+        literals.join(" | ")
+    }
+}
+
+/// The property that holds a literal in each object of a union, and its values
+fn tag(ty: &TsType) -> Option<(String, Vec<String>)> {
+    let union = match ty {
+        TsType::UnionType(union) => union,
+        _ => return None,
+    };
+    let objects: Vec<Vec<(String, String)>> = union.types.iter().map(literal_properties).collect();
+    let (first, others) = objects.split_first()?;
+    if others.is_empty() {
+        return None;
+    }
+    first.iter().find_map(|(name, _)| {
+        let values = objects
+            .iter()
+            .map(|properties| {
+                properties
+                    .iter()
+                    .find(|(other, _)| other == name)
+                    .map(|(_, value)| value.clone())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((name.clone(), values))
+    })
+}
+
+/// The properties of an object (or of the objects of an intersection) whose type is a literal
+fn literal_properties(ty: &TsType) -> Vec<(String, String)> {
+    match ty {
+        TsType::PrimaryType(PrimaryType::ObjectType(object)) => object
+            .body
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                TypeMember::PropertySignature(property) if !property.optional => {
+                    match &property.inner_type {
+                        TsType::PrimaryType(PrimaryType::LiteralType(literal)) => {
+                            Some((key(&property.name), literal.to_string()))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect(),
+        TsType::IntersectionType(intersection) => intersection
+            .types
+            .iter()
+            .flat_map(literal_properties)
+            .collect(),
+        TsType::ParenthesizedType(parenthesized) => literal_properties(&parenthesized.inner),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a type can be written in the type tests without importing other types
+fn is_self_contained(ty: &TsType) -> bool {
+    match ty {
+        TsType::PrimaryType(primary) => is_primary_self_contained(primary),
+        TsType::UnionType(union) => union.types.iter().all(is_self_contained),
+        TsType::IntersectionType(intersection) => intersection.types.iter().all(is_self_contained),
+        TsType::ParenthesizedType(parenthesized) => is_self_contained(&parenthesized.inner),
+    }
+}
+
+fn is_primary_self_contained(ty: &PrimaryType) -> bool {
+    match ty {
+        PrimaryType::Predefined(_) | PrimaryType::LiteralType(_) => true,
+        PrimaryType::ObjectType(object) => object.body.members.iter().all(|member| match member {
+            TypeMember::PropertySignature(property) => is_self_contained(&property.inner_type),
+            TypeMember::IndexSignature(signature) => {
+                is_self_contained(&signature.key_type) && is_self_contained(&signature.inner_type)
+            }
+        }),
+        PrimaryType::MappedType(mapped) => {
+            is_self_contained(&mapped.keys) && is_self_contained(&mapped.inner_type)
+        }
+        PrimaryType::ArrayType(array) => is_primary_self_contained(&array.inner_type),
+        PrimaryType::TupleType(tuple) => tuple.inner_types.iter().all(is_self_contained),
+        PrimaryType::TypeReference(_) | PrimaryType::RawType(_) => false,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        pipeline::module_step::{tests::launch_test_step, ModuleStep},
+    };
+
+    fn launch(path: &str, source: &str) -> ModuleStepResultData {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let items = syn::parse_file(source).expect("Failed to parse").items;
+        let path = syn::parse_str::<syn::Path>(path).unwrap();
+        launch_test_step(
+            ModuleStep::new(path, items, "my_crate"),
+            &solving_context,
+            &ExporterOptions::default(),
+        )
+        .expect("Failed to launch")
+        .data
+    }
+
+    #[test]
+    fn should_assert_the_structure_of_declarations() {
+        let module = launch(
+            "models::user",
+            r#"
+            #[derive(Serialize)]
+            pub struct User {
+                name: String,
+                #[serde(rename = "last-seen", skip_serializing_if = "Option::is_none")]
+                last_seen: Option<u32>,
+                role: Role,
+            }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Role { Admin { level: u8 }, Member }
+
+            #[derive(Serialize)]
+            pub enum Level { Low, High }
+            "#,
+        );
+        let mut type_tests = TypeTests::default();
+        type_tests.add_module(&module, "models/user.ts").unwrap();
+
+        let rendered = type_tests.render();
+        let body = rendered
+            .strip_prefix(&format!(
+                "{}\nimport * as models_user from \"./models/user\";\n\n{}",
+                HEADER, HELPERS
+            ))
+            .unwrap();
+        assert_eq!(
+            body,
+            "\n\
+             expectType<Equal<keyof models_user.User, \"name\" | \"last-seen\" | \"role\">>();\n\
+             expectType<Equal<OptionalKeys<models_user.User>, \"last-seen\">>();\n\
+             expectType<Equal<models_user.User[\"name\"], string>>();\n\
+             expectType<Equal<models_user.Role[\"type\"], \"Admin\" | \"Member\">>();\n\
+             expectType<Equal<models_user.Level, \"Low\" | \"High\">>();\n"
+        );
+    }
+
+    #[test]
+    fn should_import_each_module_as_its_own_namespace() {
+        let module = launch(
+            "models",
+            "#[derive(Serialize)]\npub struct User { name: String }",
+        );
+        let mut type_tests = TypeTests::default();
+        for file in [
+            "models/user.ts",
+            "models_user.ts",
+            "models/user.d.ts",
+            "2fa.ts",
+            "new.ts",
+        ] {
+            type_tests.add_module(&module, file).unwrap();
+        }
+
+        let namespaces: Vec<String> = type_tests
+            .imports
+            .iter()
+            .map(|import| import.to_string())
+            .collect();
+        assert_eq!(
+            namespaces,
+            vec![
+                "import * as models_user from \"./models/user\";",
+                "import * as models_user_2 from \"./models_user\";",
+                "import * as models_user_3 from \"./models/user\";",
+                "import * as _2fa from \"./2fa\";",
+                "import * as _new from \"./new\";",
+            ]
+        );
+    }
+}
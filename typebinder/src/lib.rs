@@ -8,7 +8,7 @@
 //! `typebinder` resolves Rust types to their TypeScript definition by using the abstraction called [TypeSolver](crate::type_solving::TypeSolver).
 //! A bunch of default solvers are already implemented and cover the types from the standard library. For special purposes, you can also implement your own.
 //!
-use contexts::type_solving::TypeSolvingContextBuilder;
+use contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder};
 use error::TsExportError;
 use exporters::stdout::StdoutExport;
 use macros::context::MacroSolvingContext;
@@ -16,13 +16,17 @@ use path_mapper::PathMapper;
 use pipeline::Pipeline;
 use step_spawner::mod_reader::RustModuleReader;
 
+pub mod attributes;
+pub mod config;
 pub mod contexts;
+pub mod diagnostic;
 pub mod error;
 pub mod exporters;
 pub mod macros;
 pub mod path_mapper;
 pub mod pipeline;
 pub mod step_spawner;
+pub mod tsconfig;
 pub mod type_solving;
 pub mod utils;
 
@@ -43,6 +47,7 @@ pub fn process_file<P: AsRef<Path>>(path: P) -> Result<(), TsExportError> {
         pipeline_step_spawner: RustModuleReader::try_new(path.as_ref().to_path_buf())?,
         exporter: StdoutExport,
         path_mapper: PathMapper::default(),
+        options: ExporterOptions::default(),
     }
     .launch(&solving_context, &macro_context)?;
 
@@ -1,6 +1,6 @@
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, path::Path, str::FromStr};
 
-use crate::error::TsExportError;
+use crate::{config::ConfigFormat, error::TsExportError};
 
 #[derive(Debug, Default)]
 /// A tool that maps a punctuated path (Rust) to a TypeScript module path.
@@ -41,69 +41,270 @@ use crate::error::TsExportError;
 /// * a::models::my::module -> types/models/a/my/module
 /// * b::api -> types/b
 ///
+/// The paths containing a `*` segment are patterns, which map whole module trees with one rule.
+/// A `*` segment matches one segment of the path and a `**` segment any number of them, the matched segments
+/// replacing the `*` (or `**`) of the output in order :
+/// * a::api::* -> ./api/* maps a::api::users to ./api/users
+/// * a::*::models -> types/* maps a::users::models to types/users
+/// * a::internal::** -> ./** maps a::internal::api::users to ./api/users, stripping the prefix
+///
+/// The mapping that matches the most segments of a path applies, an exact mapping taking precedence over a pattern.
+///
+/// The segments taken from the Rust path, like `my` and `module` above, are converted with the [PathCase]
+/// of the mapper, see [PathMapper::set_case].
+///
 /// See the tests for more information
 pub struct PathMapper {
     root: PathMapperNode,
+    patterns: Vec<PatternMapping>,
+    case: PathCase,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The case of the file names of the mapped paths, the Rust modules being snake_case
+pub enum PathCase {
+    /// `user_roles`
+    #[default]
+    Unchanged,
+    /// `user-roles`
+    KebabCase,
+    /// `userRoles`
+    CamelCase,
+    /// `UserRoles`
+    PascalCase,
+}
+
+impl PathCase {
+    pub fn apply(&self, segment: &str) -> String {
+        match self {
+            PathCase::Unchanged => segment.to_string(),
+            PathCase::KebabCase => segment.replace('_', "-"),
+            PathCase::CamelCase | PathCase::PascalCase => {
+                let mut converted = String::new();
+                let mut capitalize = *self == PathCase::PascalCase;
+                for c in segment.chars() {
+                    if c == '_' {
+                        capitalize = !converted.is_empty();
+                    } else if capitalize {
+                        converted.extend(c.to_uppercase());
+                        capitalize = false;
+                    } else {
+                        converted.push(c);
+                    }
+                }
+                converted
+            }
+        }
+    }
+}
+
+impl FromStr for PathCase {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake_case" => Ok(PathCase::Unchanged),
+            "kebab-case" => Ok(PathCase::KebabCase),
+            "camelCase" => Ok(PathCase::CamelCase),
+            "PascalCase" => Ok(PathCase::PascalCase),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown path case {}, expected snake_case, kebab-case, camelCase or PascalCase",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PatternMapping {
+    segments: Vec<String>,
+    output: String,
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct PathMapperNode {
-    mapped_ident: String,
+    mapped_ident: Option<String>,
     children: HashMap<String, PathMapperNode>,
 }
 
 impl PathMapper {
+    /// Maps a path, or the paths matching a pattern when it contains a `*` segment
     pub fn add_mapping<AS: AsRef<str>, S: Into<String>>(&mut self, path: AS, output: S) {
         let path = path.as_ref();
-        self.root.add_mapping(path, output.into())
+        if path
+            .split("::")
+            .any(|segment| segment == "*" || segment == "**")
+        {
+            self.patterns.push(PatternMapping {
+                segments: path.split("::").map(String::from).collect(),
+                output: output.into(),
+            });
+        } else {
+            self.root.add_mapping(path, output.into())
+        }
+    }
+
+    pub fn set_case(&mut self, case: PathCase) {
+        self.case = case;
     }
 
     pub fn get(&self, path: &str) -> Option<String> {
-        self.root.get(path)
+        if path.is_empty() {
+            return None;
+        }
+
+        let segments: Vec<&str> = path.split("::").collect();
+        let exact = self
+            .root
+            .deepest_mapping(&segments)
+            .map(|(mapped_ident, depth)| (mapped_ident.clone(), depth));
+        let pattern = self
+            .patterns
+            .iter()
+            .filter_map(|pattern| pattern.apply(&segments, self.case))
+            .fold(
+                None,
+                |best: Option<(String, usize)>, (mapped, depth)| match best {
+                    Some((_, best_depth)) if best_depth >= depth => best,
+                    _ => Some((mapped, depth)),
+                },
+            );
+        let (mapped, depth) = match (exact, pattern) {
+            (Some(exact), Some(pattern)) if pattern.1 > exact.1 => pattern,
+            (Some(exact), _) => exact,
+            (None, pattern) => pattern?,
+        };
+
+        Some(
+            std::iter::once(mapped)
+                .chain(
+                    segments[depth..]
+                        .iter()
+                        .map(|segment| self.case.apply(segment)),
+                )
+                .collect::<Vec<String>>()
+                .join("/"),
+        )
     }
 
+    /// Loads the mappings of a file, in the format of its extension (see [ConfigFormat]) :
+    ///
+    /// ```text
+    /// { "a::api": "types/a" }
+    /// ```
+    ///
+    /// ```text
+    /// "a::api" = "types/a"
+    /// ```
     pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Self, TsExportError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::of(path)?;
         let content = std::fs::read_to_string(path)?;
-        Self::load_from_string(&content)
+        Self::load_from_str(&content, format)
+    }
+
+    /// Parses the mappings of a file of the given format
+    pub fn load_from_str(input: &str, format: ConfigFormat) -> Result<Self, TsExportError> {
+        let map: HashMap<String, String> = format.parse(input)?;
+        Ok(Self::from_map(map))
     }
 
-    fn load_from_string(input: &str) -> Result<Self, TsExportError> {
-        let map: HashMap<String, String> = serde_json::from_str(input)?;
-        let mut root = PathMapperNode::default();
-        map.into_iter().for_each(|(k, v)| root.add_mapping(&k, v));
-        Ok(PathMapper { root })
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut mapper = PathMapper::default();
+        let mut map: Vec<(String, String)> = map.into_iter().collect();
+        map.sort();
+        map.into_iter().for_each(|(k, v)| mapper.add_mapping(k, v));
+        mapper
+    }
+}
+
+impl PatternMapping {
+    /// The output of the pattern and the number of segments it matches, `None` if the path doesn't start with the pattern
+    fn apply(&self, path: &[&str], case: PathCase) -> Option<(String, usize)> {
+        let mut captures = Vec::new();
+        let depth = match_pattern(&self.segments, path, &mut captures)?;
+
+        let mut captures = captures.into_iter().map(|captured| {
+            captured
+                .iter()
+                .map(|segment| case.apply(segment))
+                .collect::<Vec<String>>()
+                .join("/")
+        });
+        let mut mapped = String::new();
+        let mut output = self.output.as_str();
+        while let Some(index) = output.find('*') {
+            mapped.push_str(&output[..index]);
+            mapped.push_str(&captures.next().unwrap_or_default());
+            output = output[index..].trim_start_matches('*');
+        }
+        mapped.push_str(output);
+        // A `**` matching no segment leaves an empty segment
+        let mapped = mapped.replace("//", "/");
+        Some((mapped.trim_end_matches('/').to_string(), depth))
+    }
+}
+
+/// The number of segments of the path matched by the pattern, pushing the segments matched by each wildcard to `captures`
+fn match_pattern<'a>(
+    pattern: &[String],
+    path: &[&'a str],
+    captures: &mut Vec<Vec<&'a str>>,
+) -> Option<usize> {
+    let (first, rest) = match pattern.split_first() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+    match first.as_str() {
+        "**" => (0..=path.len()).rev().find_map(|matched| {
+            let mut rest_captures = vec![path[..matched].to_vec()];
+            let depth = match_pattern(rest, &path[matched..], &mut rest_captures)?;
+            captures.extend(rest_captures);
+            Some(matched + depth)
+        }),
+        segment => {
+            let (path_first, path_rest) = path.split_first()?;
+            if segment == "*" {
+                captures.push(vec![path_first]);
+            } else if segment != *path_first {
+                return None;
+            }
+            match_pattern(rest, path_rest, captures).map(|depth| depth + 1)
+        }
     }
 }
 
 impl PathMapperNode {
+    /// The mapping of the deepest mapped node of the path, followed by the rest of the path
     pub fn get(&self, path: &str) -> Option<String> {
         if path.is_empty() {
             return None;
         }
 
-        let mut splitted_path = path.split("::");
-        let first = splitted_path.clone().next().expect("Split cannot be empty");
-
-        if !self.children.contains_key(first) {
-            return None;
+        let segments: Vec<&str> = path.split("::").collect();
+        let (mapped_ident, depth) = self.deepest_mapping(&segments)?;
+        let rest = &segments[depth..];
+        if rest.is_empty() {
+            Some(mapped_ident.clone())
+        } else {
+            Some(format!("{}/{}", mapped_ident, rest.join("/")))
         }
-
-        self.get_inner(&mut splitted_path)
     }
 
-    fn get_inner<'a, I: Iterator<Item = &'a str>>(&self, path_iter: &mut I) -> Option<String> {
-        let path = path_iter.next();
-        if let Some(path) = path {
-            if let Some(child) = self.children.get(&path.to_string()) {
-                child.get_inner(path_iter)
-            } else {
-                let rest: Vec<&str> = Some(path).into_iter().chain(path_iter).collect();
-                let rest: String = rest.join("/");
-                Some(format!("{}/{}", self.mapped_ident, rest))
+    /// The mapping of the deepest mapped node of the path, and its depth
+    fn deepest_mapping(&self, segments: &[&str]) -> Option<(&String, usize)> {
+        let mut node = self;
+        let mut mapped = None;
+        for (depth, segment) in segments.iter().enumerate() {
+            node = match node.children.get(*segment) {
+                Some(child) => child,
+                None => break,
+            };
+            if let Some(mapped_ident) = &node.mapped_ident {
+                mapped = Some((mapped_ident, depth + 1));
             }
-        } else {
-            Some(self.mapped_ident.clone())
         }
+        mapped
     }
 
     pub fn add_mapping(&mut self, path: &str, output: String) {
@@ -121,14 +322,14 @@ impl PathMapperNode {
             let entry = self.children.entry(path.to_string()).or_default();
             entry.add_mapping_inner(path_iter, output)
         } else {
-            self.mapped_ident = output;
+            self.mapped_ident = Some(output);
         }
     }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::PathMapper;
+    use super::{ConfigFormat, PathCase, PathMapper};
 
     #[test]
     fn should_add_mapping() {
@@ -148,7 +349,89 @@ pub mod tests {
         assert_eq!(mapper.get("c::a"), None);
     }
 
-    const INPUT: &'static str = r#"{
+    #[test]
+    fn should_not_map_paths_under_unmapped_nodes() {
+        let mut mapper = PathMapper::default();
+        mapper.add_mapping("my_crate::models::user", "types/user");
+        mapper.add_mapping("other_crate", "@other");
+        mapper.add_mapping("other_crate::api::v1", "@other/v1");
+
+        assert_eq!(mapper.get("my_crate"), None);
+        assert_eq!(mapper.get("my_crate::models"), None);
+        assert_eq!(mapper.get("my_crate::models::group"), None);
+        assert_eq!(
+            mapper.get("my_crate::models::user::roles"),
+            Some("types/user/roles".to_string())
+        );
+        assert_eq!(
+            mapper.get("other_crate::api::v2"),
+            Some("@other/api/v2".to_string())
+        );
+        assert_eq!(
+            mapper.get("other_crate::api::v1::users"),
+            Some("@other/v1/users".to_string())
+        );
+    }
+
+    #[test]
+    fn should_map_the_patterns() {
+        let mut mapper = PathMapper::default();
+        mapper.add_mapping("my_crate::api::*", "./api/*");
+        mapper.add_mapping("my_crate::*::models", "types/*");
+        mapper.add_mapping("my_crate::internal::**", "./**");
+        mapper.add_mapping("my_crate::api::admin", "@admin");
+
+        assert_eq!(mapper.get("my_crate::api"), None);
+        assert_eq!(
+            mapper.get("my_crate::api::users"),
+            Some("./api/users".to_string())
+        );
+        assert_eq!(
+            mapper.get("my_crate::api::users::roles"),
+            Some("./api/users/roles".to_string())
+        );
+        assert_eq!(
+            mapper.get("my_crate::events::models::user"),
+            Some("types/events/user".to_string())
+        );
+        assert_eq!(
+            mapper.get("my_crate::internal::db::rows"),
+            Some("./db/rows".to_string())
+        );
+        assert_eq!(mapper.get("my_crate::internal"), Some(".".to_string()));
+        assert_eq!(
+            mapper.get("my_crate::api::admin"),
+            Some("@admin".to_string())
+        );
+        assert_eq!(
+            mapper.get("my_crate::api::admin::models"),
+            Some("@admin/models".to_string())
+        );
+    }
+
+    #[test]
+    fn should_convert_the_case_of_the_path_segments() {
+        let mut mapper = PathMapper::default();
+        mapper.add_mapping("my_crate::api::*", "./api_types/*");
+        mapper.add_mapping("other_crate", "@other/crate_types");
+        mapper.set_case(PathCase::KebabCase);
+
+        assert_eq!(
+            mapper.get("my_crate::api::user_roles::admin_users"),
+            Some("./api_types/user-roles/admin-users".to_string())
+        );
+        assert_eq!(
+            mapper.get("other_crate::user_roles"),
+            Some("@other/crate_types/user-roles".to_string())
+        );
+
+        assert_eq!(PathCase::CamelCase.apply("user_roles"), "userRoles");
+        assert_eq!(PathCase::PascalCase.apply("user_roles"), "UserRoles");
+        assert_eq!(PathCase::CamelCase.apply("_private"), "private");
+        assert_eq!(PathCase::Unchanged.apply("user_roles"), "user_roles");
+    }
+
+    const INPUT: &str = r#"{
         "a::b": "types/a/b",
         "a::b::c": "types/a/b/models/c",
         "b::a": "types/b/a",
@@ -157,7 +440,8 @@ pub mod tests {
 
     #[test]
     fn should_load_from_json() {
-        let mapper = PathMapper::load_from_string(&INPUT).expect("Failed to read PathMapper");
+        let mapper = PathMapper::load_from_str(INPUT, ConfigFormat::Json)
+            .expect("Failed to read PathMapper");
         assert_eq!(mapper.get("a::b"), Some("types/a/b".to_string()));
         assert_eq!(
             mapper.get("a::b::c"),
@@ -167,4 +451,35 @@ pub mod tests {
         assert_eq!(mapper.get(""), None);
         assert_eq!(mapper.get("c::a"), None);
     }
+
+    #[test]
+    fn should_load_from_toml() {
+        let mapper = PathMapper::load_from_str(
+            r#"
+            "a::b" = "types/a/b"
+            "a::b::c" = "types/a/b/models/c"
+            "#,
+            ConfigFormat::Toml,
+        )
+        .expect("Failed to read PathMapper");
+        assert_eq!(mapper.get("a::b::d"), Some("types/a/b/d".to_string()));
+        assert_eq!(
+            mapper.get("a::b::c"),
+            Some("types/a/b/models/c".to_string())
+        );
+    }
+
+    #[test]
+    fn should_load_from_yaml() {
+        let mapper = PathMapper::load_from_str(
+            "\"a::b\": types/a/b\n\"a::b::c\": types/a/b/models/c\n",
+            ConfigFormat::Yaml,
+        )
+        .expect("Failed to read PathMapper");
+        assert_eq!(mapper.get("a::b::d"), Some("types/a/b/d".to_string()));
+        assert_eq!(
+            mapper.get("a::b::c"),
+            Some("types/a/b/models/c".to_string())
+        );
+    }
 }
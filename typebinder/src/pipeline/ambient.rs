@@ -0,0 +1,144 @@
+//! Declarations without values, for the declaration files (`.d.ts`), see [ExporterOptions::declaration_files](crate::contexts::exporter::ExporterOptions::declaration_files)
+//!
+//! The functions (the [mocks](crate::pipeline::mocks)) keep their signature, and the constants (the [codecs](crate::pipeline::codecs)) their type :
+//!
+//! ```text
+//! export function mockUser(overrides?: Partial<User>): User;
+//! export const UserCodec: t.Type<User>;
+//! export function PageCodec<T>(T: t.Type<T>): t.Type<Page<T>>;
+//! ```
+//!
+//! The constants whose value is an arrow function are declared as functions, and those without a type annotation are `unknown`.
+use ts_json_subset::{
+    declarations::{
+        ambient::{AmbientFunctionDeclaration, AmbientVariableDeclaration},
+        function::FunctionDeclaration,
+        namespace::{NamespaceDeclaration, NamespaceElement},
+        variable::VariableDeclaration,
+    },
+    export::ExportStatement,
+    expressions::{ArrowFunction, Expression},
+    types::{PredefinedType, PrimaryType, TsType},
+};
+
+use super::module_step::ModuleStepResultData;
+
+/// Drops the values of the exports of the modules
+pub fn strip_values(results: &mut [ModuleStepResultData]) {
+    for result in results.iter_mut() {
+        for export in result.exports.iter_mut() {
+            strip_value(export);
+        }
+    }
+}
+
+fn strip_value(export: &mut ExportStatement) {
+    let ambient = match export {
+        ExportStatement::FunctionDeclaration(FunctionDeclaration {
+            ident,
+            type_params,
+            params,
+            return_type,
+            ..
+        }) => AmbientFunctionDeclaration {
+            ident: ident.clone(),
+            type_params: type_params.clone(),
+            params: params.clone(),
+            return_type: return_type.clone(),
+        }
+        .into(),
+        ExportStatement::VariableDeclaration(VariableDeclaration {
+            ident,
+            type_annotation,
+            value,
+        }) => match (type_annotation, value) {
+            (Some(type_annotation), _) => AmbientVariableDeclaration {
+                ident: ident.clone(),
+                type_annotation: type_annotation.clone(),
+            }
+            .into(),
+            (
+                None,
+                Expression::ArrowFunction(ArrowFunction {
+                    type_params,
+                    params,
+                    return_type: Some(return_type),
+                    ..
+                }),
+            ) => AmbientFunctionDeclaration {
+                ident: ident.clone(),
+                type_params: type_params.clone(),
+                params: params.clone(),
+                return_type: return_type.clone(),
+            }
+            .into(),
+            (None, _) => {
+                log::warn!("The type of the constant {} is unknown", ident);
+                AmbientVariableDeclaration {
+                    ident: ident.clone(),
+                    type_annotation: TsType::PrimaryType(PrimaryType::Predefined(
+                        PredefinedType::Unknown,
+                    )),
+                }
+                .into()
+            }
+        },
+        ExportStatement::NamespaceDeclaration(NamespaceDeclaration { elements, .. }) => {
+            for element in elements.iter_mut() {
+                if let NamespaceElement::ExportStatement(export) = element {
+                    strip_value(export);
+                }
+            }
+            return;
+        }
+        _ => return,
+    };
+    *export = ambient;
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::{
+            codecs::{add_codecs, CodecLibrary},
+            mocks::add_mocks,
+            module_step::tests::launch_test_module,
+        },
+    };
+
+    #[test]
+    fn should_strip_the_values_of_the_mocks_and_codecs() {
+        let result = launch_test_module(
+            r#"
+            #[derive(Serialize)]
+            pub struct User { name: String }
+
+            #[derive(Serialize)]
+            pub struct Page<T> { items: Vec<T> }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut results = vec![result.data];
+        add_mocks(&mut results).unwrap();
+        add_codecs(&mut results, CodecLibrary::IoTs).unwrap();
+        strip_values(&mut results);
+
+        let exports: Vec<String> = results[0]
+            .exports
+            .iter()
+            .skip(2)
+            .map(|export| export.to_string())
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                "export function mockUser(overrides?: Partial<User>): User;",
+                "export function mockPage<T>(overrides?: Partial<Page<T>>): Page<T>;",
+                "export const UserCodec: t.Type<User>;",
+                "export function PageCodec<T>(T: t.Type<T>): t.Type<Page<T>>;",
+            ]
+        );
+    }
+}
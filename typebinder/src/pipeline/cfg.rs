@@ -0,0 +1,248 @@
+//! Evaluation of the `#[cfg(...)]` attributes, see [ExporterOptions::cfg](crate::contexts::exporter::ExporterOptions::cfg)
+//!
+//! The items, fields and variants whose predicate doesn't hold are skipped, as the compiler would :
+//!
+//! ```text
+//! #[cfg(test)]
+//! pub struct Fixture { ... } // skipped, `test` is never set
+//!
+//! pub struct User {
+//!     #[cfg(feature = "admin")]
+//!     pub roles: Vec<Role>, // skipped unless the `admin` feature is enabled
+//! }
+//! ```
+use std::collections::HashSet;
+
+use syn::{
+    punctuated::Punctuated, Attribute, Data, DeriveInput, Fields, Item, Lit, Meta, NestedMeta,
+};
+
+use crate::utils::display_path::DisplayPath;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The configuration the sources are evaluated with
+pub struct CfgSet {
+    /// The enabled features, `#[cfg(feature = "...")]`
+    pub features: HashSet<String>,
+    /// The options that are set, e.g. `unix` or `debug_assertions`, `test` not being set unless given
+    pub names: HashSet<String>,
+    /// The options with a value, e.g. `("target_os", "linux")`
+    pub values: HashSet<(String, String)>,
+}
+
+impl CfgSet {
+    /// Adds an option written as in `--cfg`, `unix` or `target_os = "linux"`
+    pub fn add_option(&mut self, option: &str) {
+        match option.split_once('=') {
+            Some((key, value)) => {
+                let key = key.trim();
+                let value = value.trim().trim_matches('"');
+                if key == "feature" {
+                    self.features.insert(value.to_string());
+                } else {
+                    self.values.insert((key.to_string(), value.to_string()));
+                }
+            }
+            None => {
+                self.names.insert(option.trim().to_string());
+            }
+        }
+    }
+
+    /// Whether the `#[cfg(...)]` attributes, if any, all hold.
+    ///
+    /// An attribute that can't be parsed is considered to hold.
+    pub fn is_enabled(&self, attrs: &[Attribute]) -> bool {
+        attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .all(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) if list.nested.len() == 1 => {
+                    self.holds(&list.nested[0]).unwrap_or_else(|| {
+                        log::warn!("Unsupported cfg predicate {}", quote::quote!(#list));
+                        true
+                    })
+                }
+                _ => {
+                    log::warn!("Malformed cfg attribute {}", quote::quote!(#attr));
+                    true
+                }
+            })
+    }
+
+    /// Whether a predicate holds, `None` when it isn't supported
+    fn holds(&self, predicate: &NestedMeta) -> Option<bool> {
+        let meta = match predicate {
+            NestedMeta::Meta(meta) => meta,
+            NestedMeta::Lit(_) => return None,
+        };
+        match meta {
+            Meta::Path(path) => Some(self.names.contains(&DisplayPath(path).to_string())),
+            Meta::NameValue(name_value) => {
+                let value = match &name_value.lit {
+                    Lit::Str(value) => value.value(),
+                    _ => return None,
+                };
+                let key = DisplayPath(&name_value.path).to_string();
+                if key == "feature" {
+                    Some(self.features.contains(&value))
+                } else {
+                    Some(self.values.contains(&(key, value)))
+                }
+            }
+            Meta::List(list) => {
+                let mut predicates = list.nested.iter().map(|nested| self.holds(nested));
+                if list.path.is_ident("all") {
+                    predicates.try_fold(true, |all, holds| Some(all && holds?))
+                } else if list.path.is_ident("any") {
+                    predicates.try_fold(false, |any, holds| Some(any || holds?))
+                } else if list.path.is_ident("not") && list.nested.len() == 1 {
+                    predicates.next()?.map(|holds| !holds)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// The items that are enabled
+    pub fn strip_items(&self, items: Vec<Item>) -> Vec<Item> {
+        items
+            .into_iter()
+            .filter(|item| match item_attrs(item) {
+                Some(attrs) => self.is_enabled(attrs),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Drops the fields and the variants that are disabled
+    pub fn strip_derive_input(&self, derive_input: &mut DeriveInput) {
+        match &mut derive_input.data {
+            Data::Struct(data) => self.strip_fields(&mut data.fields),
+            Data::Enum(data) => {
+                let variants = std::mem::take(&mut data.variants);
+                data.variants = variants
+                    .into_iter()
+                    .filter(|variant| self.is_enabled(&variant.attrs))
+                    .collect::<Punctuated<_, _>>();
+                for variant in data.variants.iter_mut() {
+                    self.strip_fields(&mut variant.fields);
+                }
+            }
+            Data::Union(_) => {}
+        }
+    }
+
+    fn strip_fields(&self, fields: &mut Fields) {
+        let fields = match fields {
+            Fields::Named(fields) => &mut fields.named,
+            Fields::Unnamed(fields) => &mut fields.unnamed,
+            Fields::Unit => return,
+        };
+        let all = std::mem::take(fields);
+        *fields = all
+            .into_iter()
+            .filter(|field| self.is_enabled(&field.attrs))
+            .collect();
+    }
+}
+
+fn item_attrs(item: &Item) -> Option<&Vec<Attribute>> {
+    match item {
+        Item::Enum(item) => Some(&item.attrs),
+        Item::Struct(item) => Some(&item.attrs),
+        Item::Type(item) => Some(&item.attrs),
+        Item::Mod(item) => Some(&item.attrs),
+        Item::Macro(item) => Some(&item.attrs),
+        Item::Use(item) => Some(&item.attrs),
+        Item::Fn(item) => Some(&item.attrs),
+        Item::Const(item) => Some(&item.attrs),
+        Item::Impl(item) => Some(&item.attrs),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions, pipeline::module_step::tests::launch_test_module,
+    };
+
+    #[test]
+    fn should_evaluate_the_predicates() {
+        let mut cfg = CfgSet::default();
+        cfg.add_option("feature = \"admin\"");
+        cfg.add_option("unix");
+        cfg.add_option("target_os = \"linux\"");
+        let enabled = |attr: &str| {
+            let item: syn::ItemStruct = syn::parse_str(&format!("{} struct A;", attr)).unwrap();
+            cfg.is_enabled(&item.attrs)
+        };
+        assert!(enabled("#[cfg(feature = \"admin\")]"));
+        assert!(!enabled("#[cfg(feature = \"billing\")]"));
+        assert!(!enabled("#[cfg(test)]"));
+        assert!(enabled("#[cfg(not(test))]"));
+        assert!(enabled("#[cfg(all(unix, target_os = \"linux\"))]"));
+        assert!(!enabled("#[cfg(all(unix, target_os = \"macos\"))]"));
+        assert!(enabled("#[cfg(any(windows, feature = \"admin\"))]"));
+        assert!(!enabled("#[cfg(unix)] #[cfg(windows)]"));
+        assert!(enabled("#[derive(Serialize)]"));
+    }
+
+    #[test]
+    fn should_skip_the_disabled_items_fields_and_variants() {
+        let mut cfg = CfgSet::default();
+        cfg.add_option("feature=admin");
+        let options = ExporterOptions {
+            cfg: Some(cfg),
+            ..ExporterOptions::default()
+        };
+        let result = launch_test_module(
+            r#"
+            #[derive(Serialize)]
+            pub struct User {
+                name: String,
+                #[cfg(feature = "admin")]
+                roles: Vec<String>,
+                #[cfg(feature = "billing")]
+                plan: String,
+            }
+
+            #[derive(Serialize)]
+            pub enum Event {
+                Created,
+                #[cfg(feature = "billing")]
+                Billed,
+            }
+
+            #[cfg(test)]
+            #[derive(Serialize)]
+            pub struct Fixture { user: User }
+
+            #[cfg(test)]
+            mod tests {
+                #[derive(Serialize)]
+                pub struct Other;
+            }
+            "#,
+            &options,
+        );
+
+        let exports: Vec<String> = result
+            .data
+            .exports
+            .iter()
+            .map(|export| export.to_string())
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                "export interface User {\n\tname: string,\n\troles: string[]\n}",
+                "export type Event = \"Created\";",
+            ]
+        );
+        assert!(result.children.is_empty());
+    }
+}
@@ -0,0 +1,748 @@
+//! Generation of runtime codecs for the exported declarations, see [ExporterOptions::codecs](crate::contexts::exporter::ExporterOptions::codecs)
+//!
+//! Each interface, type alias and enum gets a `<Name>Codec` constant, built with [io-ts](https://gcanti.github.io/io-ts/)
+//! or [runtypes](https://github.com/runtypes/runtypes), to validate the payloads received at runtime :
+//!
+//! ```text
+//! import * as t from "io-ts";
+//!
+//! export const UserCodec: t.Type<User> = t.intersection([
+//!     t.type({ name: t.string, role: t.recursion<Role>("Role", () => RoleCodec) }),
+//!     t.partial({ nickname: t.string }),
+//! ]);
+//! export const PageCodec = <T>(T: t.Type<T>): t.Type<Page<T>> => t.type({ items: t.array(T) });
+//! ```
+//!
+//! * the codecs are annotated with their type, so that the compiler checks them against the declarations,
+//! * the generic declarations get a function taking the codecs of their parameters,
+//! * the references to the declarations of the crate are lazy, so that the order of the declarations and
+//!   the cycles between them don't matter, and the codecs of the other modules are imported along with their types.
+//!
+//! The types that can't be checked (types of other crates, mapped types...) accept any value, asserted as the type.
+use std::{collections::HashSet, str::FromStr};
+
+use ts_json_subset::{
+    common::StringLiteral,
+    declarations::{
+        const_enum::{ConstEnumBody, ConstEnumDeclaration},
+        enumeration::EnumDeclaration,
+        function::FunctionParameter,
+        variable::VariableDeclaration,
+    },
+    export::ExportStatement,
+    expressions::{
+        ArrayLiteral, ArrowFunction, AsExpression, CallExpression, Expression, MemberExpression,
+        ObjectLiteral, ObjectLiteralMember,
+    },
+    ident::TSIdent,
+    import::{ImportKind, ImportSpecifier, ImportStatement},
+    types::{
+        LiteralType, PredefinedType, PrimaryType, RawType, TsType, TypeArguments, TypeMember,
+        TypeParameters, TypeReference,
+    },
+};
+
+use super::{mocks::imports, module_step::ModuleStepResultData};
+use crate::{
+    error::TsExportError,
+    type_solving::ImportEntry,
+    utils::crate_paths::{module_segments, resolve_local_path},
+};
+
+/// The library the codecs are built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecLibrary {
+    /// [io-ts](https://gcanti.github.io/io-ts/), imported as `t`
+    IoTs,
+    /// [runtypes](https://github.com/runtypes/runtypes), imported as `rt`
+    Runtypes,
+}
+
+impl FromStr for CodecLibrary {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "io-ts" => Ok(CodecLibrary::IoTs),
+            "runtypes" => Ok(CodecLibrary::Runtypes),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown codec library {}, expected io-ts or runtypes",
+                s
+            ))),
+        }
+    }
+}
+
+impl CodecLibrary {
+    /// The name the library is imported as
+    fn namespace(self) -> &'static str {
+        match self {
+            CodecLibrary::IoTs => "t",
+            CodecLibrary::Runtypes => "rt",
+        }
+    }
+
+    fn package(self) -> &'static str {
+        match self {
+            CodecLibrary::IoTs => "io-ts",
+            CodecLibrary::Runtypes => "runtypes",
+        }
+    }
+
+    /// The type of the codecs of `ty`, e.g. `t.Type<User>`
+    fn codec_type(self, ty: &TsType) -> TsType {
+        let codec = match self {
+            CodecLibrary::IoTs => "Type",
+            CodecLibrary::Runtypes => "Runtype",
+        };
+        TsType::PrimaryType(PrimaryType::RawType(RawType(format!(
+            "{}.{}<{}>",
+            self.namespace(),
+            codec,
+            ty
+        ))))
+    }
+}
+
+/// The name of the codec of a declaration
+pub fn codec_name(ident: &str) -> String {
+    format!("{}Codec", ident)
+}
+
+/// Appends the codecs of the declarations of each module, importing the library and the codecs of the other modules of the crate
+pub fn add_codecs(
+    results: &mut [ModuleStepResultData],
+    library: CodecLibrary,
+) -> Result<(), TsExportError> {
+    for result in results.iter_mut() {
+        let module_path = module_segments(&result.path);
+        let local: HashSet<String> = result
+            .exports
+            .iter()
+            .filter(|export| codec_target(export).is_some())
+            .filter_map(|export| export.ident())
+            .map(|ident| ident.to_string())
+            .collect();
+        let imported: Vec<ImportEntry> = result
+            .import_entries
+            .iter()
+            .filter(|entry| resolve_local_path(entry, &module_path, &result.crate_name).is_some())
+            .cloned()
+            .collect();
+
+        let mut codecs = Codecs {
+            library,
+            local: &local,
+            imported: &imported,
+            type_params: Vec::new(),
+            used_imports: Vec::new(),
+        };
+        let declarations = result
+            .exports
+            .iter()
+            .filter_map(|export| codecs.codec_declaration(export).transpose())
+            .collect::<Result<Vec<_>, TsExportError>>()?;
+        if declarations.is_empty() {
+            continue;
+        }
+        let used_imports = codecs.used_imports;
+
+        for entry in used_imports {
+            let codec = codec_name(&entry.ident);
+            let alias = entry.alias.as_deref().map(codec_name);
+            if let Some(statement) = result
+                .imports
+                .iter_mut()
+                .find(|statement| imports(statement, entry.local_name()))
+            {
+                if let ImportKind::ImportList(list) = &mut statement.import_kind {
+                    list.items.push(ImportSpecifier {
+                        ident: TSIdent::from_str(&codec)?,
+                        alias: alias.as_deref().map(TSIdent::from_str).transpose()?,
+                    });
+                }
+            }
+            result
+                .value_imports
+                .insert(alias.clone().unwrap_or_else(|| codec.clone()));
+            result.import_entries.push(ImportEntry {
+                path: entry.path,
+                ident: codec,
+                alias,
+            });
+        }
+        result.imports.insert(
+            0,
+            ImportStatement {
+                import_kind: ImportKind::GlobAsIdentifier(TSIdent::from_str(library.namespace())?),
+                path: StringLiteral::from_raw(library.package()).to_string(),
+                type_only: false,
+            },
+        );
+        result.exports.extend(declarations);
+    }
+    Ok(())
+}
+
+/// The declarations that get a codec : their ident and type parameters
+fn codec_target(export: &ExportStatement) -> Option<(&TSIdent, &Option<TypeParameters>)> {
+    match export {
+        ExportStatement::InterfaceDeclaration(declaration) => {
+            Some((&declaration.ident, &declaration.type_params))
+        }
+        ExportStatement::TypeAliasDeclaration(declaration) => {
+            Some((&declaration.ident, &declaration.type_params))
+        }
+        ExportStatement::EnumDeclaration(EnumDeclaration { ident, .. })
+        | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, .. }) => {
+            Some((ident, &None))
+        }
+        _ => None,
+    }
+}
+
+struct Codecs<'a> {
+    library: CodecLibrary,
+    /// The declarations of the module that get a codec
+    local: &'a HashSet<String>,
+    /// The items imported from the other modules of the crate
+    imported: &'a [ImportEntry],
+    /// The type parameters of the current declaration
+    type_params: Vec<String>,
+    /// The imported items whose codec is used
+    used_imports: Vec<ImportEntry>,
+}
+
+impl Codecs<'_> {
+    fn codec_declaration(
+        &mut self,
+        export: &ExportStatement,
+    ) -> Result<Option<ExportStatement>, TsExportError> {
+        let (ident, type_params) = match codec_target(export) {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        self.type_params = type_params
+            .iter()
+            .flat_map(|params| params.parameters.iter())
+            .map(|param| param.identifier.to_string())
+            .collect();
+        let ty = reference(
+            ident.clone(),
+            self.type_params
+                .iter()
+                .map(|param| Ok(reference(TSIdent::from_str(param)?, Vec::new())))
+                .collect::<Result<Vec<_>, TsExportError>>()?,
+        );
+
+        let codec = match export {
+            ExportStatement::InterfaceDeclaration(declaration) => {
+                self.codec_members(&declaration.obj_type.body.members, &ty)?
+            }
+            ExportStatement::TypeAliasDeclaration(declaration) => {
+                match branded_primitive(&declaration.inner_type) {
+                    // A branded type is checked as its primitive, and asserted as the type
+                    Some(primitive) => {
+                        let codec = self.codec_type(primitive)?;
+                        self.asserted(codec, &ty)
+                    }
+                    None => self.codec_type(&declaration.inner_type)?,
+                }
+            }
+            ExportStatement::EnumDeclaration(EnumDeclaration { ident, body, .. })
+            | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, body }) => {
+                self.codec_enum(ident, body)?
+            }
+            _ => return Ok(None),
+        };
+
+        let ident = TSIdent::from_str(&codec_name(&ident.to_string()))?;
+        let declaration = if self.type_params.is_empty() {
+            VariableDeclaration {
+                ident,
+                type_annotation: Some(self.library.codec_type(&ty)),
+                value: codec,
+            }
+        } else {
+            // The codec of a generic declaration is built from the codecs of its parameters
+            let params = self
+                .type_params
+                .iter()
+                .map(|param| {
+                    Ok(FunctionParameter {
+                        name: TSIdent::from_str(param)?,
+                        optional: false,
+                        param_type: self
+                            .library
+                            .codec_type(&reference(TSIdent::from_str(param)?, Vec::new())),
+                    })
+                })
+                .collect::<Result<_, TsExportError>>()?;
+            VariableDeclaration {
+                ident,
+                type_annotation: None,
+                value: ArrowFunction {
+                    type_params: type_params.clone(),
+                    params,
+                    return_type: Some(self.library.codec_type(&ty)),
+                    body: Box::new(codec),
+                }
+                .into(),
+            }
+        };
+        Ok(Some(declaration.into()))
+    }
+
+    /// The codec of an object, `ty` being asserted when it can't be checked
+    fn codec_members(
+        &mut self,
+        members: &[TypeMember],
+        ty: &TsType,
+    ) -> Result<Expression, TsExportError> {
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+        for member in members {
+            match member {
+                TypeMember::PropertySignature(property) => {
+                    let codec = self.codec_type(&property.inner_type)?;
+                    match (self.library, property.optional) {
+                        (CodecLibrary::IoTs, true) => optional
+                            .push(ObjectLiteralMember::Property(property.name.clone(), codec)),
+                        (CodecLibrary::Runtypes, true) => {
+                            required.push(ObjectLiteralMember::Property(
+                                property.name.clone(),
+                                self.call("Optional", vec![codec]),
+                            ))
+                        }
+                        (_, false) => required
+                            .push(ObjectLiteralMember::Property(property.name.clone(), codec)),
+                    }
+                }
+                // A map is an object with an index signature only
+                TypeMember::IndexSignature(signature) if members.len() == 1 => {
+                    let key = self.codec_type(&signature.key_type)?;
+                    let value = self.codec_type(&signature.inner_type)?;
+                    return Ok(self.record(key, value));
+                }
+                TypeMember::IndexSignature(_) => return Ok(self.unchecked(ty)),
+            }
+        }
+        Ok(match self.library {
+            CodecLibrary::IoTs => {
+                let object = |members: Vec<ObjectLiteralMember>| -> Expression {
+                    ObjectLiteral { members }.into()
+                };
+                match (required.is_empty(), optional.is_empty()) {
+                    (false, false) => self.call(
+                        "intersection",
+                        vec![ArrayLiteral {
+                            elements: vec![
+                                self.call("type", vec![object(required)]),
+                                self.call("partial", vec![object(optional)]),
+                            ],
+                        }
+                        .into()],
+                    ),
+                    (true, false) => self.call("partial", vec![object(optional)]),
+                    (_, true) => self.call("type", vec![object(required)]),
+                }
+            }
+            CodecLibrary::Runtypes => {
+                self.call("Record", vec![ObjectLiteral { members: required }.into()])
+            }
+        })
+    }
+
+    /// The codec of the values of an enum
+    fn codec_enum(
+        &mut self,
+        ident: &TSIdent,
+        body: &ConstEnumBody,
+    ) -> Result<Expression, TsExportError> {
+        let values = body
+            .variants
+            .iter()
+            .map(|variant| {
+                let value = MemberExpression {
+                    object: ident.clone(),
+                    member: variant.ident.clone(),
+                }
+                .into();
+                self.call(self.literal(), vec![value])
+            })
+            .collect();
+        Ok(self.union(values))
+    }
+
+    fn codec_type(&mut self, ty: &TsType) -> Result<Expression, TsExportError> {
+        Ok(match ty {
+            TsType::PrimaryType(primary) => self.codec_primary_type(primary)?,
+            TsType::UnionType(union) => {
+                let codecs = union
+                    .types
+                    .iter()
+                    .map(|ty| self.codec_type(ty))
+                    .collect::<Result<_, _>>()?;
+                self.union(codecs)
+            }
+            TsType::IntersectionType(intersection) => {
+                if let Some(primitive) = branded_primitive(ty) {
+                    let codec = self.codec_type(primitive)?;
+                    return Ok(self.asserted(codec, ty));
+                }
+                let codecs: Vec<Expression> = intersection
+                    .types
+                    .iter()
+                    .map(|ty| self.codec_type(ty))
+                    .collect::<Result<_, _>>()?;
+                match self.library {
+                    CodecLibrary::IoTs => self.call(
+                        "intersection",
+                        vec![ArrayLiteral { elements: codecs }.into()],
+                    ),
+                    CodecLibrary::Runtypes => self.call("Intersect", codecs),
+                }
+            }
+            TsType::ParenthesizedType(parenthesized) => {
+                self.codec_type(parenthesized.inner.as_ref())?
+            }
+        })
+    }
+
+    fn codec_primary_type(&mut self, ty: &PrimaryType) -> Result<Expression, TsExportError> {
+        Ok(match ty {
+            PrimaryType::Predefined(predefined) => self.predefined(predefined),
+            PrimaryType::LiteralType(literal) => {
+                self.call(self.literal(), vec![literal.clone().into()])
+            }
+            PrimaryType::ObjectType(object) => {
+                self.codec_members(&object.body.members, &TsType::PrimaryType(ty.clone()))?
+            }
+            PrimaryType::ArrayType(array) => {
+                let codec = self.codec_primary_type(array.inner_type.as_ref())?;
+                self.call(self.array(), vec![codec])
+            }
+            PrimaryType::TupleType(tuple) => {
+                let codecs = tuple
+                    .inner_types
+                    .iter()
+                    .map(|ty| self.codec_type(ty))
+                    .collect::<Result<_, _>>()?;
+                match self.library {
+                    CodecLibrary::IoTs => {
+                        self.call("tuple", vec![ArrayLiteral { elements: codecs }.into()])
+                    }
+                    CodecLibrary::Runtypes => self.call("Tuple", codecs),
+                }
+            }
+            PrimaryType::TypeReference(reference) => self.codec_reference(reference)?,
+            PrimaryType::MappedType(_) | PrimaryType::RawType(_) => {
+                self.unchecked(&TsType::PrimaryType(ty.clone()))
+            }
+        })
+    }
+
+    fn codec_reference(&mut self, reference: &TypeReference) -> Result<Expression, TsExportError> {
+        let name = reference.name.to_string();
+        if self.type_params.contains(&name) {
+            return Ok(reference.name.clone().into());
+        }
+        let args: &[TsType] = match &reference.args {
+            Some(args) => &args.types,
+            None => &[],
+        };
+        match (name.as_str(), args) {
+            ("Record", [key, value]) => {
+                let key = self.codec_type(key)?;
+                let value = self.codec_type(value)?;
+                return Ok(self.record(key, value));
+            }
+            ("Array", [inner]) => {
+                let codec = self.codec_type(inner)?;
+                return Ok(self.call(self.array(), vec![codec]));
+            }
+            _ => (),
+        }
+
+        let ty = TsType::PrimaryType(PrimaryType::TypeReference(reference.clone()));
+        if !self.local.contains(&name) {
+            match self
+                .imported
+                .iter()
+                .find(|entry| entry.local_name() == name)
+            {
+                Some(entry) => {
+                    if !self.used_imports.contains(entry) {
+                        self.used_imports.push(entry.clone());
+                    }
+                }
+                None => return Ok(self.unchecked(&ty)),
+            }
+        }
+        let codec: Expression = TSIdent::from_str(&codec_name(&name))?.into();
+        let codec = if args.is_empty() {
+            codec
+        } else {
+            CallExpression {
+                callee: Box::new(codec),
+                type_args: None,
+                args: args
+                    .iter()
+                    .map(|arg| self.codec_type(arg))
+                    .collect::<Result<_, _>>()?,
+            }
+            .into()
+        };
+        // The codec may be declared later, or refer back to the current one
+        let lazy = ArrowFunction {
+            type_params: None,
+            params: Vec::new(),
+            return_type: None,
+            body: Box::new(codec),
+        }
+        .into();
+        Ok(match self.library {
+            CodecLibrary::IoTs => CallExpression {
+                callee: Box::new(self.member("recursion")),
+                type_args: Some(TypeArguments { types: vec![ty] }),
+                args: vec![LiteralType::from(StringLiteral::from(name)).into(), lazy],
+            }
+            .into(),
+            CodecLibrary::Runtypes => self.call("Lazy", vec![lazy]),
+        })
+    }
+
+    fn predefined(&self, predefined: &PredefinedType) -> Expression {
+        match (self.library, predefined) {
+            (CodecLibrary::IoTs, PredefinedType::Number) => self.member("number"),
+            (CodecLibrary::IoTs, PredefinedType::BigInt) => self.member("bigint"),
+            (CodecLibrary::IoTs, PredefinedType::Boolean) => self.member("boolean"),
+            (CodecLibrary::IoTs, PredefinedType::String) => self.member("string"),
+            (CodecLibrary::IoTs, PredefinedType::Null) => self.member("nullType"),
+            (CodecLibrary::IoTs, PredefinedType::Any | PredefinedType::Unknown) => {
+                self.member("unknown")
+            }
+            (CodecLibrary::IoTs, PredefinedType::Never) => self.member("never"),
+            (CodecLibrary::Runtypes, PredefinedType::Number) => self.member("Number"),
+            (CodecLibrary::Runtypes, PredefinedType::BigInt) => self.member("BigInt"),
+            (CodecLibrary::Runtypes, PredefinedType::Boolean) => self.member("Boolean"),
+            (CodecLibrary::Runtypes, PredefinedType::String) => self.member("String"),
+            (CodecLibrary::Runtypes, PredefinedType::Null) => {
+                self.call("Literal", vec![Expression::Null])
+            }
+            (CodecLibrary::Runtypes, PredefinedType::Any | PredefinedType::Unknown) => {
+                self.member("Unknown")
+            }
+            (CodecLibrary::Runtypes, PredefinedType::Never) => self.member("Never"),
+        }
+    }
+
+    fn union(&self, mut codecs: Vec<Expression>) -> Expression {
+        match (self.library, codecs.len()) {
+            (_, 0) => self.predefined(&PredefinedType::Never),
+            (_, 1) => codecs.remove(0),
+            (CodecLibrary::IoTs, _) => {
+                self.call("union", vec![ArrayLiteral { elements: codecs }.into()])
+            }
+            (CodecLibrary::Runtypes, _) => self.call("Union", codecs),
+        }
+    }
+
+    fn record(&self, key: Expression, value: Expression) -> Expression {
+        match self.library {
+            CodecLibrary::IoTs => self.call("record", vec![key, value]),
+            CodecLibrary::Runtypes => self.call("Dictionary", vec![value, key]),
+        }
+    }
+
+    fn literal(&self) -> &'static str {
+        match self.library {
+            CodecLibrary::IoTs => "literal",
+            CodecLibrary::Runtypes => "Literal",
+        }
+    }
+
+    fn array(&self) -> &'static str {
+        match self.library {
+            CodecLibrary::IoTs => "array",
+            CodecLibrary::Runtypes => "Array",
+        }
+    }
+
+    /// A codec that accepts any value, asserted as `ty`
+    fn unchecked(&self, ty: &TsType) -> Expression {
+        let codec = self.predefined(&PredefinedType::Unknown);
+        self.asserted(codec, ty)
+    }
+
+    /// `(codec as unknown as t.Type<T>)`
+    fn asserted(&self, codec: Expression, ty: &TsType) -> Expression {
+        AsExpression {
+            expression: Box::new(
+                AsExpression {
+                    expression: Box::new(codec),
+                    as_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Unknown)),
+                }
+                .into(),
+            ),
+            as_type: self.library.codec_type(ty),
+        }
+        .into()
+    }
+
+    /// A member of the library, e.g. `t.string`
+    fn member(&self, member: &str) -> Expression {
+        MemberExpression {
+            object: TSIdent::from_str(self.library.namespace()).expect("Valid namespace"),
+            member: TSIdent::from_str(member).expect("Valid member of the library"),
+        }
+        .into()
+    }
+
+    /// A call to a combinator of the library, e.g. `t.array(t.string)`
+    fn call(&self, combinator: &str, args: Vec<Expression>) -> Expression {
+        CallExpression {
+            callee: Box::new(self.member(combinator)),
+            type_args: None,
+            args,
+        }
+        .into()
+    }
+}
+
+/// The primitive of a branded type, `number & { __brand: ... }`
+fn branded_primitive(ty: &TsType) -> Option<&TsType> {
+    match ty {
+        TsType::IntersectionType(intersection) => intersection
+            .types
+            .iter()
+            .find(|ty| matches!(ty, TsType::PrimaryType(PrimaryType::Predefined(_)))),
+        _ => None,
+    }
+}
+
+fn reference(name: TSIdent, args: Vec<TsType>) -> TsType {
+    TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+        name,
+        args: if args.is_empty() {
+            None
+        } else {
+            Some(TypeArguments { types: args })
+        },
+    }))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::tests::{collect, launch_test_module},
+    };
+
+    fn render(source: &str, library: CodecLibrary) -> Vec<String> {
+        let result = launch_test_module(source, &ExporterOptions::default());
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        add_codecs(&mut results, library).unwrap();
+        results
+            .iter()
+            .map(|result| {
+                result
+                    .imports
+                    .iter()
+                    .map(|import| import.to_string())
+                    .chain(result.exports.iter().map(|export| export.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+        use crate::models::Role;
+        use std::collections::HashMap;
+
+        pub mod models {
+            #[derive(Serialize)]
+            pub enum Role { Admin, User }
+        }
+
+        #[derive(Serialize)]
+        #[serde(tag = "type")]
+        pub enum Event { Created { id: u32 }, Deleted }
+
+        #[derive(Serialize)]
+        pub struct User {
+            name: String,
+            role: Role,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            nickname: Option<String>,
+            manager: Option<Page<User>>,
+            scores: HashMap<String, (u32, bool)>,
+            avatar: other_crate::Image,
+        }
+
+        #[derive(Serialize)]
+        pub struct Page<T> { items: Vec<T>, current: T }
+
+        #[derive(Serialize)]
+        #[ts(nominal)]
+        pub struct UserId(u32);
+        "#;
+
+    fn codecs(module: &str) -> Vec<&str> {
+        module
+            .lines()
+            .filter(|line| line.starts_with("import") || line.starts_with("export const"))
+            .collect()
+    }
+
+    #[test]
+    fn should_add_io_ts_codecs() {
+        let rendered = render(SOURCE, CodecLibrary::IoTs);
+        assert_eq!(
+            codecs(&rendered[0]),
+            vec![
+                r#"import * as t from "io-ts";"#,
+                r#"export const RoleCodec: t.Type<Role> = t.union([t.literal("Admin"), t.literal("User")]);"#,
+            ]
+        );
+        assert_eq!(
+            codecs(&rendered[1]),
+            vec![
+                r#"import * as t from "io-ts";"#,
+                r#"import { Role, RoleCodec } from "my_crate::models";"#,
+                r#"import { Image } from "other_crate";"#,
+                r#"export const EventCodec: t.Type<Event> = t.union([t.type({ type: t.literal("Created"), id: t.number }), t.type({ type: t.literal("Deleted") })]);"#,
+                r#"export const UserCodec: t.Type<User> = t.intersection([t.type({ name: t.string, role: t.recursion<Role>("Role", () => RoleCodec), manager: t.union([t.recursion<Page<User>>("Page", () => PageCodec(t.recursion<User>("User", () => UserCodec))), t.nullType]), scores: t.record(t.string, t.tuple([t.number, t.boolean])), avatar: ((t.unknown as unknown) as t.Type<Image>) }), t.partial({ nickname: t.string })]);"#,
+                r#"export const PageCodec = <T>(T: t.Type<T>): t.Type<Page<T>> => t.type({ items: t.array(T), current: T });"#,
+                r#"export const UserIdCodec: t.Type<UserId> = ((t.number as unknown) as t.Type<UserId>);"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_add_runtypes_codecs() {
+        let rendered = render(SOURCE, CodecLibrary::Runtypes);
+        assert_eq!(
+            codecs(&rendered[0]),
+            vec![
+                r#"import * as rt from "runtypes";"#,
+                r#"export const RoleCodec: rt.Runtype<Role> = rt.Union(rt.Literal("Admin"), rt.Literal("User"));"#,
+            ]
+        );
+        assert_eq!(
+            codecs(&rendered[1]),
+            vec![
+                r#"import * as rt from "runtypes";"#,
+                r#"import { Role, RoleCodec } from "my_crate::models";"#,
+                r#"import { Image } from "other_crate";"#,
+                r#"export const EventCodec: rt.Runtype<Event> = rt.Union(rt.Record({ type: rt.Literal("Created"), id: rt.Number }), rt.Record({ type: rt.Literal("Deleted") }));"#,
+                r#"export const UserCodec: rt.Runtype<User> = rt.Record({ name: rt.String, role: rt.Lazy(() => RoleCodec), nickname: rt.Optional(rt.String), manager: rt.Union(rt.Lazy(() => PageCodec(rt.Lazy(() => UserCodec))), rt.Literal(null)), scores: rt.Dictionary(rt.Tuple(rt.Number, rt.Boolean), rt.String), avatar: ((rt.Unknown as unknown) as rt.Runtype<Image>) });"#,
+                r#"export const PageCodec = <T>(T: rt.Runtype<T>): rt.Runtype<Page<T>> => rt.Record({ items: rt.Array(T), current: T });"#,
+                r#"export const UserIdCodec: rt.Runtype<UserId> = ((rt.Number as unknown) as rt.Runtype<UserId>);"#,
+            ]
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Selection of the declarations that are exported, see [ExporterOptions::filter](crate::contexts::exporter::ExporterOptions::filter)
+//!
+//! By default, every struct, enum and type alias that serde can describe is exported. A [ProcessFilter] narrows it down
+//! to the API-facing types, by name, by module, or with the `#[ts(...)]` attributes :
+//!
+//! ```text
+//! #[derive(Serialize)]
+//! #[ts(export)]
+//! pub struct User { ... } // exported, even if its name or module is filtered out
+//!
+//! #[derive(Serialize)]
+//! #[ts(skip)]
+//! pub struct AuditRow { ... } // never exported
+//! ```
+//!
+//! The declarations that are left out are still known to the solvers, e.g. to be flattened into another one,
+//! but the declarations that refer to them get a dangling reference, which the run reports as a warning
+//! naming the referencing declaration (see [report_dangling_references]).
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    attributes::ContainerAttributes,
+    error::TsExportError,
+    exporters::bundle::ReferenceCollector,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+        glob::Glob,
+    },
+};
+use syn::Attribute;
+use ts_json_subset::visit_mut::VisitMut;
+
+use super::{
+    module_step::ModuleStepResultData,
+    report::{Recovery, Warning},
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The rules a declaration is exported by.
+///
+/// A declaration marked `#[ts(skip)]` is left out, and one marked `#[ts(export)]` is exported.
+/// Otherwise, it is exported when its name and its module match the allowed globs, if any, and none of the denied globs,
+/// unless [ProcessFilter::set_only_marked] is set.
+pub struct ProcessFilter {
+    allowed_names: Vec<Glob>,
+    denied_names: Vec<Glob>,
+    allowed_modules: Vec<Glob>,
+    denied_modules: Vec<Glob>,
+    only_marked: bool,
+}
+
+impl ProcessFilter {
+    /// Exports the declarations whose name matches this glob, e.g. `*Dto`
+    pub fn allow_name(&mut self, pattern: &str) {
+        self.allowed_names.push(Glob::new(pattern));
+    }
+
+    /// Leaves out the declarations whose name matches this glob, e.g. `*Row`
+    pub fn deny_name(&mut self, pattern: &str) {
+        self.denied_names.push(Glob::new(pattern));
+    }
+
+    /// Exports the declarations of the modules that match this glob, relatively to the crate, e.g. `api::**`.
+    ///
+    /// The segments are separated by `::`, `**` matching any number of segments (the root module has none).
+    pub fn allow_module(&mut self, pattern: &str) {
+        self.allowed_modules.push(module_glob(pattern));
+    }
+
+    /// Leaves out the declarations of the modules that match this glob, e.g. `**::db`
+    pub fn deny_module(&mut self, pattern: &str) {
+        self.denied_modules.push(module_glob(pattern));
+    }
+
+    /// Only exports the declarations marked `#[ts(export)]`
+    pub fn set_only_marked(&mut self, only_marked: bool) {
+        self.only_marked = only_marked;
+    }
+
+    /// Whether the declaration `name`, with these attributes, in the module of `module_path` (its segments, from the crate) is exported
+    pub fn accepts(
+        &self,
+        module_path: &[String],
+        name: &str,
+        attrs: &[Attribute],
+    ) -> Result<bool, TsExportError> {
+        let attributes = ContainerAttributes::from_attrs(attrs)?;
+        if attributes.skip || attributes.export {
+            return Ok(attributes.export);
+        }
+        if self.only_marked {
+            return Ok(false);
+        }
+        let module = module_path.join("/");
+        let matches = |globs: &[Glob], value: &str| globs.iter().any(|glob| glob.is_match(value));
+        Ok(
+            (self.allowed_names.is_empty() || matches(&self.allowed_names, name))
+                && (self.allowed_modules.is_empty() || matches(&self.allowed_modules, &module))
+                && !matches(&self.denied_names, name)
+                && !matches(&self.denied_modules, &module),
+        )
+    }
+}
+
+fn module_glob(pattern: &str) -> Glob {
+    Glob::new(&pattern.replace("::", "/"))
+}
+
+/// The declarations left out by the filter are only known once all the modules have been processed.
+///
+/// Warns about each reference of an exported declaration to a declaration left out, in its module or imported,
+/// which is dangling in the TS code. It runs before the references are renamed, while they are still named after the Rust idents.
+pub fn report_dangling_references(results: &[ModuleStepResultData]) -> Vec<Warning> {
+    let filtered_out: HashSet<(Vec<String>, &str)> = results
+        .iter()
+        .flat_map(|result| {
+            let module_path = module_segments(&result.path);
+            result
+                .filtered_out
+                .iter()
+                .map(move |ident| (module_path.clone(), ident.as_str()))
+        })
+        .collect();
+    if filtered_out.is_empty() {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    for result in results.iter() {
+        let module_path = module_segments(&result.path);
+        // The declarations left out, by the name they are referred to by in the module
+        let mut dangling: HashMap<&str, String> = result
+            .import_entries
+            .iter()
+            .filter(|entry| {
+                resolve_local_path(entry, &module_path, &result.crate_name)
+                    .map(|declaring_module| {
+                        filtered_out.contains(&(declaring_module, entry.ident.as_str()))
+                    })
+                    .unwrap_or(false)
+            })
+            .map(|entry| {
+                (
+                    entry.local_name(),
+                    format!("{}::{}", entry.path, entry.ident),
+                )
+            })
+            .collect();
+        dangling.extend(
+            result
+                .filtered_out
+                .iter()
+                .map(|ident| (ident.as_str(), ident.clone())),
+        );
+        if dangling.is_empty() {
+            continue;
+        }
+
+        for export in result.exports.iter() {
+            let declaration = match export.ident() {
+                Some(ident) => ident.to_string(),
+                None => continue,
+            };
+            let mut collector = ReferenceCollector::default();
+            collector.visit_export_statement_mut(&mut export.clone());
+            for reference in collector.references.iter() {
+                if let Some(filtered) = dangling.get(reference.as_str()) {
+                    let error =
+                        TsExportError::FilteredOutReference(declaration.clone(), filtered.clone());
+                    let mut warning = Warning::new(
+                        DisplayPath(&result.path).to_string(),
+                        Recovery::Dangling,
+                        error,
+                    );
+                    warning.container = Some(declaration.clone());
+                    warnings.push(warning);
+                }
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::{
+            tests::{collect, launch_test_module},
+            ModuleStepResult,
+        },
+    };
+
+    fn exports(result: &ModuleStepResult) -> Vec<String> {
+        result
+            .data
+            .exports
+            .iter()
+            .filter_map(|export| export.ident())
+            .map(|ident| ident.to_string())
+            .chain(result.children.iter().flat_map(exports))
+            .collect()
+    }
+
+    fn launch(filter: ProcessFilter) -> Vec<String> {
+        let options = ExporterOptions {
+            filter: Some(filter),
+            ..ExporterOptions::default()
+        };
+        let result = launch_test_module(
+            r#"
+            pub mod api {
+                #[derive(Serialize)]
+                pub struct UserDto { name: String }
+
+                #[derive(Serialize)]
+                #[ts(skip)]
+                pub struct DraftDto { name: String }
+
+                pub type UserIds = Vec<u32>;
+            }
+
+            pub mod db {
+                #[derive(Serialize)]
+                pub struct UserRow { id: u32 }
+
+                #[derive(Serialize)]
+                #[ts(export)]
+                pub struct Migration { version: u32 }
+            }
+
+            #[derive(Serialize)]
+            pub struct Session { id: u32 }
+            "#,
+            &options,
+        );
+        exports(&result)
+    }
+
+    #[test]
+    fn should_export_the_declarations_matching_the_filter() {
+        assert_eq!(
+            launch(ProcessFilter::default()),
+            vec!["Session", "UserDto", "UserIds", "UserRow", "Migration"]
+        );
+
+        let mut filter = ProcessFilter::default();
+        filter.allow_name("*Dto");
+        assert_eq!(launch(filter), vec!["UserDto", "Migration"]);
+
+        let mut filter = ProcessFilter::default();
+        filter.allow_module("api::**");
+        filter.deny_name("*Ids");
+        assert_eq!(launch(filter), vec!["UserDto", "Migration"]);
+
+        let mut filter = ProcessFilter::default();
+        filter.deny_module("db");
+        assert_eq!(
+            launch(filter),
+            vec!["Session", "UserDto", "UserIds", "Migration"]
+        );
+
+        let mut filter = ProcessFilter::default();
+        filter.set_only_marked(true);
+        assert_eq!(launch(filter), vec!["Migration"]);
+    }
+
+    #[test]
+    fn should_report_the_references_to_the_declarations_left_out() {
+        let mut filter = ProcessFilter::default();
+        filter.deny_module("db");
+        filter.deny_name("*Draft");
+        let options = ExporterOptions {
+            filter: Some(filter),
+            ..ExporterOptions::default()
+        };
+        let result = launch_test_module(
+            r#"
+            pub mod api {
+                use crate::db::UserRow as Row;
+
+                #[derive(Serialize)]
+                pub struct UserDraft { name: String }
+
+                #[derive(Serialize)]
+                pub struct UserDto { row: Row, draft: Option<UserDraft>, name: String }
+            }
+
+            pub mod db {
+                #[derive(Serialize)]
+                pub struct UserRow { id: u32 }
+            }
+            "#,
+            &options,
+        );
+        let mut results = Vec::new();
+        collect(&mut results, result);
+
+        let warnings: Vec<String> = report_dangling_references(&results)
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "`UserDto` refers to `my_crate::db::UserRow`, which the filter leaves out, reference left dangling",
+                "`UserDto` refers to `UserDraft`, which the filter leaves out, reference left dangling",
+            ]
+        );
+    }
+}
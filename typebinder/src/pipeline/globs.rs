@@ -0,0 +1,95 @@
+//! Resolution of the glob imports (`use module::*`) of the modules of the crate
+use std::collections::{HashMap, HashSet};
+
+use super::module_step::{import_statements, ModuleStepResultData};
+use crate::{
+    error::TsExportError, path_mapper::PathMapper, type_solving::ImportEntry,
+    utils::crate_paths::module_segments,
+};
+
+/// The glob imports of a module, and the names that could come from them
+#[derive(Debug, Default, Clone)]
+pub struct GlobImports {
+    /// The paths of the modules of the crate imported with a glob, in order
+    pub modules: Vec<Vec<String>>,
+    /// The referenced names that are neither imported by name nor declared by the module
+    pub unresolved: Vec<String>,
+}
+
+/// The items a module imports with a glob are only known once the module that declares them has been processed.
+///
+/// Imports the unresolved names of each module from the first module of its glob imports that declares or re-exports them.
+pub fn resolve_glob_imports(
+    results: &mut [ModuleStepResultData],
+    path_mapper: &PathMapper,
+) -> Result<(), TsExportError> {
+    if results
+        .iter()
+        .all(|result| result.globs.unresolved.is_empty())
+    {
+        return Ok(());
+    }
+
+    // The Rust names available in each module of each crate
+    let mut exported: HashMap<(String, Vec<String>), HashSet<String>> = HashMap::new();
+    for result in results.iter() {
+        let names = exported
+            .entry((result.crate_name.clone(), module_segments(&result.path)))
+            .or_default();
+        names.extend(
+            result
+                .exports
+                .iter()
+                .filter_map(|export| export.ident())
+                .map(|ident| ident.to_string()),
+        );
+        names.extend(result.type_names.keys().cloned());
+        names.extend(
+            result
+                .reexports
+                .iter()
+                .map(|reexport| reexport.export_as.clone()),
+        );
+    }
+
+    for result in results.iter_mut() {
+        if result.globs.unresolved.is_empty() {
+            continue;
+        }
+        let mut resolved_any = false;
+        for name in result.globs.unresolved.iter() {
+            let module = result.globs.modules.iter().find(|module| {
+                exported
+                    .get(&(result.crate_name.clone(), module.to_vec()))
+                    .map(|names| names.contains(name))
+                    .unwrap_or(false)
+            });
+            match module {
+                Some(module) => {
+                    let entry = ImportEntry {
+                        path: Some(result.crate_name.clone())
+                            .into_iter()
+                            .chain(module.iter().cloned())
+                            .collect::<Vec<_>>()
+                            .join("::"),
+                        ident: name.clone(),
+                        alias: None,
+                    };
+                    if !result.import_entries.contains(&entry) {
+                        result.import_entries.push(entry);
+                        resolved_any = true;
+                    }
+                }
+                None => log::warn!(
+                    "{} is not declared by the modules imported with a glob by {}",
+                    name,
+                    module_segments(&result.path).join("::")
+                ),
+            }
+        }
+        if resolved_any {
+            result.imports = import_statements(&result.import_entries, path_mapper)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,502 @@
+//! Generation of mock factories for the exported declarations, see [ExporterOptions::mocks](crate::contexts::exporter::ExporterOptions::mocks)
+//!
+//! Each interface, type alias and const enum gets a `mock<Name>` function, that returns a plausible value of the type :
+//!
+//! ```text
+//! export function mockUser(overrides?: Partial<User>): User {
+//!     return { name: "name", age: 0, tags: [], role: mockRole(), ...overrides };
+//! }
+//! ```
+//!
+//! * numbers are `0`, booleans `false`, strings are named after their property, arrays are empty,
+//! * the optional properties are omitted, and the nullable types are `null`,
+//! * the first member of a union is used, so that the tags of enums are literals,
+//! * the declarations of the crate are mocked by their own factory, which is imported along with the type.
+//!
+//! The values that can't be built (generic parameters, types of other crates...) are asserted as `undefined`.
+use std::{collections::HashSet, convert::TryFrom, str::FromStr};
+
+use ts_json_subset::{
+    common::{NumericLiteral, StringLiteral},
+    declarations::{
+        const_enum::ConstEnumDeclaration,
+        enumeration::EnumDeclaration,
+        function::{FunctionDeclaration, FunctionParameter},
+    },
+    export::ExportStatement,
+    expressions::{
+        ArrayLiteral, AsExpression, CallExpression, CoalesceExpression, Expression,
+        MemberExpression, ObjectLiteral, ObjectLiteralMember,
+    },
+    ident::TSIdent,
+    import::{ImportKind, ImportSpecifier, ImportStatement},
+    types::{
+        LiteralType, PredefinedType, PrimaryType, TsType, TypeArguments, TypeMember,
+        TypeParameters, TypeReference,
+    },
+};
+
+use super::module_step::ModuleStepResultData;
+use crate::{
+    error::TsExportError,
+    type_solving::ImportEntry,
+    utils::crate_paths::{module_segments, resolve_local_path},
+};
+
+const OVERRIDES: &str = "overrides";
+
+/// The name of the mock factory of a declaration
+pub fn mock_name(ident: &str) -> String {
+    format!("mock{}", ident)
+}
+
+/// Appends the mock factories of the declarations of each module, importing the factories of the other modules of the crate
+pub fn add_mocks(results: &mut [ModuleStepResultData]) -> Result<(), TsExportError> {
+    for result in results.iter_mut() {
+        let module_path = module_segments(&result.path);
+        let local: HashSet<String> = result
+            .exports
+            .iter()
+            .filter(|export| mock_target(export).is_some())
+            .filter_map(|export| export.ident())
+            .map(|ident| ident.to_string())
+            .collect();
+        let imported: Vec<ImportEntry> = result
+            .import_entries
+            .iter()
+            .filter(|entry| resolve_local_path(entry, &module_path, &result.crate_name).is_some())
+            .cloned()
+            .collect();
+
+        let mut mocker = Mocker {
+            local: &local,
+            imported: &imported,
+            type_params: Vec::new(),
+            used_imports: Vec::new(),
+        };
+        let mocks = result
+            .exports
+            .iter()
+            .filter_map(|export| mocker.mock_declaration(export).transpose())
+            .collect::<Result<Vec<_>, TsExportError>>()?;
+        let used_imports = mocker.used_imports;
+
+        for entry in used_imports {
+            let mock = mock_name(&entry.ident);
+            let alias = entry.alias.as_deref().map(mock_name);
+            if let Some(statement) = result
+                .imports
+                .iter_mut()
+                .find(|statement| imports(statement, entry.local_name()))
+            {
+                if let ImportKind::ImportList(list) = &mut statement.import_kind {
+                    list.items.push(ImportSpecifier {
+                        ident: TSIdent::from_str(&mock)?,
+                        alias: alias.as_deref().map(TSIdent::from_str).transpose()?,
+                    });
+                }
+            }
+            result
+                .value_imports
+                .insert(alias.clone().unwrap_or_else(|| mock.clone()));
+            result.import_entries.push(ImportEntry {
+                path: entry.path,
+                ident: mock,
+                alias,
+            });
+        }
+        result.exports.extend(mocks);
+    }
+    Ok(())
+}
+
+/// Whether an import statement imports `ident`
+pub(crate) fn imports(statement: &ImportStatement, ident: &str) -> bool {
+    match &statement.import_kind {
+        ImportKind::ImportList(list) => list
+            .items
+            .iter()
+            .any(|item| item.local_name().to_string() == ident),
+        _ => false,
+    }
+}
+
+/// The declarations that are mocked : their ident and type parameters
+fn mock_target(export: &ExportStatement) -> Option<(&TSIdent, &Option<TypeParameters>)> {
+    match export {
+        ExportStatement::InterfaceDeclaration(declaration) => {
+            Some((&declaration.ident, &declaration.type_params))
+        }
+        ExportStatement::TypeAliasDeclaration(declaration) => {
+            Some((&declaration.ident, &declaration.type_params))
+        }
+        ExportStatement::EnumDeclaration(EnumDeclaration { ident, .. })
+        | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, .. }) => {
+            Some((ident, &None))
+        }
+        _ => None,
+    }
+}
+
+struct Mocker<'a> {
+    /// The declarations of the module that are mocked
+    local: &'a HashSet<String>,
+    /// The items imported from the other modules of the crate
+    imported: &'a [ImportEntry],
+    /// The type parameters of the current declaration
+    type_params: Vec<String>,
+    /// The imported items whose factory is used
+    used_imports: Vec<ImportEntry>,
+}
+
+impl Mocker<'_> {
+    fn mock_declaration(
+        &mut self,
+        export: &ExportStatement,
+    ) -> Result<Option<ExportStatement>, TsExportError> {
+        let (ident, type_params) = match mock_target(export) {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        self.type_params = type_params
+            .iter()
+            .flat_map(|params| params.parameters.iter())
+            .map(|param| param.identifier.to_string())
+            .collect();
+        let return_type = reference(
+            ident.clone(),
+            self.type_params
+                .iter()
+                .map(|param| Ok(reference(TSIdent::from_str(param)?, Vec::new())))
+                .collect::<Result<Vec<_>, TsExportError>>()?,
+        );
+        let overrides: Expression = TSIdent::from_str(OVERRIDES)?.into();
+
+        let (param_type, body) = match export {
+            ExportStatement::InterfaceDeclaration(declaration) => {
+                let mut members = self.mock_members(&declaration.obj_type.body.members)?;
+                members.push(ObjectLiteralMember::Spread(overrides));
+                (
+                    partial(return_type.clone())?,
+                    ObjectLiteral { members }.into(),
+                )
+            }
+            ExportStatement::TypeAliasDeclaration(declaration) => {
+                match self.mock_type(&declaration.inner_type, &ident.to_string())? {
+                    Expression::ObjectLiteral(mut object) => {
+                        object.members.push(ObjectLiteralMember::Spread(overrides));
+                        // The spread of a partial union is not narrowed by TS
+                        let body = AsExpression {
+                            expression: Box::new(object.into()),
+                            as_type: return_type.clone(),
+                        };
+                        (partial(return_type.clone())?, body.into())
+                    }
+                    // The primitive of a branded type is asserted as the type
+                    value if matches!(declaration.inner_type, TsType::IntersectionType(_)) => {
+                        let value = AsExpression {
+                            expression: Box::new(value),
+                            as_type: return_type.clone(),
+                        };
+                        (return_type.clone(), coalesce(overrides, value.into()))
+                    }
+                    value => (return_type.clone(), coalesce(overrides, value)),
+                }
+            }
+            ExportStatement::EnumDeclaration(EnumDeclaration { ident, body, .. })
+            | ExportStatement::ConstEnumDeclaration(ConstEnumDeclaration { ident, body }) => {
+                let value = match body.variants.first() {
+                    Some(variant) => MemberExpression {
+                        object: ident.clone(),
+                        member: variant.ident.clone(),
+                    }
+                    .into(),
+                    None => undefined_as(return_type.clone()),
+                };
+                (return_type.clone(), coalesce(overrides, value))
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(
+            FunctionDeclaration {
+                ident: TSIdent::from_str(&mock_name(&ident.to_string()))?,
+                type_params: type_params.clone(),
+                params: vec![FunctionParameter {
+                    name: TSIdent::from_str(OVERRIDES)?,
+                    optional: true,
+                    param_type,
+                }],
+                return_type,
+                body,
+            }
+            .into(),
+        ))
+    }
+
+    fn mock_members(
+        &mut self,
+        members: &[TypeMember],
+    ) -> Result<Vec<ObjectLiteralMember>, TsExportError> {
+        members
+            .iter()
+            .filter_map(|member| match member {
+                TypeMember::PropertySignature(property) if !property.optional => Some(property),
+                _ => None,
+            })
+            .map(|property| {
+                let value = self.mock_type(&property.inner_type, &property.name.to_string())?;
+                Ok(ObjectLiteralMember::Property(property.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// A value of the type, `hint` naming the strings
+    fn mock_type(&mut self, ty: &TsType, hint: &str) -> Result<Expression, TsExportError> {
+        Ok(match ty {
+            TsType::PrimaryType(primary) => self.mock_primary_type(primary, hint)?,
+            TsType::UnionType(union) => {
+                let nullable = union.types.iter().any(|ty| {
+                    matches!(
+                        ty,
+                        TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Null))
+                    )
+                });
+                match union.types.first() {
+                    Some(_) if nullable => Expression::Null,
+                    Some(first) => self.mock_type(first, hint)?,
+                    None => undefined_as(ty.clone()),
+                }
+            }
+            TsType::IntersectionType(intersection) => {
+                let mut members = Vec::new();
+                for ty in intersection.types.iter() {
+                    // A branded primitive, `number & { __brand: ... }`, is a value of the primitive
+                    if let TsType::PrimaryType(PrimaryType::Predefined(_)) = ty {
+                        return self.mock_type(ty, hint);
+                    }
+                    // The object literals are merged, the other values are spread
+                    match self.mock_type(ty, hint)? {
+                        Expression::ObjectLiteral(object) => members.extend(object.members),
+                        value => members.push(ObjectLiteralMember::Spread(value)),
+                    }
+                }
+                ObjectLiteral { members }.into()
+            }
+            TsType::ParenthesizedType(parenthesized) => {
+                self.mock_type(parenthesized.inner.as_ref(), hint)?
+            }
+        })
+    }
+
+    fn mock_primary_type(
+        &mut self,
+        ty: &PrimaryType,
+        hint: &str,
+    ) -> Result<Expression, TsExportError> {
+        Ok(match ty {
+            PrimaryType::Predefined(predefined) => match predefined {
+                PredefinedType::Number => {
+                    LiteralType::NumericLiteral(NumericLiteral::try_from(0.0).expect("0 is finite"))
+                        .into()
+                }
+                PredefinedType::BigInt => CallExpression {
+                    callee: Box::new(TSIdent::from_str("BigInt")?.into()),
+                    type_args: None,
+                    args: vec![LiteralType::NumericLiteral(
+                        NumericLiteral::try_from(0.0).expect("0 is finite"),
+                    )
+                    .into()],
+                }
+                .into(),
+                PredefinedType::Boolean => LiteralType::BooleanLiteral(false.into()).into(),
+                PredefinedType::String => {
+                    LiteralType::StringLiteral(StringLiteral::from_raw(hint.trim_matches('"')))
+                        .into()
+                }
+                PredefinedType::Null | PredefinedType::Any | PredefinedType::Unknown => {
+                    Expression::Null
+                }
+                PredefinedType::Never => undefined_as(TsType::PrimaryType(ty.clone())),
+            },
+            PrimaryType::LiteralType(literal) => literal.clone().into(),
+            PrimaryType::ObjectType(object) => ObjectLiteral {
+                members: self.mock_members(&object.body.members)?,
+            }
+            .into(),
+            PrimaryType::ArrayType(_) => ArrayLiteral {
+                elements: Vec::new(),
+            }
+            .into(),
+            PrimaryType::TupleType(tuple) => ArrayLiteral {
+                elements: tuple
+                    .inner_types
+                    .iter()
+                    .map(|ty| self.mock_type(ty, hint))
+                    .collect::<Result<_, _>>()?,
+            }
+            .into(),
+            PrimaryType::TypeReference(reference) => self.mock_reference(reference)?,
+            PrimaryType::MappedType(_) | PrimaryType::RawType(_) => {
+                undefined_as(TsType::PrimaryType(ty.clone()))
+            }
+        })
+    }
+
+    fn mock_reference(&mut self, reference: &TypeReference) -> Result<Expression, TsExportError> {
+        let name = reference.name.to_string();
+        if name == "Record" {
+            return Ok(ObjectLiteral {
+                members: Vec::new(),
+            }
+            .into());
+        }
+        let is_local = self.local.contains(&name) && !self.type_params.contains(&name);
+        let import = self
+            .imported
+            .iter()
+            .find(|entry| entry.local_name() == name);
+        if !is_local {
+            match import {
+                Some(entry) => {
+                    if !self.used_imports.contains(entry) {
+                        self.used_imports.push(entry.clone());
+                    }
+                }
+                None => {
+                    return Ok(undefined_as(TsType::PrimaryType(
+                        PrimaryType::TypeReference(reference.clone()),
+                    )))
+                }
+            }
+        }
+        Ok(CallExpression {
+            callee: Box::new(TSIdent::from_str(&mock_name(&name))?.into()),
+            type_args: reference.args.clone(),
+            args: Vec::new(),
+        }
+        .into())
+    }
+}
+
+fn reference(name: TSIdent, args: Vec<TsType>) -> TsType {
+    TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+        name,
+        args: if args.is_empty() {
+            None
+        } else {
+            Some(TypeArguments { types: args })
+        },
+    }))
+}
+
+fn partial(ty: TsType) -> Result<TsType, TsExportError> {
+    Ok(reference(TSIdent::from_str("Partial")?, vec![ty]))
+}
+
+fn coalesce(overrides: Expression, value: Expression) -> Expression {
+    CoalesceExpression {
+        left: Box::new(overrides),
+        right: Box::new(value),
+    }
+    .into()
+}
+
+/// `(undefined as unknown as T)`, for the values that can't be built
+fn undefined_as(ty: TsType) -> Expression {
+    AsExpression {
+        expression: Box::new(
+            AsExpression {
+                expression: Box::new(Expression::Undefined),
+                as_type: TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Unknown)),
+            }
+            .into(),
+        ),
+        as_type: ty,
+    }
+    .into()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::tests::{collect, launch_test_module},
+    };
+
+    #[test]
+    fn should_add_mocks() {
+        let result = launch_test_module(
+            r#"
+            use crate::models::Role;
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub enum Role { Admin, User }
+            }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type")]
+            pub enum Event { Created { id: u32 }, Deleted }
+
+            #[derive(Serialize)]
+            pub struct User {
+                name: String,
+                role: Role,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                nickname: Option<String>,
+                manager: Option<Page<User>>,
+                events: Vec<Event>,
+            }
+
+            #[derive(Serialize)]
+            pub struct Page<T> { items: Vec<T>, current: T }
+
+            #[derive(Serialize)]
+            #[ts(nominal)]
+            pub struct UserId(u32);
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        add_mocks(&mut results).unwrap();
+
+        let rendered: Vec<String> = results
+            .iter()
+            .map(|result| {
+                result
+                    .imports
+                    .iter()
+                    .map(|import| import.to_string())
+                    .chain(result.exports.iter().map(|export| export.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect();
+        assert_eq!(
+            rendered[0],
+            "export type Role = \"Admin\" | \"User\";\n\
+             export function mockRole(overrides?: Role): Role {\n\treturn overrides ?? \"Admin\";\n}"
+        );
+        assert_eq!(
+            rendered[1].lines().next().unwrap(),
+            "import { Role, mockRole } from \"my_crate::models\";"
+        );
+        let mocks: Vec<&str> = rendered[1]
+            .split("export function ")
+            .skip(1)
+            .map(|mock| mock.lines().nth(1).unwrap().trim())
+            .collect();
+        assert_eq!(
+            mocks,
+            vec![
+                "return ({ type: \"Created\", id: 0, ...overrides } as Event);",
+                "return { name: \"name\", role: mockRole(), manager: null, events: [], ...overrides };",
+                "return { items: [], current: ((undefined as unknown) as T), ...overrides };",
+                "return overrides ?? (0 as UserId);",
+            ]
+        );
+    }
+}
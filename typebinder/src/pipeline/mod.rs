@@ -1,29 +1,54 @@
 //! The core logic of `typebinder`
 
+use std::collections::BTreeSet;
+
 use crate::{
-    contexts::type_solving::TypeSolvingContext, error::TsExportError, exporters::Exporter,
-    macros::context::MacroSolvingContext, path_mapper::PathMapper,
+    contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContext},
+    error::TsExportError,
+    exporters::Exporter,
+    macros::context::MacroSolvingContext,
+    path_mapper::PathMapper,
     step_spawner::PipelineStepSpawner,
+    utils::{crate_paths::module_segments, display_path::DisplayPath},
 };
-use syn::{punctuated::Punctuated, Path};
 
-use self::module_step::{ModuleStepResult, ModuleStepResultData};
+use self::{
+    module_step::{spawn_timed, ModuleStepResult, ModuleStepResultData},
+    profile::{timed, Profile},
+    report::{ModuleFailure, RunReport, Warning},
+};
 
+pub mod ambient;
+pub mod cfg;
+pub mod codecs;
+pub mod filter;
+pub mod globs;
+pub mod mocks;
 pub mod module_step;
+pub mod phantoms;
+pub mod profile;
+pub mod reexports;
+pub mod report;
 pub mod step_result;
+pub mod type_names;
+pub mod warp_endpoints;
+pub mod watch;
 
 /// The Pipeline is the starting point of `typebinder`.
 ///
 /// A Pipeline is customized with both a [PipelineStepSpawner] and an [Exporter] implementor.
 ///
-/// When launched, the [Pipeline] will use its [PipelineStepSpawner] to spawn the "default" module, that is, the module with an empty path.
+/// When launched, the [Pipeline] will use its [PipelineStepSpawner] to spawn the "default" module, that is, the module with an empty path,
+/// or the roots given by [PipelineStepSpawner::roots]. All the roots share the same contexts, so that the references between them are solved.
 /// Each [ModuleStep](crate::pipeline::module_step::ModuleStep) thereby generated is then launched, see [ModuleStep::launch](crate::pipeline::module_step::ModuleStep).  
 ///
-/// Each output is passed to the [Exporter], that is responsible for outputting the data.
+/// Each output is passed to the [Exporter], that is responsible for outputting the data,
+/// and [Exporter::finish] is called once all the modules are exported.
 pub struct Pipeline<PSS, E> {
     pub pipeline_step_spawner: PSS,
     pub exporter: E,
     pub path_mapper: PathMapper,
+    pub options: ExporterOptions,
 }
 
 impl<PSS, E> Pipeline<PSS, E>
@@ -33,43 +58,573 @@ where
     TsExportError: From<PSS::Error> + From<E::Error>,
 {
     pub fn launch(
-        &self,
+        &mut self,
         solving_context: &TypeSolvingContext,
         macro_context: &MacroSolvingContext,
-    ) -> Result<(), TsExportError> {
-        let path = Path {
-            leading_colon: None,
-            segments: Punctuated::default(),
-        };
-
-        let res = self
-            .pipeline_step_spawner
-            .create_process(path)?
-            .ok_or(TsExportError::FailedToLaunch)?
-            .launch(
-                &self.pipeline_step_spawner,
-                solving_context,
-                macro_context,
-                &self.path_mapper,
-            )?;
+    ) -> Result<RunReport, TsExportError> {
         let mut all_results: Vec<ModuleStepResultData> = Vec::new();
-        extractor(&mut all_results, res);
+        let mut failures: Vec<ModuleFailure> = Vec::new();
+        let mut warnings: Vec<Warning> = Vec::new();
+        let mut profile = if self.options.profile {
+            Some(Profile::default())
+        } else {
+            None
+        };
+        self.pipeline_step_spawner.start_run();
+        for path in self.pipeline_step_spawner.roots() {
+            let res = spawn_timed(&self.pipeline_step_spawner, path, None)?
+                .ok_or(TsExportError::FailedToLaunch)?
+                .launch(
+                    &self.pipeline_step_spawner,
+                    solving_context,
+                    macro_context,
+                    &self.path_mapper,
+                    &self.options,
+                )?;
+            extractor(
+                &mut all_results,
+                &mut failures,
+                &mut warnings,
+                &mut profile,
+                res,
+            );
+        }
+        globs::resolve_glob_imports(&mut all_results, &self.path_mapper)?;
+        reexports::resolve_reexported_imports(&mut all_results, &self.path_mapper)?;
+        phantoms::drop_phantom_arguments(&mut all_results);
+        warnings.extend(filter::report_dangling_references(&all_results));
+        type_names::rename_references(&mut all_results)?;
+        let external_crates = external_crates(&all_results);
+        if self.options.mocks {
+            mocks::add_mocks(&mut all_results)?;
+        }
+        if let Some(library) = self.options.codecs {
+            codecs::add_codecs(&mut all_results, library)?;
+        }
+        if self.options.declaration_files {
+            ambient::strip_values(&mut all_results);
+        }
 
+        let all_results: Vec<ModuleStepResultData> = all_results
+            .into_iter()
+            .filter(|result| !result.imports.is_empty() || !result.exports.is_empty())
+            .collect();
+        self.exporter.start_run();
+        self.exporter.plan_modules(
+            &all_results
+                .iter()
+                .map(|result| module_segments(&result.path))
+                .collect::<Vec<_>>(),
+        );
         for result_data in all_results.into_iter() {
-            if result_data.imports.is_empty() && result_data.exports.is_empty() {
-                continue;
+            let path = DisplayPath(&result_data.path).to_string();
+            let (exported, time) = timed(|| self.exporter.export_module(result_data));
+            exported?;
+            if let Some(module) = profile
+                .as_mut()
+                .and_then(|profile| profile.module_mut(&path))
+            {
+                module.exporting = time;
             }
-            self.exporter.export_module(result_data)?;
         }
+        self.exporter.finish()?;
 
-        Ok(())
+        Ok(RunReport {
+            profile,
+            failures,
+            warnings,
+            external_crates,
+        })
     }
 }
 
+/// The crates the modules import from, other than the standard library and the crates of the modules
+fn external_crates(results: &[ModuleStepResultData]) -> BTreeSet<String> {
+    let local_crates: BTreeSet<&str> = results
+        .iter()
+        .map(|result| result.crate_name.as_str())
+        .collect();
+    results
+        .iter()
+        .flat_map(|result| result.import_entries.iter())
+        .filter_map(|entry| entry.path.split("::").next())
+        .filter(|name| {
+            !local_crates.contains(name)
+                && !["std", "core", "alloc", "crate", "self", "super"].contains(name)
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
 /// TODO: refactor this to a closure
-fn extractor(all: &mut Vec<ModuleStepResultData>, iter: ModuleStepResult) {
+fn extractor(
+    all: &mut Vec<ModuleStepResultData>,
+    failures: &mut Vec<ModuleFailure>,
+    warnings: &mut Vec<Warning>,
+    profile: &mut Option<Profile>,
+    iter: ModuleStepResult,
+) {
     iter.children
         .into_iter()
-        .for_each(|child| extractor(all, child));
+        .for_each(|child| extractor(all, failures, warnings, profile, child));
+    failures.extend(iter.failures);
+    warnings.extend(iter.warnings);
+    if let (Some(profile), Some(module_profile)) = (profile.as_mut(), iter.profile) {
+        profile.modules.extend(module_profile.modules);
+        profile.add_solvers(module_profile.solvers);
+    }
     all.push(iter.data);
 }
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        contexts::{
+            exporter::{TypeNamePolicy, UnsolvedTypePolicy},
+            type_solving::TypeSolvingContextBuilder,
+        },
+        pipeline::{module_step::ModuleStep, report::Recovery},
+    };
+    use syn::Path;
+
+    /// Spawns the modules from their sources, by path
+    struct SourceSpawner(HashMap<String, &'static str>);
+
+    impl PipelineStepSpawner for SourceSpawner {
+        type Error = TsExportError;
+
+        fn create_process(&self, path: Path) -> Result<Option<ModuleStep>, TsExportError> {
+            let source = match self.0.get(&DisplayPath(&path).to_string()) {
+                Some(source) => source,
+                None => return Ok(None),
+            };
+            let items = syn::parse_file(source)?.items;
+            Ok(Some(ModuleStep::new(path, items, "my_crate")))
+        }
+    }
+
+    /// Records the paths of the exported modules, and whether the run is finished
+    #[derive(Default)]
+    struct PathsExporter(Vec<String>, bool);
+
+    impl Exporter for PathsExporter {
+        type Error = TsExportError;
+
+        fn export_module(
+            &mut self,
+            process_result: ModuleStepResultData,
+        ) -> Result<(), TsExportError> {
+            self.0.push(DisplayPath(&process_result.path).to_string());
+            Ok(())
+        }
+
+        fn finish(&mut self) -> Result<(), TsExportError> {
+            self.1 = true;
+            Ok(())
+        }
+    }
+
+    fn pipeline(skip_failing_modules: bool) -> Pipeline<SourceSpawner, PathsExporter> {
+        let sources = vec![
+            ("", "mod broken; mod models;"),
+            ("broken", "pub struct Broken {"),
+            (
+                "models",
+                "#[derive(Serialize)] pub struct User { name: String }",
+            ),
+        ];
+        Pipeline {
+            pipeline_step_spawner: SourceSpawner(
+                sources
+                    .into_iter()
+                    .map(|(path, source)| (path.to_string(), source))
+                    .collect(),
+            ),
+            exporter: PathsExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions {
+                skip_failing_modules,
+                ..ExporterOptions::default()
+            },
+        }
+    }
+
+    #[test]
+    fn should_skip_failing_modules() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let macro_context = MacroSolvingContext::default();
+
+        assert!(pipeline(false)
+            .launch(&solving_context, &macro_context)
+            .is_err());
+
+        let mut pipeline = pipeline(true);
+        let report = pipeline
+            .launch(&solving_context, &macro_context)
+            .expect("Failed to launch");
+        assert_eq!(pipeline.exporter.0, vec!["models".to_string()]);
+        assert!(pipeline.exporter.1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].path, "broken");
+    }
+
+    #[test]
+    fn should_recover_from_the_unsolved_types() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let macro_context = MacroSolvingContext::default();
+        let launch = |unsolved_types| {
+            let sources = vec![(
+                "",
+                "#[derive(Serialize)] pub struct Handle { id: u32, raw: Vec<*const u8> }
+                #[derive(Serialize)] pub struct User { name: String }",
+            )];
+            let mut pipeline = Pipeline {
+                pipeline_step_spawner: SourceSpawner(
+                    sources
+                        .into_iter()
+                        .map(|(path, source)| (path.to_string(), source))
+                        .collect(),
+                ),
+                exporter: SourcesExporter::default(),
+                path_mapper: PathMapper::default(),
+                options: ExporterOptions {
+                    unsolved_types,
+                    ..ExporterOptions::default()
+                },
+            };
+            pipeline
+                .launch(&solving_context, &macro_context)
+                .map(|report| (pipeline.exporter.0, report.warnings))
+        };
+
+        assert!(launch(UnsolvedTypePolicy::Error).is_err());
+
+        let (sources, warnings) = launch(UnsolvedTypePolicy::Unknown).expect("Failed to launch");
+        assert_eq!(
+            sources,
+            vec!["export interface Handle {\n\tid: number,\n\traw: unknown[]\n}\nexport interface User {\n\tname: string\n}"]
+        );
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].error, "Could not resolve type `*const u8`");
+        assert_eq!(warnings[0].recovery, Recovery::Unknown);
+        assert_eq!(warnings[0].container.as_deref(), Some("Handle"));
+        assert_eq!(warnings[0].field.as_deref(), Some("raw"));
+
+        let (sources, warnings) = launch(UnsolvedTypePolicy::Skip).expect("Failed to launch");
+        assert_eq!(sources, vec!["export interface User {\n\tname: string\n}"]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].recovery, Recovery::Skipped);
+        assert_eq!(warnings[0].container.as_deref(), Some("Handle"));
+    }
+
+    /// Starts from the given roots instead of the root module
+    struct RootsSpawner(SourceSpawner, Vec<&'static str>);
+
+    impl PipelineStepSpawner for RootsSpawner {
+        type Error = TsExportError;
+
+        fn create_process(&self, path: Path) -> Result<Option<ModuleStep>, TsExportError> {
+            self.0.create_process(path)
+        }
+
+        fn roots(&self) -> Vec<Path> {
+            self.1
+                .iter()
+                .map(|root| syn::parse_str(root).expect("Invalid root"))
+                .collect()
+        }
+    }
+
+    #[derive(Default)]
+    struct SourcesExporter(Vec<String>);
+
+    impl Exporter for SourcesExporter {
+        type Error = TsExportError;
+
+        fn export_module(
+            &mut self,
+            process_result: ModuleStepResultData,
+        ) -> Result<(), TsExportError> {
+            let source = process_result
+                .imports
+                .iter()
+                .map(|import| import.to_string())
+                .chain(
+                    process_result
+                        .exports
+                        .iter()
+                        .map(|export| export.to_string()),
+                )
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.0.push(source);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_launch_several_roots() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![
+            (
+                "api",
+                "use crate::events::Event;
+                #[derive(Serialize)] pub struct Request { event: Event }",
+            ),
+            (
+                "events",
+                "#[derive(Serialize)] #[serde(rename = \"EventDto\")] pub struct Event { name: String }",
+            ),
+        ];
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: RootsSpawner(
+                SourceSpawner(
+                    sources
+                        .into_iter()
+                        .map(|(path, source)| (path.to_string(), source))
+                        .collect(),
+                ),
+                vec!["api", "events"],
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions {
+                type_names: TypeNamePolicy::SerdeName,
+                ..ExporterOptions::default()
+            },
+        };
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        assert_eq!(
+            pipeline.exporter.0,
+            vec![
+                "import { EventDto } from \"my_crate::events\";\n\
+                 export interface Request {\n\tevent: EventDto\n}"
+                    .to_string(),
+                "export interface EventDto {\n\tname: string\n}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_import_each_module_of_the_crate_once() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![
+            (
+                "module_a",
+                "use crate::module_b::TypeB;
+                #[derive(Serialize)]
+                pub struct TypeA { b: TypeB, c: crate::module_b::TypeC, d: super::module_c::TypeD, a: self::Other }
+                #[derive(Serialize)] pub struct Other;",
+            ),
+            (
+                "module_b",
+                "#[derive(Serialize)] pub struct TypeB; #[derive(Serialize)] pub struct TypeC;",
+            ),
+            ("module_c", "#[derive(Serialize)] pub struct TypeD;"),
+        ];
+        let mut path_mapper = PathMapper::default();
+        path_mapper.add_mapping("my_crate::module_c", "@shared/c");
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: RootsSpawner(
+                SourceSpawner(
+                    sources
+                        .into_iter()
+                        .map(|(path, source)| (path.to_string(), source))
+                        .collect(),
+                ),
+                vec!["module_a"],
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper,
+            options: ExporterOptions::default(),
+        };
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        let sources = &pipeline.exporter.0;
+        assert!(
+            sources[0].starts_with(
+                "import { TypeB, TypeC } from \"my_crate::module_b\";\n\
+                 import { TypeD } from \"@shared/c\";\n\
+                 export interface TypeA"
+            ),
+            "{}",
+            sources[0]
+        );
+    }
+
+    #[test]
+    fn should_import_the_reexported_types_from_their_module() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![
+            (
+                "api",
+                "use crate::prelude::{Member, Event};
+                #[derive(Serialize)] pub struct Request { member: Member, event: Event }",
+            ),
+            (
+                "prelude",
+                "pub use crate::models::User as Member; pub use crate::events::Event;",
+            ),
+            ("events", "pub use crate::models::Event;"),
+            (
+                "models",
+                "#[derive(Serialize)] pub struct User; #[derive(Serialize)] pub struct Event;",
+            ),
+        ];
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: RootsSpawner(
+                SourceSpawner(
+                    sources
+                        .into_iter()
+                        .map(|(path, source)| (path.to_string(), source))
+                        .collect(),
+                ),
+                vec!["api", "prelude", "events", "models"],
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions::default(),
+        };
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        assert_eq!(
+            pipeline.exporter.0[0],
+            "import { User, Event } from \"my_crate::models\";\n\
+             export interface Request {\n\tmember: User,\n\tevent: Event\n}"
+        );
+    }
+
+    #[test]
+    fn should_only_reexport_the_exported_declarations() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![
+            (
+                "prelude",
+                "pub use crate::models::{User, default_user}; pub use self::Id;
+                #[derive(Serialize)] pub struct Id(u32);",
+            ),
+            (
+                "models",
+                "pub fn default_user() -> User { User }
+                #[derive(Serialize)] pub struct User;",
+            ),
+        ];
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: RootsSpawner(
+                SourceSpawner(
+                    sources
+                        .into_iter()
+                        .map(|(path, source)| (path.to_string(), source))
+                        .collect(),
+                ),
+                vec!["prelude", "models"],
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions {
+                follow_reexports: true,
+                ..ExporterOptions::default()
+            },
+        };
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        assert_eq!(
+            pipeline.exporter.0,
+            vec![
+                "import { User } from \"my_crate::models\";\nexport { User as User }\nexport type Id = number;"
+                    .to_string(),
+                "export type User = null;".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_import_the_types_of_the_glob_imports() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![
+            (
+                "",
+                "mod models; mod events;
+                #[derive(Serialize)] pub struct Id { value: u32 }",
+            ),
+            (
+                "models",
+                "use super::*; use crate::events::*;
+                #[derive(Serialize)] pub struct User { id: Id, last_event: Event }",
+            ),
+            ("events", "#[derive(Serialize)] pub struct Event;"),
+        ];
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: SourceSpawner(
+                sources
+                    .into_iter()
+                    .map(|(path, source)| (path.to_string(), source))
+                    .collect(),
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions::default(),
+        };
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        assert_eq!(
+            pipeline.exporter.0[0],
+            "import { Id } from \"my_crate\";\n\
+             import { Event } from \"my_crate::events\";\n\
+             export interface User {\n\tid: Id,\n\tlast_event: Event\n}"
+        );
+    }
+
+    #[test]
+    fn should_report_the_external_crates() {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let sources = vec![(
+            "",
+            "use other_crate::models::Dto; mod models;
+            #[derive(Serialize)] pub struct Request { dto: Dto, user: crate::models::User, name: String }",
+        ), ("models", "#[derive(Serialize)] pub struct User;")];
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: SourceSpawner(
+                sources
+                    .into_iter()
+                    .map(|(path, source)| (path.to_string(), source))
+                    .collect(),
+            ),
+            exporter: SourcesExporter::default(),
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions::default(),
+        };
+        let report = pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .expect("Failed to launch");
+        assert_eq!(
+            report.external_crates.into_iter().collect::<Vec<_>>(),
+            vec!["other_crate".to_string()]
+        );
+    }
+}
@@ -1,22 +1,57 @@
-use std::str::FromStr;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    attributes::{
+        apply_rename_all_fields, serde_container, take_untagged_variants, ContainerAttributes,
+    },
     contexts::import::ImportContext,
-    contexts::{exporter::ExporterContext, type_solving::TypeSolvingContext},
+    contexts::{
+        exporter::{
+            phantom_type_parameters, Direction, ExporterContext, ExporterOptions,
+            ReadonlyProperties, TypeNamePolicy, UnsolvedTypePolicy,
+        },
+        type_solving::TypeSolvingContext,
+    },
+    diagnostic::SourceFile,
     error::TsExportError,
     macros::context::MacroSolvingContext,
     path_mapper::PathMapper,
     step_spawner::PipelineStepSpawner,
-    type_solving::ImportEntry,
+    type_solving::{result::Solved, ImportEntry},
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        display_path::DisplayPath,
+        rename::ReferenceRenamer,
+    },
 };
 use indexmap::{IndexMap, IndexSet};
 use result::prelude::*;
-use serde_derive_internals::{ast::Container, Ctxt, Derive};
-use syn::{DeriveInput, Item, ItemMacro, ItemMod, ItemType, Path, PathArguments, PathSegment};
+
+use super::{
+    filter::ProcessFilter,
+    globs::GlobImports,
+    profile::{timed, ModuleProfile, Profile, SolverProfiler},
+    reexports::{parse_reexports, Reexport},
+    report::{ModuleFailure, Recovery, Warning},
+    warp_endpoints::parse_endpoints,
+};
+use serde_derive_internals::ast::Container;
+use syn::{
+    DeriveInput, Ident, Item, ItemMacro, ItemMod, ItemType, ItemUse, Lit, Meta, MetaNameValue,
+    Path, PathArguments, PathSegment, Type, TypePath,
+};
 use ts_json_subset::{
+    declarations::reexport::{ReexportClause, ReexportDeclaration},
     export::ExportStatement,
     ident::{IdentError, TSIdent},
-    import::{ImportKind, ImportList, ImportStatement},
+    import::{ImportKind, ImportList, ImportSpecifier, ImportStatement},
+    visit_mut::VisitMut,
 };
 
 /// A step of the pipeline that is generated by a PipelineStepSpawner.
@@ -35,11 +70,16 @@ pub struct ModuleStep {
     current_path: Path,
     items: Vec<Item>,
     import_context: ImportContext,
+    crate_name: String,
+    parsing_time: Duration,
+    spawning_time: Duration,
+    source: Option<Arc<SourceFile>>,
 }
 
 impl ModuleStep {
     pub fn new(current_path: syn::Path, items: Vec<Item>, crate_name: &str) -> Self {
         let mut import_context = ImportContext::default();
+        import_context.set_current_module(&current_path, crate_name);
         import_context.parse_imported(&items, crate_name);
         import_context.parse_scoped(&items);
 
@@ -47,26 +87,90 @@ impl ModuleStep {
             current_path,
             items,
             import_context,
+            crate_name: crate_name.to_string(),
+            parsing_time: Duration::default(),
+            spawning_time: Duration::default(),
+            source: None,
         }
     }
 
+    /// The file the module is read from, which locates its errors, see [Diagnostic](crate::diagnostic::Diagnostic).
+    ///
+    /// The inline modules are located in the file of their parent.
+    pub fn set_source(&mut self, source: Arc<SourceFile>) {
+        self.source = Some(source);
+    }
+
+    /// Records the time spent reading and parsing the module, see [ExporterOptions::profile]
+    pub fn set_parsing_time(&mut self, time: Duration) {
+        self.parsing_time = time;
+    }
+
+    pub fn parsing_time(&self) -> Duration {
+        self.parsing_time
+    }
+
+    /// Records the time spent spawning the module, parsing excluded, see [ExporterOptions::profile]
+    pub fn set_spawning_time(&mut self, time: Duration) {
+        self.spawning_time = time;
+    }
+
     pub fn launch<PSS: PipelineStepSpawner>(
         self,
         process_spawner: &PSS,
         solving_context: &TypeSolvingContext,
         macro_context: &MacroSolvingContext,
         path_mapper: &PathMapper,
+        options: &ExporterOptions,
+    ) -> Result<ModuleStepResult, TsExportError> {
+        let source = self.source.clone();
+        self.launch_unlocated(
+            process_spawner,
+            solving_context,
+            macro_context,
+            path_mapper,
+            options,
+        )
+        .map_err(|error| match &source {
+            Some(source) => error.in_file(source),
+            None => error,
+        })
+    }
+
+    fn launch_unlocated<PSS: PipelineStepSpawner>(
+        self,
+        process_spawner: &PSS,
+        solving_context: &TypeSolvingContext,
+        macro_context: &MacroSolvingContext,
+        path_mapper: &PathMapper,
+        options: &ExporterOptions,
     ) -> Result<ModuleStepResult, TsExportError> {
         let ModuleStep {
             current_path,
             import_context,
             items,
+            crate_name,
+            parsing_time,
+            spawning_time,
+            source,
         } = self;
 
+        let items = match &options.cfg {
+            Some(cfg) => cfg.strip_items(items),
+            None => items,
+        };
+
+        // The endpoint map is exported after the declarations of the module
+        let endpoints = options
+            .warp_endpoints
+            .as_deref()
+            .map(|name| (items.len(), name, parse_endpoints(&items)));
+
         let mut derive_inputs: Vec<(usize, DeriveInput)> = Vec::new();
         let mut type_aliases: Vec<(usize, ItemType)> = Vec::new();
         let mut mod_declarations: Vec<ItemMod> = Vec::new();
         let mut macros: Vec<(usize, ItemMacro)> = Vec::new();
+        let mut uses: Vec<(usize, ItemUse)> = Vec::new();
 
         items
             .into_iter()
@@ -83,109 +187,413 @@ impl ModuleStep {
                 Item::Macro(item) => {
                     macros.push((index, item));
                 }
+                Item::Use(item) => {
+                    uses.push((index, item));
+                }
                 _ => {}
             });
 
-        let children: Vec<ModuleStepResult> = mod_declarations
-            .into_iter()
-            .filter_map(|item_mod| {
-                let ident = item_mod.ident;
-                let mut path = current_path.clone();
-                path.segments.push(PathSegment {
-                    ident,
-                    arguments: PathArguments::None,
-                });
-                match item_mod.content {
-                    Some((_, items)) => Some(Ok(ModuleStep::new(path, items, "crate"))),
-                    _ => process_spawner
-                        .create_process(path)
-                        .map_err(|e| e.into())
-                        .invert(),
+        let child_modules: Vec<Ident> = mod_declarations
+            .iter()
+            .map(|item_mod| item_mod.ident.clone())
+            .collect();
+
+        let mut children: Vec<ModuleStepResult> = Vec::new();
+        let mut failures: Vec<ModuleFailure> = Vec::new();
+        for item_mod in mod_declarations {
+            let file_path = path_attribute(&item_mod);
+            let mut path = current_path.clone();
+            path.segments.push(PathSegment {
+                ident: item_mod.ident,
+                arguments: PathArguments::None,
+            });
+            let process_module_result = match item_mod.content {
+                Some((_, items)) => {
+                    let mut process_module = ModuleStep::new(path.clone(), items, &crate_name);
+                    process_module.source = source.clone();
+                    Ok(Some(process_module))
                 }
-            })
-            .map(|process_module_result| {
-                process_module_result.and_then(|process_module| {
-                    process_module.launch(
-                        process_spawner,
-                        solving_context,
-                        macro_context,
-                        path_mapper,
+                _ => spawn_timed(process_spawner, path.clone(), file_path.as_deref())
+                    .map_err(|e| e.into()),
+            };
+            let child = process_module_result.and_then(|process_module| {
+                process_module
+                    .map(|process_module| {
+                        process_module.launch(
+                            process_spawner,
+                            solving_context,
+                            macro_context,
+                            path_mapper,
+                            options,
+                        )
+                    })
+                    .invert()
+            });
+            match child {
+                Ok(Some(child)) => children.push(child),
+                Ok(None) => {}
+                Err(error) if options.skip_failing_modules => {
+                    log::error!("Skipping module {} : {}", DisplayPath(&path), error);
+                    failures.push(ModuleFailure {
+                        path: DisplayPath(&path).to_string(),
+                        error: error.to_string(),
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        if let Some(cfg) = &options.cfg {
+            for (_, derive_input) in derive_inputs.iter_mut() {
+                cfg.strip_derive_input(derive_input);
+            }
+        }
+
+        // The declarations left out by the filter are still known to the solvers, e.g. to be flattened.
+        // Without a filter, only the ones marked `#[ts(skip)]` are left out
+        let module_path = module_segments(&current_path);
+        let default_filter = ProcessFilter::default();
+        let filter = options.filter.as_ref().unwrap_or(&default_filter);
+        let declarations = derive_inputs
+            .iter()
+            .map(|(index, derive_input)| (index, &derive_input.ident, &derive_input.attrs))
+            .chain(
+                type_aliases
+                    .iter()
+                    .map(|(index, item)| (index, &item.ident, &item.attrs)),
+            );
+        let mut filtered_out: HashSet<usize> = HashSet::new();
+        // The names the references to the declarations left out have, in each direction
+        let mut filtered_names: Vec<String> = Vec::new();
+        for (index, ident, attrs) in declarations {
+            if !filter.accepts(&module_path, &ident.to_string(), attrs)? {
+                log::debug!("Filtering out {} of {}", ident, DisplayPath(&current_path));
+                filtered_out.insert(*index);
+                filtered_names.extend(options.process_mode.directions().iter().map(|direction| {
+                    format!(
+                        "{}{}",
+                        ident,
+                        options.declaration_suffix(*direction).unwrap_or_default()
                     )
-                })
+                }));
+            }
+        }
+
+        let solving_start = Instant::now();
+        let untagged_variants = derive_inputs
+            .iter_mut()
+            .map(|(_, derive_input)| {
+                apply_rename_all_fields(derive_input)
+                    .and_then(|_| take_untagged_variants(derive_input))
+                    .map(|variants| (derive_input.ident.to_string(), variants))
+                    .map_err(|error| error.in_container(&derive_input.ident))
             })
-            .collect::<Result<_, _>>()?;
+            .filter(|result| !matches!(result, Ok((_, variants)) if variants.is_empty()))
+            .collect::<Result<HashMap<_, _>, TsExportError>>()?;
 
-        let ctxt = Ctxt::default();
-        let containers: Vec<(usize, Container)> = derive_inputs
+        // The containers parsed for each direction, serde checking their attributes against the derive
+        let directed_containers: Vec<(Direction, Vec<(usize, Container)>)> = options
+            .process_mode
+            .directions()
             .iter()
-            .filter_map(|(index, derive_input)| {
-                Container::from_ast(&ctxt, &derive_input, Derive::Serialize)
-                    .map(|container| (*index, container))
+            .map(|direction| {
+                let containers = derive_inputs
+                    .iter()
+                    .filter(|(index, _)| !filtered_out.contains(index))
+                    .filter_map(|(index, derive_input)| {
+                        serde_container(derive_input, *direction)
+                            .map(|container| (*index, container))
+                    })
+                    .collect();
+                (*direction, containers)
             })
             .collect();
 
-        let exporter = ExporterContext {
-            type_solving_context: &solving_context,
-            macro_context: &macro_context,
-            import_context,
+        let local_declarations: HashMap<String, &DeriveInput> = derive_inputs
+            .iter()
+            .map(|(_, derive_input)| (derive_input.ident.to_string(), derive_input))
+            .collect();
+        let mut type_names: IndexMap<String, String> = match options.type_names {
+            TypeNamePolicy::RustIdent => IndexMap::new(),
+            TypeNamePolicy::SerdeName => directed_containers
+                .iter()
+                .flat_map(|(direction, containers)| {
+                    let suffix = options.declaration_suffix(*direction).unwrap_or_default();
+                    containers.iter().map(move |(_, container)| {
+                        (
+                            format!("{}{}", container.ident, suffix),
+                            format!("{}{}", direction.name_of(container.attrs.name()), suffix),
+                        )
+                    })
+                })
+                .filter(|(ident, name)| ident != name)
+                .collect(),
         };
+        let mut phantom_parameters: IndexMap<String, Vec<usize>> = IndexMap::new();
+        // `#[ts(rename = "...")]` names the declaration whatever the policy
+        for (direction, containers) in directed_containers.iter() {
+            let suffix = options.declaration_suffix(*direction).unwrap_or_default();
+            for (_, container) in containers.iter() {
+                let declaration = format!("{}{}", container.ident, suffix);
+                let attributes = ContainerAttributes::from_attrs(&container.original.attrs)
+                    .map_err(|error| error.in_container(&container.ident))?;
+                let phantoms = phantom_type_parameters(container, &attributes);
+                if !phantoms.is_empty() {
+                    phantom_parameters.insert(declaration.clone(), phantoms);
+                }
+                if let Some(rename) = attributes.rename {
+                    type_names.insert(declaration.clone(), format!("{}{}", rename, suffix));
+                }
+                if let Some(Err(error)) = type_names
+                    .get(&declaration)
+                    .map(|name| TSIdent::from_str(name))
+                {
+                    return Err(TsExportError::InvalidAttribute(format!(
+                        "{}, rename the TS declaration with #[ts(rename = \"...\")]",
+                        error
+                    ))
+                    .in_container(&container.ident));
+                }
+            }
+        }
 
-        let type_export_statements = type_aliases.into_iter().map(|(index, item)| {
-            exporter
-                .export_statements_from_type_alias(item)
-                .map(|statements| (index, statements))
-        });
-        let container_statements = containers.into_iter().map(|(index, container)| {
-            exporter
-                .export_statements_from_container(container)
-                .map(|statements| (index, statements))
-        });
-        let macros_statements = macros.into_iter().map(|(index, item)| {
-            exporter
-                .export_statements_from_macro(&item.into())
-                .map(|statements| (index, statements))
-        });
+        // The remote definitions are only known by the module that declares them
+        let remote_types: HashMap<String, String> = directed_containers[0]
+            .1
+            .iter()
+            .filter_map(|(_, container)| {
+                let remote = container.attrs.remote()?;
+                if let syn::Visibility::Public(_) = container.original.vis {
+                    log::warn!(
+                        "{} is the remote definition of {} in its module only, the fields of other modules are not solved through it",
+                        container.ident,
+                        DisplayPath(remote)
+                    );
+                }
+                Some((remote, container.ident.to_string()))
+            })
+            .flat_map(|(remote, ident)| {
+                let resolved = import_context.solve_import(&TypePath {
+                    qself: None,
+                    path: remote.clone(),
+                });
+                Some(DisplayPath(remote).to_string())
+                    .into_iter()
+                    .chain(resolved.and_then(|resolved| match resolved {
+                        Type::Path(resolved) => Some(DisplayPath(&resolved.path).to_string()),
+                        _ => None,
+                    }))
+                    .map(move |path| (path, ident.clone()))
+            })
+            .collect();
 
         let mut imports: Vec<ImportEntry> = Vec::new();
 
-        let mut statements: Vec<(usize, Vec<ExportStatement>)> = type_export_statements
-            .chain(container_statements)
-            .chain(macros_statements)
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .map(|(index, mut solved)| {
-                imports.append(&mut solved.import_entries);
-                (index, solved.inner)
+        let module_reexports: Vec<(usize, Vec<Reexport>)> = uses
+            .iter()
+            .map(|(index, item_use)| {
+                let reexports =
+                    parse_reexports(item_use, &current_path, &crate_name, &child_modules);
+                (*index, reexports)
             })
             .collect();
 
-        let mut all_imports: IndexMap<String, IndexSet<String>> = IndexMap::default();
-        imports.into_iter().for_each(|entry| {
+        let reexport_statements = if options.follow_reexports {
+            module_reexports
+                .iter()
+                .map(|(index, reexports)| {
+                    let reexports = reexports
+                        .iter()
+                        .map(|reexport| {
+                            let clause = ReexportClause {
+                                scope: TSIdent::from_str(&reexport.ident)?,
+                                export_as: TSIdent::from_str(&reexport.export_as)?,
+                            };
+                            imports.push(ImportEntry {
+                                path: reexport.path.clone(),
+                                ident: reexport.ident.clone(),
+                                alias: None,
+                            });
+                            Ok(clause)
+                        })
+                        .collect::<Result<Vec<ReexportClause>, IdentError>>()?;
+                    let statements = if reexports.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![ReexportDeclaration { reexports }.into()]
+                    };
+                    Ok((*index, statements))
+                })
+                .collect::<Result<Vec<_>, TsExportError>>()?
+        } else {
+            Vec::new()
+        };
+
+        let local_idents: Vec<String> = local_declarations
+            .keys()
+            .cloned()
+            .chain(type_aliases.iter().map(|(_, item)| item.ident.to_string()))
+            .collect();
+        let mut macros = Some(macros);
+        let mut endpoints = Some(endpoints);
+        let mut solver_profilers: Vec<SolverProfiler> = Vec::new();
+        let mut statements: Vec<(usize, Vec<ExportStatement>)> = Vec::new();
+        let mut warnings: Vec<(Recovery, TsExportError)> = Vec::new();
+        for (direction, containers) in directed_containers {
+            let exporter = ExporterContext {
+                type_solving_context: solving_context,
+                macro_context,
+                import_context: import_context.clone(),
+                options,
+                local_declarations: local_declarations.clone(),
+                untagged_variants: untagged_variants.clone(),
+                remote_types: remote_types.clone(),
+                solver_profiler: if options.profile {
+                    Some(SolverProfiler::default())
+                } else {
+                    None
+                },
+                direction,
+                warnings: RefCell::default(),
+            };
+
+            let type_export_statements = type_aliases
+                .iter()
+                .filter(|(index, _)| !filtered_out.contains(index))
+                .map(|(index, item)| {
+                    let solved = exporter.export_statements_from_type_alias(item.clone());
+                    recover_declaration(&exporter, &item.ident, solved)
+                        .map(|statements| (*index, statements))
+                });
+            let container_statements = containers.into_iter().map(|(index, container)| {
+                let ident = container.ident.clone();
+                let solved = exporter.export_statements_from_container(container);
+                recover_declaration(&exporter, &ident, solved).map(|statements| (index, statements))
+            });
+            // The macros and the endpoints are exported once, whatever the direction
+            let macros_statements = macros.take().into_iter().flatten().map(|(index, item)| {
+                exporter
+                    .export_statements_from_macro(&item.into())
+                    .map(|statements| (index, statements))
+            });
+            let endpoint_statements =
+                endpoints
+                    .take()
+                    .into_iter()
+                    .flatten()
+                    .map(|(index, name, endpoints)| {
+                        exporter
+                            .export_statements_from_endpoints(name, &endpoints)
+                            .map(|statements| (index, statements))
+                    });
+
+            let mut solved: Vec<(usize, Solved<Vec<ExportStatement>>)> = type_export_statements
+                .chain(container_statements)
+                .chain(macros_statements)
+                .chain(endpoint_statements)
+                .collect::<Result<Vec<_>, _>>()?;
+            // The endpoint map is declared next to the declarations of the module, none of them can have its name
+            if let Some(name) = &options.warp_endpoints {
+                let declared = solved
+                    .iter()
+                    .flat_map(|(_, solved)| solved.inner.iter())
+                    .filter_map(ExportStatement::ident)
+                    .filter(|ident| ident.to_string() == *name)
+                    .count();
+                if declared > 1 {
+                    return Err(TsExportError::InvalidConfig(format!(
+                        "The endpoints of the module {} are exported as `{}`, which the module already declares, choose another name for the endpoint map",
+                        DisplayPath(&current_path),
+                        name
+                    )));
+                }
+            }
+            if let Some(suffix) = options.declaration_suffix(direction) {
+                suffix_declarations(
+                    &mut solved,
+                    suffix,
+                    &local_idents,
+                    &module_path,
+                    &crate_name,
+                )?;
+            }
+            statements.extend(solved.into_iter().map(|(index, mut solved)| {
+                imports.append(&mut solved.import_entries);
+                (index, solved.inner)
+            }));
+            solver_profilers.extend(exporter.solver_profiler);
+            warnings.extend(exporter.warnings.into_inner());
+        }
+        statements.extend(reexport_statements);
+        if options.readonly {
+            statements
+                .iter_mut()
+                .flat_map(|(_, statements)| statements.iter_mut())
+                .for_each(|statement| ReadonlyProperties.visit_export_statement_mut(statement));
+        }
+
+        let mut all_imports: IndexMap<String, IndexSet<(String, Option<String>)>> =
+            IndexMap::default();
+        // The paths of the modules of the crate are made absolute, so that each module is imported once
+        // and mapped by the PathMapper whether it is referred to with `crate`, `self` or `super`
+        imports.into_iter().for_each(|mut entry| {
+            if let Some(resolved) = resolve_local_path(&entry, &module_path, &crate_name) {
+                entry.path = if resolved == module_path {
+                    String::new()
+                } else {
+                    Some(crate_name.clone())
+                        .into_iter()
+                        .chain(resolved)
+                        .collect::<Vec<_>>()
+                        .join("::")
+                };
+            }
             let hm_entry = all_imports.entry(entry.path).or_default();
-            hm_entry.insert(entry.ident);
+            hm_entry.insert((entry.ident, entry.alias));
         });
 
-        let imports: Vec<ImportStatement> = all_imports
+        // The names that are neither declared nor imported by name may come from the glob imports
+        let globs = GlobImports {
+            modules: import_context
+                .glob_paths()
+                .into_iter()
+                .filter_map(|path| {
+                    let entry = ImportEntry {
+                        path,
+                        ident: String::new(),
+                        alias: None,
+                    };
+                    resolve_local_path(&entry, &module_path, &crate_name)
+                })
+                .filter(|module| module != &module_path)
+                .collect(),
+            unresolved: all_imports
+                .get("")
+                .into_iter()
+                .flatten()
+                .map(|(ident, _)| ident)
+                .filter(|ident| !local_idents.contains(ident))
+                .cloned()
+                .collect(),
+        };
+        let globs = if globs.modules.is_empty() {
+            GlobImports::default()
+        } else {
+            globs
+        };
+
+        let import_entries: Vec<ImportEntry> = all_imports
             .into_iter()
-            .filter_map(|(path, items)| {
-                let items: Result<Vec<TSIdent>, IdentError> =
-                    items.into_iter().map(|i| TSIdent::from_str(&i)).collect();
-                match items {
-                    Ok(items) => {
-                        let path = path_mapper.get(&path).unwrap_or(path);
-                        if path.is_empty() {
-                            None
-                        } else {
-                            Some(Ok(ImportStatement {
-                                path: format!("\"{}\"", path),
-                                import_kind: ImportKind::ImportList(ImportList { items }),
-                            }))
-                        }
-                    }
-                    Err(e) => Some(Err(e)),
-                }
+            .filter(|(path, _)| !path.is_empty())
+            .flat_map(|(path, idents)| {
+                idents.into_iter().map(move |(ident, alias)| ImportEntry {
+                    path: path.clone(),
+                    ident,
+                    alias,
+                })
             })
-            .collect::<Result<Vec<ImportStatement>, _>>()?;
+            .collect();
+        let imports = import_statements(&import_entries, path_mapper)?;
 
         statements.sort_by_key(|(index, _)| *index);
 
@@ -194,24 +602,429 @@ impl ModuleStep {
             .flat_map(|(_, statements)| statements.into_iter())
             .collect();
 
+        let profile = if options.profile {
+            let mut profile = Profile::default();
+            profile.modules.push(ModuleProfile {
+                path: DisplayPath(&current_path).to_string(),
+                parsing: parsing_time,
+                spawning: spawning_time,
+                solving: solving_start.elapsed(),
+                exporting: Duration::default(),
+            });
+            for profiler in solver_profilers {
+                profile.add_solvers(profiler.into_inner());
+            }
+            Some(profile)
+        } else {
+            None
+        };
+
+        let module = DisplayPath(&current_path).to_string();
+        let warnings = warnings
+            .into_iter()
+            .map(|(recovery, warning)| {
+                let warning = match &source {
+                    Some(source) => warning.in_file(source),
+                    None => warning,
+                };
+                Warning::new(module.clone(), recovery, warning)
+            })
+            .collect();
+
         Ok(ModuleStepResult {
             data: ModuleStepResultData {
                 exports,
                 imports,
+                import_entries,
                 path: current_path,
+                crate_name,
+                type_names,
+                phantom_parameters,
+                filtered_out: filtered_names,
+                globs,
+                reexports: module_reexports
+                    .into_iter()
+                    .flat_map(|(_, reexports)| reexports)
+                    .collect(),
+                value_imports: BTreeSet::new(),
             },
             children,
+            profile,
+            failures,
+            warnings,
         })
     }
 }
 
+/// Locates the errors and the warnings of a declaration in it, and leaves it out on an unsolved type
+/// when [ExporterOptions::unsolved_types] is [UnsolvedTypePolicy::Skip].
+///
+/// The references to a declaration left out are kept, they fail in the TS code instead
+fn recover_declaration(
+    exporter: &ExporterContext,
+    ident: &Ident,
+    solved: Result<Solved<Vec<ExportStatement>>, TsExportError>,
+) -> Result<Solved<Vec<ExportStatement>>, TsExportError> {
+    let mut warnings = exporter.warnings.borrow_mut();
+    let located: Vec<_> = warnings
+        .drain(..)
+        .map(|(recovery, warning)| (recovery, warning.in_container(ident)))
+        .collect();
+    warnings.extend(located);
+    match solved.map_err(|error| error.in_container(ident)) {
+        Err(error)
+            if exporter.options.unsolved_types == UnsolvedTypePolicy::Skip
+                && matches!(
+                    error.inner(),
+                    TsExportError::UnsolvedType(_) | TsExportError::UnsolvedField(_)
+                ) =>
+        {
+            warnings.push((Recovery::Skipped, error));
+            Ok(Solved::default())
+        }
+        solved => solved,
+    }
+}
+
+/// Suffixes the declarations exported for the deserialized data with [ProcessMode::Both](crate::contexts::exporter::ProcessMode::Both),
+/// and their references to the declarations of the crate, which are all declared in both directions
+fn suffix_declarations(
+    solved: &mut [(usize, Solved<Vec<ExportStatement>>)],
+    suffix: &str,
+    local_idents: &[String],
+    module_path: &[String],
+    crate_name: &str,
+) -> Result<(), TsExportError> {
+    let suffixed = |ident: &str| TSIdent::from_str(&format!("{}{}", ident, suffix));
+    let mut renames: HashMap<String, TSIdent> = local_idents
+        .iter()
+        .map(|ident| Ok((ident.clone(), suffixed(ident)?)))
+        .collect::<Result<_, IdentError>>()?;
+    for entry in solved
+        .iter_mut()
+        .flat_map(|(_, solved)| solved.import_entries.iter_mut())
+    {
+        if resolve_local_path(entry, module_path, crate_name).is_some() {
+            let renamed = suffixed(entry.local_name())?;
+            renames.insert(entry.local_name().to_string(), renamed.clone());
+            if entry.alias.is_some() {
+                entry.alias = Some(renamed.to_string());
+                entry.ident = suffixed(&entry.ident)?.to_string();
+            } else {
+                entry.ident = renamed.to_string();
+            }
+        }
+    }
+
+    let mut renamer = ReferenceRenamer { renames: &renames };
+    for statement in solved
+        .iter_mut()
+        .flat_map(|(_, solved)| solved.inner.iter_mut())
+    {
+        renamer.visit_export_statement_mut(statement);
+        if let Some(ident) = statement.ident_mut() {
+            *ident = suffixed(&ident.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Groups the imported items by module, mapping the Rust paths of the modules with the PathMapper
+pub fn import_statements(
+    import_entries: &[ImportEntry],
+    path_mapper: &PathMapper,
+) -> Result<Vec<ImportStatement>, IdentError> {
+    let mut by_path: IndexMap<&str, IndexSet<(&str, Option<&str>)>> = IndexMap::default();
+    for entry in import_entries {
+        by_path
+            .entry(&entry.path)
+            .or_default()
+            .insert((&entry.ident, entry.alias.as_deref()));
+    }
+    by_path
+        .into_iter()
+        .filter_map(|(path, items)| {
+            let items: Result<Vec<ImportSpecifier>, IdentError> = items
+                .into_iter()
+                .map(|(ident, alias)| {
+                    Ok(ImportSpecifier {
+                        ident: TSIdent::from_str(ident)?,
+                        alias: alias.map(TSIdent::from_str).transpose()?,
+                    })
+                })
+                .collect();
+            match items {
+                Ok(items) => {
+                    let path = path_mapper.get(path).unwrap_or_else(|| path.to_string());
+                    if path.is_empty() {
+                        None
+                    } else {
+                        Some(Ok(ImportStatement {
+                            path: format!("\"{}\"", path),
+                            import_kind: ImportKind::ImportList(ImportList { items }),
+                            type_only: false,
+                        }))
+                    }
+                }
+                Err(e) => Some(Err(e)),
+            }
+        })
+        .collect()
+}
+
+/// The value of the `#[path = "..."]` attribute of a module declaration
+fn path_attribute(item_mod: &ItemMod) -> Option<String> {
+    item_mod
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("path"))
+        .find_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) => Some(lit.value()),
+            _ => None,
+        })
+}
+
+/// Spawns a module, recording the time it took.
+///
+/// `file_path` is the value of the `#[path = "..."]` attribute of the module, if any
+pub fn spawn_timed<PSS: PipelineStepSpawner>(
+    process_spawner: &PSS,
+    path: Path,
+    file_path: Option<&str>,
+) -> Result<Option<ModuleStep>, PSS::Error> {
+    let (process, time) = timed(|| match file_path {
+        Some(file_path) => process_spawner.create_process_at(path, file_path),
+        None => process_spawner.create_process(path),
+    });
+    process.map(|process| {
+        process.map(|mut process| {
+            process.set_spawning_time(time.saturating_sub(process.parsing_time));
+            process
+        })
+    })
+}
+
 pub struct ModuleStepResultData {
     pub exports: Vec<ExportStatement>,
     pub imports: Vec<ImportStatement>,
+    /// The Rust paths of the imported items, before they are mapped to TS modules
+    pub import_entries: Vec<ImportEntry>,
     pub path: Path,
+    /// The name of the crate the module belongs to, as used in Rust paths
+    pub crate_name: String,
+    /// The TS names of the declarations that are not named after their Rust ident, see [TypeNamePolicy]
+    pub type_names: IndexMap<String, String>,
+    /// The indices of the type parameters left out of the declarations, by Rust ident, see [phantom_type_parameters]
+    pub phantom_parameters: IndexMap<String, Vec<usize>>,
+    /// The declarations left out by the [ProcessFilter], by Rust ident (suffixed in the deserialize direction), see [report_dangling_references](super::filter::report_dangling_references)
+    pub filtered_out: Vec<String>,
+    /// The glob imports of the module, resolved once all the modules are processed
+    pub globs: GlobImports,
+    /// The items of the crate re-exported by the `pub use` declarations of the module
+    pub reexports: Vec<Reexport>,
+    /// The imported idents that are values rather than types, such as the codecs and the mock factories
+    pub value_imports: BTreeSet<String>,
 }
 
 pub struct ModuleStepResult {
     pub data: ModuleStepResultData,
     pub children: Vec<ModuleStepResult>,
+    /// The profile of the module, when [ExporterOptions::profile] is set
+    pub profile: Option<Profile>,
+    /// The child modules that were skipped, when [ExporterOptions::skip_failing_modules] is set
+    pub failures: Vec<ModuleFailure>,
+    /// The unsolved types of the module that were recovered from, see [ExporterOptions::unsolved_types]
+    pub warnings: Vec<Warning>,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::{exporter::ProcessMode, type_solving::TypeSolvingContextBuilder},
+        pipeline::type_names::rename_references,
+        step_spawner::discard::BypassProcessSpawner,
+    };
+
+    /// Launches a step without spawning its child modules from files, the errors keeping their location
+    pub(crate) fn launch_test_step(
+        step: ModuleStep,
+        solving_context: &TypeSolvingContext,
+        options: &ExporterOptions,
+    ) -> Result<ModuleStepResult, TsExportError> {
+        step.launch(
+            &BypassProcessSpawner,
+            solving_context,
+            &MacroSolvingContext::default(),
+            &PathMapper::default(),
+            options,
+        )
+    }
+
+    /// Launches the root module of `my_crate` from its source, with the default solvers
+    pub(crate) fn launch_test_module(source: &str, options: &ExporterOptions) -> ModuleStepResult {
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let items = syn::parse_file(source).expect("Failed to parse").items;
+        let path = syn::Path {
+            leading_colon: None,
+            segments: Default::default(),
+        };
+        launch_test_step(
+            ModuleStep::new(path, items, "my_crate"),
+            &solving_context,
+            options,
+        )
+        .expect("Failed to launch")
+    }
+
+    /// The results of a module and of its descendants, the children before their parent
+    pub(crate) fn collect(all: &mut Vec<ModuleStepResultData>, result: ModuleStepResult) {
+        for child in result.children {
+            collect(all, child);
+        }
+        all.push(result.data);
+    }
+
+    fn render(source: &str, options: &ExporterOptions) -> Vec<String> {
+        let result = launch_test_module(source, options);
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        rename_references(&mut results).unwrap();
+        results
+            .iter()
+            .map(|result| {
+                result
+                    .imports
+                    .iter()
+                    .map(|import| import.to_string())
+                    .chain(result.exports.iter().map(|export| export.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_declare_the_types_in_both_directions() {
+        let source = r#"
+            use crate::models::Role;
+
+            pub mod models {
+                #[derive(Serialize, Deserialize)]
+                pub enum Role { Admin, #[serde(skip_deserializing)] Owner }
+            }
+
+            pub type Roles = Vec<Role>;
+
+            #[derive(Serialize, Deserialize)]
+            #[serde(rename(serialize = "UserDto", deserialize = "UserForm"))]
+            pub struct User {
+                #[serde(skip_deserializing)]
+                id: u32,
+                #[serde(rename(serialize = "userName", deserialize = "name"))]
+                name: String,
+                #[serde(default)]
+                roles: Roles,
+                manager: Option<Box<User>>,
+            }
+            "#;
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Both,
+            ..ExporterOptions::default()
+        };
+        assert_eq!(
+            render(source, &options),
+            vec![
+                "export type Role = \"Admin\" | \"Owner\";\n\
+                 export type RoleInput = \"Admin\";"
+                    .to_string(),
+                "import { Role, RoleInput } from \"my_crate::models\";\n\
+                 export type Roles = Role[];\n\
+                 export type RolesInput = RoleInput[];\n\
+                 export interface User {\n\treadonly id: number,\n\tuserName: string,\n\troles: Roles,\n\tmanager: User | null\n}\n\
+                 export interface UserInput {\n\tname: string,\n\troles?: RolesInput,\n\tmanager: UserInput | null\n}"
+                    .to_string(),
+            ]
+        );
+
+        // The suffixed declarations follow the deserialized names of the containers
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Both,
+            input_suffix: Some("Payload".to_string()),
+            type_names: TypeNamePolicy::SerdeName,
+            ..ExporterOptions::default()
+        };
+        let rendered = render(source, &options);
+        assert!(rendered[1].contains("export interface UserDto {"));
+        assert!(rendered[1].contains(
+            "export interface UserFormPayload {\n\tname: string,\n\troles?: RolesPayload,\n\tmanager: UserFormPayload | null\n}"
+        ));
+    }
+
+    #[test]
+    fn should_keep_the_import_aliases() {
+        let source = r#"
+            pub mod models {
+                #[derive(Serialize, Deserialize)]
+                pub struct User { name: String }
+            }
+
+            pub mod api {
+                use super::models::User as MUser;
+
+                #[derive(Serialize, Deserialize)]
+                pub struct User { member: MUser, admins: Vec<MUser> }
+
+                #[derive(Serialize, Deserialize)]
+                pub struct Session { user: User, member: MUser, pair: (MUser, User) }
+            }
+            "#;
+        let rendered = render(source, &ExporterOptions::default());
+        assert_eq!(
+            rendered[1],
+            "import { User as MUser } from \"my_crate::models\";\n\
+             export interface User {\n\tmember: MUser,\n\tadmins: MUser[]\n}\n\
+             export interface Session {\n\tuser: User,\n\tmember: MUser,\n\tpair: [ MUser, User ]\n}"
+        );
+
+        // The alias of the deserialized declaration is suffixed along with it
+        let options = ExporterOptions {
+            process_mode: ProcessMode::Both,
+            ..ExporterOptions::default()
+        };
+        let rendered = render(source, &options);
+        assert!(rendered[1].starts_with(
+            "import { User as MUser, UserInput as MUserInput } from \"my_crate::models\";"
+        ));
+        assert!(rendered[1].contains(
+            "export interface UserInput {\n\tmember: MUserInput,\n\tadmins: MUserInput[]\n}"
+        ));
+    }
+
+    #[test]
+    fn should_import_the_types_of_the_child_modules() {
+        let rendered = render(
+            r#"
+            pub mod api {
+                pub mod inner_things {
+                    #[derive(Serialize)]
+                    pub struct Thing { id: u32 }
+                }
+
+                #[derive(Serialize)]
+                pub struct Holder { thing: inner_things::Thing }
+            }
+            "#,
+            &ExporterOptions::default(),
+        );
+        assert_eq!(
+            rendered[1],
+            "import { Thing } from \"my_crate::api::inner_things\";\n\
+             export interface Holder {\n\tthing: Thing\n}"
+        );
+    }
 }
@@ -0,0 +1,135 @@
+//! Keeps the references consistent with the declarations whose phantom type parameters are left out,
+//! see [phantom_type_parameters](crate::contexts::exporter::phantom_type_parameters)
+use std::collections::HashMap;
+
+use ts_json_subset::{
+    types::TypeReference,
+    visit_mut::{self, VisitMut},
+};
+
+use super::module_step::ModuleStepResultData;
+use crate::utils::crate_paths::{module_segments, resolve_local_path};
+
+/// Removes the type arguments at the given indices from the references whose name is a key of `phantoms`
+struct PhantomArgumentRemover<'a> {
+    phantoms: &'a HashMap<String, &'a Vec<usize>>,
+}
+
+impl VisitMut for PhantomArgumentRemover<'_> {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        if let (Some(indices), Some(args)) =
+            (self.phantoms.get(&node.name.to_string()), &mut node.args)
+        {
+            args.types = std::mem::take(&mut args.types)
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| !indices.contains(index))
+                .map(|(_, ty)| ty)
+                .collect();
+            if args.types.is_empty() {
+                node.args = None;
+            }
+        }
+        visit_mut::visit_type_reference_mut(self, node);
+    }
+}
+
+/// The declarations leave out their phantom type parameters, but the references to the declarations of other modules
+/// can only be rewritten once all the modules have been processed.
+///
+/// Removes the type arguments of the phantom type parameters from the references, e.g. `Id<User>` becomes `Id`.
+/// It runs before the references are renamed, while they are still named after the Rust idents.
+pub fn drop_phantom_arguments(results: &mut [ModuleStepResultData]) {
+    let declared: HashMap<(Vec<String>, String), Vec<usize>> = results
+        .iter()
+        .flat_map(|result| {
+            let module_path = module_segments(&result.path);
+            result
+                .phantom_parameters
+                .iter()
+                .map(move |(ident, indices)| {
+                    ((module_path.clone(), ident.clone()), indices.clone())
+                })
+        })
+        .collect();
+    if declared.is_empty() {
+        return;
+    }
+
+    for result in results.iter_mut() {
+        let module_path = module_segments(&result.path);
+        let mut phantoms: HashMap<String, &Vec<usize>> = result
+            .import_entries
+            .iter()
+            .filter_map(|entry| {
+                let declaring_module = resolve_local_path(entry, &module_path, &result.crate_name)?;
+                declared
+                    .get(&(declaring_module, entry.ident.clone()))
+                    .map(|indices| (entry.local_name().to_string(), indices))
+            })
+            .collect();
+        phantoms.extend(
+            result
+                .phantom_parameters
+                .iter()
+                .map(|(ident, indices)| (ident.clone(), indices)),
+        );
+
+        let mut remover = PhantomArgumentRemover {
+            phantoms: &phantoms,
+        };
+        for export in result.exports.iter_mut() {
+            remover.visit_export_statement_mut(export);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::ExporterOptions,
+        pipeline::module_step::tests::{collect, launch_test_module},
+    };
+
+    #[test]
+    fn should_drop_the_phantom_type_arguments() {
+        let result = launch_test_module(
+            r#"
+            use std::marker::PhantomData;
+            use crate::models::{Id, User};
+
+            pub mod models {
+                #[derive(Serialize)]
+                pub struct User { name: String }
+
+                #[derive(Serialize)]
+                pub struct Id<T> { id: u32, _marker: std::marker::PhantomData<T> }
+            }
+
+            #[derive(Serialize)]
+            pub struct Tagged<T, U>(T, PhantomData<U>);
+
+            #[derive(Serialize)]
+            pub struct Session { user: Id<User>, tags: Vec<Tagged<String, User>> }
+            "#,
+            &ExporterOptions::default(),
+        );
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        drop_phantom_arguments(&mut results);
+
+        let exports: Vec<String> = results[1]
+            .exports
+            .iter()
+            .map(|export| export.to_string())
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                "export type Tagged<T> = [ T, null ];".to_string(),
+                "export interface Session {\n\tuser: Id,\n\ttags: Tagged<string>[]\n}".to_string(),
+            ]
+        );
+    }
+}
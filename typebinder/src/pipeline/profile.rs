@@ -0,0 +1,181 @@
+//! Opt-in profiling of a run, see [ExporterOptions::profile](crate::contexts::exporter::ExporterOptions::profile)
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use indexmap::IndexMap;
+
+/// The time spent on a module, by phase
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModuleProfile {
+    /// The Rust path of the module
+    pub path: String,
+    /// Reading and parsing the file of the module
+    pub parsing: Duration,
+    /// Spawning the step of the module, parsing excluded
+    pub spawning: Duration,
+    /// Solving the declarations of the module to TS statements
+    pub solving: Duration,
+    /// Exporting the module with the Exporter
+    pub exporting: Duration,
+}
+
+/// The calls to a solver. As the solvers recurse through the inner types, the time includes the nested calls.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SolverProfile {
+    pub calls: usize,
+    pub time: Duration,
+}
+
+/// Records the calls to the solvers of a module
+#[derive(Debug, Default)]
+pub struct SolverProfiler {
+    solvers: RefCell<IndexMap<String, SolverProfile>>,
+}
+
+impl SolverProfiler {
+    pub fn record(&self, solver: &str, time: Duration) {
+        let mut solvers = self.solvers.borrow_mut();
+        let profile = solvers.entry(solver.to_string()).or_default();
+        profile.calls += 1;
+        profile.time += time;
+    }
+
+    pub fn into_inner(self) -> IndexMap<String, SolverProfile> {
+        self.solvers.into_inner()
+    }
+}
+
+/// The profile of a run
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub modules: Vec<ModuleProfile>,
+    pub solvers: IndexMap<String, SolverProfile>,
+}
+
+impl Profile {
+    pub fn add_solvers(&mut self, solvers: IndexMap<String, SolverProfile>) {
+        for (name, profile) in solvers {
+            let total = self.solvers.entry(name).or_default();
+            total.calls += profile.calls;
+            total.time += profile.time;
+        }
+    }
+
+    pub fn module_mut(&mut self, path: &str) -> Option<&mut ModuleProfile> {
+        self.modules.iter_mut().find(|module| module.path == path)
+    }
+
+    /// The time spent on all the modules, by phase
+    pub fn total(&self) -> ModuleProfile {
+        self.modules.iter().fold(
+            ModuleProfile {
+                path: "total".to_string(),
+                ..ModuleProfile::default()
+            },
+            |mut total, module| {
+                total.parsing += module.parsing;
+                total.spawning += module.spawning;
+                total.solving += module.solving;
+                total.exporting += module.exporting;
+                total
+            },
+        )
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<40} {:>12} {:>12} {:>12} {:>12}",
+            "module", "parsing", "spawning", "solving", "exporting"
+        )?;
+        for module in self.modules.iter().chain(Some(&self.total())) {
+            let path = if module.path.is_empty() {
+                "<root>"
+            } else {
+                &module.path
+            };
+            writeln!(
+                f,
+                "{:<40} {:>12?} {:>12?} {:>12?} {:>12?}",
+                path, module.parsing, module.spawning, module.solving, module.exporting
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<40} {:>12} {:>12}", "solver", "calls", "time")?;
+        let mut solvers: Vec<_> = self.solvers.iter().collect();
+        solvers.sort_by_key(|(_, profile)| std::cmp::Reverse(profile.time));
+        for (name, profile) in solvers {
+            writeln!(
+                f,
+                "{:<40} {:>12} {:>12?}",
+                name, profile.calls, profile.time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `f`, returning its result and the time it took
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sum_the_phases() {
+        let mut profile = Profile {
+            modules: vec![
+                ModuleProfile {
+                    path: String::new(),
+                    parsing: Duration::from_millis(2),
+                    solving: Duration::from_millis(5),
+                    ..ModuleProfile::default()
+                },
+                ModuleProfile {
+                    path: "models".to_string(),
+                    parsing: Duration::from_millis(3),
+                    exporting: Duration::from_millis(1),
+                    ..ModuleProfile::default()
+                },
+            ],
+            ..Profile::default()
+        };
+        let total = profile.total();
+        assert_eq!(total.parsing, Duration::from_millis(5));
+        assert_eq!(total.solving, Duration::from_millis(5));
+        assert_eq!(total.exporting, Duration::from_millis(1));
+
+        let profiler = SolverProfiler::default();
+        profiler.record("TupleSolver", Duration::from_millis(1));
+        profiler.record("TupleSolver", Duration::from_millis(2));
+        profile.add_solvers(profiler.into_inner());
+        profile.add_solvers(
+            vec![(
+                "TupleSolver".to_string(),
+                SolverProfile {
+                    calls: 1,
+                    time: Duration::from_millis(1),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(
+            profile.solvers["TupleSolver"],
+            SolverProfile {
+                calls: 3,
+                time: Duration::from_millis(4),
+            }
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Resolution of the `pub use` declarations that re-export items of the current crate
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use syn::{Ident, ItemUse, Path, UseTree, Visibility};
+use ts_json_subset::{export::ExportStatement, ident::TSIdent, visit_mut::VisitMut};
+
+use super::module_step::{import_statements, ModuleStepResultData};
+use crate::{
+    error::TsExportError,
+    path_mapper::PathMapper,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        rename::ReferenceRenamer,
+    },
+};
+
+/// A declaration of the current crate re-exported by a `pub use`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reexport {
+    /// Path of the module that declares the item, prefixed with the crate name (e.g. `my_crate::models`)
+    pub path: String,
+    /// Ident of the item in its module
+    pub ident: String,
+    /// Name under which the item is re-exported
+    pub export_as: String,
+}
+
+/// Lists the items of the current crate re-exported by a `pub use`.
+///
+/// Only the paths that start with `crate`, `self`, `super` or one of the `child_modules` are considered,
+/// other paths belong to external crates.
+pub fn parse_reexports(
+    item_use: &ItemUse,
+    current_path: &Path,
+    crate_name: &str,
+    child_modules: &[Ident],
+) -> Vec<Reexport> {
+    let mut reexports = Vec::new();
+    if let Visibility::Public(_) = item_use.vis {
+        let current_segments: Vec<String> = current_path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        walk_use_tree(
+            &item_use.tree,
+            None,
+            &current_segments,
+            crate_name,
+            child_modules,
+            &mut reexports,
+        );
+    }
+    reexports
+}
+
+/// `segments` is `None` while the root of the path has not been resolved
+fn walk_use_tree(
+    tree: &UseTree,
+    segments: Option<Vec<String>>,
+    current_segments: &[String],
+    crate_name: &str,
+    child_modules: &[Ident],
+    reexports: &mut Vec<Reexport>,
+) {
+    match tree {
+        UseTree::Path(use_path) => {
+            let ident = use_path.ident.to_string();
+            let segments = match (segments, ident.as_str()) {
+                (None, "crate") => Vec::new(),
+                (None, "self") => current_segments.to_vec(),
+                (None, "super") => parent(current_segments),
+                (Some(segments), "super") => parent(&segments),
+                (None, _) if child_modules.contains(&use_path.ident) => {
+                    let mut segments = current_segments.to_vec();
+                    segments.push(ident);
+                    segments
+                }
+                // The path belongs to another crate
+                (None, _) => return,
+                (Some(mut segments), _) => {
+                    segments.push(ident);
+                    segments
+                }
+            };
+            walk_use_tree(
+                use_path.tree.as_ref(),
+                Some(segments),
+                current_segments,
+                crate_name,
+                child_modules,
+                reexports,
+            )
+        }
+        UseTree::Name(name) => {
+            if let Some(segments) = segments {
+                push_reexport(
+                    reexports,
+                    segments,
+                    current_segments,
+                    &name.ident,
+                    &name.ident,
+                    crate_name,
+                )
+            }
+        }
+        UseTree::Rename(rename) => {
+            if let Some(segments) = segments {
+                push_reexport(
+                    reexports,
+                    segments,
+                    current_segments,
+                    &rename.ident,
+                    &rename.rename,
+                    crate_name,
+                )
+            }
+        }
+        UseTree::Group(group) => group.items.iter().for_each(|tree| {
+            walk_use_tree(
+                tree,
+                segments.clone(),
+                current_segments,
+                crate_name,
+                child_modules,
+                reexports,
+            )
+        }),
+        UseTree::Glob(_) => {
+            if segments.is_some() {
+                log::warn!("Glob re-exports are not supported by typebinder")
+            }
+        }
+    }
+}
+
+/// The path of a module of a crate, and the name of an item in it
+type ModuleItem = (Vec<String>, String);
+
+/// The modules only know the re-exports of their own `pub use` declarations, so the imports of a re-exported item
+/// can only be pointed to the module that declares it once all the modules have been processed.
+///
+/// Drops the re-exports of the items that are not exported declarations (e.g. functions or constants), then
+/// rewrites the imports of the re-exported items, and the references to the items re-exported under another name.
+pub fn resolve_reexported_imports(
+    results: &mut [ModuleStepResultData],
+    path_mapper: &PathMapper,
+) -> Result<(), TsExportError> {
+    // (crate, (re-exporting module, exported name)) => (declaring module, ident)
+    let reexported: HashMap<(String, ModuleItem), ModuleItem> = results
+        .iter()
+        .flat_map(|result| {
+            let module_path = module_segments(&result.path);
+            let crate_name = &result.crate_name;
+            result.reexports.iter().map(move |reexport| {
+                (
+                    (
+                        crate_name.clone(),
+                        (module_path.clone(), reexport.export_as.clone()),
+                    ),
+                    (declaring_module(reexport), reexport.ident.clone()),
+                )
+            })
+        })
+        .collect();
+    if reexported.is_empty() {
+        return Ok(());
+    }
+
+    // (crate, (declaring module, ident)) of the exported declarations, by their Rust ident
+    let declared: HashSet<(String, ModuleItem)> = results
+        .iter()
+        .flat_map(|result| {
+            let module_path = module_segments(&result.path);
+            let crate_name = &result.crate_name;
+            result
+                .exports
+                .iter()
+                .filter_map(|export| export.ident())
+                .map(|ident| ident.to_string())
+                .chain(result.type_names.keys().cloned())
+                .map(move |ident| (crate_name.clone(), (module_path.clone(), ident)))
+        })
+        .collect();
+    for result in results.iter_mut() {
+        let crate_name = result.crate_name.clone();
+        let dangling: Vec<Reexport> = result
+            .reexports
+            .iter()
+            .filter(|reexport| {
+                let (key, _) = follow_reexports(
+                    &reexported,
+                    &crate_name,
+                    (declaring_module(reexport), reexport.ident.clone()),
+                );
+                !declared.contains(&(crate_name.clone(), key))
+            })
+            .cloned()
+            .collect();
+        if dangling.is_empty() {
+            continue;
+        }
+        let follows_reexports = result
+            .exports
+            .iter()
+            .any(|export| matches!(export, ExportStatement::ReexportDeclaration(_)));
+        for reexport in dangling.iter().filter(|_| follows_reexports) {
+            log::warn!(
+                "{}::{} is not an exported declaration, it is not re-exported by {}",
+                reexport.path,
+                reexport.ident,
+                Some(crate_name.clone())
+                    .into_iter()
+                    .chain(module_segments(&result.path))
+                    .collect::<Vec<_>>()
+                    .join("::")
+            );
+        }
+        result
+            .reexports
+            .retain(|reexport| !dangling.contains(reexport));
+        result.import_entries.retain(|entry| {
+            !dangling
+                .iter()
+                .any(|reexport| reexport.path == entry.path && reexport.ident == entry.ident)
+        });
+        for export in result.exports.iter_mut() {
+            if let ExportStatement::ReexportDeclaration(declaration) = export {
+                declaration.reexports.retain(|clause| {
+                    !dangling.iter().any(|reexport| {
+                        clause.scope.to_string() == reexport.ident
+                            && clause.export_as.to_string() == reexport.export_as
+                    })
+                });
+            }
+        }
+        result.exports.retain(|export| {
+            !matches!(export, ExportStatement::ReexportDeclaration(declaration) if declaration.reexports.is_empty())
+        });
+        result.imports = import_statements(&result.import_entries, path_mapper)?;
+    }
+
+    for result in results.iter_mut() {
+        let module_path = module_segments(&result.path);
+        let mut renames: HashMap<String, TSIdent> = HashMap::new();
+        let mut resolved_any = false;
+        for entry in result.import_entries.iter_mut() {
+            let key = match resolve_local_path(entry, &module_path, &result.crate_name) {
+                Some(declaring_module) => (declaring_module, entry.ident.clone()),
+                None => continue,
+            };
+            let (key, depth) = follow_reexports(&reexported, &result.crate_name, key);
+            if depth == 0 {
+                continue;
+            }
+            let (declaring_module, ident) = key;
+            // The references to an item imported with `as` are named after its alias
+            if ident != entry.ident && entry.alias.is_none() {
+                renames.insert(entry.ident.clone(), TSIdent::from_str(&ident)?);
+            }
+            entry.path = if declaring_module == module_path {
+                String::new()
+            } else {
+                Some(result.crate_name.clone())
+                    .into_iter()
+                    .chain(declaring_module)
+                    .collect::<Vec<_>>()
+                    .join("::")
+            };
+            entry.ident = ident;
+            resolved_any = true;
+        }
+        if !resolved_any {
+            continue;
+        }
+
+        let mut entries = Vec::new();
+        for entry in result.import_entries.drain(..) {
+            if !entry.path.is_empty() && !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+        result.import_entries = entries;
+        result.imports = import_statements(&result.import_entries, path_mapper)?;
+
+        let mut renamer = ReferenceRenamer { renames: &renames };
+        for export in result.exports.iter_mut() {
+            // A re-export of a re-export keeps its exported name
+            if let ExportStatement::ReexportDeclaration(reexport) = export {
+                for clause in reexport.reexports.iter_mut() {
+                    if let Some(name) = renames.get(&clause.scope.to_string()) {
+                        clause.scope = name.clone();
+                    }
+                }
+            }
+            renamer.visit_export_statement_mut(export);
+        }
+    }
+    Ok(())
+}
+
+/// The path of the module that declares a re-exported item, without the crate name
+fn declaring_module(reexport: &Reexport) -> Vec<String> {
+    reexport
+        .path
+        .split("::")
+        .skip(1)
+        .map(|segment| segment.to_string())
+        .collect()
+}
+
+/// Follows the re-exports of re-exports up to the declaration, the depth bounding the cycles.
+///
+/// Returns the declaring module and the ident of the item, and the number of re-exports followed.
+fn follow_reexports(
+    reexported: &HashMap<(String, ModuleItem), ModuleItem>,
+    crate_name: &str,
+    mut key: ModuleItem,
+) -> (ModuleItem, usize) {
+    let mut depth = 0;
+    while let Some(declaration) = reexported.get(&(crate_name.to_string(), key.clone())) {
+        key = declaration.clone();
+        depth += 1;
+        if depth > reexported.len() {
+            break;
+        }
+    }
+    (key, depth)
+}
+
+fn parent(segments: &[String]) -> Vec<String> {
+    segments
+        .split_last()
+        .map(|(_, parent)| parent.to_vec())
+        .unwrap_or_default()
+}
+
+fn push_reexport(
+    reexports: &mut Vec<Reexport>,
+    segments: Vec<String>,
+    current_segments: &[String],
+    ident: &Ident,
+    export_as: &Ident,
+    crate_name: &str,
+) {
+    // `use module::{self}` re-exports a module, not a declaration
+    if ident == "self" {
+        return;
+    }
+    // The items of the current module are already exported by it
+    if segments == current_segments {
+        return;
+    }
+    let path = Some(crate_name.to_string())
+        .into_iter()
+        .chain(segments)
+        .collect::<Vec<String>>()
+        .join("::");
+    reexports.push(Reexport {
+        path,
+        ident: ident.to_string(),
+        export_as: export_as.to_string(),
+    });
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn reexports(input: &str, current_path: &str) -> Vec<Reexport> {
+        let item_use: ItemUse = syn::parse_str(input).expect("Failed to parse use");
+        let current_path: Path = if current_path.is_empty() {
+            Path {
+                leading_colon: None,
+                segments: Default::default(),
+            }
+        } else {
+            syn::parse_str(current_path).expect("Failed to parse path")
+        };
+        let child_modules = vec![Ident::new("models", Span::call_site())];
+        parse_reexports(&item_use, &current_path, "my_crate", &child_modules)
+    }
+
+    fn reexport(path: &str, ident: &str, export_as: &str) -> Reexport {
+        Reexport {
+            path: path.to_string(),
+            ident: ident.to_string(),
+            export_as: export_as.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_resolve_local_reexports() {
+        assert_eq!(
+            reexports("pub use self::models::User;", ""),
+            vec![reexport("my_crate::models", "User", "User")]
+        );
+        assert_eq!(
+            reexports("pub use models::{User, Group as Team};", ""),
+            vec![
+                reexport("my_crate::models", "User", "User"),
+                reexport("my_crate::models", "Group", "Team"),
+            ]
+        );
+        assert_eq!(
+            reexports("pub use crate::api::models::User;", "api"),
+            vec![reexport("my_crate::api::models", "User", "User")]
+        );
+        assert_eq!(
+            reexports("pub use super::super::User;", "api::v1"),
+            vec![reexport("my_crate", "User", "User")]
+        );
+    }
+
+    #[test]
+    fn should_ignore_other_uses() {
+        assert_eq!(reexports("use self::models::User;", ""), vec![]);
+        assert_eq!(reexports("pub use serde::Serialize;", ""), vec![]);
+        assert_eq!(reexports("pub use models::{self};", ""), vec![]);
+        assert_eq!(reexports("pub use models::*;", ""), vec![]);
+        assert_eq!(reexports("pub use self::User;", "api"), vec![]);
+        assert_eq!(
+            reexports("pub use crate::api::User as Member;", "api"),
+            vec![]
+        );
+    }
+}
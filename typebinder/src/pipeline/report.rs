@@ -0,0 +1,106 @@
+//! What a run of the [Pipeline](super::Pipeline) reports, besides its output
+use std::{collections::BTreeSet, fmt, path::PathBuf};
+
+use super::profile::Profile;
+use crate::error::TsExportError;
+
+/// The report of a [Pipeline::launch](super::Pipeline::launch)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    /// The time spent by phase, module and solver, when [ExporterOptions::profile](crate::contexts::exporter::ExporterOptions::profile) is set
+    pub profile: Option<Profile>,
+    /// The modules that were skipped, when [ExporterOptions::skip_failing_modules](crate::contexts::exporter::ExporterOptions::skip_failing_modules) is set
+    pub failures: Vec<ModuleFailure>,
+    /// The unsolved types the run recovered from, when
+    /// [ExporterOptions::unsolved_types](crate::contexts::exporter::ExporterOptions::unsolved_types) is lenient,
+    /// and the references to the declarations left out by the [ProcessFilter](super::filter::ProcessFilter)
+    pub warnings: Vec<Warning>,
+    /// The other crates the exported modules import types from, e.g. to export them with
+    /// [CargoDependencies](crate::step_spawner::dependencies::CargoDependencies)
+    pub external_crates: BTreeSet<String>,
+}
+
+/// A module that could not be spawned or solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFailure {
+    /// The Rust path of the module
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the run recovered from an error, see [UnsolvedTypePolicy](crate::contexts::exporter::UnsolvedTypePolicy)
+pub enum Recovery {
+    /// The type was exported as `unknown`
+    Unknown,
+    /// The declaration was left out
+    Skipped,
+    /// The reference was kept, the declaration it refers to being left out by the [ProcessFilter](super::filter::ProcessFilter)
+    Dangling,
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recovery::Unknown => write!(f, "exported as `unknown`"),
+            Recovery::Skipped => write!(f, "declaration left out"),
+            Recovery::Dangling => write!(f, "reference left dangling"),
+        }
+    }
+}
+
+/// An error the run recovered from, located like the errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The Rust path of the module
+    pub module: String,
+    pub file: Option<PathBuf>,
+    /// The line and the column (from 1) of the tokens at fault
+    pub position: Option<(usize, usize)>,
+    /// The struct, enum or type alias
+    pub container: Option<String>,
+    pub field: Option<String>,
+    /// The error, without its location
+    pub error: String,
+    pub recovery: Recovery,
+    /// The error with its location and code frame, as it would have been reported
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(module: String, recovery: Recovery, error: TsExportError) -> Self {
+        let message = error.to_string();
+        let (file, position, container, field, error) = match error {
+            TsExportError::Diagnostic(diagnostic) => (
+                diagnostic.file,
+                diagnostic
+                    .location
+                    .map(|(start, _)| (start.line, start.column + 1)),
+                diagnostic.container,
+                diagnostic.field,
+                diagnostic.error.to_string(),
+            ),
+            error => (None, None, None, None, error.to_string()),
+        };
+        Warning {
+            module,
+            file,
+            position,
+            container,
+            field,
+            error,
+            recovery,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    /// The message, the recovery following the error on its first line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message.split_once('\n') {
+            Some((error, location)) => write!(f, "{}, {}\n{}", error, self.recovery, location),
+            None => write!(f, "{}, {}", self.message, self.recovery),
+        }
+    }
+}
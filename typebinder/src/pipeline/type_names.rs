@@ -0,0 +1,182 @@
+//! Keeps the references consistent with the names of the declarations, see [TypeNamePolicy](crate::contexts::exporter::TypeNamePolicy)
+use std::{collections::HashMap, str::FromStr};
+
+use ts_json_subset::{
+    export::ExportStatement, ident::TSIdent, import::ImportKind, visit_mut::VisitMut,
+};
+
+use super::module_step::ModuleStepResultData;
+use crate::{
+    error::TsExportError,
+    utils::{
+        crate_paths::{module_segments, resolve_local_path},
+        rename::ReferenceRenamer,
+    },
+};
+
+/// The declarations are named by the ModuleSteps, but the references to the declarations of other modules
+/// can only be renamed once all the modules have been processed.
+///
+/// Rewrites the references, the imports and the re-exports of the declarations that are not named after their Rust ident.
+pub fn rename_references(results: &mut [ModuleStepResultData]) -> Result<(), TsExportError> {
+    let renamed: HashMap<(Vec<String>, String), TSIdent> = results
+        .iter()
+        .flat_map(|result| {
+            let module_path = module_segments(&result.path);
+            result
+                .type_names
+                .iter()
+                .map(move |(ident, name)| ((module_path.clone(), ident.clone()), name))
+        })
+        .map(|(key, name)| Ok((key, TSIdent::from_str(name)?)))
+        .collect::<Result<_, TsExportError>>()?;
+    if renamed.is_empty() {
+        return Ok(());
+    }
+
+    for result in results.iter_mut() {
+        let module_path = module_segments(&result.path);
+        let mut renames: HashMap<String, TSIdent> = HashMap::new();
+        // The items imported with `as` keep their alias, only the imported ident is renamed
+        let mut aliased: HashMap<String, TSIdent> = HashMap::new();
+        for entry in result.import_entries.iter_mut() {
+            if let Some(declaring_module) =
+                resolve_local_path(entry, &module_path, &result.crate_name)
+            {
+                if let Some(name) = renamed.get(&(declaring_module, entry.ident.clone())) {
+                    match &entry.alias {
+                        Some(alias) => aliased.insert(alias.clone(), name.clone()),
+                        None => renames.insert(entry.ident.clone(), name.clone()),
+                    };
+                    entry.ident = name.to_string();
+                }
+            }
+        }
+        for (ident, name) in result.type_names.iter() {
+            renames.insert(ident.clone(), TSIdent::from_str(name)?);
+        }
+
+        for import in result.imports.iter_mut() {
+            if let ImportKind::ImportList(list) = &mut import.import_kind {
+                for item in list.items.iter_mut() {
+                    let name = match &item.alias {
+                        Some(alias) => aliased.get(&alias.to_string()),
+                        None => renames.get(&item.ident.to_string()),
+                    };
+                    if let Some(name) = name {
+                        item.ident = name.clone();
+                    }
+                }
+            }
+        }
+
+        let mut renamer = ReferenceRenamer { renames: &renames };
+        for export in result.exports.iter_mut() {
+            if let ExportStatement::ReexportDeclaration(reexport) = export {
+                for clause in reexport.reexports.iter_mut() {
+                    if let Some(name) = renames.get(&clause.scope.to_string()) {
+                        if clause.export_as == clause.scope {
+                            clause.export_as = name.clone();
+                        }
+                        clause.scope = name.clone();
+                    }
+                }
+            }
+            renamer.visit_export_statement_mut(export);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::exporter::{ExporterOptions, TypeNamePolicy},
+        pipeline::module_step::tests::{collect, launch_test_module},
+    };
+
+    #[test]
+    fn should_rename_references_to_serde_names() {
+        let options = ExporterOptions {
+            type_names: TypeNamePolicy::SerdeName,
+            ..ExporterOptions::default()
+        };
+        let result = launch_test_module(
+            r#"
+            use crate::models::User;
+
+            pub mod models {
+                #[derive(Serialize)]
+                #[serde(rename = "UserDto")]
+                pub struct User { name: String }
+            }
+
+            #[derive(Serialize)]
+            #[serde(rename = "SessionDto")]
+            pub struct Session { user: User, children: Vec<Session> }
+            "#,
+            &options,
+        );
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        rename_references(&mut results).unwrap();
+
+        let rendered: Vec<String> = results
+            .iter()
+            .map(|result| {
+                result
+                    .imports
+                    .iter()
+                    .map(|import| import.to_string())
+                    .chain(result.exports.iter().map(|export| export.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "export interface UserDto {\n\tname: string\n}".to_string(),
+                "import { UserDto } from \"my_crate::models\";\n\
+                 export interface SessionDto {\n\tuser: UserDto,\n\tchildren: SessionDto[]\n}"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_the_aliases_of_renamed_imports() {
+        let options = ExporterOptions {
+            type_names: TypeNamePolicy::SerdeName,
+            ..ExporterOptions::default()
+        };
+        let result = launch_test_module(
+            r#"
+            use crate::models::User as Member;
+
+            pub mod models {
+                #[derive(Serialize)]
+                #[serde(rename = "UserDto")]
+                pub struct User { name: String }
+            }
+
+            #[derive(Serialize)]
+            pub struct User { member: Member }
+            "#,
+            &options,
+        );
+        let mut results = Vec::new();
+        collect(&mut results, result);
+        rename_references(&mut results).unwrap();
+
+        assert_eq!(
+            results[1].imports[0].to_string(),
+            "import { UserDto as Member } from \"my_crate::models\";"
+        );
+        assert_eq!(
+            results[1].exports[0].to_string(),
+            "export interface User {\n\tmember: Member\n}"
+        );
+    }
+}
@@ -0,0 +1,729 @@
+//! Scanning of the [warp](https://docs.rs/warp) filters of a module, to describe its endpoints, see [ExporterOptions::warp_endpoints](crate::contexts::exporter::ExporterOptions::warp_endpoints)
+//!
+//! The filters are analyzed statically, from the functions of the module that return a `Filter` :
+//! * the routes are the branches of the `or` combinators, made of the filters combined with `and`,
+//! * the path is read from `warp::path!`, `warp::path("...")` and `warp::path::param()`,
+//! * the method from `warp::get()`, `warp::post()`... (`ANY` when the filter doesn't restrict it),
+//! * the request from `warp::body::json()`,
+//! * the response from the `warp::reply::json(...)` of the handler given to `map` or `and_then`.
+//!
+//! The types that are not written with a turbofish are taken from the `Extract` of the functions returning a `Filter`,
+//! and from the parameters of the handler. The handlers must be closures or functions of the same module.
+//! A reply type is found when the replied value is a parameter of the handler, or a `let` binding with a type annotation.
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parse::Parser,
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    Expr, ExprCall, FnArg, GenericArgument, Item, ItemFn, LitStr, Local, Pat, PathArguments,
+    ReturnType, Token, Type, TypeParamBound,
+};
+
+use crate::type_solving::solvers::reference::strip_references;
+
+/// The default name of the interface the endpoints are exported as
+pub const ENDPOINTS_INTERFACE: &str = "Endpoints";
+
+/// An endpoint of a warp service
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    /// The HTTP method, `ANY` when the filter doesn't restrict it
+    pub method: String,
+    /// The path, the parameters being numbered in order (e.g. `/users/{0}/posts`)
+    pub path: String,
+    /// The types of the path parameters, `None` when they are unknown
+    pub params: Vec<Option<Type>>,
+    /// The JSON body of the request, if any
+    pub request: Option<JsonBody>,
+    /// The JSON body of the response, if any
+    pub response: Option<JsonBody>,
+}
+
+impl Endpoint {
+    /// The key of the endpoint in the endpoint map, e.g. `POST /users/{0}/posts`
+    pub fn key(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonBody {
+    Typed(Box<Type>),
+    /// A JSON body whose type could not be found
+    Untyped,
+}
+
+/// Lists the endpoints of the filters declared in `items`.
+///
+/// The functions that are used by other filters of the module are parts of their routes, they are not listed on their own.
+pub fn parse_endpoints(items: &[Item]) -> Vec<Endpoint> {
+    let functions: HashMap<String, &ItemFn> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item_fn) => Some((item_fn.sig.ident.to_string(), item_fn)),
+            _ => None,
+        })
+        .collect();
+    let analyzer = Analyzer {
+        functions: &functions,
+        called: RefCell::default(),
+        stack: RefCell::default(),
+    };
+    let filters: Vec<(&ItemFn, Vec<Route>)> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item_fn) if returns_filter(item_fn) => {
+                Some((item_fn, analyzer.routes_of_fn(item_fn)))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let called = analyzer.called.borrow();
+    let mut endpoints: Vec<Endpoint> = Vec::new();
+    for (item_fn, routes) in filters {
+        if called.contains(&item_fn.sig.ident.to_string()) {
+            continue;
+        }
+        for route in routes {
+            if let Some(endpoint) = route.into_endpoint() {
+                if !endpoints.iter().any(|other| other.key() == endpoint.key()) {
+                    endpoints.push(endpoint);
+                }
+            }
+        }
+    }
+    endpoints
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathPart {
+    Literal(String),
+    Param,
+}
+
+/// A value extracted by a filter
+#[derive(Debug, Clone, PartialEq)]
+enum Extraction {
+    Param(Option<Type>),
+    Body(Option<Type>),
+    Other,
+}
+
+/// What a handler tells about its route
+#[derive(Debug, Clone, PartialEq)]
+struct Handled {
+    params: Vec<Option<Type>>,
+    request: Option<JsonBody>,
+    response: Option<JsonBody>,
+}
+
+/// A branch of a filter
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Route {
+    method: Option<String>,
+    parts: Vec<PathPart>,
+    extractions: Vec<Extraction>,
+    handled: Option<Handled>,
+}
+
+impl Route {
+    fn extracting(extraction: Extraction) -> Self {
+        Route {
+            extractions: vec![extraction],
+            ..Route::default()
+        }
+    }
+
+    fn and(&self, other: &Route) -> Route {
+        Route {
+            method: self.method.clone().or_else(|| other.method.clone()),
+            parts: self
+                .parts
+                .iter()
+                .chain(other.parts.iter())
+                .cloned()
+                .collect(),
+            extractions: self
+                .extractions
+                .iter()
+                .chain(other.extractions.iter())
+                .cloned()
+                .collect(),
+            handled: other.handled.clone().or_else(|| self.handled.clone()),
+        }
+    }
+
+    /// Applies a handler, whose parameters are the extracted values
+    fn handle(mut self, handler: Handler) -> Route {
+        let handled = match self.handled.take() {
+            Some(mut handled) => {
+                if handled.response.is_none() {
+                    handled.response = handler.response;
+                }
+                handled
+            }
+            None => {
+                let param_type = |index: usize, ty: &Option<Type>| {
+                    ty.clone()
+                        .or_else(|| handler.params.get(index).cloned().flatten())
+                };
+                let params = self
+                    .extractions
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, extraction)| match extraction {
+                        Extraction::Param(ty) => Some(param_type(index, ty)),
+                        _ => None,
+                    })
+                    .collect();
+                let request =
+                    self.extractions
+                        .iter()
+                        .enumerate()
+                        .find_map(|(index, extraction)| match extraction {
+                            Extraction::Body(ty) => Some(match param_type(index, ty) {
+                                Some(ty) => JsonBody::Typed(Box::new(ty)),
+                                None => JsonBody::Untyped,
+                            }),
+                            _ => None,
+                        });
+                Handled {
+                    params,
+                    request,
+                    response: handler.response,
+                }
+            }
+        };
+        Route {
+            extractions: vec![Extraction::Other],
+            handled: Some(handled),
+            ..self
+        }
+    }
+
+    /// Types the extractions from the `Extract` of a function returning a `Filter`
+    fn apply_extract(&mut self, types: &[Type]) {
+        if self.handled.is_some() || self.extractions.len() != types.len() {
+            return;
+        }
+        for (extraction, ty) in self.extractions.iter_mut().zip(types.iter()) {
+            match extraction {
+                Extraction::Param(param @ None) | Extraction::Body(param @ None) => {
+                    *param = Some(ty.clone())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn into_endpoint(self) -> Option<Endpoint> {
+        let handled = self.handled?;
+        if self.parts.is_empty() && self.method.is_none() {
+            return None;
+        }
+        let mut index = 0;
+        let path: Vec<String> = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                PathPart::Literal(literal) => literal.clone(),
+                PathPart::Param => {
+                    index += 1;
+                    format!("{{{}}}", index - 1)
+                }
+            })
+            .collect();
+        Some(Endpoint {
+            method: self.method.unwrap_or_else(|| "ANY".to_string()),
+            path: format!("/{}", path.join("/")),
+            params: handled.params,
+            request: handled.request,
+            response: handled.response,
+        })
+    }
+}
+
+/// The types of the parameters of a handler, and its JSON reply
+struct Handler {
+    params: Vec<Option<Type>>,
+    response: Option<JsonBody>,
+}
+
+struct Analyzer<'a> {
+    functions: &'a HashMap<String, &'a ItemFn>,
+    /// The functions used by other filters
+    called: RefCell<HashSet<String>>,
+    /// The functions being analyzed, to stop on recursive filters
+    stack: RefCell<Vec<String>>,
+}
+
+const METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+
+/// The warp filters that extract a value, by the last segment of their path
+const EXTRACTORS: &[&str] = &[
+    "query",
+    "header",
+    "optional",
+    "form",
+    "cookie",
+    "bytes",
+    "aggregate",
+    "stream",
+    "full",
+    "tail",
+    "peek",
+    "remote",
+    "ws",
+    "multipart",
+    "host",
+];
+
+impl Analyzer<'_> {
+    fn routes_of_fn(&self, item_fn: &ItemFn) -> Vec<Route> {
+        let name = item_fn.sig.ident.to_string();
+        if self.stack.borrow().contains(&name) {
+            return vec![Route::default()];
+        }
+        self.stack.borrow_mut().push(name);
+        let mut routes = match tail_expr(&item_fn.block.stmts) {
+            Some(expr) => self.routes(expr),
+            None => vec![Route::default()],
+        };
+        self.stack.borrow_mut().pop();
+        let extract = extract_types(item_fn);
+        for route in routes.iter_mut() {
+            route.apply_extract(&extract);
+        }
+        routes
+    }
+
+    fn routes(&self, expr: &Expr) -> Vec<Route> {
+        match expr {
+            Expr::MethodCall(call) => {
+                let receiver = self.routes(&call.receiver);
+                match (call.method.to_string().as_str(), call.args.first()) {
+                    ("and", Some(arg)) => {
+                        let other = self.routes(arg);
+                        receiver
+                            .iter()
+                            .flat_map(|left| other.iter().map(move |right| left.and(right)))
+                            .collect()
+                    }
+                    ("or", Some(arg)) => receiver.into_iter().chain(self.routes(arg)).collect(),
+                    ("map" | "and_then" | "then", Some(arg)) => receiver
+                        .into_iter()
+                        .map(|route| route.handle(self.handler(arg)))
+                        .collect(),
+                    _ => receiver,
+                }
+            }
+            Expr::Call(call) => self.routes_of_call(call),
+            Expr::Macro(expr_macro) => {
+                let is_path = expr_macro
+                    .mac
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident == "path")
+                    .unwrap_or(false);
+                if is_path {
+                    path_macro_route(expr_macro.mac.tokens.clone())
+                } else {
+                    vec![Route::default()]
+                }
+            }
+            Expr::Paren(paren) => self.routes(&paren.expr),
+            Expr::Group(group) => self.routes(&group.expr),
+            Expr::Block(block) => match tail_expr(&block.block.stmts) {
+                Some(expr) => self.routes(expr),
+                None => vec![Route::default()],
+            },
+            _ => vec![Route::default()],
+        }
+    }
+
+    fn routes_of_call(&self, call: &ExprCall) -> Vec<Route> {
+        let path = match call.func.as_ref() {
+            Expr::Path(path) => &path.path,
+            _ => return vec![Route::default()],
+        };
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let last = match segments.last() {
+            Some(last) => last.as_str(),
+            None => return vec![Route::default()],
+        };
+        let turbofish = path
+            .segments
+            .last()
+            .and_then(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            });
+        let route = match (last, call.args.first()) {
+            ("path", Some(Expr::Lit(lit))) => match &lit.lit {
+                syn::Lit::Str(literal) => Route {
+                    parts: vec![PathPart::Literal(literal.value())],
+                    ..Route::default()
+                },
+                _ => Route::default(),
+            },
+            ("param", None) => Route {
+                parts: vec![PathPart::Param],
+                ..Route::extracting(Extraction::Param(turbofish))
+            },
+            ("json", None) => Route::extracting(Extraction::Body(turbofish)),
+            (method, None) if METHODS.contains(&method) => Route {
+                method: Some(method.to_uppercase()),
+                ..Route::default()
+            },
+            (extractor, _) if EXTRACTORS.contains(&extractor) => {
+                Route::extracting(Extraction::Other)
+            }
+            (name, _) => match self.functions.get(name) {
+                Some(item_fn) if segments.len() == 1 || segments[0] == "self" => {
+                    self.called.borrow_mut().insert(name.to_string());
+                    return self.routes_of_fn(item_fn);
+                }
+                // A filter of warp that doesn't extract anything, or a filter of the crate that extracts a value
+                _ if segments[0] == "warp" => Route::default(),
+                _ => Route::extracting(Extraction::Other),
+            },
+        };
+        vec![route]
+    }
+
+    fn handler(&self, expr: &Expr) -> Handler {
+        match expr {
+            Expr::Closure(closure) => {
+                let params: Vec<(Option<String>, Option<Type>)> = closure
+                    .inputs
+                    .iter()
+                    .map(|input| match input {
+                        Pat::Type(pat_type) => {
+                            (pat_ident(&pat_type.pat), Some(*pat_type.ty.clone()))
+                        }
+                        pat => (pat_ident(pat), None),
+                    })
+                    .collect();
+                let response = find_reply(&params, |visitor| visitor.visit_expr(&closure.body));
+                Handler {
+                    params: params.into_iter().map(|(_, ty)| ty).collect(),
+                    response,
+                }
+            }
+            Expr::Path(path) => {
+                let item_fn = path
+                    .path
+                    .segments
+                    .last()
+                    .and_then(|segment| self.functions.get(&segment.ident.to_string()));
+                match item_fn {
+                    Some(item_fn) => {
+                        let params: Vec<(Option<String>, Option<Type>)> = item_fn
+                            .sig
+                            .inputs
+                            .iter()
+                            .map(|input| match input {
+                                FnArg::Typed(pat_type) => {
+                                    (pat_ident(&pat_type.pat), Some(*pat_type.ty.clone()))
+                                }
+                                FnArg::Receiver(_) => (None, None),
+                            })
+                            .collect();
+                        let response =
+                            find_reply(&params, |visitor| visitor.visit_block(&item_fn.block));
+                        Handler {
+                            params: params.into_iter().map(|(_, ty)| ty).collect(),
+                            response,
+                        }
+                    }
+                    None => Handler {
+                        params: Vec::new(),
+                        response: None,
+                    },
+                }
+            }
+            _ => Handler {
+                params: Vec::new(),
+                response: None,
+            },
+        }
+    }
+}
+
+fn tail_expr(stmts: &[syn::Stmt]) -> Option<&Expr> {
+    match stmts.last()? {
+        syn::Stmt::Expr(expr) => Some(expr),
+        _ => None,
+    }
+}
+
+fn pat_ident(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether the function returns a `Filter` (e.g. `impl Filter<...>`, `BoxedFilter<...>`)
+fn returns_filter(item_fn: &ItemFn) -> bool {
+    match &item_fn.sig.output {
+        ReturnType::Type(_, ty) => ty.to_token_stream().to_string().contains("Filter"),
+        ReturnType::Default => false,
+    }
+}
+
+/// The types of `impl Filter<Extract = (A, B), ...>`
+fn extract_types(item_fn: &ItemFn) -> Vec<Type> {
+    let bounds = match &item_fn.sig.output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::ImplTrait(impl_trait) => &impl_trait.bounds,
+            _ => return Vec::new(),
+        },
+        ReturnType::Default => return Vec::new(),
+    };
+    bounds
+        .iter()
+        .filter_map(|bound| match bound {
+            TypeParamBound::Trait(bound) => bound.path.segments.last(),
+            _ => None,
+        })
+        .filter_map(|segment| match &segment.arguments {
+            PathArguments::AngleBracketed(args) => Some(args),
+            _ => None,
+        })
+        .flat_map(|args| args.args.iter())
+        .find_map(|arg| match arg {
+            GenericArgument::Binding(binding) if binding.ident == "Extract" => match &binding.ty {
+                Type::Tuple(tuple) => Some(tuple.elems.iter().cloned().collect()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Reads `warp::path!("users" / u32 / "posts")`
+fn path_macro_route(tokens: TokenStream) -> Vec<Route> {
+    let parser = Punctuated::<PathMacroSegment, Token![/]>::parse_terminated;
+    let segments = match parser.parse2(tokens) {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Could not read a path! macro : {}", error);
+            return vec![Route::default()];
+        }
+    };
+    let mut route = Route::default();
+    for segment in segments {
+        match segment {
+            PathMacroSegment::Literal(literal) => {
+                route.parts.push(PathPart::Literal(literal.value()))
+            }
+            PathMacroSegment::Param(ty) => {
+                route.parts.push(PathPart::Param);
+                route.extractions.push(Extraction::Param(Some(*ty)));
+            }
+            PathMacroSegment::Rest => {}
+        }
+    }
+    vec![route]
+}
+
+enum PathMacroSegment {
+    Literal(LitStr),
+    Param(Box<Type>),
+    /// The trailing `..` that allows more segments
+    Rest,
+}
+
+impl syn::parse::Parse for PathMacroSegment {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(PathMacroSegment::Literal(input.parse()?))
+        } else if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            Ok(PathMacroSegment::Rest)
+        } else {
+            Ok(PathMacroSegment::Param(Box::new(input.parse()?)))
+        }
+    }
+}
+
+/// Finds the type of the `warp::reply::json(...)` of a handler
+fn find_reply<F: FnOnce(&mut ReplyVisitor)>(
+    params: &[(Option<String>, Option<Type>)],
+    visit: F,
+) -> Option<JsonBody> {
+    let mut visitor = ReplyVisitor::default();
+    for (ident, ty) in params {
+        if let (Some(ident), Some(ty)) = (ident, ty) {
+            visitor.bindings.insert(ident.clone(), ty.clone());
+        }
+    }
+    visit(&mut visitor);
+    let ReplyVisitor { bindings, replies } = visitor;
+    let mut replies = replies.into_iter().map(|reply| match reply {
+        Reply::Typed(ty) => JsonBody::Typed(ty),
+        Reply::Binding(ident) => match bindings.get(&ident) {
+            Some(ty) => JsonBody::Typed(Box::new(strip_references(ty).clone())),
+            None => JsonBody::Untyped,
+        },
+        Reply::Untyped => JsonBody::Untyped,
+    });
+    let first = replies.next()?;
+    Some(match first {
+        JsonBody::Untyped => replies
+            .find(|reply| matches!(reply, JsonBody::Typed(_)))
+            .unwrap_or(JsonBody::Untyped),
+        typed => typed,
+    })
+}
+
+enum Reply {
+    Typed(Box<Type>),
+    /// The replied value is a variable, typed by a parameter or a `let` binding
+    Binding(String),
+    Untyped,
+}
+
+#[derive(Default)]
+struct ReplyVisitor {
+    bindings: HashMap<String, Type>,
+    replies: Vec<Reply>,
+}
+
+impl<'ast> Visit<'ast> for ReplyVisitor {
+    fn visit_local(&mut self, local: &'ast Local) {
+        if let Pat::Type(pat_type) = &local.pat {
+            if let Some(ident) = pat_ident(&pat_type.pat) {
+                self.bindings.insert(ident, *pat_type.ty.clone());
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(path) = call.func.as_ref() {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            let is_reply = match segments.as_slice() {
+                [.., reply, json] => reply == "reply" && json == "json",
+                [json] => json == "json",
+                [] => false,
+            };
+            if is_reply && call.args.len() == 1 {
+                let turbofish =
+                    path.path
+                        .segments
+                        .last()
+                        .and_then(|segment| match &segment.arguments {
+                            PathArguments::AngleBracketed(args) => {
+                                args.args.iter().find_map(|arg| match arg {
+                                    GenericArgument::Type(ty) => Some(ty.clone()),
+                                    _ => None,
+                                })
+                            }
+                            _ => None,
+                        });
+                let mut value = &call.args[0];
+                while let Expr::Reference(reference) = value {
+                    value = &reference.expr;
+                }
+                self.replies.push(match (turbofish, value) {
+                    (Some(ty), _) => Reply::Typed(Box::new(ty)),
+                    (None, Expr::Path(value)) => match value.path.get_ident() {
+                        Some(ident) => Reply::Binding(ident.to_string()),
+                        None => Reply::Untyped,
+                    },
+                    _ => Reply::Untyped,
+                });
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn ty(source: &str) -> Type {
+        syn::parse_str(source).unwrap()
+    }
+
+    #[test]
+    fn should_parse_endpoints() {
+        let items = syn::parse_file(
+            r#"
+            pub fn routes() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+                create_post().or(list_posts(db()))
+            }
+
+            fn create_post() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+                warp::path!("users" / u32 / "posts")
+                    .and(warp::post())
+                    .and(json_body())
+                    .and_then(create)
+            }
+
+            fn list_posts(db: Db) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+                warp::path("posts")
+                    .and(warp::path::param())
+                    .and(warp::get())
+                    .and(with_db(db))
+                    .map(|page: u32, db: Db| {
+                        let posts: Vec<Post> = db.posts(page);
+                        warp::reply::json(&posts)
+                    })
+            }
+
+            fn json_body() -> impl Filter<Extract = (NewPost,), Error = Rejection> + Clone {
+                warp::body::content_length_limit(1024 * 16).and(warp::body::json())
+            }
+
+            async fn create(user_id: u32, post: NewPost) -> Result<impl Reply, Rejection> {
+                Ok(warp::reply::json(&post))
+            }
+            "#,
+        )
+        .unwrap()
+        .items;
+        assert_eq!(
+            parse_endpoints(&items),
+            vec![
+                Endpoint {
+                    method: "POST".to_string(),
+                    path: "/users/{0}/posts".to_string(),
+                    params: vec![Some(ty("u32"))],
+                    request: Some(JsonBody::Typed(Box::new(ty("NewPost")))),
+                    response: Some(JsonBody::Typed(Box::new(ty("NewPost")))),
+                },
+                Endpoint {
+                    method: "GET".to_string(),
+                    path: "/posts/{0}".to_string(),
+                    params: vec![Some(ty("u32"))],
+                    request: None,
+                    response: Some(JsonBody::Typed(Box::new(ty("Vec<Post>")))),
+                },
+            ]
+        );
+    }
+}
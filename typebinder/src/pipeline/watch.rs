@@ -0,0 +1,219 @@
+//! Launching the [Pipeline] again whenever its sources change
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use notify_debouncer_mini::{
+    new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+    DebounceEventResult, Debouncer,
+};
+
+use super::{report::RunReport, Pipeline};
+use crate::{
+    contexts::type_solving::TypeSolvingContext, error::TsExportError, exporters::Exporter,
+    macros::context::MacroSolvingContext, step_spawner::PipelineStepSpawner,
+};
+
+/// Waits for a set of files to change, with the file system notifications of the platform.
+///
+/// The directories of the files are watched rather than the files, so that a file replaced by an editor,
+/// removed, or created after being missing counts as changed.
+/// The events are debounced, the changes made within the debounce duration (e.g. a save writing several files) being reported together.
+pub struct SourceWatcher {
+    files: BTreeSet<PathBuf>,
+    events: Receiver<DebounceEventResult>,
+    // Stops watching when dropped
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl SourceWatcher {
+    pub fn new<I: IntoIterator<Item = PathBuf>>(
+        files: I,
+        debounce: Duration,
+    ) -> Result<Self, TsExportError> {
+        let files: BTreeSet<PathBuf> = files.into_iter().map(|file| canonical(&file)).collect();
+        let (sender, events) = mpsc::channel();
+        let mut debouncer = new_debouncer(debounce, sender)?;
+        let dirs: BTreeSet<&Path> = files.iter().filter_map(|file| file.parent()).collect();
+        for dir in dirs {
+            debouncer
+                .watcher()
+                .watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        Ok(SourceWatcher {
+            files,
+            events,
+            _debouncer: debouncer,
+        })
+    }
+
+    /// Blocks until some of the files change, and returns them
+    pub fn wait(&self) -> Result<Vec<PathBuf>, TsExportError> {
+        loop {
+            let events = match self.events.recv() {
+                Ok(events) => events?,
+                Err(_) => return Ok(Vec::new()),
+            };
+            let changed: BTreeSet<PathBuf> = events
+                .into_iter()
+                .map(|event| canonical(&event.path))
+                .filter(|path| self.files.contains(path))
+                .collect();
+            if !changed.is_empty() {
+                return Ok(changed.into_iter().collect());
+            }
+        }
+    }
+}
+
+/// The path of a file through the canonical path of its directory, the file itself may be missing
+fn canonical(file: &Path) -> PathBuf {
+    match (file.parent(), file.file_name()) {
+        (Some(dir), Some(name)) => std::fs::canonicalize(if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        })
+        .map(|dir| dir.join(name))
+        .unwrap_or_else(|_| file.to_path_buf()),
+        _ => file.to_path_buf(),
+    }
+}
+
+impl<PSS, E> Pipeline<PSS, E>
+where
+    PSS: PipelineStepSpawner,
+    E: Exporter,
+    TsExportError: From<PSS::Error> + From<E::Error>,
+{
+    /// Launches the pipeline, then launches it again each time one of the [source files](PipelineStepSpawner::source_files)
+    /// it read is modified, see [SourceWatcher]. The changes are debounced over `debounce`.
+    ///
+    /// `on_run` receives the pipeline and the result of each run, a failed run (e.g. a file being edited that doesn't parse) not stopping the watch,
+    /// and the watch stops when it returns `false`. It fails when the files can't be watched.
+    /// With a [RustModuleReader](crate::step_spawner::mod_reader::RustModuleReader), only the modified files are parsed again,
+    /// and a [FileExporter](crate::exporters::file::FileExporter) only writes the modules whose output changed.
+    pub fn watch<F>(
+        &mut self,
+        solving_context: &TypeSolvingContext,
+        macro_context: &MacroSolvingContext,
+        debounce: Duration,
+        mut on_run: F,
+    ) -> Result<(), TsExportError>
+    where
+        F: FnMut(&Self, Result<RunReport, TsExportError>) -> bool,
+    {
+        loop {
+            let result = self.launch(solving_context, macro_context);
+            let watcher = SourceWatcher::new(self.pipeline_step_spawner.source_files(), debounce)?;
+            if !on_run(self, result) {
+                return Ok(());
+            }
+            let changed = watcher.wait()?;
+            log::info!("Sources changed : {:?}", changed);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        exporters::{file::FileExporter, output_options::OutputOptions},
+        path_mapper::PathMapper,
+        step_spawner::mod_reader::RustModuleReader,
+    };
+    use std::time::SystemTime;
+
+    #[test]
+    fn should_export_again_the_modules_that_changed() {
+        let root = test_dir("watch");
+        let src = root.join("src");
+        let out = root.join("types");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"watch-test\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("lib.rs"), "mod models; mod events;").unwrap();
+        std::fs::write(
+            src.join("models.rs"),
+            "#[derive(Serialize)] pub struct User { id: u32 }",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("events.rs"),
+            "#[derive(Serialize)] pub struct Event { id: u32 }",
+        )
+        .unwrap();
+
+        let mut exporter = FileExporter::new(out.clone());
+        exporter.set_output_options(OutputOptions::without_header());
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: RustModuleReader::try_new(src.join("lib.rs")).unwrap(),
+            exporter,
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions::default(),
+        };
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let mut runs = 0;
+        pipeline
+            .watch(
+                &solving_context,
+                &MacroSolvingContext::default(),
+                Duration::from_millis(50),
+                |_, result| {
+                    result.expect("Failed to run");
+                    runs += 1;
+                    if runs == 1 {
+                        // Nothing is written again when the output doesn't change
+                        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+                        for file in ["models.ts", "events.ts"].iter() {
+                            std::fs::File::options()
+                                .write(true)
+                                .open(out.join(file))
+                                .unwrap()
+                                .set_modified(old)
+                                .unwrap();
+                        }
+                        std::fs::write(
+                            src.join("models.rs"),
+                            "#[derive(Serialize)] pub struct User { id: u32, name: String }",
+                        )
+                        .unwrap();
+                        // Whatever the precision of the modification times of the filesystem
+                        std::fs::File::options()
+                            .write(true)
+                            .open(src.join("models.rs"))
+                            .unwrap()
+                            .set_modified(old)
+                            .unwrap();
+                    }
+                    runs < 2
+                },
+            )
+            .expect("Failed to watch");
+
+        assert_eq!(runs, 2);
+        assert_eq!(
+            std::fs::read_to_string(out.join("models.ts")).unwrap(),
+            "export interface User {\n\tid: number,\n\tname: string\n}\n"
+        );
+        assert_eq!(
+            std::fs::metadata(out.join("events.ts"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1000)
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! The library crates of a Cargo manifest, so that all the crates of a workspace can be processed in a single run
+use std::path::{Path, PathBuf};
+
+use cargo_toml::Manifest;
+
+use super::mod_reader::RustModuleReader;
+use crate::{error::TsExportError, utils::cargo::Error, utils::glob::Glob};
+
+/// A crate with a library target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoCrate {
+    /// The name of the package
+    pub name: String,
+    /// The root module of the library, `src/lib.rs` unless the `[lib]` section gives another path
+    pub lib_path: PathBuf,
+}
+
+impl CargoCrate {
+    /// The reader of the modules of the library
+    pub fn module_reader(&self) -> Result<RustModuleReader, TsExportError> {
+        RustModuleReader::try_new(self.lib_path.clone())
+    }
+
+    /// The name of the crate as used in Rust paths, with underscores
+    pub fn crate_name(&self) -> String {
+        self.name.replace('-', "_")
+    }
+}
+
+/// The library crates of a `Cargo.toml` : its package, and the members of its `[workspace]`.
+///
+/// The members can be globs, e.g. `crates/*`, and the `exclude` list of the workspace is respected.
+/// The crates without a library target (the binaries) are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoWorkspace {
+    crates: Vec<CargoCrate>,
+}
+
+impl CargoWorkspace {
+    pub fn load<P: AsRef<Path>>(manifest_path: P) -> Result<Self, TsExportError> {
+        let manifest_path = manifest_path.as_ref();
+        let dir = match manifest_path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let manifest = read_manifest(manifest_path)?;
+
+        let mut crates: Vec<CargoCrate> = library_crate(dir, &manifest).into_iter().collect();
+        if let Some(workspace) = &manifest.workspace {
+            let excluded: Vec<PathBuf> = workspace
+                .exclude
+                .iter()
+                .map(|exclude| dir.join(exclude))
+                .collect();
+            for member in workspace.members.iter() {
+                for member_dir in expand_member(dir, member)? {
+                    if member_dir == dir || excluded.contains(&member_dir) {
+                        continue;
+                    }
+                    let member_manifest = read_manifest(&member_dir.join("Cargo.toml"))?;
+                    crates.extend(library_crate(&member_dir, &member_manifest));
+                }
+            }
+        }
+        Ok(CargoWorkspace { crates })
+    }
+
+    pub fn crates(&self) -> &[CargoCrate] {
+        &self.crates
+    }
+
+    /// The other crates of the workspace, by their name in Rust paths, with their directory relatively to the directory of `member`,
+    /// each crate being written to a directory named after its package (see [FileExporter::add_crate_dir](crate::exporters::file::FileExporter::add_crate_dir))
+    pub fn sibling_dirs(&self, member: &CargoCrate) -> Vec<(String, PathBuf)> {
+        self.crates
+            .iter()
+            .filter(|other| *other != member)
+            .map(|other| (other.crate_name(), Path::new("..").join(&other.name)))
+            .collect()
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, TsExportError> {
+    let mut manifest = Manifest::from_slice(&std::fs::read(path)?).map_err(Error::from)?;
+    // The targets are discovered in the `src` directory, without it only the declared ones are known
+    if path.with_file_name("src").is_dir() {
+        manifest.complete_from_path(path).map_err(Error::from)?;
+    }
+    Ok(manifest)
+}
+
+fn library_crate(dir: &Path, manifest: &Manifest) -> Option<CargoCrate> {
+    let package = manifest.package.as_ref()?;
+    match &manifest.lib {
+        Some(lib) => Some(CargoCrate {
+            name: package.name.clone(),
+            lib_path: dir.join(lib.path.as_deref().unwrap_or("src/lib.rs")),
+        }),
+        None => {
+            log::info!("{} has no library target, it is skipped", package.name);
+            None
+        }
+    }
+}
+
+/// The directories of a member of the workspace that have a `Cargo.toml`, its segments being globs
+fn expand_member(dir: &Path, member: &str) -> Result<Vec<PathBuf>, TsExportError> {
+    let mut dirs = vec![dir.to_path_buf()];
+    for segment in member.split('/').filter(|segment| !segment.is_empty()) {
+        if !segment.contains(['*', '?'].as_ref()) {
+            dirs = dirs.into_iter().map(|dir| dir.join(segment)).collect();
+            continue;
+        }
+        let glob = Glob::new(segment);
+        let mut matched = Vec::new();
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if path.is_dir() && glob.is_match(&name) {
+                    matched.push(path);
+                }
+            }
+        }
+        matched.sort();
+        dirs = matched;
+    }
+    Ok(dirs
+        .into_iter()
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .collect())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        exporters::file::FileExporter,
+        macros::context::MacroSolvingContext,
+        path_mapper::PathMapper,
+        pipeline::Pipeline,
+        utils::test_dir,
+    };
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn should_find_the_libraries_of_this_workspace() {
+        let workspace = CargoWorkspace::load("../Cargo.toml").unwrap();
+        assert_eq!(
+            workspace.crates(),
+            &[
+                CargoCrate {
+                    name: "ts_json_subset".to_string(),
+                    lib_path: PathBuf::from("../ts_json_subset/src/lib.rs"),
+                },
+                CargoCrate {
+                    name: "typebinder".to_string(),
+                    lib_path: PathBuf::from("../typebinder/src/lib.rs"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_expand_the_globs_of_the_members() {
+        let root = test_dir("cargo_workspace");
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/skipped\"]\n",
+        );
+        for (name, lib) in [
+            ("models", "src/lib.rs"),
+            ("api", "api.rs"),
+            ("skipped", "src/lib.rs"),
+        ]
+        .iter()
+        {
+            let manifest = format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[lib]\npath = \"{}\"\n",
+                name, lib
+            );
+            write(
+                &root.join("crates").join(name).join("Cargo.toml"),
+                &manifest,
+            );
+            write(&root.join("crates").join(name).join(lib), "");
+        }
+        write(
+            &root.join("crates/cli/Cargo.toml"),
+            "[package]\nname = \"cli\"\nversion = \"0.1.0\"\n",
+        );
+        write(&root.join("crates/cli/src/main.rs"), "fn main() {}");
+
+        let workspace = CargoWorkspace::load(root.join("Cargo.toml")).unwrap();
+        assert_eq!(
+            workspace.crates(),
+            &[
+                CargoCrate {
+                    name: "api".to_string(),
+                    lib_path: root.join("crates/api/api.rs"),
+                },
+                CargoCrate {
+                    name: "models".to_string(),
+                    lib_path: root.join("crates/models/src/lib.rs"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_import_the_other_members_from_their_directory() {
+        let root = test_dir("cargo_siblings");
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"api\", \"shared-models\"]\n",
+        );
+        for name in ["api", "shared-models"].iter() {
+            write(
+                &root.join(name).join("Cargo.toml"),
+                &format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+            );
+        }
+        write(
+            &root.join("api/src/lib.rs"),
+            "#[derive(Serialize)] pub struct Session { dto: shared_models::models::Dto }",
+        );
+        write(&root.join("shared-models/src/lib.rs"), "pub mod models;");
+        write(
+            &root.join("shared-models/src/models.rs"),
+            "#[derive(Serialize)] pub struct Dto { id: u32 }",
+        );
+
+        let workspace = CargoWorkspace::load(root.join("Cargo.toml")).unwrap();
+        let api = &workspace.crates()[0];
+        assert_eq!(
+            workspace.sibling_dirs(api),
+            vec![(
+                "shared_models".to_string(),
+                PathBuf::from("../shared-models")
+            )]
+        );
+
+        let output = root.join("out");
+        let mut exporter = FileExporter::new(output.join(&api.name));
+        for (other, dir) in workspace.sibling_dirs(api) {
+            exporter.add_crate_dir(&other, &dir);
+        }
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: api.module_reader().unwrap(),
+            exporter,
+            path_mapper: PathMapper::default(),
+            options: ExporterOptions::default(),
+        };
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        pipeline
+            .launch(&solving_context, &MacroSolvingContext::default())
+            .unwrap();
+        let contents = std::fs::read_to_string(output.join("api/index.ts")).unwrap();
+        assert!(
+            contents.contains("import { Dto } from \"../shared-models/models\";"),
+            "{}",
+            contents
+        );
+    }
+}
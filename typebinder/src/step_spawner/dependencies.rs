@@ -0,0 +1,226 @@
+//! The sources of the dependencies of a crate, located with `cargo metadata`, so that the types of another crate can be exported too
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Deserialize;
+
+use super::mod_reader::RustModuleReader;
+use crate::error::TsExportError;
+
+/// A dependency with a library target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// The name of the dependency in Rust paths, which differs from the name of its package when renamed in the `Cargo.toml`
+    pub name: String,
+    /// The root module of the library
+    pub lib_path: PathBuf,
+}
+
+impl Dependency {
+    /// The reader of the modules of the library, named after the dependency
+    pub fn module_reader(&self) -> Result<RustModuleReader, TsExportError> {
+        let mut reader = RustModuleReader::try_new(self.lib_path.clone())?;
+        reader.set_crate_name(&self.name);
+        Ok(reader)
+    }
+}
+
+/// The dependencies of the packages of a `Cargo.toml` (and of the members of its workspace), by name
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CargoDependencies {
+    dependencies: HashMap<String, Dependency>,
+}
+
+impl CargoDependencies {
+    /// Runs `cargo metadata` on the manifest, which resolves (and may download) the dependencies
+    pub fn load<P: AsRef<Path>>(manifest_path: P) -> Result<Self, TsExportError> {
+        let manifest_path = manifest_path.as_ref();
+        let output = Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--manifest-path"])
+            .arg(manifest_path)
+            .output()?;
+        if !output.status.success() {
+            return Err(TsExportError::CargoMetadataFailed(
+                manifest_path.to_path_buf(),
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        Self::from_metadata(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Reads the output of `cargo metadata --format-version 1`.
+    ///
+    /// The other members of the workspace and the path dependencies are dependencies like the others,
+    /// only the package of the manifest is left out.
+    pub fn from_metadata(metadata: &str) -> Result<Self, TsExportError> {
+        let Metadata {
+            packages,
+            workspace_members,
+            resolve,
+        } = serde_json::from_str(metadata)?;
+        let lib_paths: HashMap<&str, &PathBuf> = packages
+            .iter()
+            .filter_map(|package| {
+                let lib = package.targets.iter().find(|target| {
+                    target
+                        .kind
+                        .iter()
+                        .any(|kind| kind == "lib" || kind == "rlib")
+                })?;
+                Some((package.id.as_str(), &lib.src_path))
+            })
+            .collect();
+        let resolve = resolve.unwrap_or_default();
+
+        let mut dependencies = HashMap::new();
+        let nodes = resolve
+            .nodes
+            .iter()
+            .filter(|node| workspace_members.contains(&node.id));
+        for node in nodes {
+            let deps = node
+                .deps
+                .iter()
+                .filter(|dep| resolve.root.as_ref() != Some(&dep.pkg));
+            for dep in deps {
+                if let Some(lib_path) = lib_paths.get(dep.pkg.as_str()) {
+                    dependencies.insert(
+                        dep.name.clone(),
+                        Dependency {
+                            name: dep.name.clone(),
+                            lib_path: (*lib_path).clone(),
+                        },
+                    );
+                }
+            }
+        }
+        Ok(CargoDependencies { dependencies })
+    }
+
+    /// The dependency named `name` in Rust paths
+    pub fn get(&self, name: &str) -> Option<&Dependency> {
+        self.dependencies.get(name)
+    }
+}
+
+/// The output of `cargo metadata --format-version 1`, without the fields typebinder doesn't read
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+    /// `null` with `--no-deps`
+    resolve: Option<Resolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    kind: Vec<String>,
+    src_path: PathBuf,
+}
+
+/// The graph of the dependencies
+#[derive(Debug, Default, Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+    /// The package of the manifest, `None` for a virtual manifest
+    root: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+}
+
+/// A dependency of a package, `name` being its name in Rust paths and `pkg` the id of its package
+#[derive(Debug, Deserialize)]
+struct NodeDep {
+    name: String,
+    pkg: String,
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "name": "my-crate",
+                "id": "my-crate 0.1.0 (path+file:///work/my-crate)",
+                "targets": [{ "kind": ["lib"], "name": "my_crate", "src_path": "/work/my-crate/src/lib.rs" }]
+            },
+            {
+                "name": "other-types",
+                "id": "other-types 1.2.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                "targets": [
+                    { "kind": ["lib"], "name": "other_types", "src_path": "/registry/other-types-1.2.0/src/lib.rs" },
+                    { "kind": ["bin"], "name": "tool", "src_path": "/registry/other-types-1.2.0/src/main.rs" }
+                ]
+            },
+            {
+                "name": "other-crate",
+                "id": "other-crate 0.1.0 (path+file:///work/other-crate)",
+                "targets": [{ "kind": ["lib"], "name": "other_crate", "src_path": "/work/other-crate/src/lib.rs" }]
+            },
+            {
+                "name": "codegen",
+                "id": "codegen 0.3.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                "targets": [{ "kind": ["bin"], "name": "codegen", "src_path": "/registry/codegen-0.3.0/src/main.rs" }]
+            }
+        ],
+        "workspace_members": [
+            "my-crate 0.1.0 (path+file:///work/my-crate)",
+            "other-crate 0.1.0 (path+file:///work/other-crate)"
+        ],
+        "resolve": {
+            "root": "my-crate 0.1.0 (path+file:///work/my-crate)",
+            "nodes": [
+                {
+                    "id": "my-crate 0.1.0 (path+file:///work/my-crate)",
+                    "deps": [
+                        { "name": "shared", "pkg": "other-types 1.2.0 (registry+https://github.com/rust-lang/crates.io-index)" },
+                        { "name": "codegen", "pkg": "codegen 0.3.0 (registry+https://github.com/rust-lang/crates.io-index)" },
+                        { "name": "other_crate", "pkg": "other-crate 0.1.0 (path+file:///work/other-crate)" }
+                    ]
+                },
+                {
+                    "id": "other-crate 0.1.0 (path+file:///work/other-crate)",
+                    "deps": [{ "name": "my_crate", "pkg": "my-crate 0.1.0 (path+file:///work/my-crate)" }]
+                },
+                { "id": "other-types 1.2.0 (registry+https://github.com/rust-lang/crates.io-index)", "deps": [] }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn should_read_the_libraries_of_the_dependencies() {
+        let dependencies = CargoDependencies::from_metadata(METADATA).unwrap();
+        assert_eq!(
+            dependencies.get("shared"),
+            Some(&Dependency {
+                name: "shared".to_string(),
+                lib_path: PathBuf::from("/registry/other-types-1.2.0/src/lib.rs"),
+            })
+        );
+        assert_eq!(
+            dependencies.get("other_crate"),
+            Some(&Dependency {
+                name: "other_crate".to_string(),
+                lib_path: PathBuf::from("/work/other-crate/src/lib.rs"),
+            })
+        );
+        assert_eq!(dependencies.get("other_types"), None);
+        assert_eq!(dependencies.get("codegen"), None);
+        assert_eq!(dependencies.get("my_crate"), None);
+    }
+}
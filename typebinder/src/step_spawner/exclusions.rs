@@ -0,0 +1,162 @@
+//! The source files that the [RustModuleReader](super::mod_reader::RustModuleReader) must not descend into
+use std::path::{Path, PathBuf};
+
+use crate::utils::glob::Glob;
+
+/// The exclusions of a crate : glob patterns relative to the directory of the crate (e.g. `**/generated/**`, `src/bench_utils.rs`),
+/// and optionally the `.gitignore` files of the repository
+#[derive(Debug, Clone)]
+pub struct Exclusions {
+    crate_dir: PathBuf,
+    globs: Vec<Glob>,
+    gitignore: bool,
+}
+
+impl Exclusions {
+    pub fn new(crate_dir: PathBuf) -> Self {
+        Exclusions {
+            crate_dir,
+            globs: Vec::new(),
+            gitignore: false,
+        }
+    }
+
+    pub fn add_glob(&mut self, pattern: &str) {
+        self.globs.push(Glob::new(pattern));
+    }
+
+    pub fn set_gitignore(&mut self, gitignore: bool) {
+        self.gitignore = gitignore;
+    }
+
+    /// Whether the source file `path` is excluded, `path` being absolute
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if let Ok(relative) = path.strip_prefix(&self.crate_dir) {
+            let relative = slash_path(relative);
+            if self.globs.iter().any(|glob| glob.is_match(&relative)) {
+                return true;
+            }
+        }
+        self.gitignore && is_git_ignored(path)
+    }
+}
+
+/// A rule of a `.gitignore` file
+struct IgnoreRule {
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// The rules of a `.gitignore` file, and the directory they are relative to
+struct IgnoreFile {
+    dir: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+fn parse_rules(contents: &str) -> Vec<IgnoreRule> {
+    contents
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            // Without a slash, a pattern matches at any depth
+            let glob = if line.contains('/') {
+                Glob::new(line)
+            } else {
+                Glob::new(&format!("**/{}", line))
+            };
+            IgnoreRule {
+                glob,
+                negated,
+                dir_only,
+            }
+        })
+        .collect()
+}
+
+/// The `.gitignore` files that apply to `path`, from the root of the repository down to its directory
+fn ignore_files(path: &Path) -> Vec<IgnoreFile> {
+    let mut files = Vec::new();
+    for dir in path.ancestors().skip(1) {
+        if let Ok(contents) = std::fs::read_to_string(dir.join(".gitignore")) {
+            files.push(IgnoreFile {
+                dir: dir.to_path_buf(),
+                rules: parse_rules(&contents),
+            });
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    files.reverse();
+    files
+}
+
+/// Follows git : the last matching rule wins, and nothing is re-included from an ignored directory
+fn is_git_ignored(path: &Path) -> bool {
+    let files = ignore_files(path);
+    let mut candidates: Vec<&Path> = path.ancestors().collect();
+    candidates.reverse();
+    candidates.iter().any(|candidate| {
+        let is_dir = *candidate != path;
+        let mut ignored = false;
+        for file in files.iter() {
+            let relative = match candidate.strip_prefix(&file.dir) {
+                Ok(relative) if !relative.as_os_str().is_empty() => slash_path(relative),
+                _ => continue,
+            };
+            for rule in file.rules.iter() {
+                if (is_dir || !rule.dir_only) && rule.glob.is_match(&relative) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+        ignored
+    })
+}
+
+fn slash_path(path: &Path) -> String {
+    path.iter()
+        .map(|segment| segment.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    #[test]
+    fn should_exclude_globs_and_ignored_files() {
+        let root = test_dir("exclusions");
+        let src = root.join("src");
+        std::fs::create_dir_all(src.join("generated")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(
+            root.join(".gitignore"),
+            "# Build artifacts\ntarget/\n*.gen.rs\n!kept.gen.rs\n",
+        )
+        .unwrap();
+
+        let mut exclusions = Exclusions::new(root.clone());
+        exclusions.add_glob("**/generated/**");
+        assert!(exclusions.is_excluded(&src.join("generated").join("mod.rs")));
+        assert!(!exclusions.is_excluded(&src.join("models.gen.rs")));
+
+        exclusions.set_gitignore(true);
+        assert!(exclusions.is_excluded(&src.join("models.gen.rs")));
+        assert!(!exclusions.is_excluded(&src.join("kept.gen.rs")));
+        assert!(exclusions.is_excluded(&root.join("target").join("lib.rs")));
+        assert!(!exclusions.is_excluded(&src.join("models.rs")));
+    }
+}
@@ -1,11 +1,16 @@
 //! How to load Rust input modules
 
+use std::path::PathBuf;
+
 use syn::Path;
 
 use crate::error::TsExportError;
 use crate::pipeline::module_step::ModuleStep;
 
+pub mod cargo;
+pub mod dependencies;
 pub mod discard;
+pub mod exclusions;
 pub mod mod_reader;
 
 /// An abstraction that specifies how to create a Step of the pipeline.
@@ -14,4 +19,33 @@ pub mod mod_reader;
 pub trait PipelineStepSpawner {
     type Error: Into<TsExportError>;
     fn create_process(&self, path: Path) -> Result<Option<ModuleStep>, Self::Error>;
+
+    /// Creates the step of a module declared with a `#[path = "..."]` attribute, `file_path` being its value.
+    ///
+    /// By default, the attribute is ignored.
+    fn create_process_at(
+        &self,
+        path: Path,
+        _file_path: &str,
+    ) -> Result<Option<ModuleStep>, Self::Error> {
+        self.create_process(path)
+    }
+
+    /// The paths of the modules the pipeline starts from, the root module (with an empty path) by default
+    fn roots(&self) -> Vec<Path> {
+        vec![Path {
+            leading_colon: None,
+            segments: Default::default(),
+        }]
+    }
+
+    /// Called by [Pipeline::launch](crate::pipeline::Pipeline::launch) before spawning the roots
+    fn start_run(&self) {}
+
+    /// The source files read by the last run, that [Pipeline::watch](crate::pipeline::Pipeline::watch) watches.
+    ///
+    /// None by default.
+    fn source_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
@@ -1,18 +1,58 @@
-use std::path::PathBuf;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
-use syn::Path;
+use syn::{punctuated::Punctuated, Expr, ExprLit, ExprMacro, Lit, LitStr, Path, Token};
 
 use crate::{
-    error::TsExportError, pipeline::module_step::ModuleStep, utils::display_path::DisplayPath,
+    diagnostic::SourceFile,
+    error::TsExportError,
+    pipeline::module_step::ModuleStep,
+    utils::{crate_paths::module_segments, display_path::DisplayPath},
 };
 
-use super::PipelineStepSpawner;
+use super::{exclusions::Exclusions, PipelineStepSpawner};
 
-/// A strategy that reads Rust Modules from file, following the typical Rust 2018 edition module architecture
+/// A strategy that reads Rust Modules from file, following the typical Rust 2018 edition module architecture :
+/// `mod foo;` is read from `foo.rs`, or `foo/mod.rs`, next to the file of its parent (in its own directory for a non-`mod.rs` parent).
+///
+/// A `#[path = "..."]` attribute is relative to the directory of the file of the parent, the module is then read as a `mod.rs` file.
+///
+/// The items of an `include!("...")` are read from the file, relatively to the directory of the including file.
+/// Its path can be built with `concat!` and `env!`, `env!("OUT_DIR")` being the directory given to [RustModuleReader::set_out_dir]
+/// (for the files generated by a build script), the other variables being read from the environment.
 pub struct RustModuleReader {
     root_path: PathBuf,
     root_module_name: String,
     crate_name: String,
+    exclusions: Exclusions,
+    roots: Vec<Path>,
+    /// The files the modules were read from, by module path
+    module_files: RefCell<HashMap<Vec<String>, ModuleFile>>,
+    /// The files read by the last run
+    read_files: RefCell<BTreeSet<PathBuf>>,
+    /// The items of the files parsed so far, reused while the files are not modified
+    parsed_files: RefCell<HashMap<PathBuf, ParsedFile>>,
+    out_dir: Option<PathBuf>,
+}
+
+/// How deep the `include!` macros are followed, so that a file including itself doesn't loop
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+struct ParsedFile {
+    modified: SystemTime,
+    items: Vec<syn::Item>,
+    source: Arc<SourceFile>,
+}
+
+struct ModuleFile {
+    file: PathBuf,
+    /// Where the child modules are, e.g. `foo/` for `foo.rs`
+    children_dir: PathBuf,
 }
 
 impl RustModuleReader {
@@ -21,12 +61,15 @@ impl RustModuleReader {
         if path.is_dir() {
             return Err(TsExportError::DirectoryGiven(path));
         }
-        let crate_name = crate::utils::cargo::fetch_crate_name_for_source_file(&path)?;
+        // Crates are referenced with underscores in Rust paths
+        let crate_name =
+            crate::utils::cargo::fetch_crate_name_for_source_file(&path)?.replace('-', "_");
         let root_module_name = path
             .file_stem()
-            .expect("Path should be a file")
+            .ok_or_else(|| TsExportError::WrongPath(path.clone()))?
             .to_string_lossy()
             .to_string();
+        let crate_dir = crate::utils::cargo::fetch_crate_dir_for_source_file(&path)?;
         let root_path = path
             .canonicalize()?
             .parent()
@@ -37,50 +80,494 @@ impl RustModuleReader {
             root_path,
             root_module_name,
             crate_name,
+            exclusions: Exclusions::new(crate_dir),
+            roots: vec![Path {
+                leading_colon: None,
+                segments: Default::default(),
+            }],
+            module_files: RefCell::default(),
+            read_files: RefCell::default(),
+            parsed_files: RefCell::default(),
+            out_dir: None,
         })
     }
+
+    /// Reads several root modules of the same crate (e.g. `src/api/mod.rs` and `src/events.rs`) in a single run.
+    ///
+    /// The roots are named after their path in the crate (`api` and `events`), relatively to its `src` directory,
+    /// so that the references between them are imported from the right modules.
+    /// A root nested in another one is dropped, as it is reached from its parent.
+    pub fn try_new_with_roots(paths: Vec<PathBuf>) -> Result<Self, TsExportError> {
+        let first = paths.first().ok_or(TsExportError::FailedToLaunch)?.clone();
+        let mut reader = RustModuleReader::try_new(first.clone())?;
+        let crate_dir = crate::utils::cargo::fetch_crate_dir_for_source_file(&first)?;
+        reader.root_path = crate_dir.join("src");
+        reader.root_module_name = "lib".to_string();
+
+        let mut roots: Vec<Path> = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                return Err(TsExportError::DirectoryGiven(path));
+            }
+            if crate::utils::cargo::fetch_crate_dir_for_source_file(&path)? != crate_dir {
+                return Err(TsExportError::RootsOfDifferentCrates(first.clone(), path));
+            }
+            let file = path.canonicalize()?;
+            let relative = match file.strip_prefix(&reader.root_path) {
+                Ok(relative) => relative.with_extension(""),
+                Err(_) => return Err(TsExportError::WrongPath(path)),
+            };
+            let mut segments: Vec<String> = relative
+                .iter()
+                .map(|segment| segment.to_string_lossy().to_string())
+                .collect();
+            if segments.last().map(String::as_str) == Some("mod") {
+                segments.pop();
+            }
+            let root = match segments.as_slice() {
+                [crate_root] if crate_root == "lib" || crate_root == "main" => {
+                    reader.root_module_name = crate_root.clone();
+                    Path {
+                        leading_colon: None,
+                        segments: Default::default(),
+                    }
+                }
+                _ => syn::parse_str(&segments.join("::"))?,
+            };
+            roots.push(root);
+        }
+
+        let nested: Vec<bool> = roots
+            .iter()
+            .enumerate()
+            .map(|(index, root)| {
+                roots.iter().enumerate().any(|(other_index, other)| {
+                    is_prefix(other, root)
+                        && (other.segments.len() < root.segments.len() || other_index < index)
+                })
+            })
+            .collect();
+        reader.roots = roots
+            .into_iter()
+            .zip(nested)
+            .filter_map(|(root, nested)| {
+                if nested {
+                    log::warn!(
+                        "Dropping the root {}, that is nested in another root",
+                        DisplayPath(&root)
+                    );
+                    None
+                } else {
+                    Some(root)
+                }
+            })
+            .collect();
+        Ok(reader)
+    }
+
+    /// Skips the modules whose file matches `pattern`, relatively to the directory of the crate (e.g. `**/generated/**`)
+    pub fn add_exclude(&mut self, pattern: &str) {
+        self.exclusions.add_glob(pattern);
+    }
+
+    /// Skips the modules whose file is ignored by the `.gitignore` files of the repository
+    pub fn set_respect_gitignore(&mut self, gitignore: bool) {
+        self.exclusions.set_gitignore(gitignore);
+    }
+
+    /// The value of `env!("OUT_DIR")` in the paths of the `include!` macros, the `OUT_DIR` environment variable by default
+    pub fn set_out_dir(&mut self, out_dir: Option<PathBuf>) {
+        self.out_dir = out_dir;
+    }
+
+    /// Names the crate in Rust paths, e.g. a dependency renamed in a `Cargo.toml`, the name of its package by default
+    pub fn set_crate_name(&mut self, crate_name: &str) {
+        self.crate_name = crate_name.to_string();
+    }
+
+    /// The name of the crate that contains the root module, as used in Rust paths
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// The directory of the child modules of a module, following its inline parents
+    fn children_dir(&self, segments: &[String]) -> PathBuf {
+        if let Some(module_file) = self.module_files.borrow().get(segments) {
+            return module_file.children_dir.clone();
+        }
+        match segments.split_last() {
+            Some((name, parent)) => self.children_dir(parent).join(name),
+            None => self.root_path.clone(),
+        }
+    }
+
+    /// The directory a `#[path]` attribute of a child module is relative to
+    fn path_attribute_dir(&self, segments: &[String]) -> PathBuf {
+        match self.module_files.borrow().get(segments) {
+            Some(ModuleFile { file, .. }) => file
+                .parent()
+                .map(|dir| dir.to_path_buf())
+                .unwrap_or_else(|| self.root_path.clone()),
+            None => self.children_dir(segments),
+        }
+    }
+
+    fn read_module(
+        &self,
+        path: Path,
+        file: PathBuf,
+        children_dir: PathBuf,
+    ) -> Result<Option<ModuleStep>, TsExportError> {
+        if self.exclusions.is_excluded(&file) {
+            log::info!(
+                "Skipping excluded module {} at {:?}",
+                DisplayPath(&path),
+                file
+            );
+            return Ok(None);
+        }
+        if !path.segments.is_empty() {
+            self.module_files.borrow_mut().insert(
+                module_segments(&path),
+                ModuleFile {
+                    file: file.clone(),
+                    children_dir,
+                },
+            );
+        }
+        let (items, parsing_time) = self.read_file(&file, 0)?;
+        let mut process_module = ModuleStep::new(path, items, &self.crate_name);
+        process_module.set_parsing_time(parsing_time);
+        if let Some(parsed) = self.parsed_files.borrow().get(&file) {
+            process_module.set_source(parsed.source.clone());
+        }
+        Ok(Some(process_module))
+    }
+
+    /// The items of a file, with the items of the files it includes, and the time spent parsing them
+    fn read_file(
+        &self,
+        file: &std::path::Path,
+        depth: usize,
+    ) -> Result<(Vec<syn::Item>, Duration), TsExportError> {
+        self.read_files.borrow_mut().insert(file.to_path_buf());
+        let (items, mut parsing_time) = self.parse_file(file)?;
+        let items = self.expand_includes(items, file, depth, &mut parsing_time)?;
+        Ok((items, parsing_time))
+    }
+
+    /// Replaces the `include!` macros by the items of the included files, in the inline modules too
+    fn expand_includes(
+        &self,
+        items: Vec<syn::Item>,
+        file: &std::path::Path,
+        depth: usize,
+        parsing_time: &mut Duration,
+    ) -> Result<Vec<syn::Item>, TsExportError> {
+        let dir = file.parent().unwrap_or_else(|| std::path::Path::new("."));
+        let mut expanded = Vec::with_capacity(items.len());
+        for item in items {
+            let included = match item {
+                syn::Item::Macro(ref item_macro) if item_macro.mac.path.is_ident("include") => {
+                    item_macro
+                        .mac
+                        .parse_body::<Expr>()
+                        .ok()
+                        .and_then(|expr| self.include_path(&expr))
+                }
+                syn::Item::Mod(mut item_mod) => {
+                    if let Some((brace, items)) = item_mod.content.take() {
+                        let items = self.expand_includes(items, file, depth, parsing_time)?;
+                        item_mod.content = Some((brace, items));
+                    }
+                    expanded.push(syn::Item::Mod(item_mod));
+                    continue;
+                }
+                item => {
+                    expanded.push(item);
+                    continue;
+                }
+            };
+            let included = match included {
+                Some(included) if depth < MAX_INCLUDE_DEPTH => dir.join(included),
+                _ => {
+                    log::warn!("Skipping {} in {:?}", quote::quote!(#item), file);
+                    continue;
+                }
+            };
+            log::info!("Including {:?} in {:?}", included, file);
+            let (items, included_parsing_time) = self.read_file(&included, depth + 1)?;
+            expanded.extend(items);
+            *parsing_time += included_parsing_time;
+        }
+        Ok(expanded)
+    }
+
+    /// The path of an `include!`, its literals being concatenated and its variables solved
+    fn include_path(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(path),
+                ..
+            }) => Some(path.value()),
+            Expr::Macro(ExprMacro { mac, .. }) if mac.path.is_ident("concat") => mac
+                .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .ok()?
+                .iter()
+                .map(|part| self.include_path(part))
+                .collect(),
+            Expr::Macro(ExprMacro { mac, .. }) if mac.path.is_ident("env") => {
+                let variable = mac.parse_body::<LitStr>().ok()?.value();
+                match &self.out_dir {
+                    Some(out_dir) if variable == "OUT_DIR" => {
+                        Some(out_dir.to_string_lossy().to_string())
+                    }
+                    _ => std::env::var(variable).ok(),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The items of a file and the time spent parsing them, the file being parsed again only if it was modified since
+    fn parse_file(
+        &self,
+        file: &std::path::Path,
+    ) -> Result<(Vec<syn::Item>, Duration), TsExportError> {
+        let modified = std::fs::metadata(file)?.modified()?;
+        if let Some(parsed) = self.parsed_files.borrow().get(file) {
+            if parsed.modified == modified {
+                log::info!("Reusing the parsed module at {:?}", file);
+                return Ok((parsed.items.clone(), Duration::default()));
+            }
+        }
+        log::info!("Reading module from path {:?}", file);
+        let start = Instant::now();
+        let source = SourceFile {
+            path: file.to_path_buf(),
+            text: std::fs::read_to_string(file)?,
+        };
+        let items = syn::parse_file(&source.text)
+            .map_err(|error| TsExportError::from(error).in_file(&source))?
+            .items;
+        let parsing_time = start.elapsed();
+        self.parsed_files.borrow_mut().insert(
+            file.to_path_buf(),
+            ParsedFile {
+                modified,
+                items: items.clone(),
+                source: Arc::new(source),
+            },
+        );
+        Ok((items, parsing_time))
+    }
 }
 
 impl PipelineStepSpawner for RustModuleReader {
     type Error = TsExportError;
 
+    fn roots(&self) -> Vec<Path> {
+        self.roots.clone()
+    }
+
+    fn source_files(&self) -> Vec<PathBuf> {
+        self.read_files.borrow().iter().cloned().collect()
+    }
+
+    fn start_run(&self) {
+        self.read_files.borrow_mut().clear();
+    }
+
     fn create_process(&self, path: Path) -> Result<Option<ModuleStep>, TsExportError> {
         log::info!("Creating process for Rust module : {}", DisplayPath(&path));
-        let file_path: PathBuf = if path.segments.is_empty() {
-            self.root_module_name.clone().into()
-        } else {
-            path.segments
-                .iter()
-                .map(|seg| seg.ident.to_string())
-                .collect()
+        let segments = module_segments(&path);
+        let (file, children_dir) = match segments.split_last() {
+            None => {
+                let mut file = self.root_path.join(&self.root_module_name);
+                file.set_extension("rs");
+                (file, self.root_path.clone())
+            }
+            Some((name, parent)) => {
+                let dir = self.children_dir(parent);
+                // Case 1: <dir>/<name>.rs, its children being in <dir>/<name>/
+                let file = dir.join(format!("{}.rs", name));
+                if file.is_file() {
+                    (file, dir.join(name))
+                } else {
+                    // Case 2: <dir>/<name>/mod.rs
+                    (dir.join(name).join("mod.rs"), dir.join(name))
+                }
+            }
         };
-        let mut full_path = self.root_path.clone();
-        full_path.push(file_path);
-
-        // Case 1: <path>/file_path/mod.rs a.k.a <full_path>/mod.rs
-        if full_path.is_dir() {
-            let mut full_path_file = full_path.clone();
-            full_path_file.push("mod");
-            full_path_file.set_extension("rs");
-            create_process_from_path(full_path_file, path, &self.crate_name)
-        } else {
-            // Case 2: <path>/file_path.rs a.k.a. <full_path>.rs
-            let mut full_path_file = full_path.clone();
-            full_path_file.set_extension("rs");
-            create_process_from_path(full_path_file, path, &self.crate_name)
-        }
+        self.read_module(path, file, children_dir)
+    }
+
+    fn create_process_at(
+        &self,
+        path: Path,
+        file_path: &str,
+    ) -> Result<Option<ModuleStep>, TsExportError> {
+        log::info!(
+            "Creating process for Rust module : {} at {}",
+            DisplayPath(&path),
+            file_path
+        );
+        let segments = module_segments(&path);
+        let parent = segments
+            .split_last()
+            .map(|(_, parent)| parent)
+            .unwrap_or(&[]);
+        let file = self.path_attribute_dir(parent).join(file_path);
+        let children_dir = file
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .ok_or_else(|| TsExportError::WrongPath(file.clone()))?;
+        self.read_module(path, file, children_dir)
     }
 }
 
-fn create_process_from_path<P: AsRef<std::path::Path> + std::fmt::Debug>(
-    full_path: P,
-    path: Path,
-    crate_name: &str,
-) -> Result<Option<ModuleStep>, TsExportError> {
-    log::info!("Reading module from path {:?}", full_path);
-    let contents = std::fs::read_to_string(&full_path)?;
-    let ast = syn::parse_file(&contents)?;
-
-    let process_module = ModuleStep::new(path, ast.items, crate_name);
-    Ok(Some(process_module))
+/// Whether the module `path` is `prefix` or one of its descendants
+fn is_prefix(prefix: &Path, path: &Path) -> bool {
+    prefix.segments.len() <= path.segments.len()
+        && prefix
+            .segments
+            .iter()
+            .zip(path.segments.iter())
+            .all(|(a, b)| a.ident == b.ident)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+    use crate::{
+        contexts::{exporter::ExporterOptions, type_solving::TypeSolvingContextBuilder},
+        macros::context::MacroSolvingContext,
+        path_mapper::PathMapper,
+        pipeline::module_step::ModuleStepResult,
+    };
+
+    fn collect(paths: &mut Vec<String>, result: &ModuleStepResult) {
+        let exports = result
+            .data
+            .exports
+            .iter()
+            .filter_map(|export| export.ident())
+            .map(|ident| ident.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        paths.push(format!("{}: {}", DisplayPath(&result.data.path), exports));
+        for child in result.children.iter() {
+            collect(paths, child);
+        }
+    }
+
+    #[test]
+    fn should_resolve_module_files() {
+        let root = test_dir("mod_reader");
+        let src = root.join("src");
+        std::fs::create_dir_all(src.join("models").join("user")).unwrap();
+        std::fs::create_dir_all(src.join("generated")).unwrap();
+        std::fs::create_dir_all(src.join("inline")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"mod-reader-test\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let files = [
+            (
+                "lib.rs",
+                "mod models; #[path = \"generated/api_impl.rs\"] mod api; mod inline { mod nested; }",
+            ),
+            ("models.rs", "mod user; #[derive(Serialize)] pub struct Model;"),
+            ("models/user/mod.rs", "#[derive(Serialize)] pub struct User { id: u32 }"),
+            ("generated/api_impl.rs", "mod routes; #[derive(Serialize)] pub struct Api { id: u32 }"),
+            ("generated/routes.rs", "#[derive(Serialize)] pub struct Route { id: u32 }"),
+            ("inline/nested.rs", "#[derive(Serialize)] pub struct Nested { id: u32 }"),
+        ];
+        for (file, contents) in files.iter() {
+            std::fs::write(src.join(file), contents).unwrap();
+        }
+
+        let reader = RustModuleReader::try_new(src.join("lib.rs")).unwrap();
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let result = reader
+            .create_process(reader.roots().remove(0))
+            .unwrap()
+            .unwrap()
+            .launch(
+                &reader,
+                &solving_context,
+                &MacroSolvingContext::default(),
+                &PathMapper::default(),
+                &ExporterOptions::default(),
+            )
+            .unwrap();
+        let mut paths = Vec::new();
+        collect(&mut paths, &result);
+        assert_eq!(
+            paths,
+            vec![
+                ": ",
+                "models: Model",
+                "models::user: User",
+                "api: Api",
+                "api::routes: Route",
+                "inline: ",
+                "inline::nested: Nested",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_read_the_included_files() {
+        let root = test_dir("mod_reader_include");
+        let src = root.join("src");
+        let out_dir = root.join("out");
+        std::fs::create_dir_all(src.join("parts")).unwrap();
+        std::fs::create_dir_all(&out_dir).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"include-test\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let files = [
+            (
+                src.join("lib.rs"),
+                "include!(\"parts/user.rs\"); mod api { include!(concat!(env!(\"OUT_DIR\"), \"/api.rs\")); }",
+            ),
+            (
+                src.join("parts/user.rs"),
+                "include!(\"role.rs\"); #[derive(Serialize)] pub struct User { role: Role }",
+            ),
+            (src.join("parts/role.rs"), "#[derive(Serialize)] pub struct Role;"),
+            (out_dir.join("api.rs"), "#[derive(Serialize)] pub struct Api { id: u32 }"),
+        ];
+        for (file, contents) in files.iter() {
+            std::fs::write(file, contents).unwrap();
+        }
+
+        let mut reader = RustModuleReader::try_new(src.join("lib.rs")).unwrap();
+        reader.set_out_dir(Some(out_dir));
+        let solving_context = TypeSolvingContextBuilder::default()
+            .add_default_solvers()
+            .finish();
+        let result = reader
+            .create_process(reader.roots().remove(0))
+            .unwrap()
+            .unwrap()
+            .launch(
+                &reader,
+                &solving_context,
+                &MacroSolvingContext::default(),
+                &PathMapper::default(),
+                &ExporterOptions::default(),
+            )
+            .unwrap();
+        let mut paths = Vec::new();
+        collect(&mut paths, &result);
+        assert_eq!(paths, vec![": Role, User", "api: Api"]);
+    }
 }
@@ -0,0 +1,406 @@
+//! Generation of `tsconfig.json` fragments, so that the imports emitted through the [PathMapper](crate::path_mapper::PathMapper) resolve,
+//! and reading of the path aliases of a `tsconfig.json`, so that the generated modules import each other through them, see [ImportAliases].
+//!
+//! When the bindings of several crates are written to different directories, an import such as `"types/models/user"`
+//! only resolves if `types/models` is mapped to the right directory in the `paths` of the `tsconfig.json`.
+//!
+//! A fragment looks like this, and is meant to be extended by the main `tsconfig.json` (`"extends": "./tsconfig.paths.json"`) :
+//!
+//! ```text
+//! {
+//!   "compilerOptions": {
+//!     "baseUrl": ".",
+//!     "paths": {
+//!       "types/models": ["./generated/models/index"],
+//!       "types/models/*": ["./generated/models/*"]
+//!     }
+//!   }
+//! }
+//! ```
+use std::{
+    io::Write,
+    path::{Component, Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::TsExportError;
+
+/// A `tsconfig.json`, the keys typebinder doesn't use are kept as is
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct TsConfigFile {
+    #[serde(rename = "compilerOptions", default)]
+    compiler_options: CompilerOptions,
+    #[serde(flatten)]
+    others: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompilerOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    paths: IndexMap<String, Vec<String>>,
+    #[serde(flatten)]
+    others: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// The `paths` of a `tsconfig.json` fragment, relative to the directory of the fragment
+pub struct TsConfigPaths {
+    config: TsConfigFile,
+}
+
+impl TsConfigPaths {
+    /// Loads an existing fragment, so that the packages of several runs can be accumulated in the same file.
+    ///
+    /// The other keys of the fragment are written back untouched, but its `baseUrl` is set to its directory.
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Self, TsExportError> {
+        let content = std::fs::read_to_string(path)?;
+        let config = serde_json::from_str(&strip_comments(&content))?;
+        Ok(TsConfigPaths { config })
+    }
+
+    /// Maps the TS import path of a package (as output by the PathMapper) to the directory its modules are written in.
+    ///
+    /// `root_module` is the file name of the module of the package root, without its extension (`index` by default).
+    pub fn add_package(&mut self, import_path: &str, dir: &str, root_module: &str) {
+        let dir = dir.trim_end_matches('/');
+        let paths = &mut self.config.compiler_options.paths;
+        paths.insert(
+            import_path.to_string(),
+            vec![format!("{}/{}", dir, root_module)],
+        );
+        paths.insert(format!("{}/*", import_path), vec![format!("{}/*", dir)]);
+    }
+
+    pub fn get(&self, pattern: &str) -> Option<&Vec<String>> {
+        self.config.compiler_options.paths.get(pattern)
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self.fragment()).expect("A tsconfig is always serializable")
+    }
+
+    /// The fragment as written, its `baseUrl` being its directory
+    fn fragment(&self) -> TsConfigFile {
+        let mut config = self.config.clone();
+        config.compiler_options.base_url = Some(".".to_string());
+        config
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), TsExportError> {
+        let path = path.as_ref();
+        log::info!("Outputting tsconfig paths at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(&mut file, &self.fragment())?;
+        file.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// The TS path aliases the modules are imported through, `@api/models` rather than `../../api/models`,
+/// read from the `paths` of a `tsconfig.json` or given explicitly.
+///
+/// An alias ending with `*` maps a directory, `"@api/*": ["src/types/api/*"]`, the other ones a single file, `"@models": ["src/types/models/index"]`.
+/// The most specific alias of a file applies, and the files without an alias are still imported relatively.
+pub struct ImportAliases {
+    aliases: Vec<ImportAlias>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportAlias {
+    /// The alias, without its `*`
+    alias: String,
+    /// The absolute path of the directory, or of the file without its extension
+    target: PathBuf,
+    wildcard: bool,
+}
+
+impl ImportAliases {
+    /// Reads the `paths` of a `tsconfig.json`, which are relative to its `baseUrl`. The `extends` of the file are not followed.
+    pub fn from_tsconfig<P: AsRef<Path>>(path: P) -> Result<Self, TsExportError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let config: TsConfigFile = serde_json::from_str(&strip_comments(&content))?;
+        let options = config.compiler_options;
+        let base = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(options.base_url.as_deref().unwrap_or("."));
+
+        let mut aliases = ImportAliases::default();
+        for (alias, targets) in options.paths.iter() {
+            if let Some(target) = targets.first() {
+                aliases.add_alias(alias, &base.join(target))?;
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Imports the files of `target` through `alias`, e.g. `@api/*` for `src/types/api/*`
+    pub fn add_alias(&mut self, alias: &str, target: &Path) -> Result<(), TsExportError> {
+        let wildcard = alias.ends_with('*');
+        let mut target = absolute_path(target)?;
+        if wildcard {
+            if target.file_name() == Some("*".as_ref()) {
+                target.pop();
+            }
+        } else if target.extension() == Some("ts".as_ref()) {
+            target.set_extension("");
+        }
+        self.aliases.push(ImportAlias {
+            alias: alias.trim_end_matches('*').to_string(),
+            target,
+            wildcard,
+        });
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// The import of the file, given by its absolute path without extension, through the most specific alias covering it
+    pub fn get(&self, file: &Path) -> Option<String> {
+        self.aliases
+            .iter()
+            .filter_map(|alias| {
+                if !alias.wildcard {
+                    return (alias.target == file)
+                        .then(|| (alias.target.components().count() + 1, alias.alias.clone()));
+                }
+                let rest = file.strip_prefix(&alias.target).ok()?;
+                let rest: Vec<_> = rest
+                    .iter()
+                    .map(|segment| segment.to_string_lossy())
+                    .collect();
+                Some((
+                    alias.target.components().count(),
+                    format!("{}{}", alias.alias, rest.join("/")),
+                ))
+            })
+            .fold(
+                None,
+                |best: Option<(usize, String)>, (depth, import)| match best {
+                    Some((best_depth, _)) if best_depth >= depth => best,
+                    _ => Some((depth, import)),
+                },
+            )
+            .map(|(_, import)| import)
+    }
+}
+
+/// The path joined to the current directory, with its `.` and `..` components resolved lexically, as it may not exist yet
+pub fn absolute_path(path: &Path) -> Result<PathBuf, TsExportError> {
+    let mut absolute = PathBuf::new();
+    for component in std::env::current_dir()?.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+    Ok(absolute)
+}
+
+/// Removes the `//` and `/* */` comments a `tsconfig.json` may contain
+fn strip_comments(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().map(|c| *c != '\n').unwrap_or(false) {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                stripped.push(c);
+            }
+        }
+    }
+    stripped
+}
+
+/// Writes `target` relatively to the directory `base`, e.g. `./generated/models` or `../types`.
+///
+/// Both paths must exist.
+pub fn relative_path(base: &Path, target: &Path) -> Result<String, TsExportError> {
+    let base = base.canonicalize()?;
+    let base: Vec<Component> = base.components().collect();
+    let target = target.canonicalize()?;
+    let target: Vec<Component> = target.components().collect();
+    let common = base
+        .iter()
+        .zip(target.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    if common == base.len() {
+        relative.push(".");
+    }
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    target[common..]
+        .iter()
+        .for_each(|component| relative.push(component));
+    Ok(relative
+        .iter()
+        .map(|segment| segment.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    #[test]
+    fn should_map_packages() {
+        let mut paths = TsConfigPaths::default();
+        paths.add_package("types/models", "./generated/models/", "index");
+        assert_eq!(
+            paths.to_json(),
+            serde_json::json!({
+                "compilerOptions": {
+                    "baseUrl": ".",
+                    "paths": {
+                        "types/models": ["./generated/models/index"],
+                        "types/models/*": ["./generated/models/*"]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn should_keep_the_other_keys_of_the_fragment() {
+        let root = test_dir("tsconfig_paths");
+        let fragment = root.join("tsconfig.paths.json");
+        std::fs::write(
+            &fragment,
+            r#"{
+                "extends": "./tsconfig.base.json",
+                "compilerOptions": {
+                    "strict": true,
+                    "paths": { "@app/*": ["./src/*"] }
+                },
+                "include": ["src"]
+            }"#,
+        )
+        .unwrap();
+
+        let mut paths = TsConfigPaths::load_from(&fragment).unwrap();
+        paths.add_package("types/models", "./generated/models", "index");
+        assert_eq!(
+            paths.to_json(),
+            serde_json::json!({
+                "extends": "./tsconfig.base.json",
+                "compilerOptions": {
+                    "baseUrl": ".",
+                    "strict": true,
+                    "paths": {
+                        "@app/*": ["./src/*"],
+                        "types/models": ["./generated/models/index"],
+                        "types/models/*": ["./generated/models/*"]
+                    }
+                },
+                "include": ["src"]
+            })
+        );
+    }
+
+    #[test]
+    fn should_read_the_aliases_of_a_tsconfig() {
+        let root = test_dir("import_aliases");
+        let tsconfig = root.join("tsconfig.json");
+        std::fs::write(
+            &tsconfig,
+            r#"{
+                // The sources are under src
+                "compilerOptions": {
+                    "baseUrl": "./src",
+                    /* The generated types */
+                    "paths": {
+                        "@types/*": ["types/*"],
+                        "@api/*": ["types/api/*"],
+                        "@models": ["types/models/index.ts"],
+                        "//not-a-comment/*": ["lib/*"]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let aliases = ImportAliases::from_tsconfig(&tsconfig).unwrap();
+        let types = root.join("src").join("types");
+        assert_eq!(
+            aliases.get(&types.join("api").join("users")),
+            Some("@api/users".to_string())
+        );
+        assert_eq!(
+            aliases.get(&types.join("events")),
+            Some("@types/events".to_string())
+        );
+        assert_eq!(
+            aliases.get(&types.join("models").join("index")),
+            Some("@models".to_string())
+        );
+        assert_eq!(
+            aliases.get(&root.join("src").join("lib").join("dates")),
+            Some("//not-a-comment/dates".to_string())
+        );
+        assert_eq!(aliases.get(&root.join("other").join("events")), None);
+    }
+
+    #[test]
+    fn should_compute_relative_paths() {
+        let root = test_dir("tsconfig");
+        let generated = root.join("generated").join("models");
+        let config = root.join("config");
+        std::fs::create_dir_all(&generated).unwrap();
+        std::fs::create_dir_all(&config).unwrap();
+
+        assert_eq!(
+            relative_path(&root, &generated).unwrap(),
+            "./generated/models"
+        );
+        assert_eq!(
+            relative_path(&config, &generated).unwrap(),
+            "../generated/models"
+        );
+    }
+}
@@ -16,6 +16,15 @@ pub mod type_info;
 pub struct ImportEntry {
     pub path: String,
     pub ident: String,
+    /// The name the item is referred to by in the module, when it is imported with `as`
+    pub alias: Option<String>,
+}
+
+impl ImportEntry {
+    /// The name the item is referred to by in the module
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.ident)
+    }
 }
 
 /// The TypeSolver is the main abstraction of Typebinder. It is what allows its modularity.
@@ -60,6 +69,8 @@ pub trait TypeSolver {
                     inner_type,
                     name: PropertyName::from(solver_info.name.clone()),
                     optional: false,
+                    readonly: false,
+                    doc: None,
                 })
             })),
             SolverResult::Error(e) => SolverResult::Error(e),
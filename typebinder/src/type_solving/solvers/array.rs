@@ -3,12 +3,41 @@ use crate::{
     error::TsExportError,
     type_solving::{result::Solved, SolverResult, TypeInfo, TypeSolver},
 };
-use syn::Type;
-use ts_json_subset::types::{ArrayType, PrimaryType, TsType};
+use syn::{Expr, ExprLit, Lit, Type};
+use ts_json_subset::types::{ArrayType, PrimaryType, TsType, TupleType};
 
 /// Solver for the Array type variant
 /// Solves both Array and Slices
-pub struct ArraySolver;
+///
+/// By default a fixed-size array is solved as an array of its element, `[u8; 4]` as `number[]`.
+/// With [ArraySolver::tuples_up_to], the arrays of a literal length are solved as tuples : `[ number, number, number, number ]`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ArraySolver {
+    max_tuple_len: Option<usize>,
+}
+
+impl ArraySolver {
+    /// Solves the fixed-size arrays as tuples up to `max_len` elements, the longer ones still being solved as arrays
+    pub fn tuples_up_to(max_len: usize) -> Self {
+        ArraySolver {
+            max_tuple_len: Some(max_len),
+        }
+    }
+
+    /// The number of elements of the tuple an array of length `len` is solved as, if any
+    fn tuple_len(&self, len: &Expr) -> Option<usize> {
+        let max_len = self.max_tuple_len?;
+        match len {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(len), ..
+            }) => len
+                .base10_parse::<usize>()
+                .ok()
+                .filter(|len| *len <= max_len),
+            _ => None,
+        }
+    }
+}
 
 impl TypeSolver for ArraySolver {
     fn solve_as_type(
@@ -16,33 +45,49 @@ impl TypeSolver for ArraySolver {
         solving_context: &ExporterContext,
         solver_info: &TypeInfo,
     ) -> SolverResult<TsType, TsExportError> {
-        let result = match solver_info.ty {
-            Type::Array(ty) => solving_context.solve_type(&TypeInfo {
-                generics: solver_info.generics,
-                ty: ty.elem.as_ref(),
-            }),
-            Type::Slice(ty) => solving_context.solve_type(&TypeInfo {
-                generics: solver_info.generics,
-                ty: ty.elem.as_ref(),
-            }),
+        let (result, tuple_len) = match solver_info.ty {
+            Type::Array(ty) => (
+                solving_context.solve_type(&TypeInfo {
+                    generics: solver_info.generics,
+                    ty: ty.elem.as_ref(),
+                }),
+                self.tuple_len(&ty.len),
+            ),
+            Type::Slice(ty) => (
+                solving_context.solve_type(&TypeInfo {
+                    generics: solver_info.generics,
+                    ty: ty.elem.as_ref(),
+                }),
+                None,
+            ),
             _ => {
                 return SolverResult::Continue;
             }
         };
 
-        match result {
-            Ok(Solved {
-                inner: TsType::PrimaryType(primary),
-                import_entries,
-                generic_constraints,
-            }) => SolverResult::Solved(Solved {
+        match (result, tuple_len) {
+            (Ok(solved), Some(len)) => SolverResult::Solved(solved.map(|inner| {
+                TsType::PrimaryType(PrimaryType::TupleType(TupleType {
+                    inner_types: vec![inner; len],
+                }))
+            })),
+            (
+                Ok(Solved {
+                    inner: TsType::PrimaryType(primary),
+                    import_entries,
+                    generic_constraints,
+                }),
+                None,
+            ) => SolverResult::Solved(Solved {
                 inner: TsType::PrimaryType(PrimaryType::ArrayType(ArrayType::new(primary))),
                 import_entries,
                 generic_constraints,
             }),
             // TODO: This is maybe unreachable ?
-            Ok(Solved { inner, .. }) => SolverResult::Error(TsExportError::UnexpectedType(inner)),
-            Err(e) => SolverResult::Error(e),
+            (Ok(Solved { inner, .. }), None) => {
+                SolverResult::Error(TsExportError::UnexpectedType(Box::new(inner)))
+            }
+            (Err(e), _) => SolverResult::Error(e),
         }
     }
 }
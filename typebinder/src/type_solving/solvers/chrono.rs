@@ -1,3 +1,4 @@
+use syn::Type;
 use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
 
 use super::path::PathSolver;
@@ -6,47 +7,72 @@ use crate::{
     error::TsExportError,
     type_solving::{fn_solver::AsFnSolver, result::Solved},
     type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+    utils::display_path::DisplayPath,
 };
 
-/// Integration for the `chrono` crate
+/// The dates and times of `chrono`
+const CHRONO_PATHS: &[&str] = &[
+    "chrono::Date",
+    "chrono::DateTime",
+    "chrono::NaiveDate",
+    "chrono::NaiveDateTime",
+    "chrono::NaiveTime",
+];
+
+/// The durations of `chrono`, which don't implement `Serialize`
+const CHRONO_DURATION_PATHS: &[&str] = &["chrono::Duration", "chrono::TimeDelta"];
+
+/// Integration for the `chrono` crate : the dates and times are solved as `string` (ISO 8601),
+/// whatever the time zone of a `DateTime<Tz>`.
+///
+/// `chrono::Duration` (`TimeDelta`) fails with [TsExportError::ForbiddenType], chrono not implementing `Serialize` for it :
+/// a field of that type needs a `serialize_with`, whose TS type is given to the [SerializeWithSolver](super::serialize_with::SerializeWithSolver).
+///
+/// For a project that serializes them another way, e.g. as timestamps with `chrono::serde::ts_seconds`,
+/// add [ChronoSolver::new] before the default solvers.
 pub struct ChronoSolver {
     inner: PathSolver,
 }
 
-fn solve_datetime(
+impl ChronoSolver {
+    /// Solves the `chrono` types as `ts_type`
+    pub fn new(ts_type: TsType) -> Self {
+        let solver = (move |_solving_context: &ExporterContext, _solver_info: &TypeInfo| {
+            SolverResult::<_, TsExportError>::Solved(Solved::new(ts_type.clone()))
+        })
+        .fn_solver()
+        .into_rc();
+        let mut inner = PathSolver::default();
+        for path in CHRONO_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        let forbidden = forbid_duration.fn_solver().into_rc();
+        for path in CHRONO_DURATION_PATHS {
+            inner.add_entry(*path, forbidden.clone());
+        }
+        ChronoSolver { inner }
+    }
+}
+
+fn forbid_duration(
     _solving_context: &ExporterContext,
-    _solver_info: &TypeInfo,
+    solver_info: &TypeInfo,
 ) -> SolverResult<TsType, TsExportError> {
-    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
-        PredefinedType::String,
-    ))))
+    let ty = match solver_info.ty {
+        Type::Path(ty) => DisplayPath(&ty.path).to_string(),
+        _ => unreachable!(),
+    };
+    SolverResult::Error(TsExportError::ForbiddenType(
+        ty,
+        "chrono doesn't serialize its durations, give the field a serialize_with mapped to its TS type".to_string(),
+    ))
 }
 
 impl Default for ChronoSolver {
     fn default() -> Self {
-        let mut inner = PathSolver::default();
-        inner.add_entry(
-            "chrono::Date".to_string(),
-            solve_datetime.fn_solver().into_rc(),
-        );
-        inner.add_entry(
-            "chrono::DateTime".to_string(),
-            solve_datetime.fn_solver().into_rc(),
-        );
-        inner.add_entry(
-            "chrono::NaiveDate".to_string(),
-            solve_datetime.fn_solver().into_rc(),
-        );
-        inner.add_entry(
-            "chrono::NaiveDateTime".to_string(),
-            solve_datetime.fn_solver().into_rc(),
-        );
-        inner.add_entry(
-            "chrono::NaiveTime".to_string(),
-            solve_datetime.fn_solver().into_rc(),
-        );
-
-        ChronoSolver { inner }
+        ChronoSolver::new(TsType::PrimaryType(PrimaryType::Predefined(
+            PredefinedType::String,
+        )))
     }
 }
 
@@ -43,7 +43,9 @@ fn solve_seq(
                         import_entries,
                         generic_constraints,
                     }),
-                    _ => SolverResult::Error(TsExportError::UnexpectedType(types[0].clone())),
+                    _ => SolverResult::Error(TsExportError::UnexpectedType(Box::new(
+                        types[0].clone(),
+                    ))),
                 },
                 Err(e) => SolverResult::Error(e),
             }
@@ -52,7 +54,7 @@ fn solve_seq(
     }
 }
 
-fn solve_map(
+pub(crate) fn solve_map(
     solving_context: &ExporterContext,
     solver_info: &TypeInfo,
 ) -> SolverResult<TsType, TsExportError> {
@@ -62,19 +64,28 @@ fn solve_map(
             let segment = ty.path.segments.last().expect("Empty path");
             match solve_segment_generics(solving_context, generics, segment) {
                 Ok(solved) => {
-                    let first = solved.inner[0].clone();
+                    // A generic key is a bare reference to the type parameter, e.g. `K` in `HashMap<K, V>`
+                    let key_ident = match &solved.inner[0] {
+                        TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+                            name,
+                            args: None,
+                        })) => Some(name.clone()),
+                        _ => None,
+                    };
                     let mut solved = solved.map(|inner| {
                         TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
                             name: TSIdent::from_str("Record").unwrap(),
                             args: Some(TypeArguments {
-                                types: vec![inner[0].clone().into(), inner[1].clone().into()],
+                                types: vec![inner[0].clone(), inner[1].clone()],
                             }),
                         }))
                     });
-                    solved.generic_constraints.add_extends_constraint(
-                        TSIdent::from_str(&format!("{}", first)).unwrap(),
-                        TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
-                    );
+                    if let Some(key_ident) = key_ident {
+                        solved.generic_constraints.add_extends_constraint(
+                            key_ident,
+                            TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
+                        );
+                    }
                     SolverResult::Solved(solved)
                 }
                 Err(e) => SolverResult::Error(e),
@@ -0,0 +1,71 @@
+use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+const DECIMAL_PATHS: &[&str] = &["rust_decimal::Decimal", "bigdecimal::BigDecimal"];
+
+/// Integration for the `rust_decimal` and `bigdecimal` crates, whose decimals are serialized as strings by default,
+/// so that they don't lose precision.
+///
+/// For the decimals serialized as numbers (e.g. with the `serde-float` feature of `rust_decimal`),
+/// add [DecimalSolver::number] before the default solvers.
+pub struct DecimalSolver {
+    inner: PathSolver,
+}
+
+fn solve_decimal_string(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::String,
+    ))))
+}
+
+fn solve_decimal_number(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::Number,
+    ))))
+}
+
+impl DecimalSolver {
+    /// Solves the decimals as `number`
+    pub fn number() -> Self {
+        let solver = solve_decimal_number.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in DECIMAL_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        DecimalSolver { inner }
+    }
+}
+
+impl Default for DecimalSolver {
+    fn default() -> Self {
+        let solver = solve_decimal_string.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in DECIMAL_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        DecimalSolver { inner }
+    }
+}
+
+impl TypeSolver for DecimalSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
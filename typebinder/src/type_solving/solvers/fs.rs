@@ -0,0 +1,130 @@
+use ts_json_subset::types::{
+    ArrayType, ObjectType, PredefinedType, PrimaryType, PropertyName, PropertySignature, TsType,
+    TypeBody, TypeMember, UnionType,
+};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+const PATH_PATHS: &[&str] = &["std::path::PathBuf", "std::path::Path"];
+const OS_STRING_PATHS: &[&str] = &["std::ffi::OsString", "std::ffi::OsStr"];
+const C_STRING_PATHS: &[&str] = &[
+    "std::ffi::CString",
+    "std::ffi::CStr",
+    "alloc::ffi::CString",
+    "alloc::ffi::CStr",
+];
+
+/// Solver for the filesystem paths and the platform strings, the way serde serializes them :
+/// * `PathBuf` and `Path` as `string`, their serialization failing when they are not valid UTF-8,
+/// * `OsString` and `OsStr` as the code units of their platform, `{ Unix: number[] } | { Windows: number[] }`,
+/// * `CString` and `CStr` as their bytes, `number[]`.
+///
+/// For the APIs that consider the platform strings as not portable, add [FsSolver::os_strings_as_never] before the default solvers.
+pub struct FsSolver {
+    inner: PathSolver,
+}
+
+fn solve_string(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::String,
+    ))))
+}
+
+fn numbers() -> TsType {
+    TsType::PrimaryType(PrimaryType::ArrayType(ArrayType::new(
+        PrimaryType::Predefined(PredefinedType::Number),
+    )))
+}
+
+fn platform(name: &str) -> TsType {
+    TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+        body: TypeBody {
+            members: vec![TypeMember::PropertySignature(PropertySignature {
+                name: PropertyName::from(name.to_string()),
+                optional: false,
+                readonly: false,
+                inner_type: numbers(),
+                doc: None,
+            })],
+        },
+    }))
+}
+
+fn solve_os_string(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::UnionType(UnionType {
+        types: vec![platform("Unix"), platform("Windows")],
+    })))
+}
+
+fn solve_c_string(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(numbers()))
+}
+
+fn solve_never(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::Never,
+    ))))
+}
+
+impl FsSolver {
+    /// Solves the platform strings (`OsString`, `CString` and their borrowed forms) as `never`, and the paths as `string`
+    pub fn os_strings_as_never() -> Self {
+        let mut inner = PathSolver::default();
+        let string = solve_string.fn_solver().into_rc();
+        for path in PATH_PATHS {
+            inner.add_entry(*path, string.clone());
+        }
+        let never = solve_never.fn_solver().into_rc();
+        for path in OS_STRING_PATHS.iter().chain(C_STRING_PATHS) {
+            inner.add_entry(*path, never.clone());
+        }
+        FsSolver { inner }
+    }
+}
+
+impl Default for FsSolver {
+    fn default() -> Self {
+        let mut inner = PathSolver::default();
+        let string = solve_string.fn_solver().into_rc();
+        for path in PATH_PATHS {
+            inner.add_entry(*path, string.clone());
+        }
+        let os_string = solve_os_string.fn_solver().into_rc();
+        for path in OS_STRING_PATHS {
+            inner.add_entry(*path, os_string.clone());
+        }
+        let c_string = solve_c_string.fn_solver().into_rc();
+        for path in C_STRING_PATHS {
+            inner.add_entry(*path, c_string.clone());
+        }
+        FsSolver { inner }
+    }
+}
+
+impl TypeSolver for FsSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
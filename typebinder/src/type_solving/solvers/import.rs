@@ -7,6 +7,7 @@ use ts_json_subset::{
         PrimaryType, PropertyName, PropertySignature, TsType, TypeArguments, TypeMember,
         TypeReference,
     },
+    visit_mut::VisitMut,
 };
 
 use crate::{
@@ -17,7 +18,7 @@ use crate::{
         generic_constraints::GenericConstraints, member_info::MemberInfo, result::Solved,
     },
     type_solving::{SolverResult, TypeInfo, TypeSolver},
-    utils::display_path::DisplayPath,
+    utils::{display_path::DisplayPath, rename::AliasRenamer},
 };
 
 /// The last solver of the pipeline. It recurses after trying to solve the type using
@@ -52,9 +53,18 @@ impl TypeSolver for ImportSolver {
                         // Try to recurse through all solvers again
                         match solving_context.solve_type(&TypeInfo {
                             generics,
-                            ty: &Type::Path(ty_import),
+                            ty: &Type::Path(ty_import.clone()),
                         }) {
-                            Ok(solved) => SolverResult::Solved(solved),
+                            Ok(mut solved) => match keep_alias(
+                                solving_context,
+                                ty_path,
+                                &ty_import,
+                                &mut solved,
+                                |renamer, inner| renamer.visit_ts_type_mut(inner),
+                            ) {
+                                Ok(()) => SolverResult::Solved(solved),
+                                Err(e) => SolverResult::Error(e),
+                            },
                             Err(e) => SolverResult::Error(e),
                         }
                     }
@@ -98,6 +108,8 @@ impl TypeSolver for ImportSolver {
                                             inner_type: ts_type,
                                             name: PropertyName::from(name.to_string()),
                                             optional: false,
+                                            readonly: false,
+                                            doc: None,
                                         })
                                     }));
                                 }
@@ -107,14 +119,23 @@ impl TypeSolver for ImportSolver {
 
                         let member_info = MemberInfo {
                             generics,
-                            ty: &Type::Path(ty_import),
+                            ty: &Type::Path(ty_import.clone()),
                             field,
                             name: name.to_string(),
                             serde_field,
                         };
 
                         match solving_context.solve_member(&member_info) {
-                            Ok(solved) => SolverResult::Solved(solved),
+                            Ok(mut solved) => match keep_alias(
+                                solving_context,
+                                ty_path,
+                                &ty_import,
+                                &mut solved,
+                                |renamer, inner| renamer.visit_type_member_mut(inner),
+                            ) {
+                                Ok(()) => SolverResult::Solved(solved),
+                                Err(e) => SolverResult::Error(e),
+                            },
                             Err(e) => SolverResult::Error(e),
                         }
                     }
@@ -124,6 +145,8 @@ impl TypeSolver for ImportSolver {
                                 inner_type: ts_type,
                                 name: PropertyName::from(name.to_string()),
                                 optional: false,
+                                readonly: false,
+                                doc: None,
                             })
                         })),
                         Err(e) => SolverResult::Error(e),
@@ -136,6 +159,57 @@ impl TypeSolver for ImportSolver {
     }
 }
 
+/// Refers to a type imported with `as` by its alias, e.g. `MUser` for `use super::models::User as MUser`,
+/// which is imported as `import { User as MUser }`, rather than by the ident it was solved from.
+///
+/// Another type of the module may have the same name as the ident, the alias is what tells them apart.
+fn keep_alias<T>(
+    solving_context: &ExporterContext,
+    ty_path: &TypePath,
+    ty_import: &TypePath,
+    solved: &mut Solved<T>,
+    visit: impl FnOnce(&mut AliasRenamer, &mut T),
+) -> Result<(), TsExportError> {
+    let alias = match ty_path.path.segments.first() {
+        Some(segment) if ty_path.path.segments.len() == 1 => &segment.ident,
+        _ => return Ok(()),
+    };
+    let ident = match solving_context.import_context.aliased_ident(alias) {
+        Some(ident) => ident.to_string(),
+        None => return Ok(()),
+    };
+    let path_len = ty_import.path.segments.len();
+    let path = ty_import
+        .path
+        .segments
+        .iter()
+        .take(path_len - 1)
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    // The entry of the type itself comes before the entries of its type arguments
+    let entry = match solved
+        .import_entries
+        .iter_mut()
+        .find(|entry| entry.ident == ident && entry.path == path && entry.alias.is_none())
+    {
+        Some(entry) => entry,
+        None => return Ok(()),
+    };
+
+    let alias = TSIdent::from_str(&alias.to_string())?;
+    let mut renamer = AliasRenamer {
+        ident: &ident,
+        alias: &alias,
+        renamed: false,
+    };
+    visit(&mut renamer, &mut solved.inner);
+    if renamer.renamed {
+        entry.alias = Some(alias.to_string());
+    }
+    Ok(())
+}
+
 pub fn solve_type_path(
     solving_context: &ExporterContext,
     generics: &Generics,
@@ -158,6 +232,7 @@ pub fn solve_type_path(
     let mut other_imports = vec![ImportEntry {
         ident: segment.ident.to_string(),
         path,
+        alias: None,
     }];
     imports.append(&mut other_imports);
 
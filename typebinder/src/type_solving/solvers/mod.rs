@@ -1,12 +1,25 @@
 pub mod array;
 pub mod chrono;
 pub mod collections;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod fs;
 pub mod generics;
 pub mod import;
+pub mod net;
 pub mod option;
 pub mod path;
+pub mod phantom_data;
 pub mod primitives;
 pub mod reference;
+pub mod remote;
+pub mod result;
 pub mod serde_json_value;
+pub mod serialize_with;
 pub mod skip_serialize_if;
+pub mod time;
+pub mod time_rs;
+pub mod transparent;
 pub mod tuple;
+pub mod type_overrides;
+pub mod uuid;
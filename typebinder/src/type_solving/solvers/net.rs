@@ -0,0 +1,54 @@
+use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+const NET_TYPES: &[&str] = &[
+    "IpAddr",
+    "Ipv4Addr",
+    "Ipv6Addr",
+    "SocketAddr",
+    "SocketAddrV4",
+    "SocketAddrV6",
+];
+
+/// Solver for the addresses of `std::net`, serialized by serde as strings in the human-readable formats
+pub struct NetSolver {
+    inner: PathSolver,
+}
+
+fn solve_address(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::String,
+    ))))
+}
+
+impl Default for NetSolver {
+    fn default() -> Self {
+        let solver = solve_address.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for ty in NET_TYPES {
+            inner.add_entry(format!("std::net::{}", ty), solver.clone());
+            inner.add_entry(format!("core::net::{}", ty), solver.clone());
+        }
+        NetSolver { inner }
+    }
+}
+
+impl TypeSolver for NetSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
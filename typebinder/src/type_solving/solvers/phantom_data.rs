@@ -0,0 +1,46 @@
+use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+/// Solver for `PhantomData<T>`, serialized by serde as a unit : `null`.
+///
+/// The type parameters that only the `PhantomData` fields use are left out of the declarations,
+/// see [phantom_type_parameters](crate::contexts::exporter::phantom_type_parameters).
+pub struct PhantomDataSolver {
+    inner: PathSolver,
+}
+
+fn solve_phantom_data(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::Null,
+    ))))
+}
+
+impl Default for PhantomDataSolver {
+    fn default() -> Self {
+        let solver = solve_phantom_data.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        inner.add_entry("std::marker::PhantomData", solver.clone());
+        inner.add_entry("core::marker::PhantomData", solver);
+        PhantomDataSolver { inner }
+    }
+}
+
+impl TypeSolver for PhantomDataSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
@@ -1,20 +1,69 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc, str::FromStr};
+
+use syn::Type;
 
 use crate::{
     contexts::exporter::ExporterContext,
     error::TsExportError,
     type_solving::{fn_solver::AsFnSolver, result::Solved},
     type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+    utils::display_path::DisplayPath,
 };
 use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
 
 use super::path::PathSolver;
 
 /// A solver for all Rust primitive types, such as u8, char, etc ...
+///
+/// The 64 and 128-bit integers are solved according to a [LargeIntegerPolicy].
+/// The `NonZero` integers of `std::num`, such as `NonZeroU32`, are solved as their integer.
 pub struct PrimitivesSolver {
     inner: PathSolver,
 }
 
+/// How `u64`, `i64`, `u128` and `i128` are exported, a JS `number` only holding integers up to 2^53 exactly
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LargeIntegerPolicy {
+    /// `number`, which loses the precision of the values above 2^53
+    #[default]
+    Number,
+    /// `bigint`, for a frontend that parses the JSON numbers as such
+    BigInt,
+    /// `string`, for a backend that serializes them as strings
+    String,
+    /// Fail with [TsExportError::ForbiddenType]
+    Error,
+}
+
+impl FromStr for LargeIntegerPolicy {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "number" => Ok(LargeIntegerPolicy::Number),
+            "bigint" => Ok(LargeIntegerPolicy::BigInt),
+            "string" => Ok(LargeIntegerPolicy::String),
+            "error" => Ok(LargeIntegerPolicy::Error),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown large integer policy {}, expected number, bigint, string or error",
+                s
+            ))),
+        }
+    }
+}
+
+const LARGE_INTEGERS: &[&str] = &["u64", "i64", "u128", "i128"];
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// `NonZeroU32` for `u32`
+fn non_zero(integer: &str) -> String {
+    let mut chars = integer.chars();
+    let first = chars.next().expect("Empty integer").to_ascii_uppercase();
+    format!("NonZero{}{}", first, chars.as_str())
+}
+
 fn solve_number(
     _exporter: &ExporterContext,
     _solver_info: &TypeInfo,
@@ -24,6 +73,70 @@ fn solve_number(
     ))
 }
 
+fn solve_predefined(predefined: PredefinedType) -> Rc<impl TypeSolver> {
+    (move |_: &ExporterContext, _: &TypeInfo| {
+        SolverResult::Solved(Solved::new(
+            PrimaryType::Predefined(predefined.clone()).into(),
+        ))
+    })
+    .fn_solver()
+    .into_rc()
+}
+
+fn forbid_large_integer(
+    _exporter: &ExporterContext,
+    solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    let ty = match solver_info.ty {
+        Type::Path(ty) => DisplayPath(&ty.path).to_string(),
+        _ => unreachable!(),
+    };
+    SolverResult::Error(TsExportError::ForbiddenType(
+        ty,
+        "a JS number can't hold all its values".to_string(),
+    ))
+}
+
+impl PrimitivesSolver {
+    pub fn new(large_integers: LargeIntegerPolicy) -> Self {
+        let mut solver = PrimitivesSolver::default();
+        let predefined = match large_integers {
+            LargeIntegerPolicy::Number => PredefinedType::Number,
+            LargeIntegerPolicy::BigInt => PredefinedType::BigInt,
+            LargeIntegerPolicy::String => PredefinedType::String,
+            LargeIntegerPolicy::Error => {
+                let forbid = forbid_large_integer.fn_solver().into_rc();
+                for integer in LARGE_INTEGERS {
+                    solver.inner.add_entry(*integer, forbid.clone());
+                }
+                solver.add_non_zero_integers();
+                return solver;
+            }
+        };
+        let solve_large_integer = solve_predefined(predefined);
+        for integer in LARGE_INTEGERS {
+            solver
+                .inner
+                .add_entry(*integer, solve_large_integer.clone());
+        }
+        solver.add_non_zero_integers();
+        solver
+    }
+
+    /// Solves each `NonZero` integer with the solver of its integer
+    fn add_non_zero_integers(&mut self) {
+        for integer in INTEGERS {
+            let solver = self.inner.entries[*integer].clone();
+            let non_zero = non_zero(integer);
+            for module in ["std::num", "core::num"].iter() {
+                self.inner
+                    .entries
+                    .insert(format!("{}::{}", module, non_zero), solver.clone());
+            }
+        }
+    }
+}
+
 impl Default for PrimitivesSolver {
     fn default() -> Self {
         let solver_number = solve_number.fn_solver().into_rc();
@@ -52,23 +165,26 @@ impl Default for PrimitivesSolver {
         inner.add_entry("u16", solver_number.clone());
         inner.add_entry("u32", solver_number.clone());
         inner.add_entry("u64", solver_number.clone());
+        inner.add_entry("u128", solver_number.clone());
         inner.add_entry("usize", solver_number.clone());
         inner.add_entry("i8", solver_number.clone());
         inner.add_entry("i16", solver_number.clone());
         inner.add_entry("i32", solver_number.clone());
         inner.add_entry("i64", solver_number.clone());
+        inner.add_entry("i128", solver_number.clone());
         inner.add_entry("isize", solver_number.clone());
         inner.add_entry("f32", solver_number.clone());
         inner.add_entry("f64", solver_number);
 
         inner.add_entry("char", solver_string.clone());
         inner.add_entry("str", solver_string.clone());
-        inner.add_entry("std::string::String", solver_string.clone());
-        inner.add_entry("std::borrow::Cow", solver_string);
+        inner.add_entry("std::string::String", solver_string);
 
         inner.add_entry("bool", solver_bool);
 
-        PrimitivesSolver { inner }
+        let mut solver = PrimitivesSolver { inner };
+        solver.add_non_zero_integers();
+        solver
     }
 }
 
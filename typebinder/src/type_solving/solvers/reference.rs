@@ -1,17 +1,31 @@
 use crate::{
     contexts::exporter::ExporterContext,
     error::TsExportError,
+    type_solving::member_info::MemberInfo,
     type_solving::{SolverResult, TypeInfo, TypeSolver},
 };
 use syn::Type;
-use ts_json_subset::types::TsType;
+use ts_json_subset::types::{TsType, TypeMember};
 
 /// A solver for a Reference.
 /// Just recurses by passing the inner type through.
 ///
+/// All the references are stripped at once (e.g. `&'a &'b Foo<'c, T>` is solved as `Foo<'c, T>`),
+/// so the remaining solvers, including the ones that work on members, see the referenced type.
+///
 /// When serializing, serde will treat references as a no-op
 pub struct ReferenceSolver;
 
+/// Strips all the references, parenthesis and invisible groups around a type
+pub fn strip_references(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(ty) => strip_references(ty.elem.as_ref()),
+        Type::Paren(ty) => strip_references(ty.elem.as_ref()),
+        Type::Group(ty) => strip_references(ty.elem.as_ref()),
+        _ => ty,
+    }
+}
+
 impl TypeSolver for ReferenceSolver {
     fn solve_as_type(
         &self,
@@ -20,8 +34,8 @@ impl TypeSolver for ReferenceSolver {
     ) -> SolverResult<TsType, TsExportError> {
         let TypeInfo { generics, ty } = solver_info;
         match ty {
-            Type::Reference(ty) => {
-                let ty = ty.elem.as_ref();
+            Type::Reference(_) | Type::Paren(_) | Type::Group(_) => {
+                let ty = strip_references(ty);
                 match solving_context.solve_type(&TypeInfo { generics, ty }) {
                     Ok(solved) => SolverResult::Solved(solved),
                     Err(e) => SolverResult::Error(e),
@@ -30,4 +44,40 @@ impl TypeSolver for ReferenceSolver {
             _ => SolverResult::Continue,
         }
     }
+
+    fn solve_as_member(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &MemberInfo,
+    ) -> SolverResult<TypeMember, TsExportError> {
+        match solver_info.ty {
+            Type::Reference(_) | Type::Paren(_) | Type::Group(_) => {
+                let member_info = MemberInfo {
+                    ty: strip_references(solver_info.ty),
+                    name: solver_info.name.clone(),
+                    ..*solver_info
+                };
+                match solving_context.solve_member(&member_info) {
+                    Ok(solved) => SolverResult::Solved(solved),
+                    Err(e) => SolverResult::Error(e),
+                }
+            }
+            _ => SolverResult::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use quote::ToTokens;
+
+    #[test]
+    fn should_strip_all_references() {
+        let ty: Type = syn::parse_str("&'a &'b (Foo<'c, T>)").expect("Failed to parse type");
+        assert_eq!(
+            strip_references(&ty).to_token_stream().to_string(),
+            "Foo < 'c , T >"
+        );
+    }
 }
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use syn::{GenericArgument, PathArguments, Type};
+use ts_json_subset::{
+    ident::TSIdent,
+    types::{PrimaryType, TsType, TypeArguments, TypeReference},
+};
+
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{generic_constraints::GenericConstraints, result::Solved, ImportEntry},
+    type_solving::{SolverResult, TypeInfo, TypeSolver},
+    utils::display_path::DisplayPath,
+};
+
+/// A solver for the foreign types described by a `#[serde(remote = "...")]` container of the current module,
+/// see [ExporterContext::remote_types].
+///
+/// The foreign type is serialized like the container, so it is solved as a reference to the container.
+///
+/// The modules are solved independently, so the foreign types of the other modules are not solved through the
+/// definition : they are left to the other solvers, and a warning is logged for the public definitions.
+pub struct RemoteSolver;
+
+impl TypeSolver for RemoteSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        let TypeInfo { generics, ty } = solver_info;
+        let ty_path = match ty {
+            Type::Path(ty_path) => ty_path,
+            _ => return SolverResult::Continue,
+        };
+        let name = match solving_context
+            .remote_types
+            .get(&DisplayPath(&ty_path.path).to_string())
+        {
+            Some(name) => name,
+            None => return SolverResult::Continue,
+        };
+        let name = match TSIdent::from_str(name) {
+            Ok(name) => name,
+            Err(e) => return SolverResult::Error(e.into()),
+        };
+
+        let mut imports: Vec<ImportEntry> = Vec::new();
+        let mut constraints = GenericConstraints::default();
+        let mut types: Vec<TsType> = Vec::new();
+        if let Some(PathArguments::AngleBracketed(arguments)) = ty_path
+            .path
+            .segments
+            .last()
+            .map(|segment| &segment.arguments)
+        {
+            for argument in arguments.args.iter() {
+                if let GenericArgument::Type(ty) = argument {
+                    match solving_context.solve_type(&TypeInfo { generics, ty }) {
+                        Ok(mut solved) => {
+                            imports.append(&mut solved.import_entries);
+                            constraints.merge(solved.generic_constraints);
+                            types.push(solved.inner);
+                        }
+                        Err(e) => return SolverResult::Error(e),
+                    }
+                }
+            }
+        }
+        let args = if types.is_empty() {
+            None
+        } else {
+            Some(TypeArguments { types })
+        };
+
+        SolverResult::Solved(Solved {
+            inner: TsType::PrimaryType(PrimaryType::TypeReference(TypeReference { name, args })),
+            import_entries: imports,
+            generic_constraints: constraints,
+        })
+    }
+}
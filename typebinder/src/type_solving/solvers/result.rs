@@ -0,0 +1,99 @@
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::fn_solver::AsFnSolver,
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+    utils::{display_path::DisplayPath, inner_generic::solve_segment_generics},
+};
+use syn::Type;
+use ts_json_subset::types::{
+    ObjectType, PrimaryType, PropertyName, PropertySignature, TsType, TypeBody, TypeMember,
+    UnionType,
+};
+
+use super::path::PathSolver;
+
+/// Solver for Result<T, E>, serialized by serde as an externally tagged enum : `{ Ok: T } | { Err: E }`.
+///
+/// For the APIs that shouldn't expose a `Result`, add [ResultSolver::forbidden] before the default solvers.
+pub struct ResultSolver {
+    inner: PathSolver,
+}
+
+fn variant(name: &str, inner_type: TsType) -> TsType {
+    TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+        body: TypeBody {
+            members: vec![TypeMember::PropertySignature(PropertySignature {
+                name: PropertyName::from(name.to_string()),
+                optional: false,
+                readonly: false,
+                inner_type,
+                doc: None,
+            })],
+        },
+    }))
+}
+
+fn solve_result(
+    solving_context: &ExporterContext,
+    solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    let TypeInfo { generics, ty } = solver_info;
+    let segment = match ty {
+        Type::Path(ty) => ty.path.segments.last().expect("Empty path"),
+        _ => unreachable!(),
+    };
+    match solve_segment_generics(solving_context, generics, segment) {
+        Ok(solved) if solved.inner.len() == 2 => SolverResult::Solved(solved.map(|types| {
+            let mut types = types.into_iter();
+            let ok = types.next().expect("Two types are solved");
+            let err = types.next().expect("Two types are solved");
+            TsType::UnionType(UnionType {
+                types: vec![variant("Ok", ok), variant("Err", err)],
+            })
+        })),
+        Ok(_) => SolverResult::Error(TsExportError::EmptyGenerics),
+        Err(e) => SolverResult::Error(e),
+    }
+}
+
+fn forbid_result(
+    _solving_context: &ExporterContext,
+    solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    let ty = match solver_info.ty {
+        Type::Path(ty) => DisplayPath(&ty.path).to_string(),
+        _ => unreachable!(),
+    };
+    SolverResult::Error(TsExportError::ForbiddenType(
+        ty,
+        "a Result should not be part of the API".to_string(),
+    ))
+}
+
+impl ResultSolver {
+    /// Fails on every `Result`, with [TsExportError::ForbiddenType]
+    pub fn forbidden() -> Self {
+        let mut inner = PathSolver::default();
+        inner.add_entry("std::result::Result", forbid_result.fn_solver().into_rc());
+        ResultSolver { inner }
+    }
+}
+
+impl Default for ResultSolver {
+    fn default() -> Self {
+        let mut inner = PathSolver::default();
+        inner.add_entry("std::result::Result", solve_result.fn_solver().into_rc());
+        ResultSolver { inner }
+    }
+}
+
+impl TypeSolver for ResultSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
@@ -1,4 +1,12 @@
-use ts_json_subset::types::{PredefinedType, PrimaryType, TsType};
+use std::{rc::Rc, str::FromStr};
+
+use syn::{GenericArgument, PathArguments, Type};
+use ts_json_subset::{
+    ident::TSIdent,
+    types::{
+        ArrayType, PredefinedType, PrimaryType, TsType, TypeArguments, TypeReference, UnionType,
+    },
+};
 
 use crate::{
     contexts::exporter::ExporterContext,
@@ -7,31 +15,92 @@ use crate::{
     type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
 };
 
-use super::path::PathSolver;
+use super::{collections::solve_map, path::PathSolver, reference::strip_references};
+use crate::utils::display_path::DisplayPath;
+
+/// The paths `serde_json::value::RawValue` is usually written with
+const RAW_VALUE_PATHS: &[&str] = &[
+    "RawValue",
+    "value::RawValue",
+    "serde_json::RawValue",
+    "serde_json::value::RawValue",
+];
+const BOX_PATHS: &[&str] = &["Box", "boxed::Box", "std::boxed::Box", "alloc::boxed::Box"];
 
-/// Support for serde_json::Value.
-/// It will deserialize to the any type.
+/// The paths `serde_json::Value` is resolved to
+const VALUE_PATHS: &[&str] = &["serde_json::Value", "serde_json::value::Value"];
+/// The paths `serde_json::Map` is resolved to
+const MAP_PATHS: &[&str] = &[
+    "serde_json::Map",
+    "serde_json::map::Map",
+    "serde_json::value::Map",
+];
+
+/// Support for serde_json::Value, solved to `unknown` by default, and serde_json::Map, solved to a `Record`.
+///
+/// Another type can be chosen for the values with [SerdeJsonValueSolver::new], by adding the solver before the default ones,
+/// e.g. [SerdeJsonValueSolver::structural] which describes the JSON values.
 pub struct SerdeJsonValueSolver {
     path_solver: PathSolver,
 }
 
-fn solve_serde_json_value(
-    _exporter_context: &ExporterContext,
-    _type_info: &TypeInfo,
-) -> SolverResult<TsType, TsExportError> {
-    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
-        PredefinedType::Any,
-    ))))
+impl SerdeJsonValueSolver {
+    pub fn new(ts_type: TsType) -> Self {
+        let mut path_solver = PathSolver::default();
+        let value_solver = Rc::new(ConstSolver(ts_type));
+        for path in VALUE_PATHS {
+            path_solver.add_entry(*path, value_solver.clone());
+        }
+        let map_solver = solve_map.fn_solver().into_rc();
+        for path in MAP_PATHS {
+            path_solver.add_entry(*path, map_solver.clone());
+        }
+        SerdeJsonValueSolver { path_solver }
+    }
+
+    /// Solves the values to `null | boolean | number | string | unknown[] | Record<string, unknown>`
+    pub fn structural() -> Self {
+        let predefined = |predefined| TsType::PrimaryType(PrimaryType::Predefined(predefined));
+        let types = vec![
+            predefined(PredefinedType::Null),
+            predefined(PredefinedType::Boolean),
+            predefined(PredefinedType::Number),
+            predefined(PredefinedType::String),
+            TsType::PrimaryType(PrimaryType::ArrayType(ArrayType::new(
+                PrimaryType::Predefined(PredefinedType::Unknown),
+            ))),
+            TsType::PrimaryType(PrimaryType::TypeReference(TypeReference {
+                name: TSIdent::from_str("Record").unwrap(),
+                args: Some(TypeArguments {
+                    types: vec![
+                        predefined(PredefinedType::String),
+                        predefined(PredefinedType::Unknown),
+                    ],
+                }),
+            })),
+        ];
+        SerdeJsonValueSolver::new(TsType::UnionType(UnionType { types }))
+    }
 }
 
 impl Default for SerdeJsonValueSolver {
     fn default() -> Self {
-        let mut path_solver = PathSolver::default();
-        path_solver.add_entry(
-            "serde_json::Value",
-            solve_serde_json_value.fn_solver().into_rc(),
-        );
-        SerdeJsonValueSolver { path_solver }
+        SerdeJsonValueSolver::new(TsType::PrimaryType(PrimaryType::Predefined(
+            PredefinedType::Unknown,
+        )))
+    }
+}
+
+/// Solves a type to a given TS type
+struct ConstSolver(TsType);
+
+impl TypeSolver for ConstSolver {
+    fn solve_as_type(
+        &self,
+        _context: &ExporterContext,
+        _type_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        SolverResult::Solved(Solved::new(self.0.clone()))
     }
 }
 
@@ -44,3 +113,83 @@ impl TypeSolver for SerdeJsonValueSolver {
         self.path_solver.solve_as_type(context, type_info)
     }
 }
+
+/// Support for serde_json::value::RawValue, which passes arbitrary JSON through.
+///
+/// It is held as `Box<RawValue>` or `&RawValue`, and solved to `unknown` by default.
+/// Another type can be chosen with [SerdeJsonRawValueSolver::new], by adding the solver before the default ones.
+pub struct SerdeJsonRawValueSolver {
+    ts_type: TsType,
+}
+
+impl SerdeJsonRawValueSolver {
+    pub fn new(ts_type: TsType) -> Self {
+        SerdeJsonRawValueSolver { ts_type }
+    }
+}
+
+impl Default for SerdeJsonRawValueSolver {
+    fn default() -> Self {
+        SerdeJsonRawValueSolver::new(TsType::PrimaryType(PrimaryType::Predefined(
+            PredefinedType::Unknown,
+        )))
+    }
+}
+
+/// Whether the type is a RawValue, possibly boxed
+fn is_raw_value(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+    let display_path = DisplayPath(path).to_string();
+    if RAW_VALUE_PATHS.contains(&display_path.as_str()) {
+        return true;
+    }
+    if !BOX_PATHS.contains(&display_path.as_str()) {
+        return false;
+    }
+    match path.segments.last().map(|segment| &segment.arguments) {
+        Some(PathArguments::AngleBracketed(arguments)) => match arguments.args.first() {
+            Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => {
+                is_raw_value(strip_references(inner))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl TypeSolver for SerdeJsonRawValueSolver {
+    fn solve_as_type(
+        &self,
+        _context: &ExporterContext,
+        type_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        if is_raw_value(type_info.ty) {
+            SolverResult::Solved(Solved::new(self.ts_type.clone()))
+        } else {
+            SolverResult::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn raw_value(ty: &str) -> bool {
+        is_raw_value(&syn::parse_str(ty).expect("Failed to parse type"))
+    }
+
+    #[test]
+    fn should_detect_raw_values() {
+        assert!(raw_value("Box<RawValue>"));
+        assert!(raw_value("Box<serde_json::value::RawValue>"));
+        assert!(raw_value("std::boxed::Box<serde_json::RawValue>"));
+        assert!(raw_value("serde_json::value::RawValue"));
+        assert!(!raw_value("Box<Value>"));
+        assert!(!raw_value("Vec<RawValue>"));
+        assert!(!raw_value("serde_json::Value"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, str::FromStr};
+
+use syn::{Path, Type, TypePath};
+use ts_json_subset::types::{
+    PredefinedType, PrimaryType, PropertyName, PropertySignature, TsType, TypeMember,
+};
+
+use super::{skip_serialize_if::apply_skip_serializing_if, type_overrides::raw_type};
+use crate::{
+    contexts::exporter::{Direction, ExporterContext},
+    error::TsExportError,
+    type_solving::{member_info::MemberInfo, result::Solved, SolverResult, TypeInfo, TypeSolver},
+    utils::display_path::DisplayPath,
+};
+
+/// What a [SerializeWithSolver] does with a field whose custom serializer has no TS type
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SerializeWithPolicy {
+    /// The field is solved from its Rust type, as if it had no custom serializer
+    #[default]
+    Passthrough,
+    /// `unknown`, which the frontend has to narrow
+    Unknown,
+    /// Fail with [TsExportError::UnmappedSerializer]
+    Error,
+}
+
+impl FromStr for SerializeWithPolicy {
+    type Err = TsExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "passthrough" => Ok(SerializeWithPolicy::Passthrough),
+            "unknown" => Ok(SerializeWithPolicy::Unknown),
+            "error" => Ok(SerializeWithPolicy::Error),
+            _ => Err(TsExportError::InvalidConfig(format!(
+                "unknown serialize_with policy {}, expected passthrough, unknown or error",
+                s
+            ))),
+        }
+    }
+}
+
+/// Solves the fields with a custom serializer, `#[serde(with = "...")]`, `serialize_with` or `deserialize_with`
+/// according to the [Direction] of the export, whose TS type can't be told from their Rust type.
+///
+/// The serializers are mapped to a TS type written by hand, by the path of their module (`with = "ts_seconds"`)
+/// or of their function (`serialize_with = "to_hex"`). A path matches a serializer as written, or as resolved
+/// through the imports (`ts_seconds` with `use chrono::serde::ts_seconds;`). The TS type is output as is.
+/// The fields whose serializer isn't mapped are solved according to a [SerializeWithPolicy].
+#[derive(Debug, Default, Clone)]
+pub struct SerializeWithSolver {
+    serializers: HashMap<String, String>,
+    policy: SerializeWithPolicy,
+}
+
+impl SerializeWithSolver {
+    pub fn new(serializers: HashMap<String, String>, policy: SerializeWithPolicy) -> Self {
+        SerializeWithSolver {
+            serializers,
+            policy,
+        }
+    }
+
+    /// The TS type of a serializer, by the path of its function, or of its module for `with`
+    fn ts_type(&self, serializer: &str) -> Option<&String> {
+        self.serializers.get(serializer).or_else(|| {
+            let (module, function) = serializer.rsplit_once("::")?;
+            match function {
+                "serialize" | "deserialize" => self.serializers.get(module),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// The path of a serializer resolved through the imports of the module, e.g. `chrono::serde::ts_seconds`
+/// for `with = "ts_seconds"` with `use chrono::serde::ts_seconds;`
+pub(crate) fn resolve_serializer(solving_context: &ExporterContext, path: &Path) -> Option<String> {
+    let ty_path = TypePath {
+        qself: None,
+        path: path.clone(),
+    };
+    match solving_context.import_context.solve_import(&ty_path)? {
+        Type::Path(resolved) => Some(DisplayPath(&resolved.path).to_string()),
+        _ => None,
+    }
+}
+
+fn is_remote_container(solving_context: &ExporterContext, serializer: &str) -> bool {
+    serializer.rsplit_once("::").is_some_and(|(container, _)| {
+        solving_context
+            .remote_types
+            .values()
+            .any(|name| name == container)
+    })
+}
+
+impl TypeSolver for SerializeWithSolver {
+    fn solve_as_type(
+        &self,
+        _solving_context: &ExporterContext,
+        _solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        SolverResult::Continue
+    }
+
+    fn solve_as_member(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &MemberInfo,
+    ) -> SolverResult<TypeMember, TsExportError> {
+        let serializer = match solving_context.direction {
+            Direction::Serialize => solver_info.serde_field.serialize_with(),
+            Direction::Deserialize => solver_info.serde_field.deserialize_with(),
+        };
+        let (serializer, resolved) = match serializer {
+            Some(serializer) => (
+                DisplayPath(&serializer.path).to_string(),
+                resolve_serializer(solving_context, &serializer.path),
+            ),
+            None => return SolverResult::Continue,
+        };
+        let ts_type = self
+            .ts_type(&serializer)
+            .or_else(|| self.ts_type(resolved.as_deref()?));
+        let inner_type = match (ts_type, self.policy) {
+            (Some(ts_type), _) => raw_type(ts_type),
+            // `with = "DurationDef"` for a `#[serde(remote = "Duration")] struct DurationDef`, solved by the RemoteSolver
+            (None, _) if is_remote_container(solving_context, &serializer) => {
+                return SolverResult::Continue
+            }
+            (None, SerializeWithPolicy::Passthrough) => return SolverResult::Continue,
+            (None, SerializeWithPolicy::Unknown) => {
+                TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::Unknown))
+            }
+            (None, SerializeWithPolicy::Error) => {
+                return SolverResult::Error(TsExportError::UnmappedSerializer(
+                    serializer,
+                    solver_info.name.clone(),
+                ))
+            }
+        };
+        let (optional, inner_type) = apply_skip_serializing_if(
+            solving_context.direction,
+            solver_info.serde_field,
+            inner_type,
+        );
+        SolverResult::Solved(Solved::new(TypeMember::PropertySignature(
+            PropertySignature {
+                name: PropertyName::from(solver_info.name.clone()),
+                optional,
+                readonly: false,
+                inner_type,
+                doc: None,
+            },
+        )))
+    }
+}
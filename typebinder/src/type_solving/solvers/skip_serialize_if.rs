@@ -1,16 +1,18 @@
-use syn::Type;
 use ts_json_subset::types::{PropertyName, PropertySignature, TsType, TypeMember};
 
 use crate::{
-    contexts::exporter::ExporterContext,
+    contexts::exporter::{strip_null, Direction, ExporterContext},
     error::TsExportError,
     type_solving::member_info::MemberInfo,
     type_solving::{result::SolverResult, type_info::TypeInfo, TypeSolver},
     utils::display_path::DisplayPath,
-    utils::inner_generic::solve_segment_generics,
 };
 
-/// A solver to handle `serde(skip_serialize_if = "...")`
+/// A solver to handle `serde(skip_serializing_if = "...")`, whose fields may be absent and are optional.
+/// The deserialized fields still require a value, unless they have a `#[serde(default)]`.
+///
+/// It comes first among the default solvers, so that it applies to every type.
+/// Only the [TimeRsSolver](super::time_rs::TimeRsSolver) comes before it, and applies it to the fields it solves.
 pub struct SkipSerializeIf;
 
 impl TypeSolver for SkipSerializeIf {
@@ -27,56 +29,52 @@ impl TypeSolver for SkipSerializeIf {
         solving_context: &ExporterContext,
         solver_info: &MemberInfo,
     ) -> SolverResult<TypeMember, TsExportError> {
-        if let Some(skip_serializing_if) = solver_info.serde_field.skip_serializing_if() {
-            if let Type::Path(ty_path) = solver_info.ty {
-                let ty_name = DisplayPath(&ty_path.path).to_string();
-                match ty_name.as_str() {
-                    "Option" => {
-                        let skip_serializing_if =
-                            DisplayPath(&skip_serializing_if.path).to_string();
-                        match skip_serializing_if.as_str() {
-                            "Option::is_none" => {
-                                // Special case: the type is Option and skip_serialize_if's function is Option::is_none
-                                // Solution: inner type of Option, field as optional
-                                let generics = solver_info.generics;
-                                let segment = ty_path.path.segments.last().expect("Empty path");
-                                match solve_segment_generics(solving_context, generics, segment) {
-                                    Ok(solved) => {
-                                        return SolverResult::Solved(solved.map(|types| {
-                                            let inner_type = types[0].clone();
-                                            TypeMember::PropertySignature(PropertySignature {
-                                                inner_type,
-                                                name: PropertyName::from(
-                                                    solver_info.name.to_string(),
-                                                ),
-                                                optional: true,
-                                            })
-                                        }))
-                                    }
-                                    Err(e) => return SolverResult::Error(e),
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            // General case the type is not an Option
-            let type_info = solver_info.as_type_info();
-            match solving_context.solve_type(&type_info) {
-                Ok(solved) => {
-                    return SolverResult::Solved(solved.map(|inner_type| {
-                        TypeMember::PropertySignature(PropertySignature {
-                            inner_type,
-                            name: PropertyName::from(solver_info.name.to_string()),
-                            optional: true,
-                        })
-                    }))
-                }
-                Err(e) => return SolverResult::Error(e),
-            }
+        if solving_context.direction == Direction::Deserialize
+            || solver_info.serde_field.skip_serializing_if().is_none()
+        {
+            return SolverResult::Continue;
         }
-        SolverResult::Continue
+        let type_info = solver_info.as_type_info();
+        match solving_context.solve_type(&type_info) {
+            Ok(solved) => SolverResult::Solved(solved.map(|inner_type| {
+                let (optional, inner_type) = apply_skip_serializing_if(
+                    solving_context.direction,
+                    solver_info.serde_field,
+                    inner_type,
+                );
+                TypeMember::PropertySignature(PropertySignature {
+                    inner_type,
+                    name: PropertyName::from(solver_info.name.to_string()),
+                    optional,
+                    readonly: false,
+                    doc: None,
+                })
+            })),
+            Err(e) => SolverResult::Error(e),
+        }
+    }
+}
+
+/// Whether a field is optional, which it is when it has a `skip_serializing_if`, and its type,
+/// which is not nullable when the field is skipped if it is None.
+///
+/// The condition only applies to the serialized data, a deserialized field is left as is.
+pub(crate) fn apply_skip_serializing_if(
+    direction: Direction,
+    serde_field: &serde_derive_internals::attr::Field,
+    inner_type: TsType,
+) -> (bool, TsType) {
+    let skip_serializing_if = match (direction, serde_field.skip_serializing_if()) {
+        (Direction::Serialize, Some(skip_serializing_if)) => {
+            DisplayPath(&skip_serializing_if.path).to_string()
+        }
+        _ => return (false, inner_type),
+    };
+    let is_none = skip_serializing_if == "Option::is_none"
+        || skip_serializing_if.ends_with("::Option::is_none");
+    if is_none {
+        (true, strip_null(inner_type).unwrap_or_else(|ty| ty))
+    } else {
+        (true, inner_type)
     }
 }
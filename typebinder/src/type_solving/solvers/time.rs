@@ -0,0 +1,105 @@
+use ts_json_subset::types::{
+    ObjectType, PredefinedType, PrimaryType, PropertyName, PropertySignature, TsType, TypeBody,
+    TypeMember,
+};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+const DURATION_PATHS: &[&str] = &["std::time::Duration", "core::time::Duration"];
+const SYSTEM_TIME_PATH: &str = "std::time::SystemTime";
+
+/// Solver for `std::time::Duration` and `std::time::SystemTime`, serialized by serde as `{ secs: number, nanos: number }`
+/// and `{ secs_since_epoch: number, nanos_since_epoch: number }`.
+///
+/// For the APIs that serialize them as milliseconds with a custom serializer, add [TimeSolver::milliseconds] before the default solvers.
+pub struct TimeSolver {
+    inner: PathSolver,
+}
+
+fn number_object(names: &[&str]) -> TsType {
+    TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+        body: TypeBody {
+            members: names
+                .iter()
+                .map(|name| {
+                    TypeMember::PropertySignature(PropertySignature {
+                        name: PropertyName::from(name.to_string()),
+                        optional: false,
+                        readonly: false,
+                        inner_type: TsType::PrimaryType(PrimaryType::Predefined(
+                            PredefinedType::Number,
+                        )),
+                        doc: None,
+                    })
+                })
+                .collect(),
+        },
+    }))
+}
+
+fn solve_duration(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(number_object(&["secs", "nanos"])))
+}
+
+fn solve_system_time(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(number_object(&[
+        "secs_since_epoch",
+        "nanos_since_epoch",
+    ])))
+}
+
+fn solve_milliseconds(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::Number,
+    ))))
+}
+
+impl TimeSolver {
+    /// Solves `Duration` and `SystemTime` to `number`, a number of milliseconds (since the epoch for `SystemTime`)
+    pub fn milliseconds() -> Self {
+        let solver = solve_milliseconds.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in DURATION_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        inner.add_entry(SYSTEM_TIME_PATH, solver);
+        TimeSolver { inner }
+    }
+}
+
+impl Default for TimeSolver {
+    fn default() -> Self {
+        let solver = solve_duration.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in DURATION_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        inner.add_entry(SYSTEM_TIME_PATH, solve_system_time.fn_solver().into_rc());
+        TimeSolver { inner }
+    }
+}
+
+impl TypeSolver for TimeSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
@@ -0,0 +1,123 @@
+use ts_json_subset::types::{
+    PredefinedType, PrimaryType, PropertyName, PropertySignature, TsType, TypeMember, UnionType,
+};
+
+use super::{
+    path::PathSolver, serialize_with::resolve_serializer,
+    skip_serialize_if::apply_skip_serializing_if,
+};
+use crate::{
+    contexts::exporter::{Direction, ExporterContext},
+    error::TsExportError,
+    type_solving::member_info::MemberInfo,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+    utils::display_path::DisplayPath,
+};
+
+/// The types of the `time` crate that implement `Serialize`
+const TIME_PATHS: &[&str] = &[
+    "time::OffsetDateTime",
+    "time::PrimitiveDateTime",
+    "time::Date",
+    "time::Time",
+    "time::UtcOffset",
+    "time::Duration",
+];
+
+/// The modules of `time` that serialize its types as timestamps, the others using a text format
+const TIMESTAMP_MODULE: &str = "time::serde::timestamp";
+
+/// Integration for the `time` crate.
+///
+/// Its types are solved as `string`, the way they are serialized to the human readable formats such as JSON
+/// (with the `serde-human-readable` feature of `time`).
+///
+/// The fields serialized with a module of `time::serde`, e.g. `#[serde(with = "time::serde::timestamp")]`
+/// or `#[serde(with = "timestamp")]` with `use time::serde::timestamp;`, are solved after it : `number` for the timestamps, `string` for the text formats (`rfc3339`, `iso8601`...),
+/// and nullable for the `option` modules. It comes before the other default solvers, which would solve the type of the field
+/// regardless of its serializer.
+pub struct TimeRsSolver {
+    inner: PathSolver,
+}
+
+fn predefined(ty: PredefinedType) -> TsType {
+    TsType::PrimaryType(PrimaryType::Predefined(ty))
+}
+
+fn solve_time(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(predefined(PredefinedType::String)))
+}
+
+impl Default for TimeRsSolver {
+    fn default() -> Self {
+        let solver = solve_time.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in TIME_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        TimeRsSolver { inner }
+    }
+}
+
+impl TypeSolver for TimeRsSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+
+    fn solve_as_member(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &MemberInfo,
+    ) -> SolverResult<TypeMember, TsExportError> {
+        let serializer = match solving_context.direction {
+            Direction::Serialize => solver_info.serde_field.serialize_with(),
+            Direction::Deserialize => solver_info.serde_field.deserialize_with(),
+        };
+        let with = match serializer {
+            Some(serializer) => resolve_serializer(solving_context, &serializer.path)
+                .unwrap_or_else(|| DisplayPath(&serializer.path).to_string()),
+            // The SkipSerializeIf solver makes the field optional, solving its type with this solver
+            None if solver_info.serde_field.skip_serializing_if().is_some() => {
+                return SolverResult::Continue
+            }
+            None => return self.inner.solve_as_member(solving_context, solver_info),
+        };
+        if !with.starts_with("time::serde::") {
+            return SolverResult::Continue;
+        }
+        let inner_type = if with.starts_with(TIMESTAMP_MODULE) {
+            predefined(PredefinedType::Number)
+        } else {
+            predefined(PredefinedType::String)
+        };
+        let inner_type = if with.split("::").any(|segment| segment == "option") {
+            TsType::UnionType(UnionType {
+                types: vec![inner_type, predefined(PredefinedType::Null)],
+            })
+        } else {
+            inner_type
+        };
+        let (optional, inner_type) = apply_skip_serializing_if(
+            solving_context.direction,
+            solver_info.serde_field,
+            inner_type,
+        );
+        SolverResult::Solved(Solved::new(TypeMember::PropertySignature(
+            PropertySignature {
+                name: PropertyName::from(solver_info.name.clone()),
+                optional,
+                readonly: false,
+                inner_type,
+                doc: None,
+            },
+        )))
+    }
+}
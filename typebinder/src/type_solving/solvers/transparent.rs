@@ -0,0 +1,73 @@
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::fn_solver::AsFnSolver,
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+    utils::inner_generic::solve_segment_generics,
+};
+use syn::Type;
+use ts_json_subset::types::TsType;
+
+use super::path::PathSolver;
+
+/// The wrappers that serde serializes as their content
+const TRANSPARENT_PATHS: &[&str] = &[
+    "std::borrow::Cow",
+    "std::boxed::Box",
+    "std::rc::Rc",
+    "std::sync::Arc",
+    "std::cell::Cell",
+    "std::cell::RefCell",
+    "std::sync::Mutex",
+    "std::sync::RwLock",
+    "std::num::Wrapping",
+    "std::num::Saturating",
+    "std::num::NonZero",
+];
+
+/// Solver for the smart pointers, cells, `Cow` and the numeric wrappers, e.g. `Arc<User>`,
+/// solved as their single generic argument : `User`.
+///
+/// The lifetimes are left out, so that `Cow<'a, [u32]>` is solved as `number[]`.
+pub struct TransparentSolver {
+    inner: PathSolver,
+}
+
+fn solve_transparent(
+    solving_context: &ExporterContext,
+    solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    let TypeInfo { generics, ty } = solver_info;
+    let segment = match ty {
+        Type::Path(ty) => ty.path.segments.last().expect("Empty path"),
+        _ => unreachable!(),
+    };
+    match solve_segment_generics(solving_context, generics, segment) {
+        Ok(solved) if solved.inner.len() == 1 => SolverResult::Solved(
+            solved.map(|types| types.into_iter().next().expect("One type is solved")),
+        ),
+        Ok(_) => SolverResult::Error(TsExportError::EmptyGenerics),
+        Err(e) => SolverResult::Error(e),
+    }
+}
+
+impl Default for TransparentSolver {
+    fn default() -> Self {
+        let solver = solve_transparent.fn_solver().into_rc();
+        let mut inner = PathSolver::default();
+        for path in TRANSPARENT_PATHS {
+            inner.add_entry(*path, solver.clone());
+        }
+        TransparentSolver { inner }
+    }
+}
+
+impl TypeSolver for TransparentSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use syn::Type;
+use ts_json_subset::types::{ParenthesizedType, PrimaryType, RawType, TsType};
+
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{result::Solved, SolverResult, TypeInfo, TypeSolver},
+    utils::display_path::DisplayPath,
+};
+
+/// Solves the types of a map of Rust paths to TS types written by hand, e.g. `rust_decimal::Decimal` to `string`,
+/// so that the types of other crates can be exported without writing a solver.
+///
+/// A path matches a type as written, or as resolved through the imports (`Decimal` with `use rust_decimal::Decimal;`),
+/// whatever its generic arguments. The TS type is output as is.
+#[derive(Debug, Default, Clone)]
+pub struct TypeOverridesSolver {
+    overrides: HashMap<String, String>,
+}
+
+impl TypeOverridesSolver {
+    pub fn new(overrides: HashMap<String, String>) -> Self {
+        TypeOverridesSolver { overrides }
+    }
+}
+
+impl TypeSolver for TypeOverridesSolver {
+    fn solve_as_type(
+        &self,
+        _solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        let ts_type = match solver_info.ty {
+            Type::Path(ty) => match self.overrides.get(&DisplayPath(&ty.path).to_string()) {
+                Some(ts_type) => ts_type,
+                None => return SolverResult::Continue,
+            },
+            _ => return SolverResult::Continue,
+        };
+        SolverResult::Solved(Solved::new(raw_type(ts_type)))
+    }
+}
+
+/// A TS type written by hand, output as is
+pub(crate) fn raw_type(ts_type: &str) -> TsType {
+    let raw = TsType::PrimaryType(PrimaryType::RawType(RawType(ts_type.to_string())));
+    // The raw type isn't parsed, a union or an intersection is parenthesized so that it can be nested, e.g. in an array
+    if ts_type.contains(['|', '&']) || ts_type.contains("=>") {
+        TsType::ParenthesizedType(ParenthesizedType {
+            inner: Box::new(raw),
+        })
+    } else {
+        raw
+    }
+}
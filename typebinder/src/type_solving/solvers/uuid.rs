@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use ts_json_subset::{
+    common::StringLiteral,
+    ident::TSIdent,
+    types::{
+        IntersectionType, LiteralType, ObjectType, PredefinedType, PrimaryType, PropertyName,
+        PropertySignature, TsType, TypeBody, TypeMember,
+    },
+};
+
+use super::path::PathSolver;
+use crate::{
+    contexts::exporter::ExporterContext,
+    error::TsExportError,
+    type_solving::{fn_solver::AsFnSolver, result::Solved},
+    type_solving::{SolverResult, TypeInfo, TypeSolver, TypeSolverExt},
+};
+
+/// Integration for the `uuid` crate
+pub struct UuidSolver {
+    inner: PathSolver,
+}
+
+fn solve_uuid(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    SolverResult::Solved(Solved::new(TsType::PrimaryType(PrimaryType::Predefined(
+        PredefinedType::String,
+    ))))
+}
+
+fn solve_branded_uuid(
+    _solving_context: &ExporterContext,
+    _solver_info: &TypeInfo,
+) -> SolverResult<TsType, TsExportError> {
+    let brand = TypeMember::PropertySignature(PropertySignature {
+        name: PropertyName::Identifier(TSIdent::from_str("__brand").unwrap()),
+        optional: false,
+        readonly: false,
+        inner_type: TsType::PrimaryType(PrimaryType::LiteralType(LiteralType::StringLiteral(
+            StringLiteral::from("Uuid".to_string()),
+        ))),
+        doc: None,
+    });
+    SolverResult::Solved(Solved::new(TsType::IntersectionType(IntersectionType {
+        types: vec![
+            TsType::PrimaryType(PrimaryType::Predefined(PredefinedType::String)),
+            TsType::PrimaryType(PrimaryType::ObjectType(ObjectType {
+                body: TypeBody {
+                    members: vec![brand],
+                },
+            })),
+        ],
+    })))
+}
+
+impl UuidSolver {
+    /// Solves `uuid::Uuid` to the branded `string & { __brand: "Uuid" }`, so that a UUID is not assignable from any string.
+    ///
+    /// Add it before the default solvers.
+    pub fn branded() -> Self {
+        let mut inner = PathSolver::default();
+        inner.add_entry("uuid::Uuid", solve_branded_uuid.fn_solver().into_rc());
+        UuidSolver { inner }
+    }
+}
+
+impl Default for UuidSolver {
+    fn default() -> Self {
+        let mut inner = PathSolver::default();
+        inner.add_entry("uuid::Uuid", solve_uuid.fn_solver().into_rc());
+        UuidSolver { inner }
+    }
+}
+
+impl TypeSolver for UuidSolver {
+    fn solve_as_type(
+        &self,
+        solving_context: &ExporterContext,
+        solver_info: &TypeInfo,
+    ) -> SolverResult<TsType, TsExportError> {
+        self.inner.solve_as_type(solving_context, solver_info)
+    }
+}
@@ -23,6 +23,24 @@ pub fn fetch_crate_name_for_source_file(source_file_path: &Path) -> Result<Strin
     Ok(package.name)
 }
 
+pub fn fetch_crate_version_for_source_file(source_file_path: &Path) -> Result<String, Error> {
+    let cargo_toml_path = find_cargo_toml_for_source_file(source_file_path)?;
+    let manifest = cargo_toml::Manifest::from_path(&cargo_toml_path)?;
+    let package = manifest
+        .package
+        .ok_or(Error::NoPackageSection(cargo_toml_path))?;
+    Ok(package.version)
+}
+
+/// The directory of the crate that contains the source file, i.e. the directory of its Cargo.toml
+pub fn fetch_crate_dir_for_source_file(source_file_path: &Path) -> Result<PathBuf, Error> {
+    let cargo_toml_path = find_cargo_toml_for_source_file(source_file_path)?;
+    Ok(cargo_toml_path
+        .parent()
+        .expect("A Cargo.toml is in a directory")
+        .to_path_buf())
+}
+
 fn find_cargo_toml_for_source_file(source_file_path: &Path) -> Result<PathBuf, Error> {
     let path = source_file_path
         .canonicalize()
@@ -51,4 +69,12 @@ mod tests {
         let crate_name = fetch_crate_name_for_source_file(&path).unwrap();
         assert_eq!(crate_name, "typebinder")
     }
+
+    #[test]
+    fn test_fetch_crate_version_for_source_file() {
+        let current_file = format!("../{}", file!());
+        let path = PathBuf::from(current_file);
+        let version = fetch_crate_version_for_source_file(&path).unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"))
+    }
 }
@@ -0,0 +1,146 @@
+//! Resolution of the paths of the modules of a crate
+use proc_macro2::Span;
+use syn::{Ident, Path, PathArguments, PathSegment};
+
+use crate::type_solving::ImportEntry;
+
+/// The segments of a module path
+pub fn module_segments(path: &syn::Path) -> Vec<String> {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect()
+}
+
+/// Resolves the segments of a path relatively to the crate root, `None` if the path belongs to another crate.
+///
+/// The paths starting with `crate`, `self`, `super` or the name of the crate are resolved against `module_path`,
+/// and the `super` segments are applied. `segment` builds the segments of `module_path`.
+///
+/// A path through a child module of the current module (`inner_things::Thing`) has been prefixed with `self`
+/// by [ImportContext::solve_import](crate::contexts::import::ImportContext::solve_import) beforehand,
+/// the other paths being those of other crates.
+fn resolve_segments<T>(
+    segments: impl IntoIterator<Item = T>,
+    ident: impl Fn(&T) -> String,
+    segment: impl Fn(&str) -> T,
+    module_path: &[String],
+    crate_name: &str,
+) -> Option<Vec<T>> {
+    let mut segments = segments.into_iter();
+    let first = segments.next()?;
+    let module = |path: &[String]| path.iter().map(|name| segment(name)).collect();
+    let mut resolved: Vec<T> = match ident(&first).as_str() {
+        "self" => module(module_path),
+        "super" => module(
+            module_path
+                .split_last()
+                .map(|(_, parent)| parent)
+                .unwrap_or_default(),
+        ),
+        "crate" => Vec::new(),
+        first if first == crate_name => Vec::new(),
+        _ => return None,
+    };
+    for segment in segments {
+        if ident(&segment) == "super" {
+            resolved.pop();
+        } else {
+            resolved.push(segment);
+        }
+    }
+    Some(resolved)
+}
+
+/// Finds the path of the module of the crate that declares an imported item, `None` if it belongs to another crate
+pub fn resolve_local_path(
+    entry: &ImportEntry,
+    module_path: &[String],
+    crate_name: &str,
+) -> Option<Vec<String>> {
+    resolve_segments(
+        entry.path.split("::").map(|segment| segment.to_string()),
+        |segment| segment.clone(),
+        |segment| segment.to_string(),
+        module_path,
+        crate_name,
+    )
+}
+
+/// Whether a path starts with `crate`, `self` or `super`
+pub fn is_relative_path(path: &Path) -> bool {
+    path.segments
+        .first()
+        .map(|segment| {
+            segment.ident == "crate" || segment.ident == "self" || segment.ident == "super"
+        })
+        .unwrap_or(false)
+}
+
+/// Makes a path that starts with `crate`, `self` or `super` absolute, that is starting with the name of the crate,
+/// so that an item maps to the same path however it is referred to.
+///
+/// The other paths are left as is.
+pub fn normalize_path(path: &Path, module_path: &[String], crate_name: &str) -> Path {
+    if !is_relative_path(path) {
+        return path.clone();
+    }
+    let segment = |name: &str| PathSegment {
+        ident: Ident::new(name, Span::call_site()),
+        arguments: PathArguments::None,
+    };
+    let resolved = resolve_segments(
+        path.segments.iter().cloned(),
+        |segment| segment.ident.to_string(),
+        segment,
+        module_path,
+        crate_name,
+    )
+    .unwrap_or_default();
+    Path {
+        leading_colon: None,
+        segments: Some(segment(crate_name))
+            .into_iter()
+            .chain(resolved)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::display_path::DisplayPath;
+
+    fn normalize(path: &str) -> String {
+        let path: Path = syn::parse_str(path).expect("Failed to parse path");
+        let module_path = vec!["api".to_string(), "v1".to_string()];
+        DisplayPath(&normalize_path(&path, &module_path, "my_crate")).to_string()
+    }
+
+    #[test]
+    fn should_normalize_the_relative_paths() {
+        assert_eq!(normalize("crate::models::User"), "my_crate::models::User");
+        assert_eq!(normalize("self::User"), "my_crate::api::v1::User");
+        assert_eq!(normalize("super::User"), "my_crate::api::User");
+        assert_eq!(
+            normalize("super::super::models::User"),
+            "my_crate::models::User"
+        );
+        assert_eq!(
+            normalize("my_crate::models::User"),
+            "my_crate::models::User"
+        );
+        assert_eq!(normalize("chrono::DateTime"), "chrono::DateTime");
+    }
+
+    #[test]
+    fn should_keep_the_generic_arguments() {
+        let path: Path = syn::parse_str("self::Page<User>").expect("Failed to parse path");
+        let normalized = normalize_path(&path, &[], "my_crate");
+        assert_eq!(normalized.segments.len(), 2);
+        assert!(matches!(
+            normalized.segments[1].arguments,
+            PathArguments::AngleBracketed(_)
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+//! A minimal glob matcher, for the paths excluded from the pipeline
+//!
+//! Paths and patterns are `/`-separated. Within a segment, `*` matches any sequence of characters and `?` a single character.
+//! A `**` segment matches any number of segments, including none : `**/generated/**` matches `src/generated` and
+//! `src/models/generated/user.rs`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    segments: Vec<String>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        Glob {
+            segments: split(pattern).map(String::from).collect(),
+        }
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        let path: Vec<&str> = split(path).collect();
+        let pattern: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        match_segments(&pattern, &path)
+    }
+}
+
+fn split(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skipped| match_segments(rest, &path[skipped..]))
+        }
+        Some((segment, rest)) => match path.split_first() {
+            Some((first, path_rest)) => {
+                let segment: Vec<char> = segment.chars().collect();
+                let first: Vec<char> = first.chars().collect();
+                match_segment(&segment, &first) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skipped| match_segment(rest, &name[skipped..])),
+        Some(('?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn should_match_globs() {
+        let glob = Glob::new("**/generated/**");
+        assert!(glob.is_match("src/generated"));
+        assert!(glob.is_match("src/models/generated/user.rs"));
+        assert!(!glob.is_match("src/generated_models.rs"));
+
+        let glob = Glob::new("src/*_test?.rs");
+        assert!(glob.is_match("src/models_tests.rs"));
+        assert!(!glob.is_match("src/models/user_tests.rs"));
+        assert!(!glob.is_match("src/models_test.rs"));
+    }
+}
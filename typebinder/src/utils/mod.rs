@@ -1,5 +1,18 @@
 //! A set of various utilities
 
 pub mod cargo;
+pub mod crate_paths;
 pub mod display_path;
+pub mod glob;
 pub mod inner_generic;
+pub mod normalize;
+pub mod rename;
+
+/// An empty temporary directory for a test, unique to the process and the test so that the tests can run concurrently
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("typebinder_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("Failed to create the test directory");
+    dir
+}
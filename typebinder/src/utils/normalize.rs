@@ -0,0 +1,172 @@
+//! Normalization of the generated TS, for snapshot tests.
+//!
+//! The output of two runs can differ without the bindings being different : the header comments mention the Rust modules,
+//! the exporters don't lay the statements out the same way, and the order of the imports depends on the order the types are met.
+//! [normalize_output] removes those differences, so that the snapshots of downstream crates only change with their bindings :
+//!
+//! ```
+//! use typebinder::utils::normalize::normalize_output;
+//!
+//! let output = "// Rust source module: models\n\nimport { User, Group } from \"types\";\nexport interface Team {\n\tusers: User[]\n}\n";
+//! assert_eq!(
+//!     normalize_output(output),
+//!     "import { Group, User } from \"types\";\nexport interface Team { users: User[] }\n"
+//! );
+//! ```
+
+/// Normalizes generated TS :
+/// * the line comments (e.g. the headers of the [FileExporter](crate::exporters::file::FileExporter)) are removed,
+/// * each top-level statement is written on its own line, its whitespace collapsed to single spaces (string literals excepted),
+/// * the imports come first, sorted, with their items sorted too. The other statements keep their order.
+pub fn normalize_output(output: &str) -> String {
+    let mut imports: Vec<String> = Vec::new();
+    let mut statements: Vec<String> = Vec::new();
+    for statement in split_statements(&strip_line_comments(output)) {
+        if statement.starts_with("import ") {
+            imports.push(sort_import_items(&statement));
+        } else {
+            statements.push(statement);
+        }
+    }
+    imports.sort();
+    imports.dedup();
+    imports
+        .into_iter()
+        .chain(statements)
+        .map(|statement| format!("{}\n", statement))
+        .collect()
+}
+
+fn strip_line_comments(output: &str) -> String {
+    output
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Whether a statement ends with its block (`export interface A { ... }`), or with a semicolon (`export type A = { ... };`)
+fn ends_with_block(statement: &str) -> bool {
+    let declaration = statement.trim_start_matches("export ");
+    [
+        "interface ",
+        "namespace ",
+        "function ",
+        "const enum ",
+        "enum ",
+    ]
+    .iter()
+    .any(|keyword| declaration.starts_with(keyword))
+}
+
+/// Splits the top-level statements, collapsing their whitespace
+fn split_statements(output: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut pending_space = false;
+
+    for c in output.chars() {
+        if let Some(q) = quote {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            pending_space = !current.is_empty();
+            continue;
+        }
+        if pending_space {
+            current.push(' ');
+            pending_space = false;
+        }
+        current.push(c);
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                if c == '}' && depth == 0 && ends_with_block(&current) {
+                    statements.push(std::mem::take(&mut current));
+                }
+            }
+            ';' if depth == 0 => {
+                let statement = std::mem::take(&mut current);
+                // The optional semicolon after a block
+                if statement != ";" {
+                    statements.push(statement);
+                }
+            }
+            _ => {}
+        }
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        statements.push(rest.to_string());
+    }
+    statements
+}
+
+/// Sorts the items of `import { B, A } from "...";`
+fn sort_import_items(statement: &str) -> String {
+    let (start, end) = match (statement.find('{'), statement.find('}')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return statement.to_string(),
+    };
+    let mut items: Vec<&str> = statement[start + 1..end]
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect();
+    items.sort_unstable();
+    items.dedup();
+    format!(
+        "{}{{ {} }}{}",
+        &statement[..start],
+        items.join(", "),
+        &statement[end + 1..]
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalize_output() {
+        let output = "//------\n\
+                      // Default module\n\
+                      // ------\n\
+                      import { Role, Id } from \"types/ids\";\n\
+                      import { User } from \"types/models\";\n\
+                      export interface Session {\n\tid: Id,\n\tuser: User\n}export type Kind = {\n\
+                      \ttype: \"A  B\"\n} | {\n\ttype: \"C\"\n};\n\
+                      export const enum Level {\n\tLow = \"low\"\n}\n";
+        assert_eq!(
+            normalize_output(output),
+            "import { Id, Role } from \"types/ids\";\n\
+             import { User } from \"types/models\";\n\
+             export interface Session { id: Id, user: User }\n\
+             export type Kind = { type: \"A  B\" } | { type: \"C\" };\n\
+             export const enum Level { Low = \"low\" }\n"
+        );
+    }
+
+    #[test]
+    fn should_not_depend_on_the_layout() {
+        let file = "// This file was auto-generated with typebinder from Rust source code.\n\
+                    // Rust source module: models\n\n\
+                    import { B, A } from \"x\";\r\n\
+                    export interface A<T = number> {\r\n    value: T\r\n}\r\n";
+        let stdout = "import { A, B } from \"x\";\nexport interface A<T = number> {\n\tvalue: T\n}";
+        assert_eq!(normalize_output(file), normalize_output(stdout));
+    }
+}
@@ -0,0 +1,42 @@
+//! Renaming of the references to TS declarations
+use std::collections::HashMap;
+
+use ts_json_subset::{
+    ident::TSIdent,
+    types::TypeReference,
+    visit_mut::{self, VisitMut},
+};
+
+/// Rewrites the type references whose name is a key of `renames`
+pub struct ReferenceRenamer<'a> {
+    pub renames: &'a HashMap<String, TSIdent>,
+}
+
+impl VisitMut for ReferenceRenamer<'_> {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        if let Some(renamed) = self.renames.get(&node.name.to_string()) {
+            node.name = renamed.clone();
+        }
+        visit_mut::visit_type_reference_mut(self, node);
+    }
+}
+
+/// Rewrites the references named `ident` to `alias`, but not the references in their type arguments,
+/// which are named in the scope of the module
+pub struct AliasRenamer<'a> {
+    pub ident: &'a str,
+    pub alias: &'a TSIdent,
+    /// Whether a reference was rewritten
+    pub renamed: bool,
+}
+
+impl VisitMut for AliasRenamer<'_> {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        if node.name.to_string() == self.ident {
+            node.name = self.alias.clone();
+            self.renamed = true;
+            return;
+        }
+        visit_mut::visit_type_reference_mut(self, node);
+    }
+}
@@ -31,17 +31,46 @@
 //!    +- index.ts
 //! ```
 //!
-use std::path::PathBuf;
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use structopt::StructOpt;
 use typebinder::{
-    contexts::type_solving::TypeSolvingContextBuilder,
+    config::{Config, DependencyMode, ExporterKind, CONFIG_FILE},
+    contexts::{
+        exporter::{ExporterOptions, ProcessMode, TypeNamePolicy, UnsolvedTypePolicy},
+        type_solving::TypeSolvingContext,
+    },
     error::TsExportError,
-    exporters::{file::FileExporter, stdout::StdoutExport},
+    exporters::{
+        bundle::BundleExporter,
+        file::{FileExporter, HeaderComment},
+        formatter::FormatterCommand,
+        import_style::ImportExtension,
+        inline::InlineExporter,
+        namespaces::NamespaceExporter,
+        npm_package::{NpmPackage, NpmPackageExporter},
+        output_options::OutputOptions,
+        stdout::StdoutExport,
+        type_tests::TYPE_TESTS_FILE,
+    },
     macros::context::MacroSolvingContext,
-    path_mapper::PathMapper,
-    pipeline::Pipeline,
-    step_spawner::mod_reader::RustModuleReader,
+    path_mapper::PathCase,
+    pipeline::{
+        cfg::CfgSet, codecs::CodecLibrary, filter::ProcessFilter, report::RunReport,
+        warp_endpoints::ENDPOINTS_INTERFACE, Pipeline,
+    },
+    step_spawner::{
+        cargo::CargoWorkspace, dependencies::CargoDependencies, mod_reader::RustModuleReader,
+    },
+    ts::format::{FormatOptions, Indent, Quotes},
+    tsconfig::{relative_path, TsConfigPaths},
+    type_solving::solvers::{primitives::LargeIntegerPolicy, serialize_with::SerializeWithPolicy},
+    utils::cargo::{fetch_crate_dir_for_source_file, fetch_crate_version_for_source_file},
 };
 
 #[derive(Debug, StructOpt)]
@@ -52,61 +81,981 @@ use typebinder::{
 /// CLI arguments
 struct Options {
     #[structopt(parse(from_os_str))]
-    /// Rust module to generate the bindings for
-    input: PathBuf,
+    /// Rust module to generate the bindings for. Several root modules of the same crate can be processed together,
+    /// e.g. `src/api/mod.rs src/events.rs`, they are then named after their path in the crate
+    input: Vec<PathBuf>,
+    #[structopt(short, long, parse(from_os_str))]
+    /// Path to a `typebinder.toml` (or `.json`, `.yaml`) describing the run, the arguments taking precedence over it.
+    /// Defaults to `./typebinder.toml` when no input is given
+    config: Option<PathBuf>,
     #[structopt(short, parse(from_os_str))]
     /// Output file, will use stdout if no file is specified
     output: Option<PathBuf>,
     #[structopt(short, parse(from_os_str))]
-    /// Path to the PathMapper definition
+    /// Path to the PathMapper definition, a JSON object or a TOML table (`.toml`) mapping Rust paths to TS import paths
     path_mapper_file: Option<PathBuf>,
+    #[structopt(long)]
+    /// The case of the file names of the paths mapped by the PathMapper: `snake_case` (unchanged), `kebab-case`,
+    /// `camelCase` or `PascalCase`
+    path_case: Option<PathCase>,
+    #[structopt(long)]
+    /// Export externally tagged enums as `{ <key>: "Variant", ...content }`, to match custom serializers
+    external_tag_key: Option<String>,
+    #[structopt(long)]
+    /// Re-export the items of the crate that are re-exported with a `pub use`
+    follow_reexports: bool,
+    #[structopt(long, number_of_values = 1)]
+    /// Import the types of a dependency from an npm package, e.g. `other_crate=@company/other-crate-types`, repeatable
+    dependency_package: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Export the types imported from this dependency to its own directory of the output, located with `cargo metadata`, repeatable
+    export_dependency: Vec<String>,
+    #[structopt(long)]
+    /// Name the structs and enums after their `#[serde(rename)]` instead of their Rust ident
+    serde_type_names: bool,
+    #[structopt(long, parse(from_os_str))]
+    /// Write a JSON map of the Rust paths of the exported types to their TS file and name
+    symbol_map: Option<PathBuf>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package"]
+    )]
+    /// Write a JSON map of the Rust paths of the exported types to a sample value
+    examples: Option<PathBuf>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package"]
+    )]
+    /// Write a JSON map of the output files to a hash of each of their declarations, to detect the declarations that changed
+    hash_manifest: Option<PathBuf>,
+    #[structopt(long, conflicts_with = "symbol-map")]
+    /// Bundle all the modules in a single file, the output being the path of that file, or a directory to write `types.ts`
+    /// (`types.d.ts` for a declaration file) in
+    bundle: bool,
+    #[structopt(long, conflicts_with_all = &["bundle", "symbol-map"])]
+    /// Output self-contained declarations in a single file (or stdout), inlining the types they reference
+    inline: bool,
+    #[structopt(long, conflicts_with_all = &["bundle", "inline", "symbol-map"])]
+    /// Output all the modules in a single file (or stdout), each module in a namespace named after its path, `api.models`
+    namespaces: bool,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["bundle", "inline", "namespaces"]
+    )]
+    /// Add the output directory to the `paths` of a tsconfig fragment, so that the imports mapped to this crate resolve
+    tsconfig_paths: Option<PathBuf>,
+    #[structopt(long, parse(from_os_str))]
+    /// Import the generated modules through the `paths` aliases of this tsconfig.json, e.g. `@api/users` rather than `../api/users`
+    tsconfig: Option<PathBuf>,
+    #[structopt(
+        long,
+        conflicts_with_all = &["bundle", "inline", "namespaces", "symbol-map", "tsconfig-paths"]
+    )]
+    /// Scaffold a publishable npm package with this name in the output directory
+    npm_package: Option<String>,
+    #[structopt(long, requires = "npm-package")]
+    /// Version of the npm package, defaults to the version of the crate
+    npm_version: Option<String>,
+    #[structopt(long, conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package"])]
+    /// Write an `index.ts` barrel re-exporting every module to the output directory, the root module being written to `lib.ts`
+    barrel: bool,
+    #[structopt(long)]
+    /// The case of the generated file names: `snake_case` (unchanged), `kebab-case`, `camelCase` or `PascalCase`
+    file_case: Option<PathCase>,
+    #[structopt(long)]
+    /// Write the modules that contain other modules to `index.ts` in their directory, `api/index.ts` instead of `api.ts`
+    index_files: bool,
+    #[structopt(long)]
+    /// Print the time spent by phase, module and solver to stderr
+    profile: bool,
+    #[structopt(long)]
+    /// Skip the modules that fail to be parsed or solved, instead of aborting
+    skip_failing_modules: bool,
+    #[structopt(long)]
+    /// What to do with the types no solver solves : `error` (the default), `unknown` to export them as `unknown`,
+    /// or `skip` to leave out their declarations. The recoveries are printed as warnings
+    unsolved_types: Option<UnsolvedTypePolicy>,
+    #[structopt(long, number_of_values = 1)]
+    /// Skip the modules whose file matches this glob, relatively to the crate directory (e.g. `**/generated/**`)
+    exclude: Vec<String>,
+    #[structopt(long)]
+    /// Skip the modules whose file is ignored by the `.gitignore` files of the repository
+    respect_gitignore: bool,
+    #[structopt(long, parse(from_os_str))]
+    /// The directory of `env!("OUT_DIR")` in the `include!` macros, where a build script writes its files
+    out_dir: Option<PathBuf>,
+    #[structopt(long)]
+    /// Skip the items, fields and variants whose `#[cfg(...)]` doesn't hold, `test` and the features not given being unset
+    respect_cfg: bool,
+    #[structopt(long, number_of_values = 1)]
+    /// Features enabled with `--respect-cfg`, separated by commas (e.g. `admin,billing`)
+    features: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// An option set with `--respect-cfg`, e.g. `unix` or `target_os="linux"`
+    cfg: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Only export the types whose name matches this glob (e.g. `*Dto`), repeatable
+    allow_type: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Skip the types whose name matches this glob (e.g. `*Row`), repeatable
+    deny_type: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Only export the types of the modules matching this glob, relatively to the crate (e.g. `api::**`), repeatable
+    allow_module: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Skip the types of the modules matching this glob (e.g. `**::db`), repeatable
+    deny_module: Vec<String>,
+    #[structopt(long)]
+    /// Only export the types marked `#[ts(export)]`
+    only_marked: bool,
+    #[structopt(long)]
+    /// Run this command on each written file, its path being appended to the arguments (e.g. `prettier --write`)
+    formatter: Option<FormatterCommand>,
+    #[structopt(long, number_of_values = 1)]
+    /// A line of the banner written at the top of each generated file instead of the standard comment,
+    /// `{module}` being replaced by the Rust module (e.g. `/* eslint-disable */`)
+    banner: Vec<String>,
+    #[structopt(long, conflicts_with = "banner")]
+    /// Don't write the standard comment at the top of each generated file
+    no_header: bool,
+    #[structopt(long)]
+    /// Indent the generated code with `tab` or a number of spaces, instead of the canonical layout
+    indent: Option<Indent>,
+    #[structopt(long)]
+    /// Don't terminate the statements of the generated code with a semicolon
+    no_semicolons: bool,
+    #[structopt(long)]
+    /// Add a comma after the last member of the objects and enums of the generated code
+    trailing_commas: bool,
+    #[structopt(long)]
+    /// `single` or `double` quotes for the string literals of the generated code
+    quotes: Option<Quotes>,
+    #[structopt(long)]
+    /// Wrap the unions of the generated lines longer than this one variant per line
+    print_width: Option<usize>,
+    #[structopt(long)]
+    /// Import the types with `import type { ... }`
+    type_imports: bool,
+    #[structopt(long)]
+    /// Append `js` or `ts` to the specifiers of the generated files, for the ESM resolution (`none` by default)
+    import_extension: Option<ImportExtension>,
+    #[structopt(long)]
+    /// Export the endpoints of the warp filters of each module as an `Endpoints` interface
+    warp_endpoints: bool,
+    #[structopt(long, requires = "warp-endpoints")]
+    /// The name of the interface of the endpoints, when a module already declares an `Endpoints` type
+    endpoints_name: Option<String>,
+    #[structopt(long)]
+    /// Export a `mock<Name>(overrides?)` factory of fake values next to each declaration
+    mocks: bool,
+    #[structopt(long)]
+    /// Export a `<Name>Codec` validating the values at runtime next to each declaration, built with `io-ts` or `runtypes`
+    codecs: Option<CodecLibrary>,
+    #[structopt(long, conflicts_with = "npm-package")]
+    /// Write declaration files (`.d.ts`), the mocks and codecs being declared without their values
+    declaration_files: bool,
+    #[structopt(long)]
+    /// Declare the bundle as an ambient module with this name, `declare module "<name>" { ... }`, for a declaration file
+    ambient_module: Option<String>,
+    #[structopt(long)]
+    /// Mark every property of the generated declarations `readonly`
+    readonly: bool,
+    #[structopt(long)]
+    /// Export the enums whose variants are all units as TS enums instead of unions of string literals
+    ts_enums: bool,
+    #[structopt(long, default_value = "serialize")]
+    /// `deserialize` to describe the data sent to Rust, where the `#[serde(default)]` fields are optional,
+    /// or `both` to declare each type twice, `User` and `UserInput`
+    mode: ProcessMode,
+    #[structopt(long)]
+    /// The suffix of the declarations of the data sent to Rust with `--mode both`, `Input` by default
+    input_suffix: Option<String>,
+    #[structopt(long)]
+    /// Deserialize the `#[serde(other)]` variants from `(string & {})` instead of `string`, keeping the known variants suggested
+    open_catch_all_variants: bool,
+    #[structopt(
+        long,
+        conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package"]
+    )]
+    /// Write type-level assertions of the structure of the exported types to `__typetests.ts` in the output directory
+    type_tests: bool,
+    #[structopt(long)]
+    /// Export `serde_json::Value` as `null | boolean | number | string | unknown[] | Record<string, unknown>` instead of `unknown`
+    structural_json_values: bool,
+    #[structopt(long)]
+    /// Export `uuid::Uuid` as the branded `string & { __brand: "Uuid" }` instead of `string`
+    branded_uuids: bool,
+    #[structopt(long)]
+    /// Fail on the `Result` types instead of exporting them as `{ Ok: T } | { Err: E }`
+    forbid_results: bool,
+    #[structopt(long)]
+    /// Export `Duration` and `SystemTime` as `number` milliseconds instead of serde's `{ secs, nanos }` objects
+    time_as_milliseconds: bool,
+    #[structopt(long)]
+    /// Export the `rust_decimal` and `bigdecimal` decimals as `number` instead of `string`
+    decimals_as_numbers: bool,
+    #[structopt(long)]
+    /// Export `OsString` and `CString` as `never`, for the APIs that consider them not portable
+    os_strings_as_never: bool,
+    #[structopt(long)]
+    /// How `u64`, `i64`, `u128` and `i128` are exported : `number` (the default), `bigint`, `string`, or `error` to fail on them
+    large_integers: Option<LargeIntegerPolicy>,
+    #[structopt(long)]
+    /// Export the fixed-size arrays up to this length as tuples, `[u8; 4]` as `[ number, number, number, number ]`
+    fixed_arrays_as_tuples: Option<usize>,
+    #[structopt(long)]
+    /// What to do with the fields whose custom serializer isn't in the `[serialize_with]` table of the configuration :
+    /// `passthrough` (the default) to solve their Rust type, `unknown`, or `error` to fail on them
+    unmapped_serialize_with: Option<SerializeWithPolicy>,
+    #[structopt(long, conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package"])]
+    /// Keep running, and export again the modules whose output changed each time a source file is modified
+    watch: bool,
+    #[structopt(
+        long,
+        conflicts_with_all = &["bundle", "inline", "namespaces", "npm-package", "watch"]
+    )]
+    /// Compare the modules to the files of the output directory instead of writing them, failing if they differ
+    check: bool,
 }
 
-fn main() -> Result<(), TsExportError> {
+/// How long the changes of the source files are debounced with `--watch`
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The file name of the root module with `--barrel`, `index.ts` being the barrel
+const BARREL_ROOT_MODULE: &str = "lib";
+
+/// The comment at the top of each file of the files exporters
+fn header_comment(banner: Option<HeaderComment>, no_header: bool) -> HeaderComment {
+    match banner {
+        _ if no_header => HeaderComment::None,
+        Some(banner) => banner,
+        None => HeaderComment::Standard,
+    }
+}
+
+fn main() {
     pretty_env_logger::init();
     let options = Options::from_args();
-    main_process(options)
+    // Displayed rather than debugged, for the code frames of the diagnostics
+    if let Err(error) = main_process(options) {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
 }
 
 fn main_process(options: Options) -> Result<(), TsExportError> {
     let Options {
         input,
+        config,
         output,
         path_mapper_file,
+        path_case,
+        external_tag_key,
+        follow_reexports,
+        dependency_package,
+        export_dependency,
+        serde_type_names,
+        symbol_map,
+        examples,
+        hash_manifest,
+        bundle,
+        inline,
+        namespaces,
+        tsconfig_paths,
+        tsconfig,
+        npm_package,
+        npm_version,
+        barrel,
+        file_case,
+        index_files,
+        profile,
+        skip_failing_modules,
+        unsolved_types,
+        exclude,
+        respect_gitignore,
+        out_dir,
+        respect_cfg,
+        features,
+        cfg,
+        allow_type,
+        deny_type,
+        allow_module,
+        deny_module,
+        only_marked,
+        formatter,
+        banner,
+        no_header,
+        indent,
+        no_semicolons,
+        trailing_commas,
+        quotes,
+        print_width,
+        type_imports,
+        import_extension,
+        warp_endpoints,
+        endpoints_name,
+        mocks,
+        codecs,
+        declaration_files,
+        ambient_module,
+        readonly,
+        ts_enums,
+        mode,
+        input_suffix,
+        open_catch_all_variants,
+        type_tests,
+        structural_json_values,
+        branded_uuids,
+        forbid_results,
+        time_as_milliseconds,
+        decimals_as_numbers,
+        os_strings_as_never,
+        large_integers,
+        fixed_arrays_as_tuples,
+        unmapped_serialize_with,
+        watch,
+        check,
     } = options;
 
-    let pipeline_step_spawner = RustModuleReader::try_new(input)?;
+    let mut config = match config {
+        Some(path) => Config::load_from(path)?,
+        None if input.is_empty() && Path::new(CONFIG_FILE).exists() => {
+            Config::load_from(CONFIG_FILE)?
+        }
+        None => Config::default(),
+    };
+    if !input.is_empty() {
+        config.input = input;
+    }
+    if output.is_some() {
+        config.output = output;
+    }
+    if !banner.is_empty() {
+        config.banner = Some(banner.join("\n"));
+    }
+    if indent.is_some()
+        || no_semicolons
+        || trailing_commas
+        || quotes.is_some()
+        || print_width.is_some()
+    {
+        let format = config.format.get_or_insert_with(FormatOptions::default);
+        if let Some(indent) = indent {
+            format.indent = indent;
+        }
+        format.semicolons &= !no_semicolons;
+        format.trailing_commas |= trailing_commas;
+        if let Some(quotes) = quotes {
+            format.quotes = quotes;
+        }
+        if print_width.is_some() {
+            format.print_width = print_width;
+        }
+    }
+    if path_mapper_file.is_some() {
+        config.path_mapper_file = path_mapper_file;
+    }
+    if let Some(path_case) = path_case {
+        config.path_case = path_case;
+    }
+    if bundle {
+        config.exporter = ExporterKind::Bundle;
+    }
+    if inline {
+        config.exporter = ExporterKind::Inline;
+    }
+    if namespaces {
+        config.exporter = ExporterKind::Namespaces;
+    }
+    config.solvers.structural_json_values |= structural_json_values;
+    config.solvers.branded_uuids |= branded_uuids;
+    config.solvers.forbid_results |= forbid_results;
+    config.solvers.time_as_milliseconds |= time_as_milliseconds;
+    config.solvers.decimals_as_numbers |= decimals_as_numbers;
+    config.solvers.os_strings_as_never |= os_strings_as_never;
+    if let Some(large_integers) = large_integers {
+        config.solvers.large_integers = large_integers;
+    }
+    if fixed_arrays_as_tuples.is_some() {
+        config.solvers.fixed_arrays_as_tuples = fixed_arrays_as_tuples;
+    }
+    if let Some(unmapped_serialize_with) = unmapped_serialize_with {
+        config.solvers.unmapped_serialize_with = unmapped_serialize_with;
+    }
+    if let Some(unsolved_types) = unsolved_types {
+        config.unsolved_types = unsolved_types;
+    }
+    for dependency in dependency_package {
+        match dependency.split_once('=') {
+            Some((name, package)) => config.dependencies.push((
+                name.trim().to_string(),
+                DependencyMode::Package(package.trim().to_string()),
+            )),
+            None => {
+                return Err(TsExportError::InvalidConfig(format!(
+                    "expected --dependency-package <crate>=<package>, found {}",
+                    dependency
+                )))
+            }
+        }
+    }
+    for name in export_dependency {
+        config.dependencies.push((name, DependencyMode::Export));
+    }
+    if tsconfig.is_some() {
+        config.tsconfig = tsconfig;
+    }
+    let has_import_aliases = config.tsconfig.is_some() || !config.import_aliases.is_empty();
+    if let Some(file_case) = file_case {
+        config.file_case = file_case;
+    }
+    config.index_files |= index_files;
+    let has_file_naming = config.file_case != PathCase::Unchanged || config.index_files;
+    check_config(
+        &config,
+        &[
+            ("--symbol-map", symbol_map.is_some()),
+            ("--examples", examples.is_some()),
+            ("--hash-manifest", hash_manifest.is_some()),
+            ("--tsconfig-paths", tsconfig_paths.is_some()),
+            ("--tsconfig", has_import_aliases),
+            ("--file-case", has_file_naming),
+            ("--npm-package", npm_package.is_some()),
+            ("--formatter", formatter.is_some()),
+            ("--type-tests", type_tests),
+            ("--barrel", barrel),
+            ("--watch", watch),
+            ("--check", check),
+            (
+                "--export-dependency",
+                !config.exported_dependencies().is_empty(),
+            ),
+        ],
+    )?;
+    if !config.exported_dependencies().is_empty()
+        && (config.exporter != ExporterKind::Files || watch || check || npm_package.is_some())
+    {
+        return Err(TsExportError::InvalidConfig(
+            "the exported dependencies require the files exporter, without --watch, --check or --npm-package".to_string(),
+        ));
+    }
+    if has_file_naming && (config.exporter != ExporterKind::Files || npm_package.is_some()) {
+        return Err(TsExportError::InvalidConfig(
+            "the file naming requires the files exporter, without --npm-package".to_string(),
+        ));
+    }
+    if has_import_aliases && (config.exporter != ExporterKind::Files || npm_package.is_some()) {
+        return Err(TsExportError::InvalidConfig(
+            "the import aliases require the files exporter, without --npm-package".to_string(),
+        ));
+    }
+    if (watch || check) && config.exporter != ExporterKind::Files {
+        return Err(TsExportError::InvalidConfig(format!(
+            "{} requires the files exporter",
+            if watch { "--watch" } else { "--check" }
+        )));
+    }
+    if ambient_module.is_some() && config.exporter != ExporterKind::Bundle {
+        return Err(TsExportError::InvalidConfig(
+            "--ambient-module requires the bundle exporter".to_string(),
+        ));
+    }
+    let banner = config.banner.clone().map(HeaderComment::Banner);
+    config.imports.type_only |= type_imports;
+    if let Some(import_extension) = import_extension {
+        config.imports.extension = import_extension;
+    }
+    let output_options = OutputOptions {
+        formatter,
+        format_options: config.format.clone(),
+        header_comment: header_comment(banner.clone(), no_header),
+        import_style: config.imports,
+    };
+    // The exporters that write a single file only have a header comment when given a banner
+    let single_file_options = OutputOptions {
+        header_comment: banner.unwrap_or(HeaderComment::None),
+        ..output_options.clone()
+    };
+    let file_case = config.file_case;
+    let index_files = config.index_files;
+    let input = config.input.clone();
+    let output = config.output.clone();
+    let bundle = config.exporter == ExporterKind::Bundle;
+    let inline = config.exporter == ExporterKind::Inline;
+    let namespaces = config.exporter == ExporterKind::Namespaces;
+    let workspace = match input.as_slice() {
+        [manifest] if manifest.file_name() == Some(OsStr::new("Cargo.toml")) => {
+            if let Some((option, _)) = [
+                ("--watch", watch),
+                ("--check", check),
+                ("--npm-package", npm_package.is_some()),
+                ("--symbol-map", symbol_map.is_some()),
+                ("--examples", examples.is_some()),
+                ("--hash-manifest", hash_manifest.is_some()),
+                ("--type-tests", type_tests),
+                (
+                    "--export-dependency",
+                    !config.exported_dependencies().is_empty(),
+                ),
+            ]
+            .iter()
+            .find(|(_, set)| *set)
+            {
+                return Err(TsExportError::InvalidConfig(format!(
+                    "{} can't be used with a Cargo.toml input",
+                    option
+                )));
+            }
+            if config.exporter != ExporterKind::Files || output.is_none() {
+                return Err(TsExportError::InvalidConfig(
+                    "a Cargo.toml input requires the files exporter and an output directory"
+                        .to_string(),
+                ));
+            }
+            Some(CargoWorkspace::load(manifest)?)
+        }
+        _ => None,
+    };
+
+    let npm_package = match npm_package {
+        Some(name) => {
+            let version = match npm_version {
+                Some(version) => version,
+                None => fetch_crate_version_for_source_file(&input[0])?,
+            };
+            Some(NpmPackage { name, version })
+        }
+        None => None,
+    };
 
-    let solving_context = TypeSolvingContextBuilder::default()
-        .add_default_solvers()
-        .finish();
+    let solving_context = config.solvers.type_solving_context();
 
     let macro_context = MacroSolvingContext::default();
 
-    let path_mapper = if let Some(path) = path_mapper_file {
-        PathMapper::load_from(path)?
+    let path_mapper = config.path_mapper()?;
+
+    let import_aliases = config.import_aliases()?;
+
+    let options = ExporterOptions {
+        external_tag_key,
+        follow_reexports,
+        type_names: if serde_type_names {
+            TypeNamePolicy::SerdeName
+        } else {
+            TypeNamePolicy::RustIdent
+        },
+        profile,
+        skip_failing_modules,
+        unsolved_types: config.unsolved_types,
+        warp_endpoints: if warp_endpoints {
+            Some(endpoints_name.unwrap_or_else(|| ENDPOINTS_INTERFACE.to_string()))
+        } else {
+            None
+        },
+        mocks,
+        codecs,
+        declaration_files: declaration_files || ambient_module.is_some(),
+        readonly,
+        ts_enums,
+        process_mode: mode,
+        input_suffix,
+        open_catch_all_variants,
+        cfg: if respect_cfg || !features.is_empty() || !cfg.is_empty() {
+            let mut cfg_set = CfgSet::default();
+            for feature in features.iter().flat_map(|features| features.split(',')) {
+                cfg_set.features.insert(feature.trim().to_string());
+            }
+            for option in cfg.iter() {
+                cfg_set.add_option(option);
+            }
+            Some(cfg_set)
+        } else {
+            None
+        },
+        filter: if only_marked
+            || !allow_type.is_empty()
+            || !deny_type.is_empty()
+            || !allow_module.is_empty()
+            || !deny_module.is_empty()
+        {
+            let mut filter = ProcessFilter::default();
+            allow_type
+                .iter()
+                .for_each(|pattern| filter.allow_name(pattern));
+            deny_type
+                .iter()
+                .for_each(|pattern| filter.deny_name(pattern));
+            allow_module
+                .iter()
+                .for_each(|pattern| filter.allow_module(pattern));
+            deny_module
+                .iter()
+                .for_each(|pattern| filter.deny_module(pattern));
+            filter.set_only_marked(only_marked);
+            Some(filter)
+        } else {
+            None
+        },
+    };
+
+    // Each library crate of the manifest is written to its own directory of the output
+    if let (Some(workspace), Some(output)) = (workspace, &output) {
+        for cargo_crate in workspace.crates() {
+            let mut pipeline_step_spawner = cargo_crate.module_reader()?;
+            for pattern in exclude.iter() {
+                pipeline_step_spawner.add_exclude(pattern);
+            }
+            pipeline_step_spawner.set_respect_gitignore(respect_gitignore);
+            pipeline_step_spawner.set_out_dir(out_dir.clone());
+            let crate_name = pipeline_step_spawner.crate_name().to_string();
+            let out_path = output.join(&cargo_crate.name);
+            let mut exporter = FileExporter::new(out_path.clone());
+            exporter.set_output_options(output_options.clone());
+            exporter.set_declaration_files(declaration_files);
+            exporter.set_import_aliases(import_aliases.clone());
+            exporter.set_file_case(file_case);
+            exporter.set_index_files(index_files);
+            // The other members are imported from their own directory of the output
+            for (other, dir) in workspace.sibling_dirs(cargo_crate) {
+                exporter.add_crate_dir(&other, &dir);
+            }
+            if barrel {
+                exporter.set_default_module_name(Path::new(BARREL_ROOT_MODULE));
+                exporter.set_barrel(true);
+            }
+            let mut pipeline = Pipeline {
+                pipeline_step_spawner,
+                exporter,
+                path_mapper: config.path_mapper()?,
+                options: options.clone(),
+            };
+            let report = pipeline.launch(&solving_context, &macro_context)?;
+            if let Some(tsconfig_path) = &tsconfig_paths {
+                if let Some(import_path) = pipeline.path_mapper.get(&crate_name) {
+                    write_tsconfig_paths(
+                        tsconfig_path,
+                        &import_path,
+                        &out_path,
+                        &pipeline.exporter.root_module_name(),
+                    )?;
+                }
+            }
+            print_report(&report);
+        }
+        return Ok(());
+    }
+
+    let manifest_path = if config.exported_dependencies().is_empty() {
+        None
+    } else {
+        Some(fetch_crate_dir_for_source_file(&input[0])?.join("Cargo.toml"))
+    };
+    let mut pipeline_step_spawner = if input.len() == 1 {
+        RustModuleReader::try_new(input.into_iter().next().expect("One input is given"))?
     } else {
-        PathMapper::default()
+        RustModuleReader::try_new_with_roots(input)?
     };
+    for pattern in exclude.iter() {
+        pipeline_step_spawner.add_exclude(pattern);
+    }
+    pipeline_step_spawner.set_respect_gitignore(respect_gitignore);
+    pipeline_step_spawner.set_out_dir(out_dir);
 
-    match output {
-        Some(out_path) => {
-            Pipeline {
+    if inline {
+        let mut exporter = InlineExporter::new(pipeline_step_spawner.crate_name());
+        exporter.set_output_options(single_file_options);
+        let mut pipeline = Pipeline {
+            exporter,
+            pipeline_step_spawner,
+            path_mapper,
+            options,
+        };
+        let report = pipeline.launch(&solving_context, &macro_context)?;
+        match output {
+            Some(out_path) => pipeline.exporter.write_to(out_path)?,
+            None => print!("{}", pipeline.exporter.render()?),
+        }
+        print_report(&report);
+        return Ok(());
+    }
+
+    if namespaces {
+        let mut exporter = NamespaceExporter::new(pipeline_step_spawner.crate_name());
+        exporter.set_output_options(single_file_options);
+        let mut pipeline = Pipeline {
+            exporter,
+            pipeline_step_spawner,
+            path_mapper,
+            options,
+        };
+        let report = pipeline.launch(&solving_context, &macro_context)?;
+        match output {
+            Some(out_path) => pipeline.exporter.write_to(out_path)?,
+            None => print!("{}", pipeline.exporter.render()?),
+        }
+        print_report(&report);
+        return Ok(());
+    }
+
+    let report = match output {
+        Some(out_path) if npm_package.is_some() => {
+            let package = npm_package.expect("The npm package is defined");
+            let mut exporter = NpmPackageExporter::new(out_path, package);
+            exporter.set_output_options(output_options.clone());
+            let mut pipeline = Pipeline {
                 pipeline_step_spawner,
-                exporter: FileExporter::new(out_path),
+                exporter,
                 path_mapper,
-            }
-            .launch(&solving_context, &macro_context)?;
+                options,
+            };
+            pipeline.launch(&solving_context, &macro_context)?
         }
-        None => {
-            Pipeline {
+        Some(out_path) if bundle => {
+            let mut exporter = BundleExporter::new(pipeline_step_spawner.crate_name());
+            exporter.set_output_options(single_file_options);
+            exporter.set_declaration_files(declaration_files || ambient_module.is_some());
+            exporter.set_ambient_module(ambient_module);
+            let mut pipeline = Pipeline {
+                exporter,
                 pipeline_step_spawner,
-                exporter: StdoutExport,
                 path_mapper,
+                options,
+            };
+            let report = pipeline.launch(&solving_context, &macro_context)?;
+            pipeline.exporter.write_to(out_path)?;
+            report
+        }
+        Some(out_path) => {
+            let crate_name = pipeline_step_spawner.crate_name().to_string();
+            let mut exporter = FileExporter::new(out_path.clone());
+            exporter.set_output_options(output_options.clone());
+            exporter.set_check(check);
+            exporter.set_declaration_files(declaration_files);
+            exporter.set_import_aliases(import_aliases.clone());
+            exporter.set_file_case(file_case);
+            exporter.set_index_files(index_files);
+            for dependency in config.exported_dependencies() {
+                exporter.add_crate_dir(dependency, Path::new(dependency));
+            }
+            if barrel {
+                exporter.set_default_module_name(Path::new(BARREL_ROOT_MODULE));
+                exporter.set_barrel(true);
+            }
+            let mut pipeline = Pipeline {
+                pipeline_step_spawner,
+                exporter,
+                path_mapper,
+                options,
+            };
+            if check {
+                let report = pipeline.launch(&solving_context, &macro_context)?;
+                print_report(&report);
+                let check_report = pipeline.exporter.check_report();
+                eprint!("{}", check_report);
+                return check_report.into_result();
+            }
+            let write_artifacts = |pipeline: &Pipeline<RustModuleReader, FileExporter>,
+                                   report: RunReport|
+             -> Result<RunReport, TsExportError> {
+                if let Some(symbol_map_path) = &symbol_map {
+                    pipeline.exporter.symbol_map().write_to(symbol_map_path)?;
+                }
+                if let Some(examples_path) = &examples {
+                    pipeline.exporter.examples().write_to(examples_path)?;
+                }
+                if let Some(hash_manifest_path) = &hash_manifest {
+                    pipeline
+                        .exporter
+                        .hash_manifest()
+                        .write_to(hash_manifest_path)?;
+                }
+                if type_tests {
+                    let type_tests_path = out_path.join(TYPE_TESTS_FILE);
+                    pipeline.exporter.type_tests().write_to(&type_tests_path)?;
+                    if let Some(formatter) = &pipeline.exporter.output_options().formatter {
+                        formatter.run(&type_tests_path)?;
+                    }
+                }
+                if let Some(tsconfig_path) = &tsconfig_paths {
+                    match pipeline.path_mapper.get(&crate_name) {
+                        Some(import_path) => write_tsconfig_paths(
+                            tsconfig_path,
+                            &import_path,
+                            &out_path,
+                            &pipeline.exporter.root_module_name(),
+                        )?,
+                        None => log::warn!(
+                            "The crate {} is not mapped by the PathMapper, no tsconfig paths are needed",
+                            crate_name
+                        ),
+                    }
+                }
+                Ok(report)
+            };
+            if watch {
+                pipeline.watch(
+                    &solving_context,
+                    &macro_context,
+                    WATCH_DEBOUNCE,
+                    |pipeline, result| {
+                        match result.and_then(|report| write_artifacts(pipeline, report)) {
+                            Ok(report) => print_report(&report),
+                            Err(e) => eprintln!("{}", e),
+                        }
+                        true
+                    },
+                )?;
+                return Ok(());
             }
-            .launch(&solving_context, &macro_context)?;
+            let report = pipeline.launch(&solving_context, &macro_context)?;
+            if let Some(manifest_path) = &manifest_path {
+                let exported = config.exported_dependencies();
+                export_dependencies(
+                    manifest_path,
+                    &exported,
+                    &report.external_crates,
+                    |dependency| {
+                        let mut exporter = FileExporter::new(out_path.join(dependency));
+                        exporter.set_output_options(output_options.clone());
+                        exporter.set_declaration_files(declaration_files);
+                        exporter.set_import_aliases(import_aliases.clone());
+                        exporter.set_file_case(file_case);
+                        // The dependencies are written next to each other
+                        for other in exported.iter() {
+                            exporter.add_crate_dir(other, &Path::new("..").join(other));
+                        }
+                        exporter
+                    },
+                    &config,
+                    &pipeline.options,
+                    &solving_context,
+                    &macro_context,
+                )?;
+            }
+            write_artifacts(&pipeline, report)?
+        }
+        None => Pipeline {
+            pipeline_step_spawner,
+            exporter: StdoutExport,
+            path_mapper,
+            options,
         }
+        .launch(&solving_context, &macro_context)?,
+    };
+    print_report(&report);
+
+    Ok(())
+}
+
+/// Checks that the merged configuration describes a run, the options that write files requiring an output
+fn check_config(config: &Config, output_options: &[(&str, bool)]) -> Result<(), TsExportError> {
+    if config.input.is_empty() {
+        return Err(TsExportError::InvalidConfig(format!(
+            "no input given, and no {} found",
+            CONFIG_FILE
+        )));
     }
+    if config.output.is_none() {
+        if let Some((option, _)) = output_options.iter().find(|(_, set)| *set) {
+            return Err(TsExportError::InvalidConfig(format!(
+                "{} requires an output",
+                option
+            )));
+        }
+        if config.exporter == ExporterKind::Bundle {
+            return Err(TsExportError::InvalidConfig(
+                "the bundle exporter requires an output".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
 
+/// Exports the dependencies marked for export that the crate imports types from, each to its own directory of the output,
+/// following the exported dependencies they import types from in turn
+#[allow(clippy::too_many_arguments)]
+fn export_dependencies(
+    manifest_path: &Path,
+    exported: &[&str],
+    external_crates: &BTreeSet<String>,
+    exporter: impl Fn(&str) -> FileExporter,
+    config: &Config,
+    options: &ExporterOptions,
+    solving_context: &TypeSolvingContext,
+    macro_context: &MacroSolvingContext,
+) -> Result<(), TsExportError> {
+    let mut pending: Vec<String> = external_crates
+        .iter()
+        .filter(|name| exported.contains(&name.as_str()))
+        .cloned()
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let dependencies = CargoDependencies::load(manifest_path)?;
+    let mut done: BTreeSet<String> = BTreeSet::new();
+    while let Some(name) = pending.pop() {
+        if !done.insert(name.clone()) {
+            continue;
+        }
+        let dependency = dependencies.get(&name).ok_or_else(|| {
+            TsExportError::InvalidConfig(format!(
+                "{} is not a dependency with a library of {:?}",
+                name, manifest_path
+            ))
+        })?;
+        let mut pipeline = Pipeline {
+            pipeline_step_spawner: dependency.module_reader()?,
+            exporter: exporter(&name),
+            path_mapper: config.path_mapper()?,
+            options: options.clone(),
+        };
+        let report = pipeline.launch(solving_context, macro_context)?;
+        print_report(&report);
+        pending.extend(
+            report
+                .external_crates
+                .into_iter()
+                .filter(|name| exported.contains(&name.as_str()) && !done.contains(name)),
+        );
+    }
     Ok(())
 }
+
+fn print_report(report: &RunReport) {
+    for failure in report.failures.iter() {
+        eprintln!("Skipped module {} : {}", failure.path, failure.error);
+    }
+    for warning in report.warnings.iter() {
+        eprintln!("Warning: {}", warning);
+    }
+    if let Some(profile) = &report.profile {
+        eprint!("{}", profile);
+    }
+}
+
+/// Adds the output directory to the tsconfig fragment, keeping the packages added by previous runs
+fn write_tsconfig_paths(
+    tsconfig_path: &Path,
+    import_path: &str,
+    out_path: &Path,
+    root_module: &str,
+) -> Result<(), TsExportError> {
+    let mut paths = if tsconfig_path.exists() {
+        TsConfigPaths::load_from(tsconfig_path)?
+    } else {
+        TsConfigPaths::default()
+    };
+    let tsconfig_dir = match tsconfig_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(tsconfig_dir)?;
+    std::fs::create_dir_all(out_path)?;
+    let dir = relative_path(tsconfig_dir, out_path)?;
+    paths.add_package(import_path, &dir, root_module);
+    paths.write_to(tsconfig_path)
+}
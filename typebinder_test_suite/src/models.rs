@@ -54,6 +54,9 @@ pub struct WithGeneric<T> {
 type ArrayOfNumbers = Vec<u32>;
 type Array<T> = Vec<T>;
 type WithGenericNumber = WithGeneric<u32>;
+type Pair = (u32, String);
+type Ids = [u64; 2];
+type Callback = fn(u32) -> u32;
 
 mod test {
     #[derive(Debug, Serialize, Deserialize)]
@@ -64,7 +67,7 @@ mod test {
 }
 
 pub struct MyCowWrapper<'a> {
-    my_cow: std::borrow::Cow<'a>,
+    my_cow: std::borrow::Cow<'a, str>,
 }
 
 #[derive(Debug, Serialize)]
@@ -117,3 +120,7 @@ pub enum B {
 pub struct MyCustomMap<T> {
     the_map: HashMap<T, u32>,
 }
+
+#[derive(Serialize, Deserialize)]
+#[ts(fields("min", "max"))]
+pub struct Range(u32, u32);